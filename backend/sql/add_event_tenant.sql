@@ -0,0 +1,4 @@
+-- Add the tenant column to an indexer database created before events named their tenant
+-- Run this in your Supabase SQL Editor, or against INDEXER_SQLITE_PATH with sqlite3
+
+ALTER TABLE registry_events ADD COLUMN tenant INTEGER NOT NULL DEFAULT 0;
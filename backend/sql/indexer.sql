@@ -0,0 +1,82 @@
+-- Chain indexer schema
+-- Written by src/indexer from the registry's CES events and deploy results.
+-- Portable between Postgres (Supabase) and SQLite; the SQLite store runs this file on startup.
+-- Every row carries the block height it was last written at, so a reorg can be rolled back.
+
+-- Sync cursor and other indexer bookkeeping
+CREATE TABLE IF NOT EXISTS indexer_state (
+    name TEXT PRIMARY KEY,
+    value TEXT NOT NULL
+);
+
+-- Blocks seen, to detect a reorg when a height comes back with another hash
+CREATE TABLE IF NOT EXISTS indexed_blocks (
+    height BIGINT PRIMARY KEY,
+    hash TEXT NOT NULL,
+    timestamp BIGINT NOT NULL                -- Block time in ms
+);
+
+-- Raw registry events, the source the other tables are replayed from
+CREATE TABLE IF NOT EXISTS registry_events (
+    event_index BIGINT PRIMARY KEY,          -- Position in the registry's __events dictionary
+    height BIGINT NOT NULL,
+    deploy_hash TEXT NOT NULL,
+    account TEXT,                            -- Account hash hex the event concerns
+    tenant INTEGER NOT NULL DEFAULT 0,       -- Tenant whose records the event changed
+    name TEXT NOT NULL,                      -- e.g. 'RecoveryStartedV3'
+    payload TEXT NOT NULL                    -- Decoded fields as JSON
+);
+
+CREATE TABLE IF NOT EXISTS guarded_accounts (
+    account TEXT PRIMARY KEY,
+    threshold INTEGER NOT NULL,
+    initialized_height BIGINT NOT NULL,
+    height BIGINT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS account_guardians (
+    account TEXT NOT NULL,
+    guardian TEXT NOT NULL,                  -- Account or contract package hash hex
+    active BOOLEAN NOT NULL,
+    added_height BIGINT NOT NULL,
+    height BIGINT NOT NULL,
+    PRIMARY KEY (account, guardian)
+);
+
+CREATE TABLE IF NOT EXISTS recoveries (
+    id TEXT PRIMARY KEY,                     -- Decimal U256 recovery id
+    account TEXT NOT NULL,
+    new_key TEXT,
+    status TEXT NOT NULL,                    -- 'pending' | 'finalized' | 'vetoed' | 'cancelled'
+    approvals INTEGER NOT NULL,
+    started_height BIGINT NOT NULL,
+    started_deploy TEXT NOT NULL,
+    expires_at BIGINT NOT NULL,              -- On the recovery's clock; 0 if it never expires
+    by_height BOOLEAN NOT NULL,              -- expires_at is a block height rather than a time in ms
+    height BIGINT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS recovery_approvals (
+    recovery_id TEXT NOT NULL,
+    guardian TEXT NOT NULL,
+    approvals INTEGER NOT NULL,              -- Count after this approval
+    deploy_hash TEXT NOT NULL,
+    height BIGINT NOT NULL,
+    PRIMARY KEY (recovery_id, guardian)
+);
+
+-- Registry calls that changed an account's configuration, with their arguments
+CREATE TABLE IF NOT EXISTS config_changes (
+    deploy_hash TEXT PRIMARY KEY,
+    account TEXT,
+    entry_point TEXT NOT NULL,
+    args TEXT NOT NULL,                      -- Parsed runtime args as JSON
+    height BIGINT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_registry_events_account ON registry_events(account);
+CREATE INDEX IF NOT EXISTS idx_registry_events_height ON registry_events(height);
+CREATE INDEX IF NOT EXISTS idx_account_guardians_guardian ON account_guardians(guardian);
+CREATE INDEX IF NOT EXISTS idx_recoveries_account ON recoveries(account);
+CREATE INDEX IF NOT EXISTS idx_recoveries_status ON recoveries(status);
+CREATE INDEX IF NOT EXISTS idx_config_changes_account ON config_changes(account);
@@ -12,6 +12,12 @@ export const config = {
     casper: {
         nodeUrl: process.env.CASPER_NODE_URL || 'http://65.109.83.79:7777/rpc',
         chainName: process.env.CASPER_CHAIN_NAME || 'casper-test',
+        // Condor (Casper 2.0) network with addressable entities enabled: accounts and contracts
+        // are read as entities, following accounts that have migrated to one
+        addressableEntity: process.env.CASPER_ADDRESSABLE_ENTITY === 'true',
+        // What prepared calls are built as: 'deploy', 'transaction' (Casper 2.0 TransactionV1), or
+        // 'auto' for transactions when the node runs protocol 2.0 or later
+        transactions: (process.env.CASPER_TRANSACTIONS || 'auto') as 'auto' | 'deploy' | 'transaction',
     },
 
     // Contract Hashes
@@ -19,6 +25,62 @@ export const config = {
         recoveryRegistryHash: process.env.RECOVERY_REGISTRY_HASH
     },
 
+    // Ed25519 key that signs exported guardianship attestations
+    attestation: {
+        keyPath: process.env.ATTESTATION_KEY_PATH,
+    },
+
+    // guardian-watchd: accounts and keys to watch, comma-separated public keys or account hashes
+    watchd: {
+        eventsUrl: process.env.WATCHD_EVENTS_URL || 'http://65.109.83.79:9999/events/main',
+        accounts: (process.env.WATCHD_ACCOUNTS || '').split(',').map((s) => s.trim()).filter(Boolean),
+        keys: (process.env.WATCHD_KEYS || '').split(',').map((s) => s.trim()).filter(Boolean),
+        statePath: path.resolve(process.env.WATCHD_STATE_PATH || './watchd-state.json'),
+        // Warn this long before a watched recovery expires (ms, or blocks for height-timed recoveries)
+        expiryWarning: parseInt(process.env.WATCHD_EXPIRY_WARNING || '86400000', 10),
+        expiryWarningBlocks: parseInt(process.env.WATCHD_EXPIRY_WARNING_BLOCKS || '1000', 10),
+        // Notification actions: log, exec, webhook
+        actions: (process.env.WATCHD_ACTIONS || 'log').split(',').map((s) => s.trim()).filter(Boolean),
+        execCommand: process.env.WATCHD_EXEC_COMMAND,
+        // Port of the Prometheus /metrics endpoint, 0 to disable
+        metricsPort: parseInt(process.env.WATCHD_METRICS_PORT || '9464', 10),
+        webhooks: {
+            // JSON array of { url, secret, events?, accounts? }
+            endpointsPath: process.env.WATCHD_WEBHOOKS_PATH,
+            statePath: path.resolve(process.env.WATCHD_WEBHOOK_STATE_PATH || './watchd-webhooks.json'),
+            maxAttempts: parseInt(process.env.WATCHD_WEBHOOK_MAX_ATTEMPTS || '8', 10),
+            initialBackoff: parseInt(process.env.WATCHD_WEBHOOK_BACKOFF || '5000', 10),
+        },
+    },
+
+    // Chain indexer: 'sqlite' (Node 22.5+) or 'postgres' (the Supabase project)
+    indexer: {
+        db: (process.env.INDEXER_DB || 'sqlite') as 'sqlite' | 'postgres',
+        sqlitePath: path.resolve(process.env.INDEXER_SQLITE_PATH || './indexer.sqlite'),
+        eventsUrl: process.env.INDEXER_EVENTS_URL || process.env.WATCHD_EVENTS_URL || 'http://65.109.83.79:9999/events/main',
+    },
+
+    // GraphQL API over the indexer
+    graphql: {
+        port: parseInt(process.env.GRAPHQL_PORT || '4000', 10),
+        // Run the indexer in the same process, which live subscriptions need
+        embedIndexer: process.env.GRAPHQL_EMBED_INDEXER !== 'false',
+    },
+
+    // Live registry events of the v1 API (GET /api/v1/events)
+    api: {
+        eventsUrl: process.env.API_EVENTS_URL || process.env.WATCHD_EVENTS_URL || 'http://65.109.83.79:9999/events/main',
+    },
+
+    // Cache of per-account state reads: 'off', 'memory' or 'disk'. Following an event stream
+    // keeps entries across blocks until a deploy or registry event touches their account
+    cache: {
+        mode: (process.env.QUERY_CACHE || 'off') as 'off' | 'memory' | 'disk',
+        path: path.resolve(process.env.QUERY_CACHE_PATH || './query-cache.json'),
+        eventsUrl: process.env.QUERY_CACHE_EVENTS_URL,
+        maxAge: parseInt(process.env.QUERY_CACHE_MAX_AGE || '600000', 10),
+    },
+
     // WASM Paths
     wasm: {
         recoveryRegistry: path.resolve(
@@ -39,6 +101,9 @@ export const config = {
         recoveryKeyRotation: path.resolve(
             process.env.WASM_RECOVERY_KEY_ROTATION_PATH || '../contracts/wasm/recovery_key_rotation.wasm'
         ),
+        relayApprovals: path.resolve(
+            process.env.WASM_RELAY_APPROVALS_PATH || '../contracts/wasm/relay_approvals.wasm'
+        ),
     },
 
     // Deploy settings
@@ -46,5 +111,6 @@ export const config = {
         paymentAmount: '5000000000', // 5 CSPR
         sessionPaymentAmount: '10000000000', // 10 CSPR for session WASMs
         ttl: 1800000, // 30 minutes
+        gasPriceTolerance: parseInt(process.env.GAS_PRICE_TOLERANCE || '1', 10), // Transactions only
     },
 };
@@ -0,0 +1,47 @@
+/**
+ * GraphQL API over the chain indexer
+ *
+ * Queries read the indexer's tables; `recoveryUpdated` subscriptions are
+ * served over graphql-ws on the same path and need the indexer running in this
+ * process (GRAPHQL_EMBED_INDEXER, on by default).
+ *
+ * Run with `npm run graphql`.
+ */
+import express from 'express';
+import cors from 'cors';
+import { createServer } from 'http';
+import { WebSocketServer } from 'ws';
+import { createHandler } from 'graphql-http/lib/use/express';
+import { useServer } from 'graphql-ws/lib/use/ws';
+import { config } from '../config';
+import { ChainIndexer } from '../indexer';
+import { IndexQueries } from '../indexer/queries';
+import { openStore } from '../indexer/store';
+import { resolvers, schema } from './schema';
+
+export function startGraphqlServer(): void {
+    const indexer = config.graphql.embedIndexer ? new ChainIndexer() : undefined;
+    const queries = new IndexQueries(indexer?.store ?? openStore());
+    const { query, subscription } = resolvers(queries, indexer);
+
+    const app = express();
+    app.use(cors());
+    app.all('/graphql', createHandler({ schema, rootValue: query }));
+
+    const server = createServer(app);
+    const wss = new WebSocketServer({ server, path: '/graphql' });
+    useServer({ schema, roots: { query, subscription } }, wss);
+
+    server.listen(config.graphql.port, () => {
+        console.log(`[graphql] Serving http://localhost:${config.graphql.port}/graphql`);
+    });
+
+    indexer?.start().catch((error) => {
+        console.error(`[graphql] Indexer stopped: ${error}`);
+        process.exit(1);
+    });
+}
+
+if (require.main === module) {
+    startGraphqlServer();
+}
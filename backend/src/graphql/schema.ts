@@ -0,0 +1,149 @@
+import { on } from 'events';
+import { buildSchema } from 'graphql';
+import { ChainIndexer } from '../indexer';
+import { IndexQueries, Recovery } from '../indexer/queries';
+import { Row } from '../indexer/store';
+
+export const schema = buildSchema(`
+    type PageInfo {
+        hasNextPage: Boolean!
+        endCursor: String
+    }
+
+    type Guardian {
+        account: String!
+        guardian: String!
+        active: Boolean!
+        addedHeight: Float!
+    }
+
+    type GuardianEdge { cursor: String! node: Guardian! }
+    type GuardianConnection { edges: [GuardianEdge!]! pageInfo: PageInfo! }
+
+    type Approval {
+        recoveryId: String!
+        guardian: String!
+        "Approval count after this approval"
+        approvals: Int!
+        deployHash: String!
+        height: Float!
+    }
+
+    type ApprovalEdge { cursor: String! node: Approval! }
+    type ApprovalConnection { edges: [ApprovalEdge!]! pageInfo: PageInfo! }
+
+    type Recovery {
+        id: String!
+        account: String!
+        newKey: String
+        "pending, finalized, vetoed or cancelled"
+        status: String!
+        approvals: Int!
+        startedHeight: Float!
+        startedDeploy: String!
+        "On the recovery's clock (ms, or a block height when byHeight); 0 if it never expires"
+        expiresAt: Float!
+        byHeight: Boolean!
+        "Until expiry as of the newest indexed block, in ms or blocks"
+        timeRemaining: Float
+        approvalHistory(first: Int = 20, after: String): ApprovalConnection!
+    }
+
+    type RecoveryEdge { cursor: String! node: Recovery! }
+    type RecoveryConnection { edges: [RecoveryEdge!]! pageInfo: PageInfo! }
+
+    type Query {
+        "Guardians of an account (account hashes are hex)"
+        guardians(account: String!, activeOnly: Boolean = true, first: Int = 20, after: String): GuardianConnection!
+        "Accounts a key currently guards"
+        accountsGuardedBy(guardian: String!, first: Int = 20, after: String): GuardianConnection!
+        "Pending recoveries, of one account or all"
+        openRecoveries(account: String, first: Int = 20, after: String): RecoveryConnection!
+        recovery(id: String!): Recovery
+        approvals(recoveryId: String!, first: Int = 20, after: String): ApprovalConnection!
+    }
+
+    type Subscription {
+        "Each change to a recovery, of one account or all"
+        recoveryUpdated(account: String): Recovery!
+    }
+`);
+
+interface PageArgs {
+    first: number;
+    after?: string;
+}
+
+const MAX_PAGE = 100;
+
+function pageOf({ first, after }: PageArgs): { limit: number; offset: number } {
+    const offset = after ? Number(Buffer.from(after, 'base64').toString('utf8')) + 1 : 0;
+    // One extra row tells whether there is a next page
+    return { limit: Math.min(Math.max(first, 1), MAX_PAGE) + 1, offset };
+}
+
+function connection<T>(rows: T[], page: { limit: number; offset: number }) {
+    const nodes = rows.slice(0, page.limit - 1);
+    const edges = nodes.map((node, i) => ({ cursor: Buffer.from(String(page.offset + i)).toString('base64'), node }));
+    return {
+        edges,
+        pageInfo: { hasNextPage: rows.length === page.limit, endCursor: edges.length ? edges[edges.length - 1].cursor : null },
+    };
+}
+
+const guardian = (row: Row) => ({
+    account: row.account, guardian: row.guardian, active: Boolean(row.active), addedHeight: Number(row.added_height),
+});
+
+const approval = (row: Row) => ({
+    recoveryId: row.recovery_id, guardian: row.guardian, approvals: Number(row.approvals),
+    deployHash: row.deploy_hash, height: Number(row.height),
+});
+
+/**
+ * Root resolvers for `schema`; subscriptions need the indexer running in this process
+ */
+export function resolvers(queries: IndexQueries, indexer?: ChainIndexer) {
+    const recovery = (r: Recovery) => ({
+        ...r,
+        approvalHistory: async (args: PageArgs) => {
+            const page = pageOf(args);
+            return connection((await queries.approvals(r.id, page)).map(approval), page);
+        },
+    });
+
+    const query = {
+        guardians: async (args: PageArgs & { account: string; activeOnly: boolean }) => {
+            const page = pageOf(args);
+            return connection((await queries.guardiansOf(args.account, args.activeOnly, page)).map(guardian), page);
+        },
+        accountsGuardedBy: async (args: PageArgs & { guardian: string }) => {
+            const page = pageOf(args);
+            return connection((await queries.accountsGuardedBy(args.guardian, page)).map(guardian), page);
+        },
+        openRecoveries: async (args: PageArgs & { account?: string }) => {
+            const page = pageOf(args);
+            return connection((await queries.openRecoveries(args.account, page)).map(recovery), page);
+        },
+        recovery: async ({ id }: { id: string }) => {
+            const r = await queries.recovery(id);
+            return r && recovery(r);
+        },
+        approvals: async (args: PageArgs & { recoveryId: string }) => {
+            const page = pageOf(args);
+            return connection((await queries.approvals(args.recoveryId, page)).map(approval), page);
+        },
+    };
+
+    const subscription = {
+        recoveryUpdated: async function* ({ account }: { account?: string }) {
+            if (!indexer) throw new Error('Subscriptions need the embedded indexer (GRAPHQL_EMBED_INDEXER=true)');
+            for await (const [row] of on(indexer.updates, 'recovery')) {
+                if (account && row.account !== account) continue;
+                yield { recoveryUpdated: recovery(queries.toRecovery(row, await queries.tip())) };
+            }
+        },
+    };
+
+    return { query, subscription };
+}
@@ -2,6 +2,8 @@ import express from 'express';
 import cors from 'cors';
 import { config } from './config';
 import routes from './routes';
+import { queryCache } from './services';
+import { RegistryEventFeed } from './watchd/feed';
 
 // Create Express app
 const app = express();
@@ -31,6 +33,13 @@ app.use((err: Error, req: express.Request, res: express.Response, next: express.
     });
 });
 
+// Keep cached account reads across blocks until the event stream invalidates them
+if (queryCache && config.cache.eventsUrl) {
+    const feed = new RegistryEventFeed(config.cache.eventsUrl, 'cache');
+    queryCache.follow(feed);
+    feed.start().catch((error) => console.error(`[cache] Event feed stopped: ${error}`));
+}
+
 // Start server
 const PORT = config.port;
 app.listen(PORT, () => {
@@ -72,6 +81,12 @@ API Endpoints:
   Notifications:
   POST /api/recovery/notify-guardians - Send email to guardians
 
+  Wallet API (v1, see /api/v1/openapi.json):
+  GET  /api/v1/accounts/:account/config - Guardian configuration
+  GET  /api/v1/recoveries/:id       - Recovery status
+  POST /api/v1/deploys/prepare      - Build unsigned deploy or transaction
+  POST /api/v1/deploys              - Submit signed deploy
+
 Email: ${process.env.SMTP_USER ? '✓ Configured' : '⚠ Not configured'}
 `);
 });
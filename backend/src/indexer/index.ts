@@ -0,0 +1,252 @@
+/**
+ * Chain indexer - ingests the registry's CES events and deploy results into the
+ * relational schema in sql/indexer.sql, for queries the contract can't answer
+ *
+ * Deploys are buffered until their BlockAdded arrives and then applied block by
+ * block; the SSE cursor only advances past whole blocks, so a restart resumes
+ * cleanly. If a height comes back with another block hash, everything indexed
+ * from that height is dropped and the affected accounts are replayed from the
+ * stored raw events.
+ *
+ * Run with `npm run indexer` (see the INDEXER_* settings in .env.example).
+ */
+import { EventEmitter } from 'events';
+import { config } from '../config';
+import { casperService } from '../services';
+import { dictionaryWrites, extractEvents, RegistryEvent } from '../watchd/ces';
+import { SseClient, SseMessage } from '../watchd/sse';
+import { IndexStore, openStore, Row } from './store';
+
+/** Registry entry points that run a recovery rather than change an account's configuration */
+const RECOVERY_FLOW = new Set([
+    'start_recovery', 'approve', 'approve_signed', 'submit_signed_approvals', 'approve_frost', 'reject', 'finalize', 'finalize_removal', 'veto', 'confirm',
+    'heartbeat', 'post_bond', 'fund_rewards', 'withdraw_rewards', 'arbiter_extend', 'arbiter_cancel',
+    'arbiter_approve', 'propose_slash', 'dispute_slash', 'cancel_slash', 'execute_slash', 'attest_share',
+    'flag_recovery', 'withdraw_flag', 'arbiter_dismiss_flags',
+]);
+
+export class ChainIndexer {
+    readonly store: IndexStore = openStore();
+    /** Emits 'recovery' with the row each time a recovery is written */
+    readonly updates = new EventEmitter();
+    private eventsUrefAddr = '';
+    private dictUrefAddr = '';
+    /** DeployProcessed bodies waiting for their block, by block hash */
+    private pending = new Map<string, any[]>();
+    private sse = new SseClient(
+        config.indexer.eventsUrl,
+        (message) => this.handle(message),
+        (error) => console.warn(`[indexer] Event stream dropped, reconnecting: ${error}`)
+    );
+
+    async start(): Promise<void> {
+        const registry = config.contract.recoveryRegistryHash;
+        if (!registry) throw new Error('RECOVERY_REGISTRY_HASH is not set');
+        this.eventsUrefAddr = await casperService.getContractNamedUref(registry, '__events');
+        this.dictUrefAddr = await casperService.getContractNamedUref(registry, 'd');
+
+        const cursor = await this.getState('cursor');
+        console.log(`[indexer] Syncing into ${config.indexer.db}` + (cursor ? `, resuming after event ${cursor}` : ''));
+        await this.sse.run(cursor ? Number(cursor) + 1 : undefined);
+    }
+
+    stop(): void {
+        this.sse.stop();
+    }
+
+    private async getState(name: string): Promise<string | undefined> {
+        const [row] = await this.store.select('indexer_state', { name });
+        return row?.value as string | undefined;
+    }
+
+    private async setState(name: string, value: string): Promise<void> {
+        await this.store.upsert('indexer_state', [{ name, value }], ['name']);
+    }
+
+    private async handle(message: SseMessage): Promise<void> {
+        const { DeployProcessed, BlockAdded } = message.data ?? {};
+        if (DeployProcessed) {
+            const deploys = this.pending.get(DeployProcessed.block_hash) ?? [];
+            deploys.push(DeployProcessed);
+            this.pending.set(DeployProcessed.block_hash, deploys);
+            return;
+        }
+        if (!BlockAdded) return;
+
+        const block = BlockAdded.block?.Version2 ?? BlockAdded.block?.Version1 ?? BlockAdded.block;
+        const hash: string = BlockAdded.block_hash ?? block.hash;
+        const deploys = this.pending.get(hash) ?? [];
+        this.pending.delete(hash);
+        await this.applyBlock(Number(block.header.height), hash, Date.parse(block.header.timestamp), deploys);
+        if (message.id !== undefined) await this.setState('cursor', String(message.id));
+    }
+
+    private async applyBlock(height: number, hash: string, timestamp: number, deploys: any[]): Promise<void> {
+        const [known] = await this.store.select('indexed_blocks', { height });
+        if (known?.hash === hash) return;
+        if (known) {
+            console.warn(`[indexer] Block ${height} changed from ${known.hash} to ${hash}, rolling back`);
+            await this.rollback(height);
+        }
+
+        for (const deploy of deploys) await this.ingestDeploy(deploy, height);
+        await this.store.upsert('indexed_blocks', [{ height, hash, timestamp }], ['height']);
+    }
+
+    private async ingestDeploy(deploy: any, height: number): Promise<void> {
+        const result = deploy.execution_result;
+        const failed = result?.Failure ?? result?.Version1?.Failure ?? result?.Version2?.error_message;
+        if (failed) return;
+
+        const seeds = new Set(dictionaryWrites(result).map((w) => w.seed));
+        if (!seeds.has(this.dictUrefAddr) && !seeds.has(this.eventsUrefAddr)) return;
+
+        for (const event of extractEvents(result, this.eventsUrefAddr)) {
+            await this.applyEvent(event, deploy.deploy_hash, height, true);
+        }
+        await this.recordConfigChange(deploy.deploy_hash, height);
+    }
+
+    /** Fold one event into the derived tables; `record` also stores it as a raw event */
+    private async applyEvent(event: RegistryEvent, deployHash: string, height: number, record: boolean): Promise<void> {
+        const recovery = event.id ? (await this.store.select('recoveries', { id: event.id }))[0] : undefined;
+        const account = event.account ?? (recovery?.account as string | undefined) ?? null;
+        if (record) {
+            await this.store.upsert('registry_events', [{
+                event_index: event.index!, height, deploy_hash: deployHash, account, tenant: event.tenant ?? 0,
+                name: event.name, payload: JSON.stringify(event),
+            }], ['event_index']);
+        }
+        // The derived tables hold tenant 0, the records the registry reads below come from
+        if (!account || (event.tenant ?? 0) !== 0) return;
+
+        const name = event.name.replace(/V\d+$/, '');
+        switch (name) {
+            case 'GuardiansInitialized': {
+                await this.store.upsert('guarded_accounts', [{
+                    account, threshold: event.threshold!, initialized_height: height, height,
+                }], ['account']);
+                // The event carries no guardian list; take the registry's current one
+                const guardians: any[] = (await casperService.readRegistryItem(`gAccountHash(${account})`)) ?? [];
+                const current = new Set(guardians.map((g) => (typeof g === 'string' ? g : Buffer.from(g).toString('hex'))));
+                for (const row of await this.store.select('account_guardians', { account })) {
+                    if (!current.has(row.guardian as string)) await this.setGuardian(account, row.guardian as string, false, height);
+                }
+                for (const guardian of current) await this.setGuardian(account, guardian, true, height);
+                break;
+            }
+            case 'GuardianAdded':
+                await this.setGuardian(account, event.guardian!, true, height);
+                break;
+            case 'GuardianRemoved':
+                await this.setGuardian(account, event.guardian!, false, height);
+                break;
+            case 'GuardianKeyRotated':
+                await this.setGuardian(account, event.guardian!, false, height);
+                await this.setGuardian(account, event.newGuardian!, true, height);
+                break;
+            case 'RecoveryStarted': {
+                const expiresAt = Number((await casperService.readRegistryItem(`re${event.id}`))?.toString() ?? 0);
+                const byHeight = (await casperService.readRegistryItem(`rm${event.id}`)) === true;
+                await this.saveRecovery({
+                    id: event.id!, account, new_key: event.newKey ?? null, status: 'pending', approvals: 0,
+                    started_height: height, started_deploy: deployHash, expires_at: expiresAt, by_height: byHeight, height,
+                });
+                break;
+            }
+            case 'RecoveryApproved':
+                await this.store.upsert('recovery_approvals', [{
+                    recovery_id: event.id!, guardian: event.guardian!, approvals: event.approvals!,
+                    deploy_hash: deployHash, height,
+                }], ['recovery_id', 'guardian']);
+                if (recovery) await this.saveRecovery({ ...recovery, approvals: event.approvals!, height });
+                break;
+            case 'RecoveryFrostApproved':
+                // One row for the whole group, under its key; the approval count is unchanged
+                await this.store.upsert('recovery_approvals', [{
+                    recovery_id: event.id!, guardian: event.guardian!, approvals: recovery?.approvals ?? 0,
+                    deploy_hash: deployHash, height,
+                }], ['recovery_id', 'guardian']);
+                if (recovery) await this.saveRecovery({ ...recovery, height });
+                break;
+            case 'RecoveryFinalized':
+            case 'RecoveryCancelled': {
+                const status = name === 'RecoveryFinalized' ? 'finalized' : event.vetoed ? 'vetoed' : 'cancelled';
+                if (recovery) await this.saveRecovery({ ...recovery, status, height });
+                break;
+            }
+        }
+    }
+
+    private async saveRecovery(row: Row): Promise<void> {
+        await this.store.upsert('recoveries', [row], ['id']);
+        this.updates.emit('recovery', row);
+    }
+
+    private async setGuardian(account: string, guardian: string, active: boolean, height: number): Promise<void> {
+        const [row] = await this.store.select('account_guardians', { account, guardian });
+        const addedHeight = active && !row?.active ? height : (row?.added_height ?? height);
+        await this.store.upsert('account_guardians', [{
+            account, guardian, active, added_height: addedHeight, height,
+        }], ['account', 'guardian']);
+    }
+
+    /** Record the entry point and arguments of a configuration call to the registry */
+    private async recordConfigChange(deployHash: string, height: number): Promise<void> {
+        const info: any = await casperService.getClient().nodeClient.getDeployInfo(deployHash);
+        const session = info?.deploy?.session ?? {};
+        const call = session.StoredContractByHash ?? session.StoredVersionedContractByHash
+            ?? session.StoredContractByName ?? session.StoredVersionedContractByName;
+        if (!call?.entry_point || RECOVERY_FLOW.has(call.entry_point)) return;
+
+        const args: Record<string, unknown> = Object.fromEntries(
+            (call.args ?? []).map(([name, value]: [string, any]) => [name, value?.parsed ?? value?.bytes])
+        );
+        await this.store.upsert('config_changes', [{
+            deploy_hash: deployHash,
+            account: typeof args.account === 'string' ? args.account.replace(/^account-hash-/, '') : null,
+            entry_point: call.entry_point,
+            args: JSON.stringify(args),
+            height,
+        }], ['deploy_hash']);
+    }
+
+    /** Drop everything indexed at or above `height` and rebuild the accounts it touched */
+    private async rollback(height: number): Promise<void> {
+        const dropped = await this.store.select('registry_events', {}, { fromHeight: height });
+        const accounts = new Set(dropped.map((e) => e.account).filter((a): a is string => typeof a === 'string'));
+
+        for (const table of ['registry_events', 'config_changes', 'indexed_blocks']) {
+            await this.store.remove(table, {}, height);
+        }
+        for (const account of accounts) await this.rebuild(account);
+    }
+
+    private async rebuild(account: string): Promise<void> {
+        for (const recovery of await this.store.select('recoveries', { account })) {
+            await this.store.remove('recovery_approvals', { recovery_id: recovery.id });
+        }
+        for (const table of ['recoveries', 'account_guardians', 'guarded_accounts']) {
+            await this.store.remove(table, { account });
+        }
+
+        const events: Row[] = await this.store.select('registry_events', { account }, { orderBy: 'event_index' });
+        for (const row of events) {
+            await this.applyEvent(JSON.parse(row.payload as string), row.deploy_hash as string, Number(row.height), false);
+        }
+    }
+}
+
+if (require.main === module) {
+    const indexer = new ChainIndexer();
+    for (const signal of ['SIGINT', 'SIGTERM'] as const) {
+        process.on(signal, () => {
+            indexer.stop();
+            process.exit(0);
+        });
+    }
+    indexer.start().catch((error) => {
+        console.error(`[indexer] ${error}`);
+        process.exit(1);
+    });
+}
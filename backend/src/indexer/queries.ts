@@ -0,0 +1,91 @@
+import { IndexStore, Row } from './store';
+
+export interface Page {
+    limit: number;
+    offset: number;
+}
+
+export interface Recovery {
+    id: string;
+    account: string;
+    newKey: string | null;
+    status: string;
+    approvals: number;
+    startedHeight: number;
+    startedDeploy: string;
+    expiresAt: number;
+    byHeight: boolean;
+    /** Until expiry at the newest indexed block, in ms or blocks; null if it never expires */
+    timeRemaining: number | null;
+}
+
+/**
+ * Read-side queries over the indexer's tables, shared by the API servers
+ */
+export class IndexQueries {
+    constructor(private store: IndexStore) {}
+
+    /** Height and time (ms) of the newest indexed block */
+    async tip(): Promise<{ height: number; timestamp: number } | null> {
+        const [block] = await this.store.select('indexed_blocks', {}, { orderBy: 'height', descending: true, limit: 1 });
+        return block ? { height: Number(block.height), timestamp: Number(block.timestamp) } : null;
+    }
+
+    async guardiansOf(account: string, activeOnly: boolean, page: Page): Promise<Row[]> {
+        const where: Row = activeOnly ? { account, active: true } : { account };
+        return this.store.select('account_guardians', where, { orderBy: 'guardian', ...page });
+    }
+
+    async accountsGuardedBy(guardian: string, page: Page): Promise<Row[]> {
+        return this.store.select('account_guardians', { guardian, active: true }, { orderBy: 'account', ...page });
+    }
+
+    async account(account: string): Promise<Row | null> {
+        const [row] = await this.store.select('guarded_accounts', { account });
+        return row ?? null;
+    }
+
+    async recovery(id: string): Promise<Recovery | null> {
+        const [row] = await this.store.select('recoveries', { id });
+        return row ? this.toRecovery(row, await this.tip()) : null;
+    }
+
+    async openRecoveries(account: string | undefined, page: Page): Promise<Recovery[]> {
+        const where: Row = account ? { account, status: 'pending' } : { status: 'pending' };
+        const rows = await this.store.select('recoveries', where, { orderBy: 'started_height', ...page });
+        const tip = await this.tip();
+        return rows.map((row) => this.toRecovery(row, tip));
+    }
+
+    async recoveriesOf(account: string, page: Page): Promise<Recovery[]> {
+        const rows = await this.store.select('recoveries', { account }, { orderBy: 'started_height', descending: true, ...page });
+        const tip = await this.tip();
+        return rows.map((row) => this.toRecovery(row, tip));
+    }
+
+    async approvals(recoveryId: string, page: Page): Promise<Row[]> {
+        return this.store.select('recovery_approvals', { recovery_id: recoveryId }, { orderBy: 'height', ...page });
+    }
+
+    async configChanges(account: string, page: Page): Promise<Row[]> {
+        return this.store.select('config_changes', { account }, { orderBy: 'height', descending: true, ...page });
+    }
+
+    toRecovery(row: Row, tip: { height: number; timestamp: number } | null): Recovery {
+        const expiresAt = Number(row.expires_at);
+        const byHeight = Boolean(row.by_height);
+        const now = byHeight ? tip?.height : tip?.timestamp;
+        return {
+            id: row.id as string,
+            account: row.account as string,
+            newKey: (row.new_key as string | null) ?? null,
+            status: row.status as string,
+            approvals: Number(row.approvals),
+            startedHeight: Number(row.started_height),
+            startedDeploy: row.started_deploy as string,
+            expiresAt,
+            byHeight,
+            timeRemaining: expiresAt === 0 || now === undefined ? null : Math.max(expiresAt - now, 0),
+        };
+    }
+}
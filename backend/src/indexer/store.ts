@@ -0,0 +1,121 @@
+import fs from 'fs';
+import path from 'path';
+import { createClient, SupabaseClient } from '@supabase/supabase-js';
+import { config } from '../config';
+
+export type Value = string | number | boolean | null;
+export type Row = Record<string, Value>;
+
+export interface SelectOptions {
+    /** Only rows whose `height` is at least this */
+    fromHeight?: number;
+    orderBy?: string;
+    descending?: boolean;
+    limit?: number;
+    offset?: number;
+}
+
+/**
+ * The few table operations the indexer needs, over the schema in sql/indexer.sql
+ */
+export interface IndexStore {
+    upsert(table: string, rows: Row[], keys: string[]): Promise<void>;
+    select(table: string, where?: Row, options?: SelectOptions): Promise<Row[]>;
+    /** Delete rows matching `where` and, if given, with `height >= fromHeight` */
+    remove(table: string, where?: Row, fromHeight?: number): Promise<void>;
+}
+
+/**
+ * SQLite through Node's built-in `node:sqlite` (Node 22.5+)
+ */
+export class SqliteStore implements IndexStore {
+    private db: any;
+
+    constructor(file: string) {
+        // eslint-disable-next-line @typescript-eslint/no-var-requires
+        const { DatabaseSync } = require('node:sqlite');
+        this.db = new DatabaseSync(file);
+        this.db.exec(fs.readFileSync(path.resolve(__dirname, '../../sql/indexer.sql'), 'utf8'));
+    }
+
+    private static bind(value: Value): string | number | null {
+        return typeof value === 'boolean' ? Number(value) : value;
+    }
+
+    private static where(where: Row = {}, fromHeight?: number): [string, Value[]] {
+        const clauses = Object.keys(where).map((k) => `${k} = ?`);
+        const params = Object.values(where);
+        if (fromHeight !== undefined) {
+            clauses.push('height >= ?');
+            params.push(fromHeight);
+        }
+        return [clauses.length ? ` WHERE ${clauses.join(' AND ')}` : '', params];
+    }
+
+    async upsert(table: string, rows: Row[], keys: string[]): Promise<void> {
+        for (const row of rows) {
+            const cols = Object.keys(row);
+            const updates = cols.filter((c) => !keys.includes(c)).map((c) => `${c} = excluded.${c}`);
+            const sql = `INSERT INTO ${table} (${cols.join(', ')}) VALUES (${cols.map(() => '?').join(', ')})` +
+                ` ON CONFLICT (${keys.join(', ')}) DO ${updates.length ? `UPDATE SET ${updates.join(', ')}` : 'NOTHING'}`;
+            this.db.prepare(sql).run(...Object.values(row).map(SqliteStore.bind));
+        }
+    }
+
+    async select(table: string, where?: Row, options: SelectOptions = {}): Promise<Row[]> {
+        const [clause, params] = SqliteStore.where(where, options.fromHeight);
+        let sql = `SELECT * FROM ${table}${clause}`;
+        if (options.orderBy) sql += ` ORDER BY ${options.orderBy}${options.descending ? ' DESC' : ''}`;
+        if (options.limit !== undefined) sql += ` LIMIT ${Number(options.limit)} OFFSET ${Number(options.offset ?? 0)}`;
+        return this.db.prepare(sql).all(...params.map(SqliteStore.bind));
+    }
+
+    async remove(table: string, where?: Row, fromHeight?: number): Promise<void> {
+        const [clause, params] = SqliteStore.where(where, fromHeight);
+        this.db.prepare(`DELETE FROM ${table}${clause}`).run(...params.map(SqliteStore.bind));
+    }
+}
+
+/**
+ * Postgres through the Supabase client; apply sql/indexer.sql to the project first
+ */
+export class PostgresStore implements IndexStore {
+    constructor(private client: SupabaseClient) {}
+
+    async upsert(table: string, rows: Row[], keys: string[]): Promise<void> {
+        if (rows.length === 0) return;
+        const { error } = await this.client.from(table).upsert(rows, { onConflict: keys.join(',') });
+        if (error) throw new Error(`upsert ${table}: ${error.message}`);
+    }
+
+    async select(table: string, where: Row = {}, options: SelectOptions = {}): Promise<Row[]> {
+        let query = this.client.from(table).select('*').match(where);
+        if (options.fromHeight !== undefined) query = query.gte('height', options.fromHeight);
+        if (options.orderBy) query = query.order(options.orderBy, { ascending: !options.descending });
+        if (options.limit !== undefined) {
+            const from = options.offset ?? 0;
+            query = query.range(from, from + options.limit - 1);
+        }
+        const { data, error } = await query;
+        if (error) throw new Error(`select ${table}: ${error.message}`);
+        return data ?? [];
+    }
+
+    async remove(table: string, where: Row = {}, fromHeight?: number): Promise<void> {
+        let query = this.client.from(table).delete().match(where);
+        if (fromHeight !== undefined) query = query.gte('height', fromHeight);
+        const { error } = await query;
+        if (error) throw new Error(`delete ${table}: ${error.message}`);
+    }
+}
+
+/** The store selected by INDEXER_DB */
+export function openStore(): IndexStore {
+    if (config.indexer.db === 'postgres') {
+        const url = process.env.SUPABASE_URL || '';
+        const key = process.env.SUPABASE_SERVICE_ROLE_KEY || '';
+        if (!url || !key) throw new Error('The postgres indexer store needs SUPABASE_URL and SUPABASE_SERVICE_ROLE_KEY');
+        return new PostgresStore(createClient(url, key));
+    }
+    return new SqliteStore(config.indexer.sqlitePath);
+}
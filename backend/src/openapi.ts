@@ -0,0 +1,15 @@
+/**
+ * Write the /api/v1 OpenAPI document to a file for client code generation
+ *
+ * Run with `npm run openapi [-- <path>]` (default openapi/v1.json) as part of
+ * each release, so partner clients are regenerated against the version they
+ * will talk to.
+ */
+import { mkdirSync, writeFileSync } from 'fs';
+import path from 'path';
+import { api, API_VERSION } from './routes/v1.routes';
+
+const out = path.resolve(process.argv[2] ?? 'openapi/v1.json');
+mkdirSync(path.dirname(out), { recursive: true });
+writeFileSync(out, JSON.stringify(api.document({ title: 'SentinelX API', version: API_VERSION }, '/api/v1'), null, 2) + '\n');
+console.log(`OpenAPI ${API_VERSION} document written to ${out}`);
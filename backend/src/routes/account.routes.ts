@@ -1,5 +1,5 @@
 import { Router, Request, Response } from 'express';
-import { casperService } from '../services';
+import { casperService, attestationService } from '../services';
 import { ApiResponse } from '../types';
 
 const router = Router();
@@ -44,6 +44,26 @@ router.get('/deploy/:deployHash', async (req: Request, res: Response) => {
     }
 });
 
+/**
+ * POST /account/attestation/verify
+ * Check the signature on an exported guardianship attestation
+ */
+router.post('/attestation/verify', async (req: Request, res: Response) => {
+    try {
+        const valid = attestationService.verifyAttestation(req.body);
+
+        res.json({
+            success: true,
+            data: { valid },
+        } as ApiResponse);
+    } catch (error) {
+        res.status(400).json({
+            success: false,
+            error: `Failed to verify attestation: ${error}`,
+        } as ApiResponse);
+    }
+});
+
 // ========================================
 // PARAMETERIZED ROUTES (wildcards)
 // ========================================
@@ -92,6 +112,28 @@ router.get('/:publicKey/keys', async (req: Request, res: Response) => {
     }
 });
 
+/**
+ * GET /account/:publicKey/attestation
+ * Export a signed, state-root-proven statement of the account's guardianship config
+ */
+router.get('/:publicKey/attestation', async (req: Request, res: Response) => {
+    try {
+        const { publicKey } = req.params;
+
+        const attestation = await attestationService.exportAttestation(publicKey);
+
+        res.json({
+            success: true,
+            data: attestation,
+        } as ApiResponse);
+    } catch (error) {
+        res.status(500).json({
+            success: false,
+            error: `Failed to export attestation: ${error}`,
+        } as ApiResponse);
+    }
+});
+
 /**
  * GET /account/:publicKey/has-guardians
  * Check if account has guardians registered (NO TRANSACTION - direct state query)
@@ -4,6 +4,7 @@ import sessionRoutes from './session.routes';
 import accountRoutes from './account.routes';
 import userRoutes from './user.routes';
 import multisigRoutes from './multisig-deploy.routes';
+import v1Routes from './v1.routes';
 
 const router = Router();
 
@@ -13,6 +14,7 @@ router.use('/session', sessionRoutes);
 router.use('/account', accountRoutes);
 router.use('/user', userRoutes);
 router.use('/multisig', multisigRoutes);
+router.use('/v1', v1Routes);
 
 
 // Health check
@@ -0,0 +1,109 @@
+import { Router, Request, Response } from 'express';
+import { ApiResponse } from '../types';
+
+type Schema = Record<string, unknown>;
+
+export interface Operation {
+    /** Stable name generated clients use for the method; never change once released */
+    operationId: string;
+    summary: string;
+    tags?: string[];
+    /** Path parameter descriptions, by name */
+    params?: Record<string, string>;
+    /** Query parameters: name -> [description, schema] */
+    query?: Record<string, [string, Schema]>;
+    body?: Schema;
+    /** Schema of `data` in the success response; may `$ref` a schema registered with `schema()` */
+    response: Schema;
+}
+
+type Handler = (req: Request) => Promise<unknown>;
+
+export class HttpError extends Error {
+    constructor(public status: number, message: string) {
+        super(message);
+    }
+}
+
+/**
+ * Express router that records an OpenAPI operation for each handler it mounts
+ *
+ * Handlers return the response `data`; thrown HttpErrors become their status,
+ * anything else a 500, all in the usual `{ success, data | error }` envelope.
+ */
+export class ApiRouter {
+    readonly router = Router();
+    private paths: Record<string, Record<string, unknown>> = {};
+    private schemas: Record<string, Schema> = {};
+
+    /** Register a named component schema, returning a `$ref` to it */
+    schema(name: string, schema: Schema): Schema {
+        this.schemas[name] = schema;
+        return { $ref: `#/components/schemas/${name}` };
+    }
+
+    get(path: string, op: Operation, handler: Handler): void {
+        this.add('get', path, op, handler);
+    }
+
+    post(path: string, op: Operation, handler: Handler): void {
+        this.add('post', path, op, handler);
+    }
+
+    private add(method: 'get' | 'post', path: string, op: Operation, handler: Handler): void {
+        this.router[method](path, async (req: Request, res: Response) => {
+            try {
+                res.json({ success: true, data: await handler(req) } as ApiResponse);
+            } catch (error) {
+                const status = error instanceof HttpError ? error.status : 500;
+                res.status(status).json({ success: false, error: String((error as Error)?.message ?? error) } as ApiResponse);
+            }
+        });
+
+        const parameters = [
+            ...Object.entries(op.params ?? {}).map(([name, description]) => ({
+                name, in: 'path', required: true, description, schema: { type: 'string' },
+            })),
+            ...Object.entries(op.query ?? {}).map(([name, [description, schema]]) => ({
+                name, in: 'query', required: false, description, schema,
+            })),
+        ];
+        const envelope = (data: Schema) => ({
+            type: 'object',
+            required: ['success', 'data'],
+            properties: { success: { type: 'boolean', enum: [true] }, data },
+        });
+
+        const openApiPath = path.replace(/:(\w+)/g, '{$1}');
+        this.paths[openApiPath] = {
+            ...this.paths[openApiPath],
+            [method]: {
+                operationId: op.operationId,
+                summary: op.summary,
+                tags: op.tags,
+                parameters,
+                ...(op.body && { requestBody: { required: true, content: { 'application/json': { schema: op.body } } } }),
+                responses: {
+                    200: { description: 'OK', content: { 'application/json': { schema: envelope(op.response) } } },
+                    default: { description: 'Error', content: { 'application/json': { schema: { $ref: '#/components/schemas/Error' } } } },
+                },
+            },
+        };
+    }
+
+    /** OpenAPI 3 document of every operation mounted so far */
+    document(info: { title: string; version: string }, serverUrl: string): Schema {
+        const error = {
+            type: 'object',
+            required: ['success', 'error'],
+            properties: { success: { type: 'boolean', enum: [false] }, error: { type: 'string' } },
+        };
+        return {
+            openapi: '3.0.3',
+            info,
+            servers: [{ url: serverUrl }],
+            paths: this.paths,
+            components: { schemas: { ...this.schemas, Error: error } },
+        };
+    }
+}
@@ -0,0 +1,462 @@
+import { readFileSync } from 'fs';
+import path from 'path';
+import { Request } from 'express';
+import { config } from '../config';
+import { casperService, deployService } from '../services';
+import { IndexQueries } from '../indexer/queries';
+import { openStore } from '../indexer/store';
+import { RegistryEvent } from '../watchd/ces';
+import { RegistryEventFeed } from '../watchd/feed';
+import { EventFilter } from '../watchd/filter';
+import { ApiRouter, HttpError } from './openapi';
+
+/**
+ * Versioned REST API for wallet backends (/api/v1)
+ *
+ * Config and recovery lookups read the chain indexer's tables; deploys are
+ * prepared unsigned for the caller to sign and then submitted here.
+ * GET /api/v1/openapi.json describes every endpoint.
+ */
+export const api = new ApiRouter();
+
+/** Backend release, so generated clients can be pinned to the API they were built from */
+export const API_VERSION: string = JSON.parse(
+    readFileSync(path.resolve(__dirname, '../../package.json'), 'utf8')
+).version;
+
+let queries: IndexQueries | undefined;
+
+/** Opened on first use so the API runs without an indexer database until one is needed */
+function index(): IndexQueries {
+    queries ??= new IndexQueries(openStore());
+    return queries;
+}
+
+/** Account hash hex of a public key or account hash */
+function accountHash(value: string): string {
+    try {
+        return casperService.accountHashHex(value);
+    } catch {
+        throw new HttpError(400, `Not a public key or account hash: ${value}`);
+    }
+}
+
+function page(req: Request) {
+    const limit = Math.min(Math.max(parseInt(String(req.query.limit ?? '20'), 10) || 20, 1), 100);
+    const offset = Math.max(parseInt(String(req.query.offset ?? '0'), 10) || 0, 0);
+    return { limit, offset };
+}
+
+const paging: Record<string, [string, Record<string, unknown>]> = {
+    limit: ['Page size, at most 100', { type: 'integer', default: 20 }],
+    offset: ['Rows to skip', { type: 'integer', default: 0 }],
+};
+
+const recoveryProperties = {
+    id: { type: 'string', description: 'Decimal recovery id' },
+    account: { type: 'string' },
+    newKey: { type: 'string', nullable: true },
+    status: { type: 'string', enum: ['pending', 'finalized', 'vetoed', 'cancelled'] },
+    approvals: { type: 'integer' },
+    startedHeight: { type: 'integer' },
+    startedDeploy: { type: 'string' },
+    expiresAt: { type: 'integer' },
+    byHeight: { type: 'boolean' },
+    timeRemaining: { type: 'integer', nullable: true },
+};
+
+const recoverySchema = api.schema('Recovery', { type: 'object', properties: recoveryProperties });
+
+const recoveryDetailSchema = api.schema('RecoveryDetail', {
+    type: 'object',
+    properties: {
+        ...recoveryProperties,
+        timelock: {
+            type: 'object',
+            nullable: true,
+            description: "Read from the registry; on the recovery's clock (ms, or blocks if byHeight). Null if the node couldn't be read",
+            properties: {
+                thresholdReached: { type: 'boolean' },
+                approvedAt: { type: 'integer', description: 'When the threshold was reached; 0 before' },
+                unlockAt: { type: 'integer', description: 'End of the settings delay; 0 for none' },
+                vetoWindow: { type: 'integer', description: "Owner's veto window after the threshold is reached" },
+                executableAt: { type: 'integer', nullable: true, description: 'When finalize stops reverting with TooEarly; null before the threshold' },
+                remaining: { type: 'integer', nullable: true, description: 'Until executableAt, at the newest indexed block' },
+            },
+        },
+        approvalHistory: {
+            type: 'array',
+            items: {
+                type: 'object',
+                properties: { guardian: { type: 'string' }, deployHash: { type: 'string' }, height: { type: 'integer' } },
+            },
+        },
+    },
+});
+
+const argSchema = api.schema('TypedArg', {
+    type: 'object',
+    required: ['name', 'type', 'value'],
+    properties: {
+        name: { type: 'string' },
+        type: { type: 'string', enum: ['u8', 'u32', 'u64', 'u256', 'u512', 'bool', 'string', 'account', 'account_list', 'public_key', 'public_key_list', 'bytes', 'signed_approval_list'] },
+        value: {},
+    },
+});
+
+const configSchema = api.schema('AccountConfig', {
+    type: 'object',
+    properties: {
+        account: { type: 'string' },
+        threshold: { type: 'integer', nullable: true },
+        guardians: { type: 'array', items: { type: 'string' } },
+        changes: {
+            type: 'array',
+            items: {
+                type: 'object',
+                properties: {
+                    entryPoint: { type: 'string' }, args: { type: 'object' }, deployHash: { type: 'string' }, height: { type: 'integer' },
+                },
+            },
+        },
+    },
+});
+
+const preparedDeploySchema = api.schema('PreparedDeploy', {
+    type: 'object',
+    description: 'A Casper 2.0 TransactionV1 when the node runs protocol 2.0 or later (CASPER_TRANSACTIONS=auto), else a deploy',
+    properties: {
+        kind: { type: 'string', enum: ['deploy', 'transaction'] },
+        deployHash: { type: 'string', description: 'Hash of the deploy or transaction, which signers sign' },
+        deploy: { type: 'object' },
+        transaction: { type: 'object', description: 'TransactionV1 JSON, { Version1: { hash, payload, approvals } }' },
+        bytes: { type: 'string', description: 'Hex' },
+    },
+});
+
+api.schema('RegistryRevert', {
+    type: 'object',
+    description: 'Registry error a failed deploy reverted with',
+    properties: {
+        code: { type: 'integer' },
+        name: { type: 'string', description: 'RegistryError variant' },
+        description: { type: 'string' },
+        entryPoint: { type: 'string' },
+        args: { type: 'object' },
+        message: { type: 'string', description: "The node's error message" },
+    },
+});
+
+const deployStatusSchema = api.schema('DeployStatus', {
+    type: 'object',
+    properties: {
+        deployHash: { type: 'string' },
+        status: { type: 'string', enum: ['pending', 'success', 'failed'] },
+        executionResult: { type: 'object' },
+        errorMessage: { type: 'string' },
+        revert: { $ref: '#/components/schemas/RegistryRevert' },
+    },
+});
+
+// ============================================================================
+// Config
+// ============================================================================
+
+api.get('/network', {
+    operationId: 'getNetwork',
+    summary: 'Chain and contract this backend serves',
+    tags: ['config'],
+    response: {
+        type: 'object',
+        properties: {
+            chainName: { type: 'string' },
+            registryHash: { type: 'string', nullable: true, description: 'Recovery registry contract hash' },
+            protocolVersion: { type: 'string', nullable: true, description: "The node's protocol version; null if it can't be reached" },
+            apiVersion: { type: 'string' },
+        },
+    },
+}, async () => ({
+    chainName: config.casper.chainName,
+    registryHash: config.contract.recoveryRegistryHash ?? null,
+    protocolVersion: await casperService.nodeProtocolVersion().catch(() => null),
+    apiVersion: API_VERSION,
+}));
+
+api.get('/accounts/:account/config', {
+    operationId: 'getAccountConfig',
+    summary: 'Guardian configuration of an account',
+    tags: ['config'],
+    params: { account: 'Public key or account hash' },
+    response: configSchema,
+}, async (req) => {
+    const account = accountHash(req.params.account);
+    const row = await index().account(account);
+    if (!row) throw new HttpError(404, 'Account has no guardians registered');
+    const guardians = await index().guardiansOf(account, true, { limit: 1000, offset: 0 });
+    const changes = await index().configChanges(account, { limit: 20, offset: 0 });
+    return {
+        account,
+        threshold: Number(row.threshold),
+        guardians: guardians.map((g) => g.guardian),
+        changes: changes.map((c) => ({
+            entryPoint: c.entry_point, args: JSON.parse(c.args as string), deployHash: c.deploy_hash, height: Number(c.height),
+        })),
+    };
+});
+
+api.get('/guardians/:guardian/accounts', {
+    operationId: 'listGuardedAccounts',
+    summary: 'Accounts a key currently guards',
+    tags: ['config'],
+    params: { guardian: 'Public key or account hash' },
+    query: paging,
+    response: { type: 'array', items: { type: 'string' } },
+}, async (req) => (await index().accountsGuardedBy(accountHash(req.params.guardian), page(req))).map((g) => g.account));
+
+api.get('/accounts/:account/relayers', {
+    operationId: 'listRelayers',
+    summary: 'Relayers allowed to submit meta-approvals for an account; empty if anyone may',
+    tags: ['config'],
+    params: { account: 'Public key or account hash' },
+    response: { type: 'array', items: { type: 'string' } },
+}, async (req) => {
+    const relayers: any[] = (await casperService.readRegistryItem(`mlAccountHash(${accountHash(req.params.account)})`)) ?? [];
+    return relayers.map((r) => (typeof r === 'string' ? r : Buffer.from(r).toString('hex')));
+});
+
+api.get('/registry/:key', {
+    operationId: 'getRegistryItem',
+    summary: "Raw item of the registry's storage dictionary",
+    description: 'For clients that decode records themselves, e.g. with guardian-types; keys are a '
+        + '`guardian_types::prefixes` prefix followed by what the record belongs to, like `stAccountHash(<hex>)`',
+    tags: ['config'],
+    params: { key: 'Dictionary item key' },
+    response: {
+        type: 'object',
+        properties: {
+            key: { type: 'string' },
+            bytes: { type: 'string', nullable: true, description: "The value's bytesrepr (hex); null if the item isn't set" },
+            clType: { description: "The value's CLType as the node reports it" },
+        },
+    },
+}, async (req) => {
+    const item = await casperService.readRegistryBytes(req.params.key);
+    return { key: req.params.key, bytes: item?.bytes ?? null, clType: item?.clType ?? null };
+});
+
+api.get('/guardians/:guardian/meta-nonce', {
+    operationId: 'getMetaNonce',
+    summary: "Nonce a guardian's next meta-approval has to be signed with",
+    tags: ['config'],
+    params: { guardian: 'Public key or account hash' },
+    response: { type: 'object', properties: { guardian: { type: 'string' }, nonce: { type: 'integer' } } },
+}, async (req) => {
+    const guardian = accountHash(req.params.guardian);
+    const nonce = Number((await casperService.readRegistryItem(`mnAccountHash(${guardian})`))?.toString() ?? 0);
+    return { guardian, nonce };
+});
+
+// ============================================================================
+// Recovery status
+// ============================================================================
+
+api.get('/recoveries/:id', {
+    operationId: 'getRecovery',
+    summary: 'Status and approvals of a recovery',
+    tags: ['recovery'],
+    params: { id: 'Decimal recovery id' },
+    response: recoveryDetailSchema,
+}, async (req) => {
+    const recovery = await index().recovery(req.params.id);
+    if (!recovery) throw new HttpError(404, 'Recovery not found');
+    const approvals = await index().approvals(recovery.id, { limit: 100, offset: 0 });
+    return {
+        ...recovery,
+        timelock: await timelock(recovery.id, recovery.byHeight).catch(() => null),
+        approvalHistory: approvals.map((a) => ({ guardian: a.guardian, deployHash: a.deploy_hash, height: Number(a.height) })),
+    };
+});
+
+/** Where recovery `id` stands against the delay and veto window `finalize` checks */
+async function timelock(id: string, byHeight: boolean) {
+    const read = async (prefix: string) => Number((await casperService.readRegistryItem(`${prefix}${id}`))?.toString() ?? 0);
+    const thresholdReached = (await casperService.readRegistryItem(`ro${id}`)) === true;
+    const [approvedAt, unlockAt, vetoWindow] = await Promise.all([read('ap'), read('ru'), read('vw')]);
+    const executableAt = thresholdReached ? Math.max(unlockAt, approvedAt + vetoWindow) : null;
+    const tip = await index().tip();
+    const now = byHeight ? tip?.height : tip?.timestamp;
+    const remaining = executableAt === null || now === undefined ? null : Math.max(executableAt - now, 0);
+    return { thresholdReached, approvedAt, unlockAt, vetoWindow, executableAt, remaining };
+}
+
+api.get('/accounts/:account/recoveries', {
+    operationId: 'listAccountRecoveries',
+    summary: 'Recoveries of an account, newest first',
+    tags: ['recovery'],
+    params: { account: 'Public key or account hash' },
+    query: paging,
+    response: { type: 'array', items: recoverySchema },
+}, async (req) => index().recoveriesOf(accountHash(req.params.account), page(req)));
+
+api.get('/recoveries', {
+    operationId: 'listOpenRecoveries',
+    summary: 'Pending recoveries, of one account or all',
+    tags: ['recovery'],
+    query: { account: ['Public key or account hash', { type: 'string' }], ...paging },
+    response: { type: 'array', items: recoverySchema },
+}, async (req) => {
+    const account = req.query.account ? accountHash(String(req.query.account)) : undefined;
+    return index().openRecoveries(account, page(req));
+});
+
+// ============================================================================
+// Deploys
+// ============================================================================
+
+api.post('/deploys/prepare', {
+    operationId: 'prepareDeploy',
+    summary: 'Build an unsigned registry call deploy or transaction',
+    tags: ['deploy'],
+    body: {
+        type: 'object',
+        required: ['sender', 'entryPoint', 'args'],
+        properties: {
+            sender: { type: 'string', description: 'Public key of the account paying for and signing the call' },
+            entryPoint: { type: 'string' },
+            args: { type: 'array', items: argSchema },
+            paymentAmount: { type: 'string', description: 'Motes; defaults to the backend setting' },
+        },
+    },
+    response: preparedDeploySchema,
+}, async (req) => {
+    const { sender, entryPoint, args, paymentAmount } = req.body ?? {};
+    if (!sender || !entryPoint || !Array.isArray(args)) throw new HttpError(400, 'sender, entryPoint and args are required');
+    try {
+        return await deployService.prepareRegistryCall(sender, entryPoint, args, paymentAmount);
+    } catch (error) {
+        throw new HttpError(400, String((error as Error).message ?? error));
+    }
+});
+
+api.post('/deploys/prepare-relay', {
+    operationId: 'prepareRelayDeploy',
+    summary: "Build an unsigned relay_approvals deploy or transaction submitting guardians' meta-approvals",
+    tags: ['deploy'],
+    body: {
+        type: 'object',
+        required: ['sender', 'approvals'],
+        properties: {
+            sender: { type: 'string', description: 'Public key of the relayer paying for and signing the deploy' },
+            approvals: {
+                type: 'array',
+                items: {
+                    type: 'object',
+                    required: ['id', 'guardian', 'nonce', 'signature'],
+                    properties: {
+                        id: { type: 'string', description: 'Decimal recovery id' },
+                        guardian: { type: 'string', description: 'Guardian public key' },
+                        nonce: { type: 'integer' },
+                        signature: { type: 'string', description: 'Hex, without the algorithm tag byte' },
+                    },
+                },
+            },
+            memo: { type: 'string', description: 'Memo every approval was signed with' },
+            paymentAmount: { type: 'string', description: 'Motes; defaults to the backend setting' },
+        },
+    },
+    response: preparedDeploySchema,
+}, async (req) => {
+    const { sender, approvals, memo, paymentAmount } = req.body ?? {};
+    if (!sender || !Array.isArray(approvals)) throw new HttpError(400, 'sender and approvals are required');
+    try {
+        return await deployService.prepareRelayCall(sender, approvals, memo, paymentAmount);
+    } catch (error) {
+        throw new HttpError(400, String((error as Error).message ?? error));
+    }
+});
+
+api.post('/deploys', {
+    operationId: 'submitDeploy',
+    summary: 'Submit a signed deploy or transaction',
+    tags: ['deploy'],
+    body: {
+        type: 'object',
+        properties: {
+            deploy: { type: 'object', description: 'Signed deploy JSON' },
+            transaction: { type: 'object', description: 'Signed TransactionV1 JSON' },
+        },
+    },
+    response: { type: 'object', properties: { deployHash: { type: 'string', description: 'Hash of the deploy or transaction' } } },
+}, async (req) => {
+    const { deploy, transaction } = req.body ?? {};
+    if (!deploy && !transaction) throw new HttpError(400, 'deploy or transaction is required');
+    const result = transaction
+        ? await casperService.submitTransactionJson(transaction)
+        : await casperService.submitDeployJson(deploy);
+    if (!result.success) throw new HttpError(400, result.message);
+    return { deployHash: result.deployHash };
+});
+
+api.get('/deploys/:hash', {
+    operationId: 'getDeployStatus',
+    summary: 'Execution status of a deploy or transaction',
+    tags: ['deploy'],
+    params: { hash: 'Deploy hash' },
+    response: deployStatusSchema,
+}, async (req) => (await casperService.getDeployStatus(req.params.hash)) ?? { deployHash: req.params.hash, status: 'pending' });
+
+// ============================================================================
+// Live events
+// ============================================================================
+
+let feed: RegistryEventFeed | undefined;
+
+/** Started for the first subscriber so the API runs without a node stream until one is needed */
+function events(): RegistryEventFeed {
+    if (!feed) {
+        const started = new RegistryEventFeed(config.api.eventsUrl, 'api');
+        started.start().catch((error) => {
+            console.error(`[api] Event feed stopped: ${error}`);
+            if (feed === started) feed = undefined;
+        });
+        feed = started;
+    }
+    return feed;
+}
+
+/**
+ * Server-sent registry events, each `data` a RegistryEvent with its `deployHash`. `account` and
+ * `guardian` (comma-separated keys or account hashes) keep events of those accounts or naming
+ * those guardians, and `type` (comma-separated event names, with or without their version
+ * suffix) keeps only those types; with none, every event is sent.
+ */
+api.router.get('/events', (req, res) => {
+    const list = (value: unknown) => String(value ?? '').split(',').filter(Boolean);
+    let filter: EventFilter;
+    try {
+        const accounts = list(req.query.account);
+        const guardians = list(req.query.guardian);
+        [...accounts, ...guardians].forEach(accountHash);
+        filter = EventFilter.accounts(...accounts).guardian(...guardians).type(...list(req.query.type));
+    } catch (error) {
+        res.status(400).json({ success: false, error: String((error as Error).message ?? error) });
+        return;
+    }
+
+    res.writeHead(200, { 'Content-Type': 'text/event-stream', 'Cache-Control': 'no-cache', Connection: 'keep-alive' });
+    res.write(': connected\n\n');
+    const unsubscribe = events().subscribe(filter, (event: RegistryEvent, deployHash: string) => {
+        res.write(`event: ${event.name}\ndata: ${JSON.stringify({ ...event, deployHash })}\n\n`);
+    });
+    const keepAlive = setInterval(() => res.write(': ping\n\n'), 15000);
+    req.on('close', () => {
+        clearInterval(keepAlive);
+        unsubscribe();
+    });
+});
+
+api.router.get('/openapi.json', (req, res) => {
+    res.json(api.document({ title: 'SentinelX API', version: API_VERSION }, '/api/v1'));
+});
+
+export default api.router;
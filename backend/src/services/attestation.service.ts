@@ -0,0 +1,141 @@
+import { createHash } from 'crypto';
+import { CLPublicKey, Keys } from 'casper-js-sdk';
+import { config } from '../config';
+
+/**
+ * A dictionary entry read from global state together with its Merkle proof
+ */
+export interface ProvenEntry {
+    dictionaryItemKey: string;
+    storedValue: any;
+    merkleProof: string;
+}
+
+/**
+ * Signed statement that an account had M-of-N guardianship configured at a given state root
+ */
+export interface GuardianshipAttestation {
+    version: 1;
+    chainName: string;
+    contractHash: string;
+    account: string;
+    guardians: string[];
+    threshold: number;
+    blockHash: string;
+    blockHeight: number;
+    stateRootHash: string;
+    proofs: ProvenEntry[];
+    issuedAt: string;
+    attester: string;
+    signature: string;
+}
+
+/**
+ * AttestationService - Exports guardianship config as a portable, signed document
+ */
+export class AttestationService {
+    private async rpc(method: string, params: any): Promise<any> {
+        const response = await fetch(config.casper.nodeUrl, {
+            method: 'POST',
+            headers: {
+                'Content-Type': 'application/json',
+            },
+            body: JSON.stringify({ jsonrpc: '2.0', id: Date.now(), method, params }),
+        });
+        const result = await response.json() as { error?: { message?: string }; result?: any };
+        if (result.error) {
+            throw new Error(`RPC error: ${result.error.message || JSON.stringify(result.error)}`);
+        }
+        return result.result;
+    }
+
+    private async getProvenEntry(contractHash: string, stateRootHash: string, key: string): Promise<ProvenEntry> {
+        const result = await this.rpc('state_get_dictionary_item', {
+            state_root_hash: stateRootHash,
+            dictionary_identifier: {
+                ContractNamedKey: {
+                    key: `hash-${contractHash}`,
+                    dictionary_name: 'd',
+                    dictionary_item_key: key,
+                },
+            },
+        });
+        return {
+            dictionaryItemKey: key,
+            storedValue: result.stored_value,
+            merkleProof: result.merkle_proof,
+        };
+    }
+
+    /**
+     * Canonical bytes covered by the attester's signature (every field except the signature)
+     */
+    private digest(attestation: Omit<GuardianshipAttestation, 'signature'>): string {
+        const { signature: _, ...body } = attestation as GuardianshipAttestation;
+        return createHash('sha256').update(JSON.stringify(body)).digest('hex');
+    }
+
+    /**
+     * Read the account's guardians and threshold at the latest block, with Merkle proofs
+     * against that block's state root hash, and sign the result with the attester key
+     */
+    async exportAttestation(publicKeyHex: string): Promise<GuardianshipAttestation> {
+        if (!config.contract.recoveryRegistryHash) {
+            throw new Error('RECOVERY_REGISTRY_HASH is not defined in environment variables');
+        }
+        if (!config.attestation.keyPath) {
+            throw new Error('ATTESTATION_KEY_PATH is not defined in environment variables');
+        }
+        const contractHash = config.contract.recoveryRegistryHash;
+        const attester = Keys.Ed25519.loadKeyPairFromPrivateFile(config.attestation.keyPath);
+
+        const accountHashHex = Buffer.from(CLPublicKey.fromHex(publicKeyHex).toAccountHash()).toString('hex');
+        const debugFormat = `AccountHash(${accountHashHex})`;
+
+        // Pin every read to one block so the proofs share a state root
+        const { block } = await this.rpc('chain_get_block', {});
+        const stateRootHash: string = block.header.state_root_hash;
+
+        const guardiansEntry = await this.getProvenEntry(contractHash, stateRootHash, `g${debugFormat}`);
+        const thresholdEntry = await this.getProvenEntry(contractHash, stateRootHash, `t${debugFormat}`);
+
+        const guardians = guardiansEntry.storedValue?.CLValue?.parsed || [];
+        const threshold = Number(thresholdEntry.storedValue?.CLValue?.parsed || 0);
+
+        const body: Omit<GuardianshipAttestation, 'signature'> = {
+            version: 1,
+            chainName: config.casper.chainName,
+            contractHash,
+            account: accountHashHex,
+            guardians: guardians.map((g: any) => String(g)),
+            threshold,
+            blockHash: block.hash,
+            blockHeight: block.header.height,
+            stateRootHash,
+            proofs: [guardiansEntry, thresholdEntry],
+            issuedAt: new Date().toISOString(),
+            attester: attester.publicKey.toHex(),
+        };
+
+        const signature = Keys.signFormattedMessage(attester, this.digest(body));
+        return { ...body, signature: Buffer.from(signature).toString('hex') };
+    }
+
+    /**
+     * Offline check that the document is unmodified and was signed by the named attester.
+     * The embedded proofs can then be checked against `stateRootHash` with `provenLeaves`.
+     */
+    verifyAttestation(attestation: GuardianshipAttestation): boolean {
+        try {
+            return Keys.verifyMessageSignature(
+                CLPublicKey.fromHex(attestation.attester),
+                this.digest(attestation),
+                Buffer.from(attestation.signature, 'hex')
+            );
+        } catch {
+            return false;
+        }
+    }
+}
+
+export const attestationService = new AttestationService();
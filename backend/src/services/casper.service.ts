@@ -4,6 +4,9 @@ import {
     DeployUtil,
 } from 'casper-js-sdk';
 import { config } from '../config';
+import { accountRecord, contractKey } from './host';
+import { queryCache } from './query-cache';
+import { decodeRevert, formatRevert, RegistryRevert } from './revert';
 
 /**
  * CasperService - Handles connection to Casper node and basic operations
@@ -74,14 +77,53 @@ export class CasperService {
         const publicKey = CLPublicKey.fromHex(publicKeyHex);
         const accountHash = publicKey.toAccountHashStr();
 
-        const stateRootHash = await this.client.nodeClient.getStateRootHash();
-        const accountInfo = await this.client.nodeClient.getBlockState(
-            stateRootHash,
-            accountHash,
-            []
+        if (config.casper.addressableEntity) {
+            return this.cached('account', publicKeyHex, (stateRootHash) => this.getEntityAccount(stateRootHash, accountHash));
+        }
+        return this.cached('account', publicKeyHex, (stateRootHash) =>
+            this.client.nodeClient.getBlockState(stateRootHash, accountHash, [])
         );
+    }
+
+    /**
+     * Account record on a Condor network with addressable entities. An account that has migrated
+     * leaves a key to its entity under its account hash, which is followed; one that hasn't is read
+     * as it is
+     */
+    private async getEntityAccount(stateRootHash: string, accountHash: string): Promise<any> {
+        const query = (key: string) => this.rpc('query_global_state', {
+            state_identifier: { StateRootHash: stateRootHash },
+            key,
+            path: [],
+        });
+        let stored = (await query(accountHash)).stored_value;
+        const entity = stored?.CLValue?.parsed;
+        if (typeof entity === 'string' && entity.startsWith('entity-account-')) {
+            stored = (await query(entity)).stored_value;
+        }
+        return accountRecord(stored);
+    }
 
-        return accountInfo;
+    private async rpc(method: string, params: any): Promise<any> {
+        const response = await fetch(config.casper.nodeUrl, {
+            method: 'POST',
+            headers: { 'Content-Type': 'application/json' },
+            body: JSON.stringify({ jsonrpc: '2.0', id: Date.now(), method, params }),
+        });
+        const result = await response.json() as { error?: { message?: string; code?: number }; result?: any };
+        if (result.error) {
+            throw Object.assign(new Error(`RPC error: ${result.error.message || JSON.stringify(result.error)}`), { code: result.error.code });
+        }
+        return result.result;
+    }
+
+    /**
+     * `load` at the current state root, through the query cache when it's enabled
+     */
+    private async cached<T>(kind: string, account: string, load: (stateRootHash: string) => Promise<T>): Promise<T> {
+        const currentRoot = () => this.client.nodeClient.getStateRootHash();
+        if (!queryCache) return load(await currentRoot());
+        return queryCache.get(kind, this.accountHashHex(account), currentRoot, load);
     }
 
     /**
@@ -126,7 +168,7 @@ export class CasperService {
         try {
             const result = await this.client.nodeClient.getBlockState(
                 stateRootHash,
-                `hash-${contractHash}`,
+                contractKey(contractHash),
                 [key]
             );
             return result;
@@ -162,9 +204,9 @@ export class CasperService {
      * Query contract dictionary
      * The contract uses dictionary 'd' to store all data
      */
-    async queryContractDictionary(contractHash: string, dictionaryName: string, dictionaryKey: string): Promise<any> {
+    async queryContractDictionary(contractHash: string, dictionaryName: string, dictionaryKey: string, stateRoot?: string): Promise<any> {
         try {
-            const stateRootHash = await this.client.nodeClient.getStateRootHash();
+            const stateRootHash = stateRoot ?? await this.client.nodeClient.getStateRootHash();
 
             console.log('\n=== Querying Contract Dictionary ===');
             console.log('Contract Hash:', contractHash);
@@ -173,7 +215,7 @@ export class CasperService {
 
             const result = await this.client.nodeClient.getDictionaryItemByName(
                 stateRootHash,
-                `hash-${contractHash}`,
+                contractKey(contractHash),
                 dictionaryName,
                 dictionaryKey
             );
@@ -186,6 +228,70 @@ export class CasperService {
         }
     }
 
+    /**
+     * Account hash hex of a public key, or of an account hash given as hex, `account-hash-…` or
+     * `entity-account-…`
+     */
+    accountHashHex(value: string): string {
+        const hex = value.replace(/^(account-hash-|entity-account-)/, '');
+        if (/^[0-9a-fA-F]{64}$/.test(hex)) return hex.toLowerCase();
+        return Buffer.from(CLPublicKey.fromHex(value).toAccountHash()).toString('hex');
+    }
+
+    /**
+     * Read one item of the registry's 'd' dictionary without logging
+     * Returns the item's CLValue data, or undefined if it isn't set
+     */
+    async readRegistryItem(key: string): Promise<any> {
+        try {
+            const stateRootHash = await this.client.nodeClient.getStateRootHash();
+            const item: any = await this.client.nodeClient.getDictionaryItemByName(
+                stateRootHash,
+                contractKey(config.contract.recoveryRegistryHash!),
+                'd',
+                key
+            );
+            return item?.CLValue?.data;
+        } catch {
+            return undefined;
+        }
+    }
+
+    /**
+     * bytesrepr (hex) and CLType of one item of the registry's 'd' dictionary, for callers that
+     * decode it themselves, e.g. the `CLType::Any` records; undefined if it isn't set
+     */
+    async readRegistryBytes(key: string): Promise<{ bytes: string; clType: unknown } | undefined> {
+        try {
+            const result = await this.rpc('state_get_dictionary_item', {
+                state_root_hash: await this.client.nodeClient.getStateRootHash(),
+                dictionary_identifier: {
+                    ContractNamedKey: {
+                        key: contractKey(config.contract.recoveryRegistryHash!),
+                        dictionary_name: 'd',
+                        dictionary_item_key: key,
+                    },
+                },
+            });
+            const value = result?.stored_value?.CLValue;
+            return value ? { bytes: value.bytes, clType: value.cl_type } : undefined;
+        } catch {
+            return undefined;
+        }
+    }
+
+    /**
+     * Address (hex, without the `uref-` prefix and access bits) of a URef named key of a contract
+     */
+    async getContractNamedUref(contractHash: string, name: string): Promise<string> {
+        const stateRootHash = await this.client.nodeClient.getStateRootHash();
+        const stored: any = await this.client.nodeClient.getBlockState(stateRootHash, `hash-${contractHash}`, []);
+        const namedKeys: { name: string; key: string }[] = stored?.Contract?.namedKeys ?? [];
+        const key = namedKeys.find((k) => k.name === name);
+        if (!key) throw new Error(`Contract has no ${name} named key`);
+        return key.key.replace(/^uref-/, '').replace(/-\d{3}$/, '');
+    }
+
     /**
      * Get guardians registered in the contract for an account
      * Uses the contract's dictionary to look up guardian data
@@ -196,58 +302,68 @@ export class CasperService {
         threshold: number;
     }> {
         try {
-            const publicKey = CLPublicKey.fromHex(publicKeyHex);
-            const accountHash = publicKey.toAccountHash();
-            const accountHashHex = Buffer.from(accountHash).toString('hex');
+            return await this.cached(`registry:${contractHash}`, publicKeyHex, (stateRootHash) =>
+                this.readGuardiansFromContract(contractHash, publicKeyHex, stateRootHash)
+            );
+        } catch (error) {
+            console.error('Error getting guardians from contract:', error);
+            return { isInitialized: false, guardians: [], threshold: 0 };
+        }
+    }
 
-            // The contract stores data with keys like "i{:?}" where {:?} is the Debug format of AccountHash
-            // In Rust, AccountHash Debug format is: AccountHash(hex_bytes)
-            const debugFormat = `AccountHash(${accountHashHex})`;
+    private async readGuardiansFromContract(contractHash: string, publicKeyHex: string, stateRootHash: string): Promise<{
+        isInitialized: boolean;
+        guardians: string[];
+        threshold: number;
+    }> {
+        const publicKey = CLPublicKey.fromHex(publicKeyHex);
+        const accountHash = publicKey.toAccountHash();
+        const accountHashHex = Buffer.from(accountHash).toString('hex');
 
-            console.log('\n=== Checking Contract Registry for Account ===');
-            console.log('Public Key:', publicKeyHex);
-            console.log('Account Hash (hex):', accountHashHex);
-            console.log('Debug Format Key: i' + debugFormat);
+        // The contract stores data with keys like "i{:?}" where {:?} is the Debug format of AccountHash
+        // In Rust, AccountHash Debug format is: AccountHash(hex_bytes)
+        const debugFormat = `AccountHash(${accountHashHex})`;
 
-            // Check if initialized: key is "i{:?}" (e.g., "iAccountHash(abc123...)")
-            const initKey = `i${debugFormat}`;
-            const initResult = await this.queryContractDictionary(contractHash, 'd', initKey);
+        console.log('\n=== Checking Contract Registry for Account ===');
+        console.log('Public Key:', publicKeyHex);
+        console.log('Account Hash (hex):', accountHashHex);
+        console.log('Debug Format Key: i' + debugFormat);
 
-            const isInitialized = initResult?.stored_value?.CLValue?.data === true;
-            console.log('Is Initialized:', isInitialized);
+        // Check if initialized: key is "i{:?}" (e.g., "iAccountHash(abc123...)")
+        const initKey = `i${debugFormat}`;
+        const initResult = await this.queryContractDictionary(contractHash, 'd', initKey, stateRootHash);
 
-            if (!isInitialized) {
-                console.log('Account NOT registered in contract dictionary');
-                console.log('========================================\n');
-                return { isInitialized: false, guardians: [], threshold: 0 };
-            }
+        const isInitialized = initResult?.stored_value?.CLValue?.data === true;
+        console.log('Is Initialized:', isInitialized);
 
-            // Get guardians: key is "g{:?}"
-            const guardiansKey = `g${debugFormat}`;
-            const guardiansResult = await this.queryContractDictionary(contractHash, 'd', guardiansKey);
+        if (!isInitialized) {
+            console.log('Account NOT registered in contract dictionary');
+            console.log('========================================\n');
+            return { isInitialized: false, guardians: [], threshold: 0 };
+        }
 
-            // Get threshold: key is "t{:?}"
-            const thresholdKey = `t${debugFormat}`;
-            const thresholdResult = await this.queryContractDictionary(contractHash, 'd', thresholdKey);
+        // Get guardians: key is "g{:?}"
+        const guardiansKey = `g${debugFormat}`;
+        const guardiansResult = await this.queryContractDictionary(contractHash, 'd', guardiansKey, stateRootHash);
 
-            const guardians = guardiansResult?.stored_value?.CLValue?.data || [];
-            const threshold = thresholdResult?.stored_value?.CLValue?.data || 0;
+        // Get threshold: key is "t{:?}"
+        const thresholdKey = `t${debugFormat}`;
+        const thresholdResult = await this.queryContractDictionary(contractHash, 'd', thresholdKey, stateRootHash);
 
-            console.log('Guardians from contract:', guardians);
-            console.log('Threshold:', threshold);
-            console.log('========================================\n');
+        const guardians = guardiansResult?.stored_value?.CLValue?.data || [];
+        const threshold = thresholdResult?.stored_value?.CLValue?.data || 0;
 
-            return {
-                isInitialized,
-                guardians: Array.isArray(guardians) ? guardians.map((g: any) =>
-                    typeof g === 'string' ? g : Buffer.from(g).toString('hex')
-                ) : [],
-                threshold: Number(threshold),
-            };
-        } catch (error) {
-            console.error('Error getting guardians from contract:', error);
-            return { isInitialized: false, guardians: [], threshold: 0 };
-        }
+        console.log('Guardians from contract:', guardians);
+        console.log('Threshold:', threshold);
+        console.log('========================================\n');
+
+        return {
+            isInitialized,
+            guardians: Array.isArray(guardians) ? guardians.map((g: any) =>
+                typeof g === 'string' ? g : Buffer.from(g).toString('hex')
+            ) : [],
+            threshold: Number(threshold),
+        };
     }
 
     /**
@@ -434,6 +550,76 @@ export class CasperService {
         }
     }
 
+    /**
+     * Submit a signed TransactionV1's JSON (`{ Version1: ... }`, wrapped in `transaction` or not)
+     */
+    async submitTransactionJson(transactionJson: any): Promise<{
+        deployHash: string;
+        success: boolean;
+        message: string;
+    }> {
+        try {
+            let transaction = typeof transactionJson === 'string' ? JSON.parse(transactionJson) : transactionJson;
+            if (transaction.transaction) transaction = transaction.transaction;
+            const result = await this.rpc('account_put_transaction', { transaction });
+            const hash = result?.transaction_hash?.Version1 ?? result?.transaction_hash?.Deploy ?? '';
+            return { deployHash: hash, success: true, message: 'Transaction submitted successfully via RPC' };
+        } catch (error) {
+            console.error('Error submitting transaction via RPC:', error);
+            return {
+                deployHash: '',
+                success: false,
+                message: `Error submitting transaction: ${error instanceof Error ? error.message : String(error)}`,
+            };
+        }
+    }
+
+    private protocolVersion?: Promise<string>;
+
+    /**
+     * Whether prepared calls are built as Casper 2.0 TransactionV1s rather than Deploys: as
+     * configured, or for `auto` whether the node runs protocol 2.0 or later (asked once)
+     */
+    async usesTransactions(): Promise<boolean> {
+        if (config.casper.transactions !== 'auto') return config.casper.transactions === 'transaction';
+        return parseInt(await this.nodeProtocolVersion(), 10) >= 2;
+    }
+
+    /** Protocol version the node reports, e.g. `2.0.0`; read once */
+    async nodeProtocolVersion(): Promise<string> {
+        this.protocolVersion ??= this.client.nodeClient.getStatus()
+            .then((status: any) => String(status.protocol_version ?? status.api_version ?? ''))
+            .catch((error) => {
+                this.protocolVersion = undefined;
+                throw error;
+            });
+        return this.protocolVersion;
+    }
+
+    /**
+     * Status of a TransactionV1 in the shape of `getDeployStatus`, or null if the node doesn't
+     * know it (or predates transactions)
+     */
+    async getTransactionStatus(hash: string): Promise<{
+        deployHash: string;
+        status: 'pending' | 'success' | 'failed';
+        executionResult?: any;
+        errorMessage?: string;
+        revert?: RegistryRevert;
+    } | null> {
+        let result: any;
+        try {
+            result = await this.rpc('info_get_transaction', { transaction_hash: { Version1: hash }, finalized_approvals: false });
+        } catch {
+            return null;
+        }
+        const executionResult = result?.execution_info?.execution_result;
+        if (!executionResult) return { deployHash: hash, status: 'pending' };
+        const errorMessage: string | undefined = executionResult.Version2?.error_message ?? undefined;
+        const revert = errorMessage ? decodeRevert(errorMessage, result.transaction) ?? undefined : undefined;
+        return { deployHash: hash, status: errorMessage ? 'failed' : 'success', executionResult, errorMessage, revert };
+    }
+
     /**
      * Wait for deploy execution using polling
      */
@@ -452,10 +638,9 @@ export class CasperService {
                     if (executionResult.result.Success) {
                         return { success: true, message: 'Deploy executed successfully' };
                     } else {
-                        return {
-                            success: false,
-                            message: executionResult.result.Failure?.error_message || 'Unknown error',
-                        };
+                        const error = executionResult.result.Failure?.error_message || 'Unknown error';
+                        const revert = decodeRevert(error, (deployResult as any).deploy);
+                        return { success: false, message: revert ? formatRevert(revert) : error };
                     }
                 }
             } catch {
@@ -477,6 +662,8 @@ export class CasperService {
         status: 'pending' | 'success' | 'failed';
         executionResult?: any;
         errorMessage?: string;
+        /** The registry error a failed deploy reverted with */
+        revert?: RegistryRevert;
     } | null> {
         try {
             console.log('\n=== Getting Deploy Status ===');
@@ -547,6 +734,9 @@ export class CasperService {
                 console.log('No execution results yet - deploy is pending');
             }
 
+            const revert = errorMessage ? decodeRevert(errorMessage, result.deploy) ?? undefined : undefined;
+            if (revert) console.log('Registry revert:', formatRevert(revert));
+
             console.log('Final status:', status);
             console.log('========================================\n');
 
@@ -554,13 +744,17 @@ export class CasperService {
                 deployHash,
                 status,
                 executionResult,
-                errorMessage
+                errorMessage,
+                revert,
             };
         } catch (error: any) {
             console.error('Error getting deploy status:', error);
 
             // If deploy not found, treat as pending
             if (error.message?.includes('deploy not known') || error.code === -32003) {
+                // Casper 2.0 nodes look transactions up separately
+                const transaction = await this.getTransactionStatus(deployHash);
+                if (transaction) return transaction;
                 console.log('Deploy not found - might still be pending or invalid hash');
                 return {
                     deployHash,
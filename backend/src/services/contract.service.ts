@@ -63,10 +63,25 @@ export class ContractService {
             return new CLAccountHash(pk.toAccountHash());
         });
 
+        // Distinct keys (e.g. an ed25519 and a secp256k1 key) can belong to one controller;
+        // the contract would reject the collapsed set, so fail early with a clearer message
+        const seen = new Map<string, string>();
+        seen.set(Buffer.from(userPublicKey.toAccountHash()).toString('hex'), userPublicKeyHex);
+        guardians.forEach((g, i) => {
+            const hash = Buffer.from(guardianAccountHashes[i].data).toString('hex');
+            const other = seen.get(hash);
+            if (other) {
+                throw new Error(`Guardian ${g} resolves to the same account hash as ${other}`);
+            }
+            seen.set(hash, g);
+        });
+
         const args = RuntimeArgs.fromMap({
             account: userAccountHash,
             guardians: CLValueBuilder.list(guardianAccountHashes),
             threshold: CLValueBuilder.u8(threshold),
+            // Only checked when the account has a guardian key policy, but always cheap to send
+            guardian_keys: CLValueBuilder.list(guardians.map((g) => CLPublicKey.fromHex(g))),
         });
 
         // Use stored contract call so data is stored in contract's dictionary
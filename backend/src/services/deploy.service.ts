@@ -5,12 +5,15 @@ import {
     DeployUtil,
     RuntimeArgs,
     CLValueBuilder,
+    CLValue,
     CLKey,
     CLAccountHash,
     Keys,
 } from 'casper-js-sdk';
 import { config } from '../config';
 import { casperService } from './casper.service';
+import { MetaApproval, PreparedDeploy, TypedArg } from '../types';
+import { buildTransaction, TransactionTarget } from './transaction';
 
 /**
  * DeployService - Handles building and managing deploys
@@ -146,6 +149,132 @@ export class DeployService {
         );
     }
 
+    /**
+     * Build a runtime argument from its JSON description
+     */
+    typedArg({ name, type, value }: TypedArg): CLValue {
+        const account = (v: string) => new CLAccountHash(Uint8Array.from(Buffer.from(casperService.accountHashHex(v), 'hex')));
+        const bytes = (hex: string) => CLValueBuilder.list(Array.from(Buffer.from(hex, 'hex')).map((b) => CLValueBuilder.u8(b)));
+        const list = (v: unknown) => {
+            if (!Array.isArray(v) || v.length === 0) throw new Error(`Argument ${name} must be a non-empty list`);
+            return v;
+        };
+        switch (type) {
+            case 'u8': return CLValueBuilder.u8(value);
+            case 'u32': return CLValueBuilder.u32(value);
+            case 'u64': return CLValueBuilder.u64(value);
+            case 'u256': return CLValueBuilder.u256(value);
+            case 'u512': return CLValueBuilder.u512(value);
+            case 'bool': return CLValueBuilder.bool(value);
+            case 'string': return CLValueBuilder.string(value);
+            case 'account': return account(value);
+            case 'account_list': return CLValueBuilder.list(list(value).map(account));
+            case 'public_key': return CLPublicKey.fromHex(value);
+            case 'public_key_list': return CLValueBuilder.list(list(value).map((k: string) => CLPublicKey.fromHex(k)));
+            // Vec<u8> (e.g. approve_signed's signature), given as hex
+            case 'bytes': return bytes(value);
+            // submit_signed_approvals' (guardian key, signature) pairs, given as [{ guardian, signature }]
+            case 'signed_approval_list': return CLValueBuilder.list(list(value).map((a: { guardian: string; signature: string }) =>
+                CLValueBuilder.tuple2([CLPublicKey.fromHex(a.guardian), bytes(a.signature)])));
+            default: throw new Error(`Unsupported type ${type} for argument ${name}`);
+        }
+    }
+
+    /**
+     * Unsigned TransactionV1 of `sender` running `target`, in the shape of a prepared deploy
+     */
+    prepareTransaction(
+        senderPublicKeyHex: string,
+        target: TransactionTarget,
+        args: RuntimeArgs,
+        paymentAmount: string
+    ): PreparedDeploy {
+        const transaction = buildTransaction({
+            initiator: CLPublicKey.fromHex(senderPublicKeyHex),
+            chainName: config.casper.chainName,
+            ttl: config.deploy.ttl,
+            paymentAmount,
+            gasPriceTolerance: config.deploy.gasPriceTolerance,
+            target,
+            args,
+        });
+        return { kind: 'transaction', deployHash: transaction.hash, transaction: transaction.json, bytes: transaction.bytes };
+    }
+
+    /**
+     * Build an unsigned call of a registry entry point, for the sender to sign: a TransactionV1
+     * targeting the registry's entity on Casper 2.0 nodes, a deploy before
+     */
+    async prepareRegistryCall(
+        senderPublicKeyHex: string,
+        entryPoint: string,
+        args: TypedArg[],
+        paymentAmount: string = config.deploy.paymentAmount
+    ): Promise<PreparedDeploy> {
+        const registry = config.contract.recoveryRegistryHash;
+        if (!registry) throw new Error('RECOVERY_REGISTRY_HASH is not set');
+
+        const runtimeArgs = RuntimeArgs.fromMap(Object.fromEntries(args.map((a) => [a.name, this.typedArg(a)])));
+        if (await casperService.usesTransactions()) {
+            return this.prepareTransaction(senderPublicKeyHex, { kind: 'stored', hash: registry, entryPoint }, runtimeArgs, paymentAmount);
+        }
+        const deploy = this.buildContractCallDeploy(
+            CLPublicKey.fromHex(senderPublicKeyHex),
+            registry,
+            entryPoint,
+            runtimeArgs,
+            paymentAmount
+        );
+        return {
+            kind: 'deploy',
+            deployHash: Buffer.from(deploy.hash).toString('hex'),
+            deploy: this.deployToJson(deploy),
+            bytes: Buffer.from(DeployUtil.deployToBytes(deploy)).toString('hex'),
+        };
+    }
+
+    /**
+     * Build an unsigned relay_approvals session deploy (or TransactionV1, on Casper 2.0 nodes)
+     * submitting `approvals` to the registry's `approve_meta`, paid for by the relayer
+     */
+    async prepareRelayCall(
+        relayerPublicKeyHex: string,
+        approvals: MetaApproval[],
+        memo: string = '',
+        paymentAmount: string = config.deploy.sessionPaymentAmount
+    ): Promise<PreparedDeploy> {
+        const registry = config.contract.recoveryRegistryHash;
+        if (!registry) throw new Error('RECOVERY_REGISTRY_HASH is not set');
+        if (approvals.length === 0) throw new Error('approvals must be a non-empty list');
+
+        const bytes = (hex: string) => CLValueBuilder.list(Array.from(Buffer.from(hex, 'hex')).map((b) => CLValueBuilder.u8(b)));
+        const args = RuntimeArgs.fromMap({
+            registry: CLValueBuilder.key(CLValueBuilder.byteArray(Uint8Array.from(Buffer.from(registry, 'hex')))),
+            approvals: CLValueBuilder.list(approvals.map((a) => CLValueBuilder.tuple3([
+                CLValueBuilder.u256(a.id),
+                CLPublicKey.fromHex(a.guardian),
+                CLValueBuilder.tuple2([CLValueBuilder.u64(a.nonce), bytes(a.signature)]),
+            ]))),
+            memo: CLValueBuilder.string(memo),
+        });
+        if (await casperService.usesTransactions()) {
+            const moduleBytes = this.loadWasm(config.wasm.relayApprovals);
+            return this.prepareTransaction(relayerPublicKeyHex, { kind: 'session', moduleBytes }, args, paymentAmount);
+        }
+        const deploy = this.buildSessionWasmDeploy(
+            CLPublicKey.fromHex(relayerPublicKeyHex),
+            config.wasm.relayApprovals,
+            args,
+            paymentAmount
+        );
+        return {
+            kind: 'deploy',
+            deployHash: Buffer.from(deploy.hash).toString('hex'),
+            deploy: this.deployToJson(deploy),
+            bytes: Buffer.from(DeployUtil.deployToBytes(deploy)).toString('hex'),
+        };
+    }
+
     /**
      * Sign a deploy with a key
      */
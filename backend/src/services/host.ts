@@ -0,0 +1,29 @@
+import { config } from '../config';
+
+/**
+ * Global state key of a contract: `hash-…` on Casper 1.x and on Condor networks without
+ * addressable entities, `entity-contract-…` on Condor networks with them
+ */
+export function contractKey(contractHash: string): string {
+    const hex = contractHash.replace(/^(hash-|entity-contract-)/, '');
+    return config.casper.addressableEntity ? `entity-contract-${hex}` : `hash-${hex}`;
+}
+
+/**
+ * Account record in the shape Casper 1.x nodes return (`{ Account: { main_purse, associated_keys,
+ * action_thresholds } }`), from either an account or the addressable entity Condor migrated it to
+ */
+export function accountRecord(storedValue: any): any {
+    if (storedValue?.Account) return { Account: storedValue.Account };
+    const entity = storedValue?.AddressableEntity;
+    if (!entity) return undefined;
+    return {
+        Account: {
+            account_hash: entity.entity_kind?.Account,
+            main_purse: entity.main_purse,
+            associated_keys: entity.associated_keys ?? [],
+            action_thresholds: entity.action_thresholds ?? {},
+            named_keys: [],
+        },
+    };
+}
@@ -3,6 +3,10 @@ export { deployService, DeployService } from './deploy.service';
 export { contractService, ContractService } from './contract.service';
 export { sessionService, SessionService } from './session.service';
 export { multisigService, MultisigService } from './multisig.service';
+export { attestationService, AttestationService } from './attestation.service';
+export { proofService, ProofService, provenLeaves } from './proof.service';
+export { queryCache, QueryCache } from './query-cache';
+export { decodeRevert, formatRevert, userErrorCode, RegistryRevert } from './revert';
 export { checkUserEmail, submitUserEmail, getEmailByAccountHash } from './user.service';
 export { notifyGuardiansOfRecovery, getEmailForPublicKey, getGuardianPublicKeysForAccount } from './email.service';
 
@@ -0,0 +1,294 @@
+import { blake2b } from '@noble/hashes/blake2b';
+import { config } from '../config';
+import { Reader } from '../watchd/ces';
+import { casperService } from './casper.service';
+
+/**
+ * Guardian configuration read from global state and proven against `stateRootHash`
+ */
+export interface VerifiedGuardianConfig {
+    stateRootHash: string;
+    account: string;
+    guardians: string[];
+    threshold: number;
+}
+
+/**
+ * Recovery state read from global state and proven against `stateRootHash`
+ */
+export interface VerifiedRecoveryStatus {
+    stateRootHash: string;
+    id: string;
+    account: string;
+    newKey: string;
+    status: 'pending' | 'finalized' | 'cancelled';
+    approvals: number;
+    /** The recovery's threshold snapshot, or the account's threshold, before any decay */
+    threshold: number;
+    /** Block time (ms) or height the recovery expires at, 0 if never */
+    expiresAt: number;
+    byHeight: boolean;
+}
+
+/** One leaf of a `merkle_proof` and the state root hash its proof steps hash up to */
+interface ProvenLeaf {
+    key: Buffer;
+    value: Buffer;
+    root: string;
+}
+
+const KEY_HASH = 1;
+const KEY_UREF = 2;
+const KEY_DICTIONARY = 9;
+const STORED_CL_VALUE = 0;
+const STORED_CONTRACT = 3;
+const TRIE_LEAF = 0;
+const TRIE_NODE = 1;
+const TRIE_EXTENSION = 2;
+
+function digest(...parts: Buffer[]): Buffer {
+    return Buffer.from(blake2b(Buffer.concat(parts), { dkLen: 32 }));
+}
+
+function u32(n: number): Buffer {
+    const out = Buffer.alloc(4);
+    out.writeUInt32LE(n);
+    return out;
+}
+
+/** Skip a Key; registry reads only prove account, hash, uref and dictionary keys */
+function skipKey(r: Reader): void {
+    const tag = r.u8();
+    if (tag === 0 || tag === KEY_HASH || tag === KEY_DICTIONARY) r.take(32);
+    else if (tag === KEY_UREF) r.take(33);
+    else throw new Error(`Unsupported key tag ${tag} in proof`);
+}
+
+/** Named keys of a legacy `Contract` value (after its tag), reading through the whole value */
+function contractNamedKeys(r: Reader): Map<string, Buffer> {
+    r.take(64); // package and wasm hashes
+    const named = new Map<string, Buffer>();
+    for (let i = r.u32(); i > 0; i--) {
+        const name = r.string();
+        const start = r.position;
+        skipKey(r);
+        named.set(name, r.since(start));
+    }
+    for (let i = r.u32(); i > 0; i--) {
+        r.string(); // map key
+        r.string(); // name
+        for (let args = r.u32(); args > 0; args--) {
+            r.string();
+            r.clType();
+        }
+        r.clType(); // return type
+        if (r.u8() === 1) for (let groups = r.u32(); groups > 0; groups--) r.string();
+        r.u8(); // entry point type
+    }
+    r.take(12); // protocol version
+    return named;
+}
+
+/** Skip a StoredValue; registry reads only prove CLValues and the registry contract */
+function skipStoredValue(r: Reader): void {
+    const tag = r.u8();
+    if (tag === STORED_CL_VALUE) {
+        r.bytes();
+        r.clType();
+    } else if (tag === STORED_CONTRACT) {
+        contractNamedKeys(r);
+    } else {
+        throw new Error(`Unsupported stored value tag ${tag} in proof`);
+    }
+}
+
+/**
+ * Decode a node's `merkle_proof` (bytesrepr `Vec<TrieMerkleProof<Key, StoredValue>>`) and hash
+ * each leaf up its proof steps, as the node's trie does: a leaf is `0 ‖ key ‖ value`, a node
+ * `1 ‖ its (index, pointer) pairs`, an extension `2 ‖ affix ‖ pointer`, each hashed with
+ * Blake2b-256. The first step points at the leaf, every later one at a node.
+ */
+export function provenLeaves(merkleProof: string): ProvenLeaf[] {
+    const r = new Reader(Buffer.from(merkleProof, 'hex'));
+    const leaves: ProvenLeaf[] = [];
+
+    for (let n = r.u32(); n > 0; n--) {
+        let start = r.position;
+        skipKey(r);
+        const key = r.since(start);
+        start = r.position;
+        skipStoredValue(r);
+        const value = r.since(start);
+
+        let hash = digest(Buffer.from([TRIE_LEAF]), key, value);
+        const steps = r.u32();
+        for (let i = 0; i < steps; i++) {
+            const pointer = Buffer.concat([Buffer.from([i === 0 ? 0 : 1]), hash]);
+            const tag = r.u8();
+            if (tag === 0) {
+                const hole = r.u8();
+                const pointers: [number, Buffer][] = [[hole, pointer]];
+                for (let count = r.u32(); count > 0; count--) pointers.push([r.u8(), r.take(33)]);
+                pointers.sort(([a], [b]) => a - b);
+                hash = digest(
+                    Buffer.from([TRIE_NODE]), u32(pointers.length),
+                    ...pointers.map(([index, p]) => Buffer.concat([Buffer.from([index]), p]))
+                );
+            } else if (tag === 1) {
+                const affix = r.bytes();
+                hash = digest(Buffer.from([TRIE_EXTENSION]), u32(affix.length), affix, pointer);
+            } else {
+                throw new Error(`Unknown proof step tag ${tag}`);
+            }
+        }
+        leaves.push({ key, value, root: hash.toString('hex') });
+    }
+
+    if (r.remaining() !== 0) throw new Error('Trailing bytes after Merkle proof');
+    return leaves;
+}
+
+/**
+ * ProofService - Registry reads a light client can check without trusting the node
+ *
+ * Every value is taken from the node's Merkle proof rather than its decoded response, and only
+ * after the proof hashes up to the given state root hash. A dishonest node can make a read fail
+ * but not change its result. Reads anchor to the node's latest block unless the caller passes a
+ * state root hash it trusts (a checkpoint, or a block seen from several nodes). Absence isn't
+ * proven: an entry the node reports missing reads as unset.
+ */
+export class ProofService {
+    /** `d` dictionary seed of each registry, once proven */
+    private seeds = new Map<string, Buffer>();
+
+    private async rpc(method: string, params: any): Promise<any> {
+        const response = await fetch(config.casper.nodeUrl, {
+            method: 'POST',
+            headers: { 'Content-Type': 'application/json' },
+            body: JSON.stringify({ jsonrpc: '2.0', id: Date.now(), method, params }),
+        });
+        const result = await response.json() as { error?: { message?: string; code?: number }; result?: any };
+        if (result.error) {
+            throw Object.assign(new Error(`RPC error: ${result.error.message || JSON.stringify(result.error)}`), { code: result.error.code });
+        }
+        return result.result;
+    }
+
+    /**
+     * State root hash of block `blockHash`, or of the node's latest block
+     */
+    async stateRootHash(blockHash?: string): Promise<string> {
+        const result = await this.rpc('chain_get_block', blockHash ? { block_identifier: { Hash: blockHash } } : {});
+        const block = result.block_with_signatures?.block ?? result.block;
+        const header = block?.header ?? block?.Version2?.header ?? block?.Version1?.header;
+        if (!header) throw new Error('Block not found');
+        return header.state_root_hash;
+    }
+
+    /**
+     * Value proven under `key` (its bytesrepr) at `stateRootHash`
+     */
+    private verify(merkleProof: string, stateRootHash: string, key: Buffer): Buffer {
+        const [leaf, ...rest] = provenLeaves(merkleProof);
+        if (!leaf || rest.length > 0) throw new Error('Expected a proof of exactly one value');
+        if (!leaf.key.equals(key)) throw new Error('Merkle proof is for a different key');
+        if (leaf.root !== stateRootHash.toLowerCase()) throw new Error('Merkle proof does not match the state root hash');
+        return leaf.value;
+    }
+
+    /** Seed uref address of the registry's `d` dictionary, from its proven named keys */
+    private async registrySeed(stateRootHash: string): Promise<Buffer> {
+        const contractHash = config.contract.recoveryRegistryHash;
+        if (!contractHash) throw new Error('RECOVERY_REGISTRY_HASH is not defined in environment variables');
+        const known = this.seeds.get(contractHash);
+        if (known) return known;
+
+        const result = await this.rpc('query_global_state', {
+            state_identifier: { StateRootHash: stateRootHash },
+            key: `hash-${contractHash}`,
+            path: [],
+        });
+        const r = new Reader(this.verify(result.merkle_proof, stateRootHash, Buffer.concat([Buffer.from([KEY_HASH]), Buffer.from(contractHash, 'hex')])));
+        if (r.u8() !== STORED_CONTRACT) throw new Error('Registry hash is not a contract');
+        const d = contractNamedKeys(r).get('d');
+        if (!d || d[0] !== KEY_UREF) throw new Error('Registry has no d dictionary');
+
+        const seed = d.subarray(1, 33);
+        this.seeds.set(contractHash, seed);
+        return seed;
+    }
+
+    /**
+     * Reader over the value of registry dictionary item `itemKey` at `stateRootHash`, or null if
+     * the node reports it unset
+     */
+    async registryItem(itemKey: string, stateRootHash: string): Promise<Reader | null> {
+        const seed = await this.registrySeed(stateRootHash);
+        let result;
+        try {
+            result = await this.rpc('state_get_dictionary_item', {
+                state_root_hash: stateRootHash,
+                dictionary_identifier: { URef: { seed_uref: `uref-${seed.toString('hex')}-007`, dictionary_item_key: itemKey } },
+            });
+        } catch (error: any) {
+            if (error.code === -32003 || /not found|ValueNotFound/i.test(String(error.message))) return null;
+            throw error;
+        }
+
+        const address = digest(seed, Buffer.from(itemKey, 'utf8'));
+        const r = new Reader(this.verify(result.merkle_proof, stateRootHash, Buffer.concat([Buffer.from([KEY_DICTIONARY]), address])));
+        if (r.u8() !== STORED_CL_VALUE) throw new Error('Dictionary item is not a CLValue');
+
+        // Dictionary items are stored as (value CLValue, seed address, item key)
+        const entry = new Reader(r.bytes());
+        const value = entry.bytes();
+        entry.clType();
+        if (!entry.bytes().equals(seed) || entry.string() !== itemKey) throw new Error('Dictionary item does not match its key');
+        return new Reader(value);
+    }
+
+    /**
+     * Guardians and threshold of an account, proven against `stateRootHash` (default: the
+     * node's latest block)
+     */
+    async guardianConfig(account: string, stateRootHash?: string): Promise<VerifiedGuardianConfig> {
+        const root = stateRootHash ?? await this.stateRootHash();
+        const hash = casperService.accountHashHex(account);
+        const guardians = (await this.registryItem(`gAccountHash(${hash})`, root))?.hashes() ?? [];
+        const threshold = (await this.registryItem(`tAccountHash(${hash})`, root))?.u8() ?? 0;
+        return { stateRootHash: root, account: hash, guardians, threshold };
+    }
+
+    /**
+     * Status of recovery `id`, proven against `stateRootHash` (default: the node's latest block)
+     */
+    async recoveryStatus(id: string, stateRootHash?: string): Promise<VerifiedRecoveryStatus> {
+        const root = stateRootHash ?? await this.stateRootHash();
+        const read = (prefix: string) => this.registryItem(`${prefix}${id}`, root);
+
+        const accountReader = await read('ra');
+        if (!accountReader) throw new Error('Recovery not found');
+        const account = accountReader.hash();
+        const newKey = (await read('rk'))?.publicKey() ?? '';
+        const approvals = (await read('rc'))?.u8() ?? 0;
+        const finalized = (await read('rf'))?.bool() ?? false;
+        const cancelled = (await read('rx'))?.bool() ?? false;
+        const threshold = (await read('rq'))?.u8()
+            ?? (await this.registryItem(`tAccountHash(${account})`, root))?.u8()
+            ?? 0;
+
+        return {
+            stateRootHash: root,
+            id,
+            account,
+            newKey,
+            status: finalized ? 'finalized' : cancelled ? 'cancelled' : 'pending',
+            approvals,
+            threshold,
+            expiresAt: (await read('re'))?.u64() ?? 0,
+            byHeight: (await read('rm'))?.bool() ?? false,
+        };
+    }
+}
+
+export const proofService = new ProofService();
@@ -0,0 +1,110 @@
+import fs from 'fs';
+import { EventEmitter } from 'events';
+import { config } from '../config';
+
+interface Entry {
+    stateRootHash: string;
+    storedAt: number;
+    value: unknown;
+}
+
+/**
+ * Cache of per-account state reads, keyed by (account, state root hash)
+ *
+ * On its own an entry only answers reads at the state root it was read at. Once
+ * it follows a RegistryEventFeed the cache learns new state roots from the
+ * stream's blocks and keeps an account's entries across them until a deploy
+ * from that account or a registry event about it invalidates them; entries
+ * stored before the stream connected, or while it was down, stay pinned to
+ * their own root. With a `path` entries are also kept on disk across restarts.
+ */
+export class QueryCache {
+    private entries = new Map<string, Entry>();
+    /** Keys the stream has vouched for since they were stored */
+    private live = new Set<string>();
+    private stateRootHash?: string;
+    private saveTimer?: NodeJS.Timeout;
+
+    constructor(private path?: string, private maxAge = 600000) {
+        if (path && fs.existsSync(path)) {
+            for (const [key, entry] of Object.entries(JSON.parse(fs.readFileSync(path, 'utf8')))) {
+                this.entries.set(key, entry as Entry);
+            }
+        }
+    }
+
+    /**
+     * `kind` of `account` from the cache, else `load`ed at the current state root and stored.
+     * `currentRoot` is only asked for when the stream hasn't supplied one.
+     */
+    async get<T>(
+        kind: string,
+        account: string,
+        currentRoot: () => Promise<string>,
+        load: (stateRootHash: string) => Promise<T>
+    ): Promise<T> {
+        const key = `${kind}:${account}`;
+        const root = this.stateRootHash ?? await currentRoot();
+        const entry = this.entries.get(key);
+        if (entry && Date.now() - entry.storedAt < this.maxAge
+            && (entry.stateRootHash === root || this.live.has(key))) {
+            return entry.value as T;
+        }
+
+        const value = await load(root);
+        this.entries.set(key, { stateRootHash: root, storedAt: Date.now(), value });
+        if (this.stateRootHash === root) this.live.add(key);
+        else this.live.delete(key);
+        this.scheduleSave();
+        return value;
+    }
+
+    /** Drop every entry of `account` (account hash hex) */
+    invalidate(account: string): void {
+        for (const key of this.entries.keys()) {
+            if (key.endsWith(`:${account}`)) {
+                this.entries.delete(key);
+                this.live.delete(key);
+            }
+        }
+        this.scheduleSave();
+    }
+
+    clear(): void {
+        this.entries.clear();
+        this.live.clear();
+        this.scheduleSave();
+    }
+
+    /** Track state roots and invalidations from a RegistryEventFeed's 'block', 'deploy' and 'event' */
+    follow(feed: EventEmitter): void {
+        feed.on('block', (stateRootHash: string) => {
+            this.stateRootHash = stateRootHash;
+        });
+        feed.on('deploy', (_deployHash: string, account: string) => this.invalidate(account));
+        feed.on('event', (event: { account?: string }) => {
+            if (event.account) this.invalidate(event.account);
+        });
+        // Anything may have changed while the stream was down
+        feed.on('reconnect', () => {
+            this.stateRootHash = undefined;
+            this.live.clear();
+        });
+    }
+
+    private scheduleSave(): void {
+        if (!this.path || this.saveTimer) return;
+        this.saveTimer = setTimeout(() => {
+            this.saveTimer = undefined;
+            const tmp = `${this.path}.tmp`;
+            fs.writeFileSync(tmp, JSON.stringify(Object.fromEntries(this.entries)));
+            fs.renameSync(tmp, this.path!);
+        }, 1000);
+        this.saveTimer.unref();
+    }
+}
+
+/** The backend's cache, or undefined with QUERY_CACHE=off */
+export const queryCache = config.cache.mode === 'off'
+    ? undefined
+    : new QueryCache(config.cache.mode === 'disk' ? config.cache.path : undefined, config.cache.maxAge);
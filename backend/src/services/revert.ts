@@ -0,0 +1,66 @@
+import { REGISTRY_ERRORS } from '../types/registry-errors';
+
+/**
+ * A registry revert decoded from a deploy's execution error, with the call that caused it
+ */
+export interface RegistryRevert {
+    /** Registry error code, `ApiError::User(code)` */
+    code: number;
+    /** RegistryError variant, e.g. `NotGuardian`; `Unknown` for codes this backend doesn't know */
+    name: string;
+    description: string;
+    /** Entry point the deploy called, if it called the registry by name */
+    entryPoint?: string;
+    /** The call's runtime args by name, as the node decoded them */
+    args?: Record<string, unknown>;
+    /** The node's error message */
+    message: string;
+}
+
+/**
+ * User error code in a node's execution error message: `User error: 12` (Casper 1.x) or
+ * `ApiError::User(12) [65548]` (2.x); null for any other failure (gas, missing keys, ...)
+ */
+export function userErrorCode(message: string): number | null {
+    const match = /User error: (\d+)/.exec(message) ?? /User\((\d+)\)/.exec(message);
+    return match ? Number(match[1]) : null;
+}
+
+/**
+ * Entry point and args of a deploy's stored-contract session, or of a TransactionV1's call of a
+ * stored contract, from its JSON
+ */
+function sessionCall(deploy: any): { entryPoint?: string; args?: Record<string, unknown> } {
+    const fields = (deploy?.Version1 ?? deploy)?.payload?.fields;
+    const session = deploy?.session ?? {};
+    const call = fields
+        ? { entry_point: fields.entry_point?.Custom, args: fields.args?.Named }
+        : session.StoredContractByHash ?? session.StoredContractByName
+            ?? session.StoredVersionedContractByHash ?? session.StoredVersionedContractByName;
+    if (!call?.entry_point) return {};
+    const args: Record<string, unknown> = {};
+    for (const [name, value] of call.args ?? []) {
+        args[name] = value?.parsed ?? value?.bytes;
+    }
+    return { entryPoint: call.entry_point, args };
+}
+
+/**
+ * Decode a failed execution's `errorMessage` into the registry error it reverted with, attaching
+ * the entry point and args of `deploy` (its JSON, or a transaction's) when given. Null if it isn't a user error.
+ */
+export function decodeRevert(errorMessage: string, deploy?: any): RegistryRevert | null {
+    const code = userErrorCode(errorMessage);
+    if (code === null) return null;
+    const info = REGISTRY_ERRORS[code] ?? { name: 'Unknown', description: `Unknown registry error ${code}` };
+    return { code, ...info, ...sessionCall(deploy), message: errorMessage };
+}
+
+/**
+ * One line for logs and API errors, e.g. `approve(id=12) failed: NotGuardian (5) - Caller is not a guardian of the account`
+ */
+export function formatRevert(revert: RegistryRevert): string {
+    const args = Object.entries(revert.args ?? {}).map(([k, v]) => `${k}=${typeof v === 'string' ? v : JSON.stringify(v)}`).join(', ');
+    const call = revert.entryPoint ? `${revert.entryPoint}(${args}) failed: ` : '';
+    return `${call}${revert.name} (${revert.code}) - ${revert.description}`;
+}
@@ -0,0 +1,181 @@
+import { blake2b } from '@noble/hashes/blake2b';
+import { CLPublicKey, CLValueParsers, RuntimeArgs } from 'casper-js-sdk';
+
+/**
+ * Casper 2.0 (Condor) `TransactionV1`s, the successor of Deploys, built without SDK support:
+ * casper-js-sdk 2.x only knows Deploys. The bytes follow casper-types' calltable encoding, where
+ * every struct and enum variant is a list of (field index, offset) pairs followed by the fields'
+ * bytesrepr, and the transaction hash is the blake2b-256 of the payload's bytes.
+ *
+ * Only what the registry needs is covered: a public key initiator, payment-limited standard
+ * payment, named args, standard scheduling, and either a stored contract addressed by its entity
+ * hash or session module bytes.
+ */
+
+/** What a transaction runs */
+export type TransactionTarget =
+    /** Entry point `entryPoint` of the contract whose entity (contract) hash is `hash` */
+    | { kind: 'stored'; hash: string; entryPoint: string }
+    /** `call` of session module bytes */
+    | { kind: 'session'; moduleBytes: Uint8Array };
+
+export interface TransactionParams {
+    initiator: CLPublicKey;
+    chainName: string;
+    /** ms */
+    ttl: number;
+    /** Motes */
+    paymentAmount: string;
+    gasPriceTolerance: number;
+    target: TransactionTarget;
+    args: RuntimeArgs;
+    /** ms since the Unix epoch; now if not given */
+    timestamp?: number;
+}
+
+export interface BuiltTransaction {
+    hash: string;
+    /** `{ Version1: { hash, payload, approvals } }`, as `account_put_transaction` takes it */
+    json: any;
+    /** Hex of the TransactionV1's bytesrepr */
+    bytes: string;
+}
+
+const u8 = (n: number) => Uint8Array.of(n);
+const bool = (b: boolean) => u8(b ? 1 : 0);
+
+function u16(n: number): Uint8Array {
+    const out = new Uint8Array(2);
+    new DataView(out.buffer).setUint16(0, n, true);
+    return out;
+}
+
+function u32(n: number): Uint8Array {
+    const out = new Uint8Array(4);
+    new DataView(out.buffer).setUint32(0, n, true);
+    return out;
+}
+
+function u64(n: number | string): Uint8Array {
+    const out = new Uint8Array(8);
+    new DataView(out.buffer).setBigUint64(0, BigInt(n), true);
+    return out;
+}
+
+function concat(...parts: Uint8Array[]): Uint8Array {
+    const out = new Uint8Array(parts.reduce((len, p) => len + p.length, 0));
+    let offset = 0;
+    for (const p of parts) {
+        out.set(p, offset);
+        offset += p.length;
+    }
+    return out;
+}
+
+/** `Bytes`/`Vec<u8>`: u32 length, then the bytes */
+const sized = (bytes: Uint8Array) => concat(u32(bytes.length), bytes);
+const string = (s: string) => sized(Uint8Array.from(Buffer.from(s, 'utf8')));
+
+/** Calltable envelope of `fields`, the i-th at field index i */
+function calltable(...fields: Uint8Array[]): Uint8Array {
+    let offset = 0;
+    const index = fields.map((f, i) => {
+        const entry = concat(u16(i), u32(offset));
+        offset += f.length;
+        return entry;
+    });
+    return concat(u32(fields.length), ...index, sized(concat(...fields)));
+}
+
+const hex = (bytes: Uint8Array) => Buffer.from(bytes).toString('hex');
+const unhex = (s: string) => Uint8Array.from(Buffer.from(s, 'hex'));
+
+/** `TransactionRuntimeParams::VmCasperV1` */
+const VM_CASPER_V1 = calltable(u8(0));
+
+function targetBytes(target: TransactionTarget): Uint8Array {
+    if (target.kind === 'stored') {
+        // TransactionTarget::Stored { id: TransactionInvocationTarget::ByHash, runtime }
+        return calltable(u8(1), calltable(u8(0), unhex(target.hash)), VM_CASPER_V1);
+    }
+    // TransactionTarget::Session { is_install_upgrade, runtime, module_bytes }
+    return calltable(u8(2), bool(false), VM_CASPER_V1, sized(target.moduleBytes));
+}
+
+function entryPointBytes(target: TransactionTarget): Uint8Array {
+    // TransactionEntryPoint::Custom(name) for contracts, ::Call for session code
+    return target.kind === 'stored' ? calltable(u8(1), string(target.entryPoint)) : calltable(u8(0));
+}
+
+function targetJson(target: TransactionTarget): any {
+    return target.kind === 'stored'
+        ? { Stored: { id: { ByHash: target.hash }, runtime: 'VmCasperV1' } }
+        : { Session: { is_install_upgrade: false, runtime: 'VmCasperV1', module_bytes: hex(target.moduleBytes) } };
+}
+
+/** A TimeDiff the way the node prints one: `30m`, `90s`, `1500ms` */
+function timeDiff(ms: number): string {
+    if (ms % 3_600_000 === 0) return `${ms / 3_600_000}h`;
+    if (ms % 60_000 === 0) return `${ms / 60_000}m`;
+    if (ms % 1000 === 0) return `${ms / 1000}s`;
+    return `${ms}ms`;
+}
+
+/**
+ * Unsigned `TransactionV1`. Sign its hash the way a deploy's is signed and add
+ * `{ signer, signature }` to `json.Version1.approvals`.
+ */
+export function buildTransaction(params: TransactionParams): BuiltTransaction {
+    const { initiator, chainName, ttl, paymentAmount, gasPriceTolerance, target, args } = params;
+    const timestamp = params.timestamp ?? Date.now();
+    const initiatorHex = initiator.toHex();
+
+    const fields = [
+        // BTreeMap<u16, Bytes>: args, target, entry point, scheduling
+        args.toBytes().unwrap(),
+        targetBytes(target),
+        entryPointBytes(target),
+        calltable(u8(0)),
+    ].map((value, key) => concat(u16(key), sized(value)));
+
+    const payload = calltable(
+        calltable(u8(0), unhex(initiatorHex)),
+        u64(timestamp),
+        u64(ttl),
+        string(chainName),
+        calltable(u8(0), u64(paymentAmount), u8(gasPriceTolerance), bool(true)),
+        concat(u32(fields.length), ...fields),
+    );
+    const hash = blake2b(payload, { dkLen: 32 });
+
+    const namedArgs = Object.entries(args.args).map(([name, value]) => [name, CLValueParsers.toJSON(value).unwrap()]);
+    const json = {
+        Version1: {
+            hash: hex(hash),
+            payload: {
+                initiator_addr: { PublicKey: initiatorHex },
+                timestamp: new Date(timestamp).toISOString(),
+                ttl: timeDiff(ttl),
+                chain_name: chainName,
+                pricing_mode: {
+                    PaymentLimited: {
+                        payment_amount: Number(paymentAmount),
+                        gas_price_tolerance: gasPriceTolerance,
+                        standard_payment: true,
+                    },
+                },
+                fields: {
+                    args: { Named: namedArgs },
+                    target: targetJson(target),
+                    entry_point: target.kind === 'stored' ? { Custom: target.entryPoint } : 'Call',
+                    scheduling: 'Standard',
+                },
+            },
+            approvals: [],
+        },
+    };
+
+    // TransactionV1 { hash, payload, approvals (empty) }
+    const bytes = calltable(hash, payload, u32(0));
+    return { hash: hex(hash), json, bytes: hex(bytes) };
+}
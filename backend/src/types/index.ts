@@ -80,6 +80,37 @@ export interface MultiSigDeploy {
     signatures: string[];
 }
 
+/** One runtime argument of a registry call, described in JSON */
+export interface TypedArg {
+    name: string;
+    type: 'u8' | 'u32' | 'u64' | 'u256' | 'u512' | 'bool' | 'string'
+        | 'account' | 'account_list' | 'public_key' | 'public_key_list' | 'bytes' | 'signed_approval_list';
+    value: any;
+}
+
+/** A guardian's signed meta-approval, as relayed to `approve_meta` */
+export interface MetaApproval {
+    /** Decimal recovery id */
+    id: string;
+    /** Guardian public key hex */
+    guardian: string;
+    nonce: number;
+    /** Signature hex, without the algorithm tag byte */
+    signature: string;
+}
+
+export interface PreparedDeploy {
+    /** `transaction` for a Casper 2.0 TransactionV1, `deploy` for a legacy deploy */
+    kind: 'deploy' | 'transaction';
+    /** Hash of the deploy or transaction, which signers sign */
+    deployHash: string;
+    deploy?: any;
+    /** `{ Version1: ... }` */
+    transaction?: any;
+    /** Hex of the deploy's or transaction's bytesrepr */
+    bytes: string;
+}
+
 // ============================================================================
 // API Response Types
 // ============================================================================
@@ -0,0 +1,81 @@
+// Generated by `make errors-ts` from guardian-types' RegistryError. Do not edit.
+
+export interface RegistryErrorInfo {
+    name: string;
+    description: string;
+}
+
+/** Registry `ApiError::User` codes */
+export const REGISTRY_ERRORS: Record<number, RegistryErrorInfo> = {
+    1: { name: 'NotOwner', description: "Caller is not the account owner, nor a member holding the role the call needs" },
+    2: { name: 'AlreadyInit', description: "Guardians are already set up for this account" },
+    3: { name: 'BadGuardians', description: "Fewer than two guardians, a duplicate, or the owner among them" },
+    4: { name: 'BadThreshold', description: "Threshold is zero or above the guardian count" },
+    5: { name: 'NotGuardian', description: "Caller is not a guardian of the account" },
+    6: { name: 'RecoveryExists', description: "The account already has a recovery open" },
+    7: { name: 'NotFound', description: "No such recovery, or it is closed" },
+    8: { name: 'AlreadyApproved', description: "Caller already voted on this recovery" },
+    9: { name: 'NotApproved', description: "The recovery hasn't reached its threshold" },
+    10: { name: 'NotInit', description: "The account has no guardians set up" },
+    11: { name: 'MissingDict', description: "The registry's storage dictionary is missing; call `init_storage`" },
+    12: { name: 'NotHeir', description: "Caller is not an heir of the account" },
+    13: { name: 'BadHeir', description: "Heir is the owner, a guardian or already designated" },
+    14: { name: 'NotHeirKey', description: "The new key isn't one an heir registered" },
+    15: { name: 'BadType', description: "Unknown recovery type" },
+    16: { name: 'BondRequired', description: "Caller hasn't posted the account's guardian bond" },
+    17: { name: 'BadBond', description: "Bond already posted in full, or the transfer failed" },
+    18: { name: 'Vetoed', description: "The recovery was vetoed" },
+    19: { name: 'NotVetoed', description: "The recovery wasn't vetoed" },
+    20: { name: 'BadSlash', description: "Slash share out of range, evidence too long, or already proposed" },
+    21: { name: 'NoSlash', description: "No slash proposed for this recovery" },
+    22: { name: 'DisputeWindow', description: "Outside the slash dispute window" },
+    23: { name: 'SlashPending', description: "A slash against the account's guardians is still open" },
+    24: { name: 'InsufficientRewards', description: "The reward pool can't cover the withdrawal" },
+    25: { name: 'Finalized', description: "The recovery is already finalized" },
+    26: { name: 'DepositRequired', description: "The account requires a recovery deposit; pass a funded `purse`" },
+    27: { name: 'BadDelegate', description: "Delegate is the owner, a guardian, already taken, or the delegation has already ended" },
+    28: { name: 'BadProof', description: "Merkle proof doesn't show the caller in the committed guardian set" },
+    29: { name: 'BadVerifier', description: "The recovery type's verifier isn't available for this account" },
+    30: { name: 'NoShare', description: "Caller stored no share for the account" },
+    31: { name: 'BadShare', description: "Share URI too long, or the attested hash doesn't match" },
+    32: { name: 'BadUri', description: "Instructions URI longer than 256 bytes" },
+    33: { name: 'NotArbiter', description: "Caller is not the recovery's arbiter" },
+    34: { name: 'BadArbiter', description: "Arbiter is the owner or a guardian, or a tie-break isn't possible" },
+    35: { name: 'ArbiterUsed', description: "The arbiter already used this power on the recovery" },
+    36: { name: 'Expired', description: "The recovery has expired" },
+    37: { name: 'NotConfirmed', description: "The recovery lacks its confirmation quorum" },
+    38: { name: 'ConfirmWindow', description: "Outside the confirmation window" },
+    39: { name: 'BadMemo', description: "Memo longer than 140 bytes" },
+    40: { name: 'BadKeyType', description: "A guardian key's algorithm isn't allowed by the account's key policy" },
+    41: { name: 'BadSignature', description: "Signature doesn't verify against the payload" },
+    42: { name: 'BadSettings', description: 'Settings under which no recovery could finalize, or more anomaly limits than counters' },
+    43: { name: 'TooEarly', description: "The wait before this step hasn't passed" },
+    44: { name: 'TooManyAttempts', description: "Too many unfinalized recoveries; the owner has to reset the count" },
+    45: { name: 'BadProvider', description: "Reward provider must be an account or a contract" },
+    46: { name: 'BadBatch', description: "Batch empty or larger than the limit" },
+    47: { name: 'BadWatcher', description: "Watcher already added, list full, or not a watcher" },
+    48: { name: 'Frozen', description: "Configuration is frozen after a malicious veto" },
+    49: { name: 'NoFrostKey', description: "No FROST group key registered, or the guardian set changed since it was" },
+    50: { name: 'Flagged', description: "Enough guardians flagged the recovery to hold it until they withdraw or the arbiter rules" },
+    51: { name: 'BadFlag', description: "Caller already flagged the recovery, has no flag on it to withdraw, or the flag limit is zero" },
+    52: { name: 'BadPolicy', description: 'Approval policy malformed, its weights or category minimums invalid for the guardians, or not one this entry point can change or approve under' },
+    53: { name: 'CoolingDown', description: "A recovery of the account finalized too recently; its cool-down hasn't passed" },
+    54: { name: 'BadModule', description: "Too many recovery modules, one listed twice, or one that isn't a contract package" },
+    55: { name: 'Decommissioned', description: 'Registry is decommissioning and takes no new accounts or recoveries, or its sunset has passed and it takes no changes at all' },
+    56: { name: 'NotAdmin', description: "Caller is not the registry's admin, or the registry is already decommissioning" },
+    57: { name: 'BadNonce', description: "Meta-approval signed for another nonce than the guardian's current one: already used, voided, or ahead of one not yet submitted" },
+    58: { name: 'NotRelayer', description: "Caller isn't on the account's relayer allowlist, or the allowlist is too long or names a relayer twice" },
+    59: { name: 'UnexpectedArgument', description: "Call passed an argument the entry point doesn't take, on a registry installed with `strict_args`" },
+    60: { name: 'BadMigration', description: "The records are in a newer schema than this build migrates to" },
+    61: { name: 'NoInvitation', description: "The caller has no pending invitation from the account" },
+    62: { name: 'InvitationExpired', description: "The invitation's deadline has passed, or the deadline given is already past" },
+    63: { name: 'NotListed', description: "The caller isn't listed in the guardian directory" },
+    64: { name: 'BadFeeSchedule', description: "Fee schedule doesn't give exactly one fee per chargeable operation" },
+    65: { name: 'FeeRequired', description: "The operation carries a protocol fee and the call passed no `purse`, or one that couldn't pay it" },
+    66: { name: 'BadDestination', description: "Fee withdrawal destination isn't an account, or is the all-zero account hash" },
+    67: { name: 'BadWithdrawal', description: "Fee withdrawal of nothing, or of more than the fee purse holds" },
+    68: { name: 'NoTenant', description: "The call names a tenant that doesn't exist, or tenant 0 where a created tenant is needed" },
+    69: { name: 'BadRole', description: "Role change naming no known role, a member that is the owner itself, or one member too many" },
+    70: { name: 'NotInitiator', description: "The account has recovery initiators and the caller isn't one of them" },
+    71: { name: 'BadTemplate', description: "No built-in template has that name, or the guardians don't fill its slots exactly" },
+};
@@ -0,0 +1,76 @@
+import { spawn } from 'child_process';
+import { config } from '../config';
+import { WebhookDispatcher } from './webhooks';
+
+export type NotificationKind =
+    'recovery_initiated' | 'recovery_approved' | 'recovery_flagged' | 'recovery_anomaly' | 'recovery_near_expiry';
+
+export interface Notification {
+    kind: NotificationKind;
+    recoveryId: string;
+    account: string;
+    guardian?: string;
+    newKey?: string;
+    approvals?: number;
+    /** Flag reason code and the recovery's flag count, for `recovery_flagged` */
+    reason?: number;
+    flags?: number;
+    /** Counter name, its lifetime count and the account's limit, for `recovery_anomaly` */
+    counter?: string;
+    count?: number;
+    limit?: number;
+    expiresAt?: number;
+    deployHash?: string;
+}
+
+export type Action = (notification: Notification) => Promise<void>;
+
+/** Print the notification to stdout */
+const log: Action = async (n) => {
+    console.log(`[watchd] ${n.kind} recovery=${n.recoveryId} account=${n.account}`, JSON.stringify(n));
+};
+
+/**
+ * Run WATCHD_EXEC_COMMAND through the shell with the notification as JSON on
+ * stdin and its main fields in GUARDIAN_* environment variables
+ */
+const exec: Action = (n) => new Promise((resolve, reject) => {
+    if (!config.watchd.execCommand) return reject(new Error('WATCHD_EXEC_COMMAND is not set'));
+    const child = spawn(config.watchd.execCommand, {
+        shell: true,
+        stdio: ['pipe', 'inherit', 'inherit'],
+        env: {
+            ...process.env,
+            GUARDIAN_EVENT: n.kind,
+            GUARDIAN_RECOVERY_ID: n.recoveryId,
+            GUARDIAN_ACCOUNT: n.account,
+        },
+    });
+    child.on('error', reject);
+    child.on('exit', (code) => (code === 0 ? resolve() : reject(new Error(`exec action exited with ${code}`))));
+    child.stdin.end(JSON.stringify(n));
+});
+
+let dispatcher: WebhookDispatcher | undefined;
+
+/** The process-wide webhook dispatcher, created on first use */
+export function webhooks(): WebhookDispatcher {
+    dispatcher ??= new WebhookDispatcher();
+    return dispatcher;
+}
+
+/** Queue signed deliveries to the endpoints in WATCHD_WEBHOOKS_PATH */
+const webhook: Action = async (n) => webhooks().enqueue(n);
+
+const ACTIONS: Record<string, Action> = { log, exec, webhook };
+
+/**
+ * The actions named in WATCHD_ACTIONS, in order
+ */
+export function configuredActions(): { name: string; run: Action }[] {
+    return config.watchd.actions.map((name) => {
+        const run = ACTIONS[name];
+        if (!run) throw new Error(`Unknown watchd action: ${name}`);
+        return { name, run };
+    });
+}
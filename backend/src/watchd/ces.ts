@@ -0,0 +1,347 @@
+/**
+ * Decoding of the registry's CES events out of deploy execution results
+ *
+ * The registry appends each event to its `__events` dictionary as
+ * `event_<Name>` followed by the fields in schema order (see contracts/types/src/events.rs).
+ * A dictionary write shows up in the execution effects as a `CLType::Any` value holding the
+ * serialized DictionaryValue: (CLValue, seed uref address, item key).
+ */
+
+export interface RegistryEvent {
+    name: string;
+    /** Position in the `__events` dictionary */
+    index?: number;
+    /** Recovery id as a decimal string */
+    id?: string;
+    /** Account hashes are lowercase hex without the `account-hash-` prefix */
+    account?: string;
+    guardian?: string;
+    /** Replacement guardian of a key rotation */
+    newGuardian?: string;
+    /** Block time (ms) an invitation lapses at */
+    deadline?: number;
+    newKey?: string;
+    threshold?: number;
+    approvals?: number;
+    vetoed?: boolean;
+    /** Per-guardian results of a signed approval batch: 0 if counted, else the registry error code */
+    outcomes?: { guardian: string; code: number }[];
+    /** Reason code of a recovery flag (contracts/types/src/flags.rs) */
+    reason?: number;
+    /** Flags on the recovery after this event */
+    flags?: number;
+    /** Lifetime counter (contracts/types/src/counters.rs) that passed its anomaly limit, its count and the limit */
+    counter?: number;
+    count?: number;
+    limit?: number;
+    /** Of a rejection: the entry point, its registry error code, and the offending argument's
+     * position in the entry point's parameters and within that argument if it is a list */
+    entryPoint?: string;
+    code?: number;
+    arg?: number;
+    item?: number;
+    /** Entry point that replaced a deprecated `entryPoint` */
+    replacement?: string;
+    /** Account that called a deprecated entry point */
+    caller?: string;
+    /** Of a guardian directory listing: fee in motes as a decimal string, contact hash (hex) and the
+     * `interfaces` mask of what the guardian supports */
+    fee?: string;
+    contact?: string;
+    policies?: number;
+    /** Of protocol fees: the schedule in motes indexed by `guardian_types::fees`, or one fee's
+     * operation, payer and amount in motes */
+    fees?: string[];
+    operation?: number;
+    payer?: string;
+    amount?: string;
+    /** Account a fee withdrawal paid out to */
+    destination?: string;
+    /** Tenant whose records the event changed (0 for the registry's own), or the tenant created or
+     * handed over; absent from events older than the tenants */
+    tenant?: number;
+    /** Admin of a tenant created or handed over */
+    admin?: string;
+    /** Account or contract package granted roles, and the `roles` bits it now holds */
+    member?: string;
+    roles?: number;
+    /** Built-in policy template an account was set up from */
+    template?: string;
+    notify: string[];
+}
+
+/** Cursor over bytesrepr bytes */
+export class Reader {
+    private offset = 0;
+
+    constructor(private bytes: Buffer) {}
+
+    get position(): number {
+        return this.offset;
+    }
+
+    /** Bytes read since `start` */
+    since(start: number): Buffer {
+        return this.bytes.subarray(start, this.offset);
+    }
+
+    take(n: number): Buffer {
+        if (this.offset + n > this.bytes.length) throw new Error('Unexpected end of bytes');
+        const out = this.bytes.subarray(this.offset, this.offset + n);
+        this.offset += n;
+        return out;
+    }
+
+    u8(): number {
+        return this.take(1)[0];
+    }
+
+    u32(): number {
+        return this.take(4).readUInt32LE(0);
+    }
+
+    u64(): number {
+        return Number(this.take(8).readBigUInt64LE(0));
+    }
+
+    remaining(): number {
+        return this.bytes.length - this.offset;
+    }
+
+    /** Skip one serialized CLType */
+    clType(): void {
+        const tag = this.u8();
+        if (tag === 13 || tag === 14 || tag === 18) this.clType();
+        else if (tag === 15) this.u32();
+        else if (tag === 16 || tag === 17 || tag === 19) { this.clType(); this.clType(); }
+        else if (tag === 20) { this.clType(); this.clType(); this.clType(); }
+        else if (tag > 22) throw new Error(`Unknown CLType tag ${tag}`);
+    }
+
+    bool(): boolean {
+        return this.u8() === 1;
+    }
+
+    bytes(): Buffer {
+        return this.take(this.u32());
+    }
+
+    string(): string {
+        return this.bytes().toString('utf8');
+    }
+
+    u256(): string {
+        const le = this.take(this.u8());
+        return BigInt('0x' + (Buffer.from(le).reverse().toString('hex') || '0')).toString();
+    }
+
+    hash(): string {
+        return this.take(32).toString('hex');
+    }
+
+    publicKey(): string {
+        const tag = this.u8();
+        const len = tag === 1 ? 32 : tag === 2 ? 33 : 0;
+        return '0' + tag + this.take(len).toString('hex');
+    }
+
+    /** Only account, hash and uref keys appear in registry events */
+    key(): string {
+        const tag = this.u8();
+        if (tag === 0 || tag === 1) return this.hash();
+        if (tag === 2) return this.take(33).toString('hex');
+        throw new Error(`Unsupported key tag ${tag}`);
+    }
+
+    hashes(): string[] {
+        const count = this.u32();
+        return Array.from({ length: count }, () => this.hash());
+    }
+}
+
+/** Field readers per event, in schema order */
+const LAYOUTS: Record<string, (r: Reader) => Partial<RegistryEvent>> = {
+    GuardiansInitializedV1: (r) => ({ account: r.hash(), threshold: r.u8() }),
+    GuardianAddedV1: (r) => ({ account: r.hash(), guardian: r.key() }),
+    GuardianRemovedV1: (r) => ({ account: r.hash(), guardian: r.key() }),
+    GuardianRotationRequestedV1: (r) => ({ account: r.hash(), guardian: r.hash(), newKey: r.publicKey() }),
+    GuardianKeyRotatedV1: (r) => ({ account: r.hash(), guardian: r.hash(), newGuardian: r.hash() }),
+    GuardianInvitedV1: (r) => ({ account: r.hash(), guardian: r.hash(), deadline: r.u64() }),
+    InvitationExpiredV1: (r) => ({ account: r.hash(), guardian: r.hash(), deadline: r.u64() }),
+    RecoveryStartedV1: (r) => ({ id: r.u256(), account: r.hash(), newKey: r.publicKey() }),
+    RecoveryApprovedV1: (r) => ({ id: r.u256(), guardian: r.key(), approvals: r.u8() }),
+    RecoveryFinalizedV1: (r) => ({ id: r.u256(), account: r.hash() }),
+    RecoveryCancelledV1: (r) => ({ id: r.u256(), account: r.hash(), vetoed: r.bool() }),
+    RecoveryStartedV2: (r) => ({ id: r.u256(), account: r.hash(), newKey: r.publicKey(), notify: r.hashes() }),
+    RecoveryApprovedV2: (r) => ({
+        id: r.u256(), account: r.hash(), guardian: r.key(), approvals: r.u8(), notify: r.hashes(),
+    }),
+    RecoveryFinalizedV2: (r) => ({ id: r.u256(), account: r.hash(), notify: r.hashes() }),
+    RecoveryCancelledV2: (r) => ({ id: r.u256(), account: r.hash(), vetoed: r.bool(), notify: r.hashes() }),
+    // The group key stands in for the approving guardian
+    RecoveryFrostApprovedV1: (r) => ({ id: r.u256(), account: r.hash(), guardian: r.publicKey(), notify: r.hashes() }),
+    // `approvals` is how many of the batch were counted
+    SignedApprovalsSubmittedV1: (r) => ({
+        id: r.u256(), account: r.hash(), approvals: r.u8(),
+        outcomes: Array.from({ length: r.u32() }, () => ({ guardian: r.publicKey(), code: r.u32() })),
+        notify: r.hashes(),
+    }),
+    RecoveryFlaggedV1: (r) => ({
+        id: r.u256(), account: r.hash(), guardian: r.key(), reason: r.u8(), flags: r.u8(), notify: r.hashes(),
+    }),
+    RecoveryFlagWithdrawnV1: (r) => ({ id: r.u256(), account: r.hash(), guardian: r.key(), flags: r.u8() }),
+    RecoveryAnomalyV1: (r) => ({
+        id: r.u256(), account: r.hash(), counter: r.u8(), count: r.u32(), limit: r.u32(), notify: r.hashes(),
+    }),
+    RejectedV1: (r) => ({
+        entryPoint: r.string(), account: r.hash(), guardian: r.key(), code: r.u32(), arg: r.u8(), item: r.u32(),
+    }),
+    EntryPointDeprecatedV1: (r) => ({ entryPoint: r.string(), replacement: r.string(), caller: r.hash() }),
+    GuardianListedV1: (r) => ({ guardian: r.hash(), fee: r.u256(), contact: r.hash(), policies: r.u64() }),
+    GuardianUnlistedV1: (r) => ({ guardian: r.hash() }),
+    FeeScheduleSetV1: (r) => ({ fees: Array.from({ length: r.u32() }, () => r.u256()) }),
+    FeeChargedV1: (r) => ({ operation: r.u8(), account: r.hash(), payer: r.hash(), amount: r.u256() }),
+    FeesWithdrawnV1: (r) => ({ destination: r.hash(), amount: r.u256() }),
+    TenantCreatedV1: (r) => ({ tenant: r.u32(), admin: r.hash() }),
+    TenantAdminChangedV1: (r) => ({ tenant: r.u32(), admin: r.hash() }),
+    // From here on, events of tenant-scoped operations lead with their tenant
+    GuardiansInitializedV2: (r) => ({ tenant: r.u32(), account: r.hash(), threshold: r.u8() }),
+    GuardianAddedV2: (r) => ({ tenant: r.u32(), account: r.hash(), guardian: r.key() }),
+    GuardianRemovedV2: (r) => ({ tenant: r.u32(), account: r.hash(), guardian: r.key() }),
+    GuardianRotationRequestedV2: (r) => ({ tenant: r.u32(), account: r.hash(), guardian: r.hash(), newKey: r.publicKey() }),
+    GuardianKeyRotatedV2: (r) => ({ tenant: r.u32(), account: r.hash(), guardian: r.hash(), newGuardian: r.hash() }),
+    GuardianInvitedV2: (r) => ({ tenant: r.u32(), account: r.hash(), guardian: r.hash(), deadline: r.u64() }),
+    InvitationExpiredV2: (r) => ({ tenant: r.u32(), account: r.hash(), guardian: r.hash(), deadline: r.u64() }),
+    RecoveryStartedV3: (r) => ({
+        tenant: r.u32(), id: r.u256(), account: r.hash(), newKey: r.publicKey(), notify: r.hashes(),
+    }),
+    RecoveryApprovedV3: (r) => ({
+        tenant: r.u32(), id: r.u256(), account: r.hash(), guardian: r.key(), approvals: r.u8(), notify: r.hashes(),
+    }),
+    RecoveryFinalizedV3: (r) => ({ tenant: r.u32(), id: r.u256(), account: r.hash(), notify: r.hashes() }),
+    RecoveryCancelledV3: (r) => ({
+        tenant: r.u32(), id: r.u256(), account: r.hash(), vetoed: r.bool(), notify: r.hashes(),
+    }),
+    RecoveryFrostApprovedV2: (r) => ({
+        tenant: r.u32(), id: r.u256(), account: r.hash(), guardian: r.publicKey(), notify: r.hashes(),
+    }),
+    SignedApprovalsSubmittedV2: (r) => ({
+        tenant: r.u32(), id: r.u256(), account: r.hash(), approvals: r.u8(),
+        outcomes: Array.from({ length: r.u32() }, () => ({ guardian: r.publicKey(), code: r.u32() })),
+        notify: r.hashes(),
+    }),
+    RecoveryFlaggedV2: (r) => ({
+        tenant: r.u32(), id: r.u256(), account: r.hash(), guardian: r.key(), reason: r.u8(), flags: r.u8(),
+        notify: r.hashes(),
+    }),
+    RecoveryFlagWithdrawnV2: (r) => ({
+        tenant: r.u32(), id: r.u256(), account: r.hash(), guardian: r.key(), flags: r.u8(),
+    }),
+    RecoveryAnomalyV2: (r) => ({
+        tenant: r.u32(), id: r.u256(), account: r.hash(), counter: r.u8(), count: r.u32(), limit: r.u32(),
+        notify: r.hashes(),
+    }),
+    RejectedV2: (r) => ({
+        tenant: r.u32(), entryPoint: r.string(), account: r.hash(), guardian: r.key(), code: r.u32(), arg: r.u8(),
+        item: r.u32(),
+    }),
+    GuardianListedV2: (r) => ({
+        tenant: r.u32(), guardian: r.hash(), fee: r.u256(), contact: r.hash(), policies: r.u64(),
+    }),
+    GuardianUnlistedV2: (r) => ({ tenant: r.u32(), guardian: r.hash() }),
+    FeeScheduleSetV2: (r) => ({ tenant: r.u32(), fees: Array.from({ length: r.u32() }, () => r.u256()) }),
+    FeeChargedV2: (r) => ({
+        tenant: r.u32(), operation: r.u8(), account: r.hash(), payer: r.hash(), amount: r.u256(),
+    }),
+    FeesWithdrawnV2: (r) => ({ tenant: r.u32(), destination: r.hash(), amount: r.u256() }),
+    RolesChangedV1: (r) => ({ tenant: r.u32(), account: r.hash(), member: r.key(), roles: r.u8() }),
+    TemplateAppliedV1: (r) => ({ tenant: r.u32(), account: r.hash(), template: r.string() }),
+};
+
+/** Name of an `__events` entry without the `event_` prefix, read without decoding its fields */
+export function eventName(eventBytes: Buffer): string {
+    return new Reader(eventBytes).string().replace(/^event_/, '');
+}
+
+/**
+ * Decode one `__events` entry. Returns null for events this daemon doesn't know.
+ */
+export function decodeEvent(eventBytes: Buffer): RegistryEvent | null {
+    const r = new Reader(eventBytes);
+    const name = r.string().replace(/^event_/, '');
+    const layout = LAYOUTS[name];
+    if (!layout) return null;
+    return { name, notify: [], ...layout(r) };
+}
+
+/**
+ * Hex bytes of every CLValue written by an execution result, for both the 1.x
+ * (`Success.effect.transforms`) and 2.x (`Version2.effects`) layouts
+ */
+function writtenValues(executionResult: any): string[] {
+    const out: string[] = [];
+    const transforms = executionResult?.Success?.effect?.transforms
+        ?? executionResult?.Version1?.Success?.effect?.transforms
+        ?? [];
+    for (const t of transforms) {
+        const bytes = t?.transform?.WriteCLValue?.bytes;
+        if (bytes) out.push(bytes);
+    }
+    for (const e of executionResult?.Version2?.effects ?? []) {
+        const bytes = e?.kind?.Write?.CLValue?.bytes;
+        if (bytes) out.push(bytes);
+    }
+    return out;
+}
+
+export interface DictionaryWrite {
+    /** Seed uref address (hex) of the dictionary */
+    seed: string;
+    itemKey: string;
+    /** The stored value's bytesrepr */
+    value: Buffer;
+}
+
+/** Every dictionary item an execution result wrote, in effect order */
+export function dictionaryWrites(executionResult: any): DictionaryWrite[] {
+    const out: DictionaryWrite[] = [];
+    for (const hex of writtenValues(executionResult)) {
+        try {
+            const r = new Reader(Buffer.from(hex, 'hex'));
+            const value = r.bytes();
+            r.clType();
+            const seed = r.bytes().toString('hex');
+            const itemKey = r.bytes().toString('utf8');
+            if (r.remaining() === 0) out.push({ seed, itemKey, value });
+        } catch {
+            // Not a dictionary value
+        }
+    }
+    return out;
+}
+
+/**
+ * Registry events written to the `__events` dictionary seeded by `eventsUrefAddr`
+ * (hex, without the `uref-` prefix and access suffix), in emission order. With `filter`, events
+ * whose name it doesn't want are skipped before their fields are decoded.
+ */
+export function extractEvents(
+    executionResult: any,
+    eventsUrefAddr: string,
+    filter?: { wantsName(name: string): boolean },
+): RegistryEvent[] {
+    const events: RegistryEvent[] = [];
+    for (const write of dictionaryWrites(executionResult)) {
+        if (write.seed !== eventsUrefAddr) continue;
+        try {
+            // Events are stored as `Bytes`: a length-prefixed byte list
+            const bytes = new Reader(write.value).bytes();
+            if (filter && !filter.wantsName(eventName(bytes))) continue;
+            const event = decodeEvent(bytes);
+            if (event) events.push({ ...event, index: Number(write.itemKey) });
+        } catch {
+            // Not one of ours
+        }
+    }
+    return events.sort((a, b) => a.index! - b.index!);
+}
@@ -0,0 +1,77 @@
+import { EventEmitter } from 'events';
+import { config } from '../config';
+import { casperService } from '../services';
+import { extractEvents, RegistryEvent } from './ces';
+import { EventFilter } from './filter';
+import { SseClient } from './sse';
+
+export type EventListener = (event: RegistryEvent, deployHash: string) => void;
+
+/**
+ * Registry events decoded from a node's live stream, emitted as 'event' with the deploy hash
+ *
+ * Also emits 'deploy' (deploy hash, sender account hash) for every processed deploy, 'block'
+ * (state root hash) for every added block and 'reconnect' when the stream drops.
+ *
+ * A feed built with a `filter` only emits the events it matches. `subscribe` gives one listener
+ * its own filter on a shared feed. Either way, event types no listener wants are not decoded.
+ */
+export class RegistryEventFeed extends EventEmitter {
+    private sse?: SseClient;
+    private subscriptions = new Set<{ filter: EventFilter; listener: EventListener }>();
+
+    constructor(private eventsUrl: string, private tag: string, private filter?: EventFilter) {
+        super();
+        this.setMaxListeners(0);
+    }
+
+    /** Call `listener` with the events `filter` matches until the returned function is called */
+    subscribe(filter: EventFilter, listener: EventListener): () => void {
+        const subscription = { filter, listener };
+        this.subscriptions.add(subscription);
+        return () => {
+            this.subscriptions.delete(subscription);
+        };
+    }
+
+    /** Whether any listener can want an event called `name` */
+    wantsName(name: string): boolean {
+        if (this.listenerCount('event') > 0 && (this.filter?.wantsName(name) ?? true)) return true;
+        for (const { filter } of this.subscriptions) if (filter.wantsName(name)) return true;
+        return false;
+    }
+
+    async start(): Promise<void> {
+        const registry = config.contract.recoveryRegistryHash;
+        if (!registry) throw new Error('RECOVERY_REGISTRY_HASH is not set');
+        const eventsUrefAddr = await casperService.getContractNamedUref(registry, '__events');
+
+        this.sse = new SseClient(
+            this.eventsUrl,
+            async ({ data }) => {
+                const block = data?.BlockAdded?.block;
+                const header = block?.header ?? block?.Version2?.header ?? block?.Version1?.header;
+                if (header?.state_root_hash) this.emit('block', header.state_root_hash);
+
+                const deploy = data?.DeployProcessed;
+                if (!deploy) return;
+                if (deploy.account) this.emit('deploy', deploy.deploy_hash, casperService.accountHashHex(deploy.account));
+                for (const event of extractEvents(deploy.execution_result, eventsUrefAddr, this)) {
+                    if (!this.filter || this.filter.matches(event)) this.emit('event', event, deploy.deploy_hash);
+                    for (const { filter, listener } of this.subscriptions) {
+                        if (filter.matches(event)) listener(event, deploy.deploy_hash);
+                    }
+                }
+            },
+            (error) => {
+                this.emit('reconnect', error);
+                console.warn(`[${this.tag}] Event stream dropped, reconnecting: ${error}`);
+            }
+        );
+        await this.sse.run();
+    }
+
+    stop(): void {
+        this.sse?.stop();
+    }
+}
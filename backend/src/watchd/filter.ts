@@ -0,0 +1,76 @@
+import { casperService } from '../services';
+import { RegistryEvent } from './ces';
+
+/**
+ * Client-side selection of registry events for listeners that follow a few accounts or
+ * guardians instead of the whole registry
+ *
+ * Accounts and guardians widen the filter: an event passes if it is on one of the accounts or
+ * names one of the guardians (as the approving guardian or in its `notify` list). Types narrow
+ * it: with any set, only events of those types pass, and `wantsName` lets the decoder skip the
+ * others before reading their fields. An empty filter passes everything.
+ */
+export class EventFilter {
+    private accountSet = new Set<string>();
+    private guardianSet = new Set<string>();
+    private typeSet = new Set<string>();
+
+    /** Every registry event */
+    static all(): EventFilter {
+        return new EventFilter();
+    }
+
+    static accounts(...accounts: string[]): EventFilter {
+        return new EventFilter().account(...accounts);
+    }
+
+    static guardians(...keys: string[]): EventFilter {
+        return new EventFilter().guardian(...keys);
+    }
+
+    static types(...names: string[]): EventFilter {
+        return new EventFilter().type(...names);
+    }
+
+    /** Keep events on these accounts (public keys or account hashes) */
+    account(...accounts: string[]): this {
+        for (const a of accounts) this.accountSet.add(casperService.accountHashHex(a));
+        return this;
+    }
+
+    /**
+     * Keep events naming these guardians. A public key matches both the events that carry the
+     * guardian's account hash and those that carry the key itself.
+     */
+    guardian(...keys: string[]): this {
+        for (const k of keys) {
+            this.guardianSet.add(casperService.accountHashHex(k));
+            if (!/^(account-hash-|entity-account-)/.test(k) && !/^[0-9a-fA-F]{64}$/.test(k)) {
+                this.guardianSet.add(k.toLowerCase());
+            }
+        }
+        return this;
+    }
+
+    /**
+     * Keep only events of these types: a full name (`RecoveryApprovedV2`) or one without its
+     * version suffix (`RecoveryApproved`) for every version
+     */
+    type(...names: string[]): this {
+        for (const n of names) this.typeSet.add(n);
+        return this;
+    }
+
+    /** Whether an event called `name` can pass, checked before its fields are decoded */
+    wantsName(name: string): boolean {
+        return this.typeSet.size === 0 || this.typeSet.has(name) || this.typeSet.has(name.replace(/V\d+$/, ''));
+    }
+
+    matches(event: RegistryEvent): boolean {
+        if (!this.wantsName(event.name)) return false;
+        if (this.accountSet.size === 0 && this.guardianSet.size === 0) return true;
+        if (event.account !== undefined && this.accountSet.has(event.account)) return true;
+        const named = [event.guardian, ...(event.outcomes ?? []).map((o) => o.guardian), ...event.notify];
+        return named.some((k) => k !== undefined && this.guardianSet.has(k));
+    }
+}
@@ -0,0 +1,166 @@
+/**
+ * guardian-watchd - long-running watcher for guardians and account owners
+ *
+ * Follows the node's SSE stream, decodes the registry's CES events, and runs the
+ * configured notification actions when a recovery on a watched account is
+ * initiated, approved, flagged, or about to expire, or when the account's
+ * lifetime recovery counts pass its anomaly limits. The last processed event id and the
+ * open recoveries are kept in a local state file. Prometheus metrics are served
+ * on WATCHD_METRICS_PORT at `/metrics`.
+ *
+ * Run with `npm run watchd` (see the WATCHD_* settings in .env.example).
+ */
+import { config } from '../config';
+import { casperService } from '../services';
+import { extractEvents, RegistryEvent } from './ces';
+import { configuredActions, Notification, webhooks } from './actions';
+import { metrics } from './metrics';
+import { SseClient, SseMessage } from './sse';
+import { StateStore } from './state';
+
+/** Names of the registry's lifetime recovery counters, by index (contracts/types/src/counters.rs) */
+const COUNTERS = ['started', 'finalized', 'vetoed', 'expired'];
+
+export class GuardianWatchd {
+    private accounts = new Set(config.watchd.accounts.map((a) => casperService.accountHashHex(a)));
+    private keys = new Set(config.watchd.keys.map((k) => casperService.accountHashHex(k)));
+    private store = new StateStore(config.watchd.statePath);
+    private actions = configuredActions();
+    private eventsUrefAddr = '';
+    private sse = new SseClient(
+        config.watchd.eventsUrl,
+        (message) => this.handle(message),
+        (error) => {
+            metrics.inc('guardian_watchd_sse_reconnects_total');
+            console.warn(`[watchd] Event stream dropped, reconnecting: ${error}`);
+        }
+    );
+
+    async start(): Promise<void> {
+        const registry = config.contract.recoveryRegistryHash;
+        if (!registry) throw new Error('RECOVERY_REGISTRY_HASH is not set');
+        this.eventsUrefAddr = await casperService.getContractNamedUref(registry, '__events');
+        if (config.watchd.actions.includes('webhook')) webhooks();
+        if (config.watchd.metricsPort > 0) metrics.listen(config.watchd.metricsPort);
+        metrics.set('guardian_watchd_pending_recoveries', Object.keys(this.store.state.recoveries).length);
+
+        const cursor = this.store.state.cursor;
+        console.log(`[watchd] Watching ${this.accounts.size} account(s) and ${this.keys.size} key(s)` +
+            (cursor === undefined ? '' : `, resuming after event ${cursor}`));
+        await this.sse.run(cursor === undefined ? undefined : cursor + 1);
+    }
+
+    stop(): void {
+        this.sse.stop();
+        this.store.save();
+        if (config.watchd.actions.includes('webhook')) webhooks().stop();
+    }
+
+    private async handle(message: SseMessage): Promise<void> {
+        const { DeployProcessed, BlockAdded } = message.data ?? {};
+        if (DeployProcessed) {
+            for (const event of extractEvents(DeployProcessed.execution_result, this.eventsUrefAddr)) {
+                metrics.inc('guardian_watchd_events_processed_total', { event: event.name });
+                await this.onEvent(event, DeployProcessed.deploy_hash);
+            }
+        } else if (BlockAdded) {
+            const header = BlockAdded.block?.header ?? BlockAdded.block?.Version2?.header ?? BlockAdded.block?.Version1?.header;
+            if (header) {
+                metrics.observeBlock(Date.parse(header.timestamp));
+                await this.checkExpiries(Number(header.height), Date.parse(header.timestamp));
+            }
+        }
+
+        metrics.set('guardian_watchd_pending_recoveries', Object.keys(this.store.state.recoveries).length);
+        if (message.id !== undefined) {
+            this.store.state.cursor = message.id;
+            this.store.save();
+            metrics.set('guardian_watchd_last_event_id', message.id);
+        }
+    }
+
+    /** True if the event concerns a watched account or names a watched key */
+    private watched(event: RegistryEvent, account?: string): boolean {
+        if (account && this.accounts.has(account)) return true;
+        if (event.guardian && this.keys.has(event.guardian)) return true;
+        return event.notify.some((k) => this.keys.has(k));
+    }
+
+    private async onEvent(event: RegistryEvent, deployHash: string): Promise<void> {
+        if (!event.id) return;
+        const tracked = this.store.state.recoveries[event.id];
+        const account = event.account ?? tracked?.account;
+
+        if (event.name.startsWith('RecoveryStarted')) {
+            if (!account || !this.watched(event, account)) return;
+            this.store.state.recoveries[event.id] = { account, warned: false, ...(await this.readExpiry(event.id)) };
+            await this.notify({ kind: 'recovery_initiated', recoveryId: event.id, account, newKey: event.newKey, deployHash });
+        } else if (event.name.startsWith('RecoveryApproved') || event.name.startsWith('RecoveryFrostApproved')) {
+            if (!account || !(tracked || this.watched(event, account))) return;
+            await this.notify({
+                kind: 'recovery_approved', recoveryId: event.id, account,
+                guardian: event.guardian, approvals: event.approvals, deployHash,
+            });
+        } else if (event.name.startsWith('RecoveryFlagged')) {
+            if (!account || !(tracked || this.watched(event, account))) return;
+            await this.notify({
+                kind: 'recovery_flagged', recoveryId: event.id, account,
+                guardian: event.guardian, reason: event.reason, flags: event.flags, deployHash,
+            });
+        } else if (event.name.startsWith('RecoveryAnomaly')) {
+            if (!account || !this.watched(event, account)) return;
+            await this.notify({
+                kind: 'recovery_anomaly', recoveryId: event.id, account,
+                counter: COUNTERS[event.counter ?? -1] ?? 'unknown', count: event.count, limit: event.limit, deployHash,
+            });
+        } else if (event.name.startsWith('RecoveryFinalized') || event.name.startsWith('RecoveryCancelled')) {
+            delete this.store.state.recoveries[event.id];
+        }
+    }
+
+    /** Expiry of recovery `id` on its own clock, from the registry's `re`/`rm` entries */
+    private async readExpiry(id: string): Promise<{ expiresAt: number; byHeight: boolean }> {
+        const expiresAt = Number((await casperService.readRegistryItem(`re${id}`))?.toString() ?? 0);
+        const byHeight = (await casperService.readRegistryItem(`rm${id}`)) === true;
+        return { expiresAt, byHeight };
+    }
+
+    private async checkExpiries(height: number, timestamp: number): Promise<void> {
+        for (const [recoveryId, r] of Object.entries(this.store.state.recoveries)) {
+            if (r.warned || r.expiresAt === 0) continue;
+            const remaining = r.byHeight ? r.expiresAt - height : r.expiresAt - timestamp;
+            const warning = r.byHeight ? config.watchd.expiryWarningBlocks : config.watchd.expiryWarning;
+            if (remaining > warning) continue;
+
+            r.warned = true;
+            if (remaining > 0) {
+                await this.notify({ kind: 'recovery_near_expiry', recoveryId, account: r.account, expiresAt: r.expiresAt });
+            }
+        }
+    }
+
+    private async notify(notification: Notification): Promise<void> {
+        for (const action of this.actions) {
+            try {
+                await action.run(notification);
+            } catch (error) {
+                metrics.inc('guardian_watchd_notification_failures_total', { action: action.name });
+                console.error(`[watchd] Notification action ${action.name} failed: ${error}`);
+            }
+        }
+    }
+}
+
+if (require.main === module) {
+    const daemon = new GuardianWatchd();
+    for (const signal of ['SIGINT', 'SIGTERM'] as const) {
+        process.on(signal, () => {
+            daemon.stop();
+            process.exit(0);
+        });
+    }
+    daemon.start().catch((error) => {
+        console.error(`[watchd] ${error}`);
+        process.exit(1);
+    });
+}
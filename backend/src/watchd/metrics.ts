@@ -0,0 +1,76 @@
+import http from 'http';
+
+type Labels = Record<string, string>;
+
+interface Metric {
+    help: string;
+    type: 'counter' | 'gauge';
+    values: Map<string, number>;
+}
+
+function labelString(labels: Labels): string {
+    const parts = Object.entries(labels).map(([k, v]) => `${k}="${v.replace(/["\\\n]/g, (c) => (c === '\n' ? '\\n' : `\\${c}`))}"`);
+    return parts.length ? `{${parts.join(',')}}` : '';
+}
+
+/**
+ * Prometheus metrics of guardian-watchd, served in the text exposition format
+ */
+export class Metrics {
+    private metrics = new Map<string, Metric>();
+    private lastBlockTimestamp?: number;
+
+    constructor() {
+        this.define('guardian_watchd_events_processed_total', 'counter', 'Registry events decoded from the stream, by event name');
+        this.define('guardian_watchd_pending_recoveries', 'gauge', 'Open recoveries on watched accounts');
+        this.define('guardian_watchd_notification_failures_total', 'counter', 'Failed notification actions, by action');
+        this.define('guardian_watchd_sse_reconnects_total', 'counter', 'Reconnects to the node event stream');
+        this.define('guardian_watchd_chain_lag_seconds', 'gauge', 'Age of the newest block seen on the stream');
+        this.define('guardian_watchd_last_event_id', 'gauge', 'Id of the last stream event processed');
+    }
+
+    private define(name: string, type: Metric['type'], help: string): void {
+        this.metrics.set(name, { help, type, values: new Map() });
+    }
+
+    inc(name: string, labels: Labels = {}, by = 1): void {
+        const values = this.metrics.get(name)!.values;
+        const key = labelString(labels);
+        values.set(key, (values.get(key) ?? 0) + by);
+    }
+
+    set(name: string, value: number, labels: Labels = {}): void {
+        this.metrics.get(name)!.values.set(labelString(labels), value);
+    }
+
+    /** Chain lag is measured against the wall clock when scraped */
+    observeBlock(timestamp: number): void {
+        this.lastBlockTimestamp = timestamp;
+    }
+
+    render(): string {
+        if (this.lastBlockTimestamp !== undefined) {
+            this.set('guardian_watchd_chain_lag_seconds', Math.max(Date.now() - this.lastBlockTimestamp, 0) / 1000);
+        }
+        const lines: string[] = [];
+        for (const [name, m] of this.metrics) {
+            lines.push(`# HELP ${name} ${m.help}`, `# TYPE ${name} ${m.type}`);
+            for (const [labels, value] of m.values) lines.push(`${name}${labels} ${value}`);
+        }
+        return lines.join('\n') + '\n';
+    }
+
+    /** Serve `GET /metrics` on `port` */
+    listen(port: number): http.Server {
+        return http.createServer((req, res) => {
+            if (req.method === 'GET' && req.url === '/metrics') {
+                res.writeHead(200, { 'Content-Type': 'text/plain; version=0.0.4' });
+                res.end(this.render());
+            } else {
+                res.writeHead(404).end();
+            }
+        }).listen(port);
+    }
+}
+
+export const metrics = new Metrics();
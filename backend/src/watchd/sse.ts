@@ -0,0 +1,82 @@
+import axios from 'axios';
+
+export interface SseMessage {
+    id?: number;
+    data: any;
+}
+
+const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
+
+/**
+ * Minimal client for the node's SSE event stream
+ *
+ * Reconnects with backoff whenever the stream drops, resuming from the last
+ * event id it saw via `start_from`.
+ */
+export class SseClient {
+    private lastId?: number;
+    private stopped = false;
+
+    constructor(
+        private url: string,
+        private onMessage: (message: SseMessage) => Promise<void>,
+        private onReconnect: (error: unknown) => void = () => {}
+    ) {}
+
+    async run(startFrom?: number): Promise<void> {
+        this.lastId = startFrom === undefined ? undefined : startFrom - 1;
+        let backoff = 1000;
+        while (!this.stopped) {
+            try {
+                await this.connect();
+                backoff = 1000;
+            } catch (error) {
+                if (this.stopped) break;
+                this.onReconnect(error);
+            }
+            await sleep(backoff);
+            backoff = Math.min(backoff * 2, 60000);
+        }
+    }
+
+    stop(): void {
+        this.stopped = true;
+    }
+
+    private async connect(): Promise<void> {
+        const url = this.lastId === undefined ? this.url : `${this.url}?start_from=${this.lastId + 1}`;
+        const response = await axios.get(url, { responseType: 'stream', timeout: 0 });
+
+        let buffer = '';
+        let data: string[] = [];
+        let id: number | undefined;
+
+        // Process messages one at a time so the cursor never runs ahead of handled events
+        for await (const chunk of response.data) {
+            if (this.stopped) {
+                response.data.destroy();
+                return;
+            }
+            buffer += chunk.toString('utf8');
+            let newline: number;
+            while ((newline = buffer.indexOf('\n')) >= 0) {
+                const line = buffer.slice(0, newline).replace(/\r$/, '');
+                buffer = buffer.slice(newline + 1);
+
+                if (line === '') {
+                    if (data.length > 0) {
+                        await this.onMessage({ id, data: JSON.parse(data.join('\n')) });
+                        if (id !== undefined) this.lastId = id;
+                    }
+                    data = [];
+                    id = undefined;
+                } else if (line.startsWith('data:')) {
+                    data.push(line.slice(5).trimStart());
+                } else if (line.startsWith('id:')) {
+                    id = parseInt(line.slice(3).trim(), 10);
+                }
+            }
+        }
+        throw new Error('Event stream closed');
+    }
+}
@@ -0,0 +1,37 @@
+import fs from 'fs';
+
+/** A recovery on a watched account that hasn't finalized or been cancelled yet */
+export interface TrackedRecovery {
+    account: string;
+    /** Expiry on the recovery's own clock (block time in ms, or block height); 0 if it never expires */
+    expiresAt: number;
+    byHeight: boolean;
+    warned: boolean;
+}
+
+export interface WatchdState {
+    /** Id of the last SSE event fully processed */
+    cursor?: number;
+    recoveries: Record<string, TrackedRecovery>;
+}
+
+/**
+ * Local state of the daemon, kept in a JSON file so restarts resume where they stopped
+ */
+export class StateStore {
+    state: WatchdState;
+
+    constructor(private path: string) {
+        this.state = { recoveries: {} };
+        if (fs.existsSync(path)) {
+            this.state = { ...this.state, ...JSON.parse(fs.readFileSync(path, 'utf8')) };
+        }
+    }
+
+    /** Write via a temp file so a crash never leaves a truncated state */
+    save(): void {
+        const tmp = `${this.path}.tmp`;
+        fs.writeFileSync(tmp, JSON.stringify(this.state, null, 2));
+        fs.renameSync(tmp, this.path);
+    }
+}
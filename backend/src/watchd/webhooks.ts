@@ -0,0 +1,157 @@
+import axios from 'axios';
+import crypto from 'crypto';
+import fs from 'fs';
+import { config } from '../config';
+import type { Notification, NotificationKind } from './actions';
+import { metrics } from './metrics';
+
+export interface WebhookEndpoint {
+    url: string;
+    /** HMAC-SHA256 key for the X-Guardian-Signature header */
+    secret: string;
+    /** Only these notification kinds; all if omitted */
+    events?: NotificationKind[];
+    /** Only these accounts (account hash hex); all if omitted */
+    accounts?: string[];
+}
+
+export interface Delivery {
+    id: string;
+    url: string;
+    body: string;
+    attempts: number;
+    nextAttemptAt: number;
+    status: 'pending' | 'delivered' | 'failed';
+    lastError?: string;
+    deliveredAt?: number;
+}
+
+/**
+ * Outbound webhooks for guardian-watchd
+ *
+ * Each matching endpoint gets a delivery holding the JSON payload. Deliveries are
+ * retried with exponential backoff until one gets a 2xx response or they run out
+ * of attempts, and their status is kept in WATCHD_WEBHOOK_STATE_PATH.
+ *
+ * Receivers verify `X-Guardian-Signature: sha256=<hex>`, the HMAC of
+ * `<X-Guardian-Timestamp>.<body>` under the endpoint's secret.
+ */
+export class WebhookDispatcher {
+    private endpoints: WebhookEndpoint[] = [];
+    private deliveries: Delivery[] = [];
+    private timer?: NodeJS.Timeout;
+    private running = false;
+
+    constructor(private statePath: string = config.watchd.webhooks.statePath) {
+        const endpointsPath = config.watchd.webhooks.endpointsPath;
+        if (endpointsPath && fs.existsSync(endpointsPath)) {
+            this.endpoints = JSON.parse(fs.readFileSync(endpointsPath, 'utf8'));
+        }
+        if (fs.existsSync(statePath)) {
+            this.deliveries = JSON.parse(fs.readFileSync(statePath, 'utf8')).deliveries ?? [];
+        }
+        // Resume retries left over from the previous run
+        if (this.deliveries.some((d) => d.status === 'pending')) this.schedule(0);
+    }
+
+    /** Queue a delivery of `notification` to every endpoint whose filters match */
+    enqueue(notification: Notification): void {
+        const createdAt = Date.now();
+        for (const endpoint of this.endpoints) {
+            if (endpoint.events && !endpoint.events.includes(notification.kind)) continue;
+            if (endpoint.accounts && !endpoint.accounts.includes(notification.account)) continue;
+
+            const id = crypto.randomUUID();
+            const body = JSON.stringify({ id, type: notification.kind, createdAt, data: notification });
+            this.deliveries.push({ id, url: endpoint.url, body, attempts: 0, nextAttemptAt: createdAt, status: 'pending' });
+        }
+        this.save();
+        this.schedule(0);
+    }
+
+    /** Status of recent deliveries, newest first */
+    list(): Delivery[] {
+        return [...this.deliveries].reverse();
+    }
+
+    stop(): void {
+        if (this.timer) clearTimeout(this.timer);
+        this.save();
+    }
+
+    private schedule(delay: number): void {
+        if (this.timer) clearTimeout(this.timer);
+        this.timer = setTimeout(() => void this.flush(), delay);
+    }
+
+    private async flush(): Promise<void> {
+        if (this.running) return;
+        this.running = true;
+        try {
+            const now = Date.now();
+            for (const delivery of this.deliveries) {
+                if (delivery.status === 'pending' && delivery.nextAttemptAt <= now) await this.attempt(delivery);
+            }
+            this.prune();
+            this.save();
+        } finally {
+            this.running = false;
+        }
+
+        const next = this.deliveries
+            .filter((d) => d.status === 'pending')
+            .reduce((min, d) => Math.min(min, d.nextAttemptAt), Infinity);
+        if (next !== Infinity) this.schedule(Math.max(next - Date.now(), 0));
+    }
+
+    private async attempt(delivery: Delivery): Promise<void> {
+        const endpoint = this.endpoints.find((e) => e.url === delivery.url);
+        if (!endpoint) {
+            delivery.status = 'failed';
+            delivery.lastError = 'Endpoint no longer configured';
+            return;
+        }
+
+        const timestamp = Math.floor(Date.now() / 1000).toString();
+        const signature = crypto.createHmac('sha256', endpoint.secret).update(`${timestamp}.${delivery.body}`).digest('hex');
+        delivery.attempts += 1;
+        try {
+            await axios.post(endpoint.url, delivery.body, {
+                timeout: 10000,
+                headers: {
+                    'Content-Type': 'application/json',
+                    'X-Guardian-Delivery': delivery.id,
+                    'X-Guardian-Timestamp': timestamp,
+                    'X-Guardian-Signature': `sha256=${signature}`,
+                },
+            });
+            delivery.status = 'delivered';
+            delivery.deliveredAt = Date.now();
+            delivery.lastError = undefined;
+        } catch (error: any) {
+            delivery.lastError = error?.response ? `HTTP ${error.response.status}` : String(error?.message ?? error);
+            if (delivery.attempts >= config.watchd.webhooks.maxAttempts) {
+                delivery.status = 'failed';
+                metrics.inc('guardian_watchd_notification_failures_total', { action: 'webhook' });
+            } else {
+                const backoff = config.watchd.webhooks.initialBackoff * 2 ** (delivery.attempts - 1);
+                delivery.nextAttemptAt = Date.now() + Math.min(backoff, 6 * 60 * 60 * 1000);
+            }
+        }
+    }
+
+    /** Keep every pending delivery but only the most recent finished ones */
+    private prune(): void {
+        const finished = this.deliveries.filter((d) => d.status !== 'pending');
+        const excess = finished.length - 1000;
+        if (excess <= 0) return;
+        const drop = new Set(finished.slice(0, excess).map((d) => d.id));
+        this.deliveries = this.deliveries.filter((d) => !drop.has(d.id));
+    }
+
+    private save(): void {
+        const tmp = `${this.statePath}.tmp`;
+        fs.writeFileSync(tmp, JSON.stringify({ deliveries: this.deliveries }, null, 2));
+        fs.renameSync(tmp, this.statePath);
+    }
+}
@@ -4,6 +4,11 @@
 #[cfg(not(target_arch = "wasm32"))]
 compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
 
+#[cfg(not(feature = "casper-2"))]
+extern crate casper_contract_1 as casper_contract;
+#[cfg(not(feature = "casper-2"))]
+extern crate casper_types_1 as casper_types;
+
 use add_associated_key::constants::{
     RUNTIME_ARG_NEW_ASSOCIATED_KEY, RUNTIME_ARG_NEW_ASSOCIATED_KEY_WEIGHT,
 };
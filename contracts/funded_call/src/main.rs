@@ -0,0 +1,86 @@
+//! Funded Registry Call
+//!
+//! Stored contracts can't draw from a caller's main purse, so registry entry points that take
+//! CSPR (e.g. `post_bond`) are reached through this session:
+//! 1. Moves `amount` from the caller's main purse into a fresh purse
+//! 2. Calls `entry_point` on the registry with `call_args` plus that purse as `purse`
+//! 3. For `start_recovery` (`returns_id`), stores the new recovery ID under `last_recovery_id`
+
+#![no_std]
+#![no_main]
+
+#[cfg(not(target_arch = "wasm32"))]
+compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
+
+#[cfg(not(feature = "casper-2"))]
+extern crate casper_contract_1 as casper_contract;
+#[cfg(not(feature = "casper-2"))]
+extern crate casper_types_1 as casper_types;
+
+extern crate alloc;
+
+use alloc::string::String;
+use casper_contract::contract_api::{account, runtime, storage, system};
+use casper_contract::unwrap_or_revert::UnwrapOrRevert;
+use casper_types::bytesrepr::{Bytes, FromBytes};
+use casper_types::contracts::ContractHash;
+use casper_types::{ApiError, Key, RuntimeArgs, U256, U512};
+
+// Runtime argument names
+const ARG_REGISTRY: &str = "registry";
+const ARG_ENTRY_POINT: &str = "entry_point";
+const ARG_AMOUNT: &str = "amount";
+const ARG_CALL_ARGS: &str = "call_args";
+const ARG_RETURNS_ID: &str = "returns_id";
+const ARG_PURSE: &str = "purse";
+
+const LAST_RECOVERY_ID: &str = "last_recovery_id";
+
+// Custom errors
+#[repr(u16)]
+enum FundedCallError {
+    InvalidRegistry = 1,
+    InvalidCallArgs = 2,
+    TransferFailed = 3,
+}
+
+impl From<FundedCallError> for ApiError {
+    fn from(error: FundedCallError) -> Self {
+        ApiError::User(error as u16)
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let registry: Key = runtime::get_named_arg(ARG_REGISTRY);
+    let entry_point: String = runtime::get_named_arg(ARG_ENTRY_POINT);
+    let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+    let call_args: Bytes = runtime::get_named_arg(ARG_CALL_ARGS);
+    let returns_id: bool = runtime::try_get_named_arg(ARG_RETURNS_ID).unwrap_or(false);
+
+    let registry_hash = match registry {
+        Key::Hash(hash) => ContractHash::new(hash),
+        _ => runtime::revert(FundedCallError::InvalidRegistry),
+    };
+
+    // `call_args` is a bytesrepr-serialized RuntimeArgs for the target entry point
+    let (mut args, rest) = RuntimeArgs::from_bytes(&call_args)
+        .unwrap_or_revert_with(FundedCallError::InvalidCallArgs);
+    if !rest.is_empty() {
+        runtime::revert(FundedCallError::InvalidCallArgs);
+    }
+
+    let purse = system::create_purse();
+    system::transfer_from_purse_to_purse(account::get_main_purse(), purse, amount, None)
+        .unwrap_or_revert_with(FundedCallError::TransferFailed);
+
+    args.insert(ARG_PURSE, purse)
+        .unwrap_or_revert_with(FundedCallError::InvalidCallArgs);
+    // The return type has to be known statically, so value-returning entry points are opted into
+    if returns_id {
+        let id: U256 = runtime::call_contract(registry_hash, &entry_point, args);
+        runtime::put_key(LAST_RECOVERY_ID, storage::new_uref(id).into());
+    } else {
+        runtime::call_contract::<()>(registry_hash, &entry_point, args);
+    }
+}
@@ -0,0 +1,10 @@
+//! Arbitrary bytes decode to a stored type only in its one encoding, and never panic
+#![no_main]
+
+use guardian_types::{fuzzing, policy::ApprovalPolicy, settings::AccountSettings};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|bytes: &[u8]| {
+    fuzzing::canonical::<ApprovalPolicy>(bytes);
+    fuzzing::canonical::<AccountSettings>(bytes);
+});
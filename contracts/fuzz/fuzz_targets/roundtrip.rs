@@ -0,0 +1,11 @@
+//! Every stored type survives to_bytes → from_bytes unchanged, with a correct length
+#![no_main]
+
+use guardian_types::{fuzzing, meta::NonceLedger, policy::ApprovalPolicy, settings::AccountSettings};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|values: (ApprovalPolicy, AccountSettings, NonceLedger)| {
+    fuzzing::roundtrip(&values.0);
+    fuzzing::roundtrip(&values.1);
+    fuzzing::roundtrip(&values.2);
+});
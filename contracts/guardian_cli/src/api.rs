@@ -0,0 +1,126 @@
+//! Minimal client of the backend's versioned REST API.
+
+use std::io::{BufRead, BufReader};
+
+use anyhow::{anyhow, Context, Result};
+use casper_types::{account::AccountHash, bytesrepr::FromBytes};
+use guardian_types::host;
+use serde_json::{json, Value};
+
+#[derive(Clone)]
+pub struct Api {
+    base: String,
+}
+
+impl Api {
+    pub fn new(base: &str) -> Self {
+        Api { base: base.trim_end_matches('/').to_string() }
+    }
+
+    /// `data` of the `{ success, data | error }` envelope, or the backend's error message
+    fn send(&self, path: &str, body: Option<Value>) -> Result<Value> {
+        let url = format!("{}{}", self.base, path);
+        let response = match body {
+            Some(body) => ureq::post(&url).send_json(body),
+            None => ureq::get(&url).call(),
+        };
+        let envelope: Value = match response {
+            Ok(r) | Err(ureq::Error::Status(_, r)) => r.into_json().with_context(|| format!("reading {url}"))?,
+            Err(e) => return Err(anyhow!("{url}: {e}")),
+        };
+        if envelope["success"] == true {
+            Ok(envelope["data"].clone())
+        } else {
+            Err(anyhow!("{}", envelope["error"].as_str().unwrap_or("request failed")))
+        }
+    }
+
+    pub fn get(&self, path: &str) -> Result<Value> {
+        self.send(path, None)
+    }
+
+    pub fn post(&self, path: &str, body: Value) -> Result<Value> {
+        self.send(path, Some(body))
+    }
+
+    /// Unsigned deploy, or TransactionV1 on Casper 2.0 nodes, of `sender` calling `entry_point` with
+    /// `{name, type, value}` args
+    pub fn prepare(&self, sender: &str, entry_point: &str, args: Value) -> Result<Value> {
+        self.post("/deploys/prepare", json!({ "sender": sender, "entryPoint": entry_point, "args": args }))
+    }
+
+    /// Submit a signed deploy or transaction, as `kind` says, returning its hash
+    pub fn submit(&self, kind: &str, deploy: &Value) -> Result<String> {
+        let data = self.post("/deploys", json!({ kind: deploy }))?;
+        Ok(data["deployHash"].as_str().unwrap_or_default().to_string())
+    }
+
+    /// Registry events of `accounts` or naming `guardians`, from the server-sent `/events` stream
+    /// until it ends
+    pub fn events(&self, accounts: &[String], guardians: &[String]) -> Result<impl Iterator<Item = Value>> {
+        let url = format!("{}/events", self.base);
+        let response = ureq::get(&url)
+            .query("account", &accounts.join(","))
+            .query("guardian", &guardians.join(","))
+            .call()
+            .map_err(|e| anyhow!("{url}: {e}"))?;
+        let mut data = String::new();
+        Ok(BufReader::new(response.into_reader()).lines().map_while(Result::ok).filter_map(move |line| {
+            if let Some(chunk) = line.strip_prefix("data:") {
+                data.push_str(chunk.trim_start());
+                None
+            } else if line.is_empty() && !data.is_empty() {
+                serde_json::from_str(&std::mem::take(&mut data)).ok()
+            } else {
+                None
+            }
+        }))
+    }
+
+    /// Item `key` of the registry's storage dictionary decoded as `T`; `None` if it isn't set
+    pub fn registry_item<T: FromBytes>(&self, key: &str) -> Result<Option<T>> {
+        let Some(bytes) = self.registry_bytes(key)? else { return Ok(None) };
+        let (value, _) = T::from_bytes(&bytes).map_err(|e| anyhow!("decoding registry item {key}: {e}"))?;
+        Ok(Some(value))
+    }
+
+    /// bytesrepr of item `key` of the registry's storage dictionary; `None` if it isn't set
+    pub fn registry_bytes(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let item = self.get(&format!("/registry/{key}"))?;
+        let Some(hex) = item["bytes"].as_str() else { return Ok(None) };
+        Ok(Some(hex::decode(hex).with_context(|| format!("registry item {key}"))?))
+    }
+
+    /// Account hashes of `account`'s current guardians
+    pub fn guardians(&self, account: &str) -> Result<Vec<AccountHash>> {
+        let config = self.get(&format!("/accounts/{account}/config"))?;
+        config["guardians"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|g| {
+                let hex = g.as_str().unwrap_or_default();
+                let bytes = hex::decode(hex).ok().filter(|b| b.len() == 32).ok_or_else(|| anyhow!("bad guardian hash {hex}"))?;
+                AccountHash::try_from(bytes.as_slice()).map_err(|_| anyhow!("bad guardian hash {hex}"))
+            })
+            .collect()
+    }
+}
+
+/// Account hash of a hex public key, an `account-hash-…` or `entity-account-…` string or raw
+/// account hash hex
+pub fn account_hash(value: &str) -> Result<AccountHash> {
+    if value.contains('-') {
+        return host::formatted_account(value).ok_or_else(|| anyhow!("not an account hash: {value}"));
+    }
+    let bytes = hex::decode(value).map_err(|_| anyhow!("not hex: {value}"))?;
+    if bytes.len() == 32 {
+        return AccountHash::try_from(bytes.as_slice()).map_err(|_| anyhow!("not an account hash: {value}"));
+    }
+    let key = casper_types::PublicKey::from_bytes(&bytes)
+        .ok()
+        .filter(|(_, rest)| rest.is_empty())
+        .map(|(key, _)| key)
+        .ok_or_else(|| anyhow!("not a public key or account hash: {value}"))?;
+    Ok(key.to_account_hash())
+}
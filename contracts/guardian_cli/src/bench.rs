@@ -0,0 +1,169 @@
+//! `guardian-cli bench`: what keeping the registry's per-account records under named keys costs
+//! against keeping them in a dictionary, measured on a local NCTL network. The `storage_bench`
+//! contract stores the same record per account either way; `bench` fills both layouts to each
+//! size in turn and measures storing, reading and rewriting single accounts.
+//!
+//! Gas is what the node reports a call consumed. Latency is wall-clock time from sending a call to
+//! seeing it executed, so block time dominates it: it shows whether a layout slows calls down
+//! noticeably, not how long execution took.
+
+use std::{
+    fmt::Write as _,
+    fs,
+    path::PathBuf,
+    time::{Duration, Instant as Clock},
+};
+
+use anyhow::{bail, Result};
+use clap::Args;
+use serde_json::{json, Value};
+
+use crate::nctl::{self, step, Local};
+
+/// Named key the storage_bench installer leaves on the installing account
+const CONTRACT_KEY: &str = "storage_bench_contract_hash";
+/// Layouts as `storage_bench` names them, in report column order
+const LAYOUTS: [&str; 2] = ["named", "dictionary"];
+/// Accounts stored per `populate` call
+const BATCH: u32 = 100;
+/// Payment for a `populate` call of `BATCH` accounts
+const BATCH_PAYMENT: &str = "500000000000";
+
+#[derive(Args)]
+pub struct Bench {
+    /// Numbers of registered accounts to measure at, ascending
+    #[arg(long, value_delimiter = ',', default_value = "10,100,1000")]
+    sizes: Vec<u32>,
+
+    /// Calls measured per operation, layout and size; the report gives their mean
+    #[arg(long, default_value_t = 3)]
+    samples: u32,
+
+    /// Also write the report, as Markdown, to this file
+    #[arg(long)]
+    out: Option<PathBuf>,
+
+    #[command(flatten)]
+    network: nctl::Network,
+}
+
+/// One operation at one size, for each of `LAYOUTS`
+struct Row {
+    size: u32,
+    operation: &'static str,
+    gas: [u64; 2],
+    /// None where calls don't measure one account at a time
+    latency: [Option<Duration>; 2],
+}
+
+pub fn run(args: Bench) -> Result<()> {
+    if args.sizes.is_empty() || args.sizes.windows(2).any(|w| w[0] >= w[1]) || args.sizes[0] == 0 {
+        bail!("--sizes takes ascending account counts above zero");
+    }
+    if args.samples == 0 {
+        bail!("--samples must be at least 1");
+    }
+    let local = args.network.connect()?;
+    step("installing storage_bench");
+    let contract = local.install_contract("storage_bench.wasm", CONTRACT_KEY)?;
+    println!("  contract {contract}");
+
+    let mut rows = Vec::new();
+    let mut stored = 0;
+    for &size in &args.sizes {
+        step(&format!("storing accounts {stored} to {} under each layout", size - 1));
+        let mut insert = [0; 2];
+        while stored < size {
+            let count = BATCH.min(size - stored);
+            insert = populate(&local, &contract, stored, count)?;
+            stored += count;
+        }
+        rows.push(Row { size, operation: "store (per account)", gas: insert, latency: [None, None] });
+
+        step(&format!("measuring reads and writes at {size} accounts"));
+        for operation in ["read", "write"] {
+            let mut gas = [0; 2];
+            let mut latency = [Duration::ZERO; 2];
+            for sample in 0..args.samples {
+                // Spread over the stored accounts, first to last
+                let account = (size - 1) * sample / (args.samples - 1).max(1);
+                for (i, layout) in LAYOUTS.iter().enumerate() {
+                    let started = Clock::now();
+                    let hash = local.node.send(&local.faucet, &contract, operation, account_args(layout, account))?;
+                    gas[i] += local.node.gas(operation, &hash)?;
+                    latency[i] += started.elapsed();
+                }
+            }
+            rows.push(Row {
+                size,
+                operation,
+                gas: gas.map(|g| g / args.samples as u64),
+                latency: latency.map(|l| Some(l / args.samples)),
+            });
+        }
+    }
+
+    let report = report(&rows);
+    println!("\n{report}");
+    if let Some(out) = &args.out {
+        fs::write(out, &report)?;
+        println!("report written to {}", out.display());
+    }
+    Ok(())
+}
+
+/// Store accounts `from` to `from + count - 1` under both layouts at once, returning the gas per
+/// account each took
+fn populate(local: &Local, contract: &str, from: u32, count: u32) -> Result<[u64; 2]> {
+    let hashes = LAYOUTS.map(|layout| {
+        let args = json!([
+            { "name": "layout", "type": "String", "value": layout },
+            { "name": "from", "type": "U32", "value": from },
+            { "name": "count", "type": "U32", "value": count },
+        ]);
+        local.node.send_paying(&local.faucet, contract, "populate", args, BATCH_PAYMENT)
+    });
+    let mut gas = [0; 2];
+    for (i, hash) in hashes.into_iter().enumerate() {
+        gas[i] = local.node.gas(&format!("populate {}", LAYOUTS[i]), &hash?)? / count as u64;
+    }
+    Ok(gas)
+}
+
+fn account_args(layout: &str, account: u32) -> Value {
+    json!([
+        { "name": "layout", "type": "String", "value": layout },
+        { "name": "account", "type": "U32", "value": account },
+    ])
+}
+
+/// The measurements as a Markdown report: a table per size, then how each operation's gas grew
+/// from the smallest size to the largest under each layout
+fn report(rows: &[Row]) -> String {
+    let mut out = String::from("# Storage layout benchmark\n\n");
+    out.push_str("Gas per call, mean latency from send to executed. Named keys: a named key per account; dictionary: an item per account, the registry's layout.\n\n");
+    out.push_str("| Accounts | Operation | Named keys gas | Dictionary gas | Named / dictionary | Named keys latency | Dictionary latency |\n");
+    out.push_str("|---:|---|---:|---:|---:|---:|---:|\n");
+    for r in rows {
+        let ratio = if r.gas[1] == 0 { "-".to_string() } else { format!("{:.2}", r.gas[0] as f64 / r.gas[1] as f64) };
+        let latency = |l: Option<Duration>| l.map_or("-".to_string(), |l| format!("{:.1}s", l.as_secs_f64()));
+        let _ = writeln!(
+            out,
+            "| {} | {} | {} | {} | {ratio} | {} | {} |",
+            r.size, r.operation, r.gas[0], r.gas[1], latency(r.latency[0]), latency(r.latency[1]),
+        );
+    }
+
+    let (Some(first), Some(last)) = (rows.first().map(|r| r.size), rows.last().map(|r| r.size)) else { return out };
+    if first == last {
+        return out;
+    }
+    let _ = writeln!(out, "\nGrowth in gas from {first} to {last} accounts:\n");
+    for operation in ["store (per account)", "read", "write"] {
+        let at = |size: u32| rows.iter().find(|r| r.size == size && r.operation == operation);
+        let (Some(a), Some(b)) = (at(first), at(last)) else { continue };
+        let growth = |i: usize| if a.gas[i] == 0 { "-".to_string() } else { format!("{:+.1}%", (b.gas[i] as f64 / a.gas[i] as f64 - 1.0) * 100.0) };
+        let _ = writeln!(out, "- {operation}: named keys {}, dictionary {}", growth(0), growth(1));
+    }
+    out
+}
@@ -0,0 +1,313 @@
+//! `guardian-cli dashboard`: the accounts a guardian key protects and their open recoveries, kept
+//! current from the backend's event stream, with one-key approve and reject.
+
+use std::{
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Result;
+use clap::Args;
+use guardian_signer::{PemSigner, Signer};
+use guardian_types::time::{Instant, MS_PER_SECOND};
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout},
+    style::{Modifier, Style},
+    widgets::{Block, Paragraph, Row, Table, TableState},
+    DefaultTerminal, Frame,
+};
+use serde_json::{json, Value};
+
+use crate::{api::Api, keystore::Keystore, recovery, sign};
+
+/// Retry delay after the event stream drops
+const RECONNECT: Duration = Duration::from_secs(5);
+const LOG_LINES: usize = 5;
+
+#[derive(Args)]
+pub struct Dashboard {
+    /// Keystore key of the guardian, which also pays for approve and reject deploys
+    #[arg(long)]
+    key: String,
+}
+
+struct Account {
+    hash: String,
+    threshold: u64,
+    guardians: usize,
+}
+
+struct Pending {
+    id: String,
+    account: String,
+    new_key: String,
+    approvals: u64,
+    threshold: u64,
+    /// This guardian already approved or rejected
+    voted: bool,
+    expires_at: u64,
+    by_height: bool,
+    /// Blocks left of a height-timed recovery, at the newest indexed block
+    blocks_left: Option<u64>,
+}
+
+enum Msg {
+    Event(String),
+    Dropped(String),
+}
+
+struct App<'a> {
+    api: &'a Api,
+    name: String,
+    signer: PemSigner,
+    /// Account hash hex of the guardian key
+    me: String,
+    accounts: Vec<Account>,
+    pending: Vec<Pending>,
+    table: TableState,
+    log: Vec<String>,
+}
+
+pub fn run(args: Dashboard, api: &Api, keystore: &Keystore) -> Result<()> {
+    let signer = sign::unlock(keystore, &args.key)?;
+    let me = hex::encode(signer.public_key().to_account_hash().value());
+    let mut app = App {
+        api,
+        name: args.key,
+        signer,
+        me,
+        accounts: Vec::new(),
+        pending: Vec::new(),
+        table: TableState::default(),
+        log: Vec::new(),
+    };
+    // Fail before taking over the terminal if the backend can't be reached
+    app.reload()?;
+
+    let (tx, rx) = mpsc::channel();
+    let accounts = app.accounts.iter().map(|a| a.hash.clone()).collect();
+    let (stream, guardian) = (api.clone(), app.me.clone());
+    thread::spawn(move || follow(stream, accounts, guardian, tx));
+
+    let mut terminal = ratatui::init();
+    let result = app.run(&mut terminal, rx);
+    ratatui::restore();
+    result
+}
+
+/// Forward registry events to the UI, reconnecting whenever the stream drops
+fn follow(api: Api, accounts: Vec<String>, guardian: String, tx: Sender<Msg>) {
+    loop {
+        let msg = match api.events(&accounts, &[guardian.clone()]) {
+            Ok(events) => {
+                for event in events {
+                    let line = format!(
+                        "{} {} {}",
+                        event["name"].as_str().unwrap_or("event"),
+                        event["id"].as_str().unwrap_or_default(),
+                        short(event["account"].as_str().unwrap_or_default()),
+                    );
+                    if tx.send(Msg::Event(line)).is_err() {
+                        return;
+                    }
+                }
+                Msg::Dropped("stream closed".into())
+            }
+            Err(e) => Msg::Dropped(e.to_string()),
+        };
+        if tx.send(msg).is_err() {
+            return;
+        }
+        thread::sleep(RECONNECT);
+    }
+}
+
+impl App<'_> {
+    fn run(&mut self, terminal: &mut DefaultTerminal, rx: Receiver<Msg>) -> Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            while let Ok(msg) = rx.try_recv() {
+                match msg {
+                    Msg::Event(line) => {
+                        self.note(line);
+                        self.refresh();
+                    }
+                    Msg::Dropped(error) => self.note(format!("event stream dropped ({error}), reconnecting")),
+                }
+            }
+
+            // Redraw at least every 250 ms so the countdowns tick
+            if !event::poll(Duration::from_millis(250))? {
+                continue;
+            }
+            let Event::Key(key) = event::read()? else { continue };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Down | KeyCode::Char('j') => self.table.select_next(),
+                KeyCode::Up | KeyCode::Char('k') => self.table.select_previous(),
+                KeyCode::Char('a') => self.vote("approve"),
+                KeyCode::Char('r') => self.vote("reject"),
+                KeyCode::Char('u') => self.refresh(),
+                _ => {}
+            }
+        }
+    }
+
+    /// Re-read the guarded accounts and their pending recoveries
+    fn reload(&mut self) -> Result<()> {
+        let mut accounts = Vec::new();
+        let mut pending = Vec::new();
+        let guarded = self.api.get(&format!("/guardians/{}/accounts?limit=100", self.me))?;
+
+        for hash in guarded.as_array().into_iter().flatten().filter_map(Value::as_str) {
+            let config = self.api.get(&format!("/accounts/{hash}/config"))?;
+            let threshold = config["threshold"].as_u64().unwrap_or(0);
+            accounts.push(Account {
+                hash: hash.to_string(),
+                threshold,
+                guardians: config["guardians"].as_array().map_or(0, Vec::len),
+            });
+
+            let recoveries = self.api.get(&format!("/recoveries?account={hash}&limit=100"))?;
+            for r in recoveries.as_array().into_iter().flatten() {
+                let id = r["id"].as_str().unwrap_or_default().to_string();
+                let detail = self.api.get(&format!("/recoveries/{id}"))?;
+                let voted = detail["approvalHistory"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .any(|a| a["guardian"] == self.me.as_str());
+                let by_height = r["byHeight"].as_bool().unwrap_or(false);
+                pending.push(Pending {
+                    id,
+                    account: hash.to_string(),
+                    new_key: r["newKey"].as_str().unwrap_or("-").to_string(),
+                    approvals: r["approvals"].as_u64().unwrap_or(0),
+                    threshold,
+                    voted,
+                    expires_at: r["expiresAt"].as_u64().unwrap_or(0),
+                    by_height,
+                    blocks_left: if by_height { r["timeRemaining"].as_u64() } else { None },
+                });
+            }
+        }
+
+        self.accounts = accounts;
+        self.pending = pending;
+        if self.pending.is_empty() {
+            self.table.select(None);
+        } else if self.table.selected().is_none_or(|i| i >= self.pending.len()) {
+            self.table.select(Some(0));
+        }
+        Ok(())
+    }
+
+    fn refresh(&mut self) {
+        if let Err(e) = self.reload() {
+            self.note(format!("refresh failed: {e}"));
+        }
+    }
+
+    /// Call `entry_point` (approve or reject) on the selected recovery
+    fn vote(&mut self, entry_point: &str) {
+        let Some(p) = self.table.selected().and_then(|i| self.pending.get(i)) else { return };
+        let id = p.id.clone();
+        if p.voted {
+            self.note(format!("already voted on {id}"));
+            return;
+        }
+        let args = vec![json!({ "name": "id", "type": "u256", "value": id })];
+        match recovery::submit_call(self.api, &self.signer, entry_point, args) {
+            Ok(hash) => self.note(format!("{entry_point} {id}: deploy {hash}")),
+            Err(e) => self.note(format!("{entry_point} {id} failed: {e}")),
+        }
+    }
+
+    fn note(&mut self, line: String) {
+        self.log.push(line);
+        if self.log.len() > LOG_LINES {
+            self.log.remove(0);
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [header, accounts, pending, log, help] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Length(self.accounts.len() as u16 + 3),
+            Constraint::Min(5),
+            Constraint::Length(LOG_LINES as u16 + 2),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+        let bold = Style::new().add_modifier(Modifier::BOLD);
+
+        let title = format!("{}  account-hash-{}", self.name, self.me);
+        frame.render_widget(Paragraph::new(title).block(Block::bordered().title(" guardian ")), header);
+
+        let rows = self.accounts.iter().map(|a| {
+            let open = self.pending.iter().filter(|p| p.account == a.hash).count();
+            Row::new([a.hash.clone(), a.threshold.to_string(), a.guardians.to_string(), open.to_string()])
+        });
+        let widths = [Constraint::Length(66), Constraint::Length(10), Constraint::Length(10), Constraint::Length(6)];
+        let table = Table::new(rows, widths)
+            .header(Row::new(["account", "threshold", "guardians", "open"]).style(bold))
+            .block(Block::bordered().title(" guarded accounts "));
+        frame.render_widget(table, accounts);
+
+        let rows = self.pending.iter().map(|p| {
+            Row::new([
+                short(&p.id),
+                short(&p.account),
+                short(&p.new_key),
+                format!("{}/{}", p.approvals, p.threshold),
+                if p.voted { "yes" } else { "no" }.to_string(),
+                countdown(p),
+            ])
+        });
+        let widths = [
+            Constraint::Length(14),
+            Constraint::Length(14),
+            Constraint::Length(14),
+            Constraint::Length(9),
+            Constraint::Length(6),
+            Constraint::Min(16),
+        ];
+        let table = Table::new(rows, widths)
+            .header(Row::new(["recovery", "account", "new key", "approvals", "voted", "expires in"]).style(bold))
+            .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+            .block(Block::bordered().title(" pending recoveries "));
+        frame.render_stateful_widget(table, pending, &mut self.table);
+
+        frame.render_widget(Paragraph::new(self.log.join("\n")).block(Block::bordered().title(" events ")), log);
+        frame.render_widget(Paragraph::new(" ↑/↓ select   a approve   r reject   u refresh   q quit"), help);
+    }
+}
+
+/// Time or blocks left before a recovery expires
+fn countdown(p: &Pending) -> String {
+    if p.expires_at == 0 {
+        return "never".into();
+    }
+    if p.by_height {
+        return p.blocks_left.map_or("?".into(), |b| format!("{b} blocks"));
+    }
+    let now = Instant(SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64);
+    match Instant(p.expires_at).remaining(now) {
+        left if left.0 < MS_PER_SECOND => "expired".into(),
+        left => recovery::duration(left),
+    }
+}
+
+fn short(value: &str) -> String {
+    if value.chars().count() <= 12 {
+        value.to_string()
+    } else {
+        format!("{}…", value.chars().take(11).collect::<String>())
+    }
+}
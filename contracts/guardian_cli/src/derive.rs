@@ -0,0 +1,74 @@
+//! Casper keys from BIP39 mnemonics.
+//!
+//! The seed is the BIP39 seed of the mnemonic and optional passphrase. Keys follow Casper's SLIP-44
+//! coin type, 506:
+//!
+//! - secp256k1: BIP32 `m/44'/506'/0'/0/{index}`, the path Casper Wallet and the Ledger app use
+//! - Ed25519: SLIP-0010 `m/44'/506'/0'/0'/{index}'`; SLIP-0010 Ed25519 derivation only has
+//!   hardened steps, so every level is hardened
+
+use anyhow::{anyhow, Result};
+use bip32::{DerivationPath, XPrv};
+use bip39::Mnemonic;
+use casper_types::SecretKey;
+use clap::ValueEnum;
+use hmac::{Hmac, Mac};
+use sha2::Sha512;
+
+const HARDENED: u32 = 0x8000_0000;
+/// SLIP-44 coin type of Casper
+const COIN_TYPE: u32 = 506;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Algorithm {
+    Ed25519,
+    Secp256k1,
+}
+
+/// Derivation path of key `index` for `algorithm`
+pub fn path(algorithm: Algorithm, index: u32) -> String {
+    match algorithm {
+        Algorithm::Ed25519 => format!("m/44'/{COIN_TYPE}'/0'/0'/{index}'"),
+        Algorithm::Secp256k1 => format!("m/44'/{COIN_TYPE}'/0'/0/{index}"),
+    }
+}
+
+/// Key `index` of `mnemonic` (with BIP39 `passphrase`, empty if none)
+pub fn derive(mnemonic: &str, passphrase: &str, algorithm: Algorithm, index: u32) -> Result<SecretKey> {
+    if index >= HARDENED {
+        return Err(anyhow!("index must be below 2^31"));
+    }
+    let mnemonic = Mnemonic::parse_normalized(mnemonic).map_err(|e| anyhow!("invalid mnemonic: {e}"))?;
+    let seed = mnemonic.to_seed_normalized(passphrase);
+
+    match algorithm {
+        Algorithm::Ed25519 => {
+            let key = slip10_ed25519(&seed, &[44, COIN_TYPE, 0, 0, index]);
+            SecretKey::ed25519_from_bytes(key).map_err(|e| anyhow!("{e}"))
+        }
+        Algorithm::Secp256k1 => {
+            let path: DerivationPath = path(algorithm, index).parse().map_err(|e| anyhow!("{e}"))?;
+            let xprv = XPrv::derive_from_path(seed, &path).map_err(|e| anyhow!("{e}"))?;
+            SecretKey::secp256k1_from_bytes(xprv.private_key().to_bytes()).map_err(|e| anyhow!("{e}"))
+        }
+    }
+}
+
+/// SLIP-0010 Ed25519 private key at the all-hardened `path`
+fn slip10_ed25519(seed: &[u8], path: &[u32]) -> [u8; 32] {
+    let hmac = |key: &[u8], data: &[u8]| -> [u8; 64] {
+        let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC takes keys of any length");
+        mac.update(data);
+        mac.finalize().into_bytes().into()
+    };
+
+    let mut node = hmac(b"ed25519 seed", seed);
+    for index in path {
+        let mut data = Vec::with_capacity(37);
+        data.push(0);
+        data.extend_from_slice(&node[..32]);
+        data.extend_from_slice(&(index | HARDENED).to_be_bytes());
+        node = hmac(&node[32..], &data);
+    }
+    node[..32].try_into().expect("half of 64 bytes")
+}
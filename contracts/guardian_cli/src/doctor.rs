@@ -0,0 +1,191 @@
+//! `guardian-cli doctor`: an audit of an account's guardian setup as it stands on chain, flagging
+//! configurations that make a recovery too easy, impossible, or not what the owner expects. Records
+//! are read raw from the registry and decoded with guardian-types, so the audit sees exactly what the
+//! contract does rather than the indexer's view of it.
+
+use std::{
+    collections::BTreeSet,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Result;
+use casper_types::{account::AccountHash, Key};
+use clap::{Args, ValueEnum};
+use guardian_types::{
+    prefixes,
+    settings::AccountSettings,
+    time::{Instant, Span},
+};
+use serde::Serialize;
+use serde_json::json;
+
+use crate::{
+    api::{account_hash, Api},
+    recovery,
+};
+
+/// Exit status when no finding reaches `--fail-on`
+pub const HEALTHY: i32 = 0;
+/// Exit status when a finding reaches `--fail-on`
+pub const UNHEALTHY: i32 = 2;
+
+/// Threshold the registry applies to accounts that never stored one
+const DEFAULT_THRESHOLD: u8 = 2;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Worth knowing, not a risk by itself
+    Info,
+    /// Weakens recovery or the owner's protection against it
+    Warning,
+    /// Recovery can be taken over or can't happen at all
+    Critical,
+}
+
+#[derive(Args)]
+pub struct Doctor {
+    /// Account to audit: public key or account hash
+    account: String,
+
+    /// Print the findings as JSON
+    #[arg(long)]
+    json: bool,
+
+    /// Exit with status 2 if any finding is at least this severe
+    #[arg(long, value_enum, default_value = "critical")]
+    fail_on: Severity,
+}
+
+#[derive(Serialize)]
+struct Finding {
+    severity: Severity,
+    /// Stable name of the check, for scripts
+    check: &'static str,
+    message: String,
+}
+
+/// What the audit reads of an account
+pub struct Setup {
+    pub owner: AccountHash,
+    pub guardians: Vec<AccountHash>,
+    pub contract_guardians: Vec<Key>,
+    /// Private guardians, hashed or behind a Merkle root, counted but not named
+    pub private: usize,
+    pub threshold: u8,
+    pub settings: Option<AccountSettings>,
+    pub terms: Vec<(Key, u64)>,
+    pub suspended: Vec<Key>,
+    pub heartbeat: u64,
+    pub heartbeat_period: u64,
+}
+
+/// Audit the account and return the exit status `--fail-on` maps its findings to
+pub fn run(args: Doctor, api: &Api) -> Result<i32> {
+    let owner = account_hash(&args.account)?;
+    let setup = Setup::read(api, owner)?;
+    let now = Instant(SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64);
+    let mut findings = setup.audit(now);
+    findings.sort_by(|a, b| b.severity.cmp(&a.severity));
+
+    if args.json {
+        let report = json!({ "account": hex::encode(owner.value()), "findings": findings });
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else if findings.is_empty() {
+        println!("account-hash-{}: no issues found", hex::encode(owner.value()));
+    } else {
+        println!("account-hash-{}:", hex::encode(owner.value()));
+        for f in &findings {
+            let severity = format!("{:?}", f.severity).to_uppercase();
+            println!("  {severity:<8} {:<20} {}", f.check, f.message);
+        }
+    }
+    Ok(if findings.iter().any(|f| f.severity >= args.fail_on) { UNHEALTHY } else { HEALTHY })
+}
+
+impl Setup {
+    pub fn read(api: &Api, owner: AccountHash) -> Result<Self> {
+        let key = |prefix: &str| format!("{prefix}{owner:?}");
+        let merkle = api.registry_item::<Option<([u8; 32], u32)>>(&key(prefixes::MERKLE_ROOT))?.flatten();
+        let hashed = api.registry_item::<Vec<[u8; 32]>>(&key(prefixes::HASHED_GUARDIANS))?.unwrap_or_default();
+        Ok(Setup {
+            owner,
+            guardians: api.registry_item(&key(prefixes::GUARDIANS))?.unwrap_or_default(),
+            contract_guardians: api.registry_item(&key(prefixes::CONTRACT_GUARDIANS))?.unwrap_or_default(),
+            private: hashed.len() + merkle.map_or(0, |(_, n)| n as usize),
+            threshold: api.registry_item(&key(prefixes::THRESHOLD))?.unwrap_or(DEFAULT_THRESHOLD),
+            settings: api.registry_item(&key(prefixes::SETTINGS))?,
+            terms: api.registry_item(&key(prefixes::GUARDIAN_TERMS))?.unwrap_or_default(),
+            suspended: api.registry_item(&key(prefixes::SUSPENDED))?.unwrap_or_default(),
+            heartbeat: api.registry_item(&key(prefixes::HEARTBEAT))?.unwrap_or(0),
+            heartbeat_period: api.registry_item(&key(prefixes::HEARTBEAT_PERIOD))?.unwrap_or(0),
+        })
+    }
+
+    /// Every guardian, the way the registry counts them
+    pub fn count(&self) -> usize {
+        self.guardians.len() + self.contract_guardians.len() + self.private
+    }
+
+    fn audit(&self, now: Instant) -> Vec<Finding> {
+        let (count, threshold) = (self.count(), self.threshold as usize);
+        if count == 0 {
+            let message = "the account has no guardians, so it can't be recovered".into();
+            return vec![Finding { severity: Severity::Critical, check: "no-guardians", message }];
+        }
+        let mut out = Vec::new();
+        let mut flag = |severity, check, message: String| out.push(Finding { severity, check, message });
+        if threshold <= 1 {
+            flag(Severity::Critical, "threshold-one", "a single guardian can recover the account on their own".into());
+        }
+        if threshold > count {
+            flag(Severity::Critical, "threshold-unreachable", format!("threshold {threshold} exceeds the {count} guardians"));
+        } else if threshold == count && count > 1 {
+            flag(Severity::Warning, "threshold-all", format!("threshold {threshold} needs every guardian: losing one makes recovery impossible"));
+        }
+
+        let mut seen = BTreeSet::new();
+        for g in &self.guardians {
+            if !seen.insert(*g) {
+                flag(Severity::Critical, "duplicate-guardian", format!("account-hash-{} is listed more than once", hex::encode(g.value())));
+            }
+        }
+        if seen.contains(&self.owner) {
+            flag(Severity::Warning, "owner-guardian", "the owner guards their own account, which a lost key can't help recover".into());
+        }
+
+        let expired: Vec<&Key> = self.terms.iter().filter(|(_, until)| Instant(*until).reached(now)).map(|(k, _)| k).collect();
+        for k in &expired {
+            flag(Severity::Warning, "guardian-expired", format!("{k}'s term has expired; they can't approve until it's renewed"));
+        }
+        let inactive: BTreeSet<&Key> = expired.into_iter().chain(&self.suspended).collect();
+        let active = count.saturating_sub(inactive.len());
+        if !inactive.is_empty() && active < threshold {
+            flag(Severity::Critical, "too-few-active", format!("only {active} guardians are active, below threshold {threshold}"));
+        }
+
+        match &self.settings {
+            Some(s) if s.delay == 0 && s.veto_window == 0 => flag(
+                Severity::Warning,
+                "no-delay",
+                "no recovery delay or veto window: a recovery finalizes as soon as it is approved".into(),
+            ),
+            Some(s) if s.delay == 0 => flag(Severity::Info, "no-delay", "no recovery delay; only the veto window protects the owner".into()),
+            None => flag(Severity::Info, "default-settings", "account uses the registry's default settings".into()),
+            _ => {}
+        }
+
+        // Heartbeats run on block time even for `by_height` accounts
+        let period = Span(self.heartbeat_period);
+        if !period.is_zero() {
+            let last = Instant(self.heartbeat);
+            if last.has_elapsed(period, now) {
+                flag(Severity::Warning, "heartbeat-stale", "heartbeat missed: the account counts as inactive".into());
+            } else if last.has_elapsed(Span(period.0 / 4 * 3), now) {
+                let left = recovery::duration(last.plus(period).remaining(now));
+                flag(Severity::Info, "heartbeat-due", format!("heartbeat due within {left}"));
+            }
+        }
+        out
+    }
+}
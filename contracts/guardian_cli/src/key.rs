@@ -0,0 +1,157 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Result};
+use casper_types::{bytesrepr::ToBytes, PublicKey, SecretKey};
+use clap::{Args, Subcommand};
+
+use crate::{
+    api::{account_hash, Api},
+    derive::{self, Algorithm},
+    keystore::Keystore,
+    prompt,
+};
+
+#[derive(Subcommand)]
+pub enum KeyCommand {
+    /// Derive a Casper key from a BIP39 mnemonic, read from the terminal or stdin
+    FromMnemonic(FromMnemonic),
+    /// Encrypt a secret_key.pem into the keystore
+    Import {
+        /// Name to store the key under
+        name: String,
+        /// casper-client secret key file
+        #[arg(long)]
+        pem: PathBuf,
+    },
+    /// Decrypt a keystore key to PEM files
+    Export {
+        name: String,
+        /// Directory for secret_key.pem, public_key.pem and public_key_hex
+        #[arg(long)]
+        out: PathBuf,
+    },
+    /// Keys in the keystore
+    List,
+}
+
+#[derive(Args)]
+pub struct FromMnemonic {
+    #[arg(long, value_enum, default_value = "ed25519")]
+    algorithm: Algorithm,
+
+    /// Account index in the derivation path
+    #[arg(long, default_value_t = 0)]
+    index: u32,
+
+    /// Also ask for the BIP39 passphrase ("25th word")
+    #[arg(long)]
+    passphrase: bool,
+
+    /// Encrypt the key into the keystore under this name
+    #[arg(long)]
+    save: Option<String>,
+
+    /// Write secret_key.pem, public_key.pem and public_key_hex here, as casper-client keygen does
+    #[arg(long)]
+    out: Option<PathBuf>,
+
+    /// Fail unless the derived key is this public key or account hash
+    #[arg(long)]
+    expect: Option<String>,
+
+    /// Fail unless the derived key is a registered guardian of this account
+    #[arg(long)]
+    guardian_of: Option<String>,
+}
+
+pub fn run(cmd: KeyCommand, api: &Api, keystore: &Keystore) -> Result<()> {
+    match cmd {
+        KeyCommand::FromMnemonic(args) => from_mnemonic(args, api, keystore),
+        KeyCommand::Import { name, pem } => {
+            let secret = SecretKey::from_file(&pem)?;
+            keystore.insert(&name, &secret, &prompt::new_password()?)?;
+            println!("{name}: {}", hex::encode(PublicKey::from(&secret).to_bytes()?));
+            println!("imported; {} can now be deleted", pem.display());
+            Ok(())
+        }
+        KeyCommand::Export { name, out } => {
+            prompt::confirm(&format!("Write {name}'s secret key unencrypted to {}", out.display()))?;
+            let secret = keystore.unlock(&name, &prompt::secret(&format!("Password for {name}: "))?)?;
+            write_pem(&out, &secret)
+        }
+        KeyCommand::List => {
+            for (name, public_key) in keystore.list()? {
+                println!("{name}\t{public_key}");
+            }
+            Ok(())
+        }
+    }
+}
+
+fn from_mnemonic(args: FromMnemonic, api: &Api, keystore: &Keystore) -> Result<()> {
+    let mnemonic = prompt::secret("Mnemonic: ")?;
+    let passphrase = if args.passphrase { prompt::secret("Passphrase: ")? } else { String::new() };
+
+    let secret = derive::derive(&mnemonic, &passphrase, args.algorithm, args.index)?;
+    let public = PublicKey::from(&secret);
+    let hash = public.to_account_hash();
+
+    println!("path:         {}", derive::path(args.algorithm, args.index));
+    println!("public key:   {}", hex::encode(public.to_bytes()?));
+    println!("account hash: {}", hex::encode(hash.value()));
+
+    if let Some(expect) = &args.expect {
+        if account_hash(expect)? != hash {
+            bail!("the mnemonic does not reproduce {expect}; check the words, passphrase, algorithm and index");
+        }
+        println!("matches {expect}");
+    }
+    if let Some(account) = &args.guardian_of {
+        if !api.guardians(account)?.contains(&hash) {
+            bail!("this key is not a registered guardian of {account}");
+        }
+        println!("registered guardian of {account}");
+    }
+
+    if let Some(name) = &args.save {
+        keystore.insert(name, &secret, &prompt::new_password()?)?;
+        println!("saved to the keystore as {name}");
+    }
+    if let Some(dir) = &args.out {
+        write_pem(dir, &secret)?;
+    }
+    Ok(())
+}
+
+/// Write `secret` as casper-client keygen does
+fn write_pem(dir: &Path, secret: &SecretKey) -> Result<()> {
+    let public = PublicKey::from(secret);
+    fs::create_dir_all(dir)?;
+    let secret_path = dir.join("secret_key.pem");
+    if secret_path.exists() {
+        bail!("{} already exists", secret_path.display());
+    }
+    write_private(&secret_path, &secret.to_pem()?)?;
+    fs::write(dir.join("public_key.pem"), public.to_pem()?)?;
+    fs::write(dir.join("public_key_hex"), hex::encode(public.to_bytes()?))?;
+    println!("keys written to {}", dir.display());
+    Ok(())
+}
+
+/// Write a file only the current user can read
+pub(crate) fn write_private(path: &Path, contents: &str) -> Result<()> {
+    #[cfg(unix)]
+    {
+        use std::{io::Write, os::unix::fs::OpenOptionsExt};
+        let mut file = fs::OpenOptions::new().write(true).create_new(true).mode(0o600).open(path)?;
+        file.write_all(contents.as_bytes())?;
+        Ok(())
+    }
+    #[cfg(not(unix))]
+    {
+        fs::write(path, contents).map_err(Into::into)
+    }
+}
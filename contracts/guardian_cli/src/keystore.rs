@@ -0,0 +1,164 @@
+//! Password-encrypted key files.
+//!
+//! Each key is `<dir>/<name>.json` holding its secret key PEM sealed with AES-256-GCM under a key
+//! derived from the password by scrypt. The public key stays in the clear, so keys can be listed
+//! without a password, and is the ciphertext's associated data, so it can't be swapped. `seal` and
+//! `open` are the same encryption for other secrets, like recovery kits.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use aes_gcm::{
+    aead::{rand_core::RngCore, Aead, AeadCore, KeyInit, OsRng, Payload},
+    Aes256Gcm, Key, Nonce,
+};
+use anyhow::{anyhow, bail, Context, Result};
+use casper_types::{bytesrepr::ToBytes, PublicKey, SecretKey};
+use serde::{Deserialize, Serialize};
+
+use crate::key::write_private;
+
+const VERSION: u8 = 1;
+/// scrypt cost: 2^15 iterations of 8 × 128-byte blocks, 32 MiB
+const SCRYPT_LOG_N: u8 = 15;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct KeyFile {
+    version: u8,
+    public_key: String,
+    kdf: Kdf,
+    cipher: Cipher,
+    ciphertext: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Kdf {
+    name: String,
+    log_n: u8,
+    r: u32,
+    p: u32,
+    salt: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Cipher {
+    name: String,
+    nonce: String,
+}
+
+pub struct Keystore {
+    dir: PathBuf,
+}
+
+impl Keystore {
+    /// Keystore in `dir`, by default `~/.guardian/keystore`
+    pub fn open(dir: Option<PathBuf>) -> Result<Self> {
+        let dir = match dir {
+            Some(dir) => dir,
+            None => {
+                let home = std::env::var_os("HOME").ok_or_else(|| anyhow!("HOME is not set; pass --keystore"))?;
+                Path::new(&home).join(".guardian").join("keystore")
+            }
+        };
+        Ok(Keystore { dir })
+    }
+
+    fn path(&self, name: &str) -> Result<PathBuf> {
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            bail!("key names may only use letters, digits, '-' and '_'");
+        }
+        Ok(self.dir.join(format!("{name}.json")))
+    }
+
+    fn read(&self, name: &str) -> Result<KeyFile> {
+        let path = self.path(name)?;
+        let json = fs::read_to_string(&path).with_context(|| format!("no key {name} in {}", self.dir.display()))?;
+        let file: KeyFile = serde_json::from_str(&json).with_context(|| format!("reading {}", path.display()))?;
+        if file.version != VERSION {
+            bail!("{} has unsupported version {}", path.display(), file.version);
+        }
+        Ok(file)
+    }
+
+    /// Names and public key hex of every stored key
+    pub fn list(&self) -> Result<Vec<(String, String)>> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut keys = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|e| e == "json") {
+                let name = path.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+                keys.push((name.clone(), self.read(&name)?.public_key));
+            }
+        }
+        keys.sort();
+        Ok(keys)
+    }
+
+    /// Seal `secret` under `password` as `name`; existing keys are never overwritten
+    pub fn insert(&self, name: &str, secret: &SecretKey, password: &str) -> Result<()> {
+        let path = self.path(name)?;
+        if path.exists() {
+            bail!("key {name} already exists");
+        }
+        let public_key = hex::encode(PublicKey::from(secret).to_bytes()?);
+        let (kdf, cipher, ciphertext) = seal(password, secret.to_pem()?.as_bytes(), public_key.as_bytes())?;
+        let file = KeyFile { version: VERSION, public_key, kdf, cipher, ciphertext: hex::encode(ciphertext) };
+        fs::create_dir_all(&self.dir)?;
+        write_private(&path, &serde_json::to_string_pretty(&file)?)
+    }
+
+    /// Decrypt key `name` with `password`
+    pub fn unlock(&self, name: &str, password: &str) -> Result<SecretKey> {
+        let file = self.read(name)?;
+        let pem = open(&file.kdf, &file.cipher, password, &hex::decode(&file.ciphertext)?, file.public_key.as_bytes())
+            .map_err(|e| anyhow!("{e} for key {name}"))?;
+
+        let secret = SecretKey::from_pem(String::from_utf8(pem)?)?;
+        if hex::encode(PublicKey::from(&secret).to_bytes()?) != file.public_key {
+            bail!("key {name} doesn't match its public key");
+        }
+        Ok(secret)
+    }
+}
+
+/// Encrypt `msg` with AES-256-GCM under a key scrypt derives from `password`, binding `aad` to it
+pub fn seal(password: &str, msg: &[u8], aad: &[u8]) -> Result<(Kdf, Cipher, Vec<u8>)> {
+    let mut salt = [0u8; 32];
+    OsRng.fill_bytes(&mut salt);
+    let cipher = cipher(password, &salt, SCRYPT_LOG_N, SCRYPT_R, SCRYPT_P)?;
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher.encrypt(&nonce, Payload { msg, aad }).map_err(|_| anyhow!("encryption failed"))?;
+    Ok((
+        Kdf { name: "scrypt".into(), log_n: SCRYPT_LOG_N, r: SCRYPT_R, p: SCRYPT_P, salt: hex::encode(salt) },
+        Cipher { name: "aes-256-gcm".into(), nonce: hex::encode(nonce) },
+        ciphertext,
+    ))
+}
+
+/// Decrypt what `seal` sealed
+pub fn open(kdf: &Kdf, cipher_params: &Cipher, password: &str, ciphertext: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+    if kdf.name != "scrypt" || cipher_params.name != "aes-256-gcm" {
+        bail!("unsupported {} / {} encryption", kdf.name, cipher_params.name);
+    }
+    let nonce = hex::decode(&cipher_params.nonce)?;
+    if nonce.len() != 12 {
+        bail!("malformed nonce");
+    }
+    cipher(password, &hex::decode(&kdf.salt)?, kdf.log_n, kdf.r, kdf.p)?
+        .decrypt(Nonce::from_slice(&nonce), Payload { msg: ciphertext, aad })
+        .map_err(|_| anyhow!("wrong password"))
+}
+
+fn cipher(password: &str, salt: &[u8], log_n: u8, r: u32, p: u32) -> Result<Aes256Gcm> {
+    let params = scrypt::Params::new(log_n, r, p, 32).map_err(|e| anyhow!("scrypt parameters: {e}"))?;
+    let mut key = [0u8; 32];
+    scrypt::scrypt(password.as_bytes(), salt, &params, &mut key).map_err(|e| anyhow!("scrypt: {e}"))?;
+    Ok(Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key)))
+}
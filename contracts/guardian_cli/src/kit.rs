@@ -0,0 +1,266 @@
+//! Recovery kits: an account's guardian setup, the chain and contract it lives on, and the owner's
+//! notes on reaching each guardian, sealed under a password in one file to keep offline.
+//!
+//! A kit holds every configuration record of the account as the registry stores it, raw, so
+//! `backup verify` can tell byte for byte whether the chain still matches. Kits use the keystore's
+//! encryption: AES-256-GCM under a scrypt key, with the account as associated data.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+use casper_types::account::AccountHash;
+use clap::{Args, Subcommand};
+use guardian_types::prefixes;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::{account_hash, Api},
+    key::write_private,
+    keystore::{self, Cipher, Kdf},
+    prompt,
+};
+
+const VERSION: u8 = 1;
+const FORMAT: &str = "guardian-recovery-kit";
+
+/// Exit status of `backup verify` when the kit matches the chain
+pub const MATCHES: i32 = 0;
+/// Exit status of `backup verify` when the chain has changed since the kit was made
+pub const DRIFTED: i32 = 2;
+
+/// Configuration records a kit keeps, by name. The heartbeat itself is left out: it changes every
+/// time the owner checks in.
+const RECORDS: &[(&str, &str)] = &[
+    ("guardians", prefixes::GUARDIANS),
+    ("contract_guardians", prefixes::CONTRACT_GUARDIANS),
+    ("hashed_guardians", prefixes::HASHED_GUARDIANS),
+    ("merkle_root", prefixes::MERKLE_ROOT),
+    ("threshold", prefixes::THRESHOLD),
+    ("threshold_decay", prefixes::THRESHOLD_DECAY),
+    ("approval_policy", prefixes::APPROVAL_POLICY),
+    ("categories", prefixes::CATEGORIES),
+    ("settings", prefixes::SETTINGS),
+    ("guardian_terms", prefixes::GUARDIAN_TERMS),
+    ("suspended", prefixes::SUSPENDED),
+    ("key_policy", prefixes::KEY_POLICY),
+    ("arbiter", prefixes::ARBITER),
+    ("heartbeat_period", prefixes::HEARTBEAT_PERIOD),
+    ("heirs", prefixes::HEIRS),
+    ("recovery_types", prefixes::RECOVERY_TYPES),
+    ("modules", prefixes::MODULES),
+    ("relayers", prefixes::RELAYERS),
+    ("watchers", prefixes::WATCHERS),
+    ("instructions", prefixes::INSTRUCTIONS),
+];
+
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+pub struct Backup {
+    #[command(subcommand)]
+    command: Option<BackupCommand>,
+
+    /// Account to back up: public key or account hash
+    #[arg(required = true)]
+    account: Option<String>,
+
+    /// File to write the kit to
+    #[arg(long, short, required = true)]
+    out: Option<PathBuf>,
+
+    /// How to reach a guardian, as `<guardian>=<note>`; repeatable
+    #[arg(long = "contact", value_name = "GUARDIAN=NOTE")]
+    contacts: Vec<String>,
+
+    /// File of free-form notes to include, e.g. where the guardians' details are kept
+    #[arg(long)]
+    notes: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum BackupCommand {
+    /// Check a kit still matches the account's setup on chain; exits 2 if it doesn't
+    Verify {
+        /// Kit file
+        kit: PathBuf,
+    },
+}
+
+/// The sealed file
+#[derive(Serialize, Deserialize)]
+struct KitFile {
+    format: String,
+    version: u8,
+    /// Account hash hex, also the ciphertext's associated data
+    account: String,
+    kdf: Kdf,
+    cipher: Cipher,
+    ciphertext: String,
+}
+
+/// What a kit holds once decrypted
+#[derive(Serialize, Deserialize)]
+pub struct Kit {
+    /// Account hash hex
+    pub account: String,
+    /// ms since the Unix epoch
+    pub created_at: u64,
+    pub network: Network,
+    /// Guardian account hashes, for reading the kit by eye; `records` is authoritative
+    pub guardians: Vec<String>,
+    /// bytesrepr hex of each of `RECORDS`, null if the account never set it
+    pub records: BTreeMap<String, Option<String>>,
+    pub contacts: Vec<Contact>,
+    pub notes: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Network {
+    /// Backend API root the kit was made through
+    pub api: String,
+    pub chain_name: String,
+    /// Recovery registry contract hash
+    pub registry_hash: Option<String>,
+    pub protocol_version: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Contact {
+    /// Guardian account hash hex
+    pub guardian: String,
+    pub note: String,
+}
+
+pub fn run(args: Backup, api: &Api, api_root: &str) -> Result<i32> {
+    if let Some(BackupCommand::Verify { kit }) = args.command {
+        return verify(&kit, api);
+    }
+    let (Some(account), Some(out)) = (args.account, args.out) else { bail!("backup needs an account and --out") };
+    let owner = account_hash(&account)?;
+    if out.exists() {
+        bail!("{} already exists", out.display());
+    }
+
+    let mut contacts = Vec::new();
+    for c in &args.contacts {
+        let (guardian, note) = c.split_once('=').ok_or_else(|| anyhow!("--contact takes <guardian>=<note>: {c}"))?;
+        contacts.push(Contact { guardian: hex::encode(account_hash(guardian)?.value()), note: note.trim().to_string() });
+    }
+    let notes = match &args.notes {
+        Some(path) => fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?,
+        None => String::new(),
+    };
+
+    let records = read_records(api, owner)?;
+    if ["guardians", "contract_guardians", "hashed_guardians", "merkle_root"].iter().all(|r| records[*r].is_none()) {
+        bail!("account-hash-{} has no guardians registered", hex::encode(owner.value()));
+    }
+    let guardians: Vec<AccountHash> = api.registry_item(&format!("{}{owner:?}", prefixes::GUARDIANS))?.unwrap_or_default();
+    let kit = Kit {
+        account: hex::encode(owner.value()),
+        created_at: SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64,
+        network: network(api, api_root)?,
+        guardians: guardians.iter().map(|g| hex::encode(g.value())).collect(),
+        records,
+        contacts,
+        notes,
+    };
+
+    let password = prompt::new_secret("recovery kit password")?;
+    let (kdf, cipher, ciphertext) = keystore::seal(&password, &serde_json::to_vec(&kit)?, kit.account.as_bytes())?;
+    let file = KitFile {
+        format: FORMAT.into(),
+        version: VERSION,
+        account: kit.account.clone(),
+        kdf,
+        cipher,
+        ciphertext: hex::encode(ciphertext),
+    };
+    write_private(&out, &serde_json::to_string_pretty(&file)?)?;
+    let set = kit.records.values().filter(|r| r.is_some()).count();
+    println!("recovery kit for account-hash-{} written to {} ({set} records)", kit.account, out.display());
+    Ok(MATCHES)
+}
+
+/// Decrypt the kit at `path`, asking for its password
+pub fn open(path: &Path) -> Result<Kit> {
+    let json = fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    let file: KitFile = serde_json::from_str(&json).with_context(|| format!("{} is not a recovery kit", path.display()))?;
+    if file.format != FORMAT || file.version != VERSION {
+        bail!("{} is a {} v{}, not a {FORMAT} v{VERSION}", path.display(), file.format, file.version);
+    }
+    let password = prompt::secret("Recovery kit password: ")?;
+    let plain = keystore::open(&file.kdf, &file.cipher, &password, &hex::decode(&file.ciphertext)?, file.account.as_bytes())
+        .map_err(|e| anyhow!("{e} for {}", path.display()))?;
+    let kit: Kit = serde_json::from_slice(&plain)?;
+    if kit.account != file.account {
+        bail!("{} was tampered with: its account doesn't match", path.display());
+    }
+    Ok(kit)
+}
+
+/// Print how the kit at `path` compares with the chain, returning `MATCHES` or `DRIFTED`
+fn verify(path: &Path, api: &Api) -> Result<i32> {
+    let kit = open(path)?;
+    if drift(&kit, api)? {
+        println!("account-hash-{}: the kit is out of date; make a new one", kit.account);
+        Ok(DRIFTED)
+    } else {
+        println!("account-hash-{}: the kit matches the chain", kit.account);
+        Ok(MATCHES)
+    }
+}
+
+/// Print every way the chain differs from `kit`: the network the backend serves, and each record
+/// set, changed or removed since. True if anything did.
+pub fn drift(kit: &Kit, api: &Api) -> Result<bool> {
+    let owner = account_hash(&kit.account)?;
+    let mut drifted = false;
+
+    let now = network(api, &kit.network.api)?;
+    if now.chain_name != kit.network.chain_name || now.registry_hash != kit.network.registry_hash {
+        println!(
+            "network  kit is for {} registry {}, the backend serves {} registry {}",
+            kit.network.chain_name,
+            kit.network.registry_hash.as_deref().unwrap_or("-"),
+            now.chain_name,
+            now.registry_hash.as_deref().unwrap_or("-"),
+        );
+        drifted = true;
+    }
+
+    let current = read_records(api, owner)?;
+    for (name, kept) in &kit.records {
+        let status = match (kept, current.get(name).cloned().flatten()) {
+            (a, b) if *a == b => continue,
+            (None, Some(_)) => "set since the kit was made",
+            (Some(_), None) => "removed since the kit was made",
+            _ => "changed since the kit was made",
+        };
+        println!("{name:<18} {status}");
+        drifted = true;
+    }
+    Ok(drifted)
+}
+
+/// Every one of `RECORDS` of `owner`, as bytesrepr hex
+fn read_records(api: &Api, owner: AccountHash) -> Result<BTreeMap<String, Option<String>>> {
+    RECORDS
+        .iter()
+        .map(|(name, prefix)| Ok((name.to_string(), api.registry_bytes(&format!("{prefix}{owner:?}"))?.map(hex::encode))))
+        .collect()
+}
+
+fn network(api: &Api, api_root: &str) -> Result<Network> {
+    let n = api.get("/network")?;
+    Ok(Network {
+        api: api_root.to_string(),
+        chain_name: n["chainName"].as_str().unwrap_or_default().to_string(),
+        registry_hash: n["registryHash"].as_str().map(String::from),
+        protocol_version: n["protocolVersion"].as_str().map(String::from),
+    })
+}
@@ -0,0 +1,117 @@
+//! `guardian-cli`: key handling and registry lookups for guardians and account owners.
+//!
+//! Lookups go through the backend's `/api/v1` (`--api` or `GUARDIAN_API`, else the `--profile`'s).
+//! Signing keys live in a password-encrypted keystore (`--keystore` or `GUARDIAN_KEYSTORE`,
+//! `~/.guardian/keystore` by default) and are unlocked per command. `--no-input` and `--yes` make
+//! it safe to script: it fails rather than waiting on a prompt.
+
+mod api;
+mod bench;
+mod dashboard;
+mod derive;
+mod doctor;
+mod key;
+mod keystore;
+mod kit;
+mod nctl;
+mod profile;
+mod prompt;
+mod recovery;
+mod rehearse;
+mod restore;
+mod sign;
+mod template;
+mod watch;
+
+use std::path::PathBuf;
+
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::Shell;
+
+const DEFAULT_API: &str = "http://localhost:3001/api/v1";
+
+#[derive(Parser)]
+#[command(name = "guardian-cli", version, about = "SentinelX guardian and account owner tool")]
+struct Cli {
+    /// Backend API root; the profile's if not given, else http://localhost:3001/api/v1
+    #[arg(long, global = true, env = "GUARDIAN_API")]
+    api: Option<String>,
+
+    /// Profile in ~/.guardian/profiles to take defaults from, e.g. one `restore` wrote
+    #[arg(long, global = true, env = "GUARDIAN_PROFILE")]
+    profile: Option<String>,
+
+    /// Keystore directory
+    #[arg(long, global = true, env = "GUARDIAN_KEYSTORE")]
+    keystore: Option<PathBuf>,
+
+    /// Never prompt on the terminal; fail instead. Secrets may still be piped on stdin
+    #[arg(long, global = true, env = "GUARDIAN_NO_INPUT")]
+    no_input: bool,
+
+    /// Answer yes to confirmations
+    #[arg(long, short, global = true)]
+    yes: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create and check guardian keys
+    #[command(subcommand)]
+    Key(key::KeyCommand),
+    /// Approve a recovery with a deploy from a keystore key
+    Approve(recovery::Approve),
+    /// Sign a recovery approval for a relayer to submit with approve_signed
+    SignApproval(recovery::SignApproval),
+    /// Live view of the accounts a key guards, with one-key approve and reject
+    Dashboard(dashboard::Dashboard),
+    /// Follow a recovery of an account until it is executable; the exit status says how it ended
+    Watch(watch::Watch),
+    /// Audit an account's guardian setup for risky configurations; exits 2 on findings at `--fail-on`
+    Doctor(doctor::Doctor),
+    /// Write an encrypted recovery kit of an account's setup, or `backup verify` one against the chain
+    Backup(kit::Backup),
+    /// Restore an account's recovery kit on this machine: profile, guardians to reach, heartbeat
+    Restore(restore::Restore),
+    /// Practise a full recovery of an account's setup on a local NCTL network with test keys
+    Rehearse(rehearse::Rehearse),
+    /// List the built-in guardian policy templates, or set an account up from one
+    #[command(subcommand)]
+    Template(template::TemplateCommand),
+    /// Compare the gas of named-key and dictionary storage layouts on a local NCTL network
+    Bench(bench::Bench),
+    /// Print a shell completion script
+    Completions {
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    prompt::configure(cli.no_input, cli.yes);
+    let profile = cli.profile.as_deref().map(profile::load).transpose()?;
+    let api_root = cli.api.or(profile.map(|p| p.api));
+    let api = api::Api::new(api_root.as_deref().unwrap_or(DEFAULT_API));
+    let keystore = keystore::Keystore::open(cli.keystore)?;
+    match cli.command {
+        Command::Key(cmd) => key::run(cmd, &api, &keystore),
+        Command::Approve(args) => recovery::approve(args, &api, &keystore),
+        Command::SignApproval(args) => recovery::sign_approval(args, &api, &keystore),
+        Command::Dashboard(args) => dashboard::run(args, &api, &keystore),
+        Command::Watch(args) => std::process::exit(watch::run(args, &api)?),
+        Command::Doctor(args) => std::process::exit(doctor::run(args, &api)?),
+        Command::Backup(args) => std::process::exit(kit::run(args, &api, api_root.as_deref().unwrap_or(DEFAULT_API))?),
+        Command::Rehearse(args) => rehearse::run(args, &api),
+        Command::Template(cmd) => template::run(cmd, &api, &keystore),
+        Command::Bench(args) => bench::run(args),
+        Command::Restore(args) => restore::run(args, api_root.as_deref(), &keystore),
+        Command::Completions { shell } => {
+            clap_complete::generate(shell, &mut Cli::command(), "guardian-cli", &mut std::io::stdout());
+            Ok(())
+        }
+    }
+}
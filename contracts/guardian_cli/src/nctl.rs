@@ -0,0 +1,391 @@
+//! A registry on a local NCTL network, for `rehearse` and `bench`: throwaway keys funded from the
+//! faucet, contracts installed from built WASMs, and calls made and awaited.
+//!
+//! Everything goes through `casper-client`, which ships with NCTL, so nothing here builds or signs
+//! deploys itself. Calls report a revert as the `RegistryError` behind it, for callers that expect
+//! one.
+
+use std::{
+    cell::Cell,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    thread,
+    time::{Duration, Instant as Clock},
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+use casper_types::{account::AccountHash, U256};
+use clap::Args;
+use guardian_types::{errors::RegistryError, settings::AccountSettings};
+use serde_json::{json, Value};
+
+use crate::api::account_hash;
+
+/// Motes each test account is funded with
+const FUNDING: &str = "1000000000000";
+/// Payment for installing the registry
+const INSTALL_PAYMENT: &str = "600000000000";
+/// Payment for each registry call
+const CALL_PAYMENT: &str = "10000000000";
+/// How long a deploy may take to execute
+const EXECUTION_TIMEOUT: Duration = Duration::from_secs(180);
+const POLL: Duration = Duration::from_secs(2);
+/// Named key the registry's installer leaves on the installing account
+const REGISTRY_KEY: &str = "recovery_registry_contract_hash";
+
+#[derive(Args)]
+pub struct Network {
+    /// Start NCTL first, from the checkout `$NCTL` points at
+    #[arg(long)]
+    start: bool,
+
+    /// Node to run on
+    #[arg(long, default_value = "http://localhost:11101")]
+    node: String,
+
+    #[arg(long, default_value = "casper-net-1")]
+    chain_name: String,
+
+    /// Directory with the faucet's secret_key.pem and public_key_hex; NCTL's net-1 faucet by default
+    #[arg(long)]
+    faucet: Option<PathBuf>,
+
+    /// Directory holding the contract WASMs, as `make copy-wasm` leaves it
+    #[arg(long, default_value = "wasm")]
+    wasm: PathBuf,
+
+    /// Where test keys are written
+    #[arg(long, default_value = "rehearsal")]
+    dir: PathBuf,
+}
+
+/// A node that answered, with the faucet to fund test keys from
+pub struct Local {
+    pub node: Node,
+    pub faucet: TestKey,
+    pub dir: PathBuf,
+    wasm: PathBuf,
+    /// Last transfer id used
+    transfers: Cell<u64>,
+}
+
+/// A test account: its key files and account hash
+pub struct TestKey {
+    pub name: String,
+    pub secret: PathBuf,
+    /// Public key hex
+    pub public: String,
+    pub hash: AccountHash,
+}
+
+pub struct Node {
+    address: String,
+    chain_name: String,
+}
+
+/// What an executed deploy reports
+struct Executed {
+    /// Its error message if it failed
+    error: Option<String>,
+    /// Gas it consumed
+    gas: Option<u64>,
+}
+
+impl Network {
+    /// Check the WASM and faucet are there, start NCTL if asked, and wait for the node
+    pub fn connect(self) -> Result<Local> {
+        let faucet = match self.faucet {
+            Some(dir) => dir,
+            None => {
+                let nctl = std::env::var_os("NCTL").ok_or_else(|| anyhow!("NCTL is not set; pass --faucet"))?;
+                Path::new(&nctl).join("assets").join("net-1").join("faucet")
+            }
+        };
+        if !self.wasm.is_dir() {
+            bail!("{} not found; build the WASMs with `make build copy-wasm` or pass --wasm", self.wasm.display());
+        }
+        let faucet = TestKey::load("faucet", &faucet)?;
+
+        if self.start {
+            step("starting NCTL");
+            let status = Command::new("bash").args(["-c", "source \"$NCTL/activate\" && nctl-start"]).status()?;
+            if !status.success() {
+                bail!("nctl-start failed");
+            }
+        }
+        let node = Node { address: self.node.trim_end_matches('/').to_string(), chain_name: self.chain_name };
+        step(&format!("waiting for {}", node.address));
+        node.ready()?;
+        fs::create_dir_all(&self.dir)?;
+        Ok(Local { node, faucet, dir: self.dir, wasm: self.wasm, transfers: Cell::new(0) })
+    }
+}
+
+impl Local {
+    /// A new ed25519 key in `dir/name`, replacing any of that name
+    pub fn key(&self, name: &str) -> Result<TestKey> {
+        let path = self.dir.join(name);
+        let path_str = path.to_string_lossy();
+        client(&["keygen", "--force", &path_str])?;
+        TestKey::load(name, &path)
+    }
+
+    /// Fund `keys` from the faucet and wait for every transfer
+    pub fn fund(&self, keys: &[&TestKey]) -> Result<()> {
+        let hashes = keys
+            .iter()
+            .map(|k| {
+                self.transfers.set(self.transfers.get() + 1);
+                self.node.transfer(&self.faucet, k, self.transfers.get())
+            })
+            .collect::<Result<Vec<_>>>()?;
+        hashes.iter().try_for_each(|hash| self.node.succeed("transfer", hash))
+    }
+
+    /// Install the registry from the faucet and create its storage, returning its contract hash
+    /// (`hash-…`)
+    pub fn install(&self) -> Result<String> {
+        self.install_contract("recovery_registry.wasm", REGISTRY_KEY)
+    }
+
+    /// Install contract WASM `file` from the faucet and call its `init_storage`, returning the
+    /// contract hash its installer leaves under named key `key`
+    pub fn install_contract(&self, file: &str, key: &str) -> Result<String> {
+        let wasm = self.wasm.join(file);
+        if !wasm.exists() {
+            bail!("{} not found; build it with `make build copy-wasm` or pass --wasm", wasm.display());
+        }
+        let install = self.node.put(&self.faucet, &["--session-path", &wasm.to_string_lossy()], INSTALL_PAYMENT, Value::Array(vec![]))?;
+        self.node.succeed(&format!("installing {file}"), &install)?;
+        let contract = self.node.named_key(&self.faucet, key)?;
+        self.node.call(&self.faucet, &contract, "init_storage", Value::Array(vec![]))?;
+        Ok(contract)
+    }
+
+    /// Register `guardians` with `threshold` for `owner` on `registry` and apply `settings`
+    pub fn set_up(&self, registry: &str, owner: &TestKey, guardians: &[TestKey], threshold: u8, settings: &AccountSettings) -> Result<()> {
+        let list: Vec<String> = guardians.iter().map(|g| hex::encode(g.hash.value())).collect();
+        self.node.call(owner, registry, "init_guardians", json!([
+            account_arg(owner),
+            { "name": "guardians", "type": { "List": { "ByteArray": 32 } }, "value": list },
+            { "name": "threshold", "type": "U8", "value": threshold },
+        ]))?;
+        self.node.call(owner, registry, "update_settings", settings_args(owner, settings))
+    }
+}
+
+/// `update_settings` args applying the parts of `settings` a plain recovery touches to `owner`
+pub fn settings_args(owner: &TestKey, settings: &AccountSettings) -> Value {
+    json!([
+        account_arg(owner),
+        { "name": "expiry", "type": "U64", "value": settings.expiry },
+        { "name": "delay", "type": "U64", "value": settings.delay },
+        { "name": "max_attempts", "type": "U8", "value": settings.max_attempts },
+        { "name": "veto_window", "type": "U64", "value": settings.veto_window },
+        { "name": "approval_window", "type": "U64", "value": settings.approval_window },
+    ])
+}
+
+/// `account` session arg naming `key`'s account
+pub fn account_arg(key: &TestKey) -> Value {
+    json!({ "name": "account", "type": { "ByteArray": 32 }, "value": hex::encode(key.hash.value()) })
+}
+
+/// Session args of an entry point taking just a recovery `id`
+pub fn id_args(id: U256) -> Value {
+    json!([{ "name": "id", "type": "U256", "value": id.to_string() }])
+}
+
+/// Print a step of a run
+pub fn step(text: &str) {
+    println!("- {text}");
+}
+
+impl TestKey {
+    /// Key files `casper-client keygen` writes, or NCTL keeps, in `dir`
+    fn load(name: &str, dir: &Path) -> Result<Self> {
+        let public = fs::read_to_string(dir.join("public_key_hex")).with_context(|| format!("no public_key_hex in {}", dir.display()))?;
+        let public = public.trim().to_string();
+        Ok(TestKey { name: name.to_string(), secret: dir.join("secret_key.pem"), hash: account_hash(&public)?, public })
+    }
+}
+
+/// Run `casper-client` and parse what it prints, if it prints JSON
+fn client(args: &[&str]) -> Result<Value> {
+    let out = Command::new("casper-client").args(args).output().context("running casper-client; is it on PATH?")?;
+    if !out.status.success() {
+        bail!("casper-client {}: {}", args[0], String::from_utf8_lossy(&out.stderr).trim());
+    }
+    Ok(serde_json::from_slice(&out.stdout).unwrap_or(Value::Null))
+}
+
+impl Node {
+    fn ready(&self) -> Result<()> {
+        let started = Clock::now();
+        while self.state_root_hash().is_err() {
+            if started.elapsed() > EXECUTION_TIMEOUT {
+                bail!("no node answering at {}", self.address);
+            }
+            thread::sleep(POLL);
+        }
+        Ok(())
+    }
+
+    fn state_root_hash(&self) -> Result<String> {
+        let r = client(&["get-state-root-hash", "--node-address", &self.address])?;
+        r["result"]["state_root_hash"].as_str().map(String::from).ok_or_else(|| anyhow!("node has no state root hash yet"))
+    }
+
+    fn transfer(&self, from: &TestKey, to: &TestKey, transfer_id: u64) -> Result<String> {
+        let secret = from.secret.to_string_lossy();
+        let r = client(&[
+            "transfer", "--node-address", &self.address, "--chain-name", &self.chain_name, "--secret-key", &secret,
+            "--amount", FUNDING, "--target-account", &to.public, "--transfer-id", &transfer_id.to_string(),
+            "--payment-amount", "100000000",
+        ])?;
+        deploy_hash(&r)
+    }
+
+    /// Send a deploy of `session` (casper-client session flags) with `args`, returning its hash
+    fn put(&self, signer: &TestKey, session: &[&str], payment: &str, args: Value) -> Result<String> {
+        let secret = signer.secret.to_string_lossy();
+        let args = args.to_string();
+        let mut cmd = vec![
+            "put-deploy", "--node-address", &self.address, "--chain-name", &self.chain_name, "--secret-key", &secret,
+            "--payment-amount", payment, "--session-args-json", &args,
+        ];
+        cmd.extend_from_slice(session);
+        deploy_hash(&client(&cmd)?)
+    }
+
+    /// Send a call of registry `entry_point` as `signer` without waiting for it, returning its hash
+    pub fn send(&self, signer: &TestKey, registry: &str, entry_point: &str, args: Value) -> Result<String> {
+        self.send_paying(signer, registry, entry_point, args, CALL_PAYMENT)
+    }
+
+    /// `send` a call of `contract`'s `entry_point` with `payment` motes instead of the usual
+    pub fn send_paying(&self, signer: &TestKey, contract: &str, entry_point: &str, args: Value, payment: &str) -> Result<String> {
+        self.put(signer, &["--session-hash", contract, "--session-entry-point", entry_point], payment, args)
+    }
+
+    /// Call registry `entry_point` as `signer` and wait for it to succeed
+    pub fn call(&self, signer: &TestKey, registry: &str, entry_point: &str, args: Value) -> Result<()> {
+        let hash = self.send(signer, registry, entry_point, args)?;
+        self.succeed(entry_point, &hash)
+    }
+
+    /// Call registry `entry_point` as `signer` and wait for it: `None` if it succeeded, the error if
+    /// the registry reverted, and an error for any other failure
+    pub fn attempt(&self, signer: &TestKey, registry: &str, entry_point: &str, args: Value) -> Result<Option<RegistryError>> {
+        let hash = self.send(signer, registry, entry_point, args)?;
+        self.outcome(entry_point, &hash)
+    }
+
+    /// Finalize recovery `id` as `signer`, trying again while the registry says it's too early since
+    /// block time trails the wall clock by up to a block. `None` if it finalized, else the error.
+    pub fn finalize(&self, signer: &TestKey, registry: &str, id: U256) -> Result<Option<RegistryError>> {
+        let started = Clock::now();
+        loop {
+            match self.attempt(signer, registry, "finalize", id_args(id))? {
+                Some(RegistryError::TooEarly) if started.elapsed() < EXECUTION_TIMEOUT => thread::sleep(POLL * 5),
+                outcome => return Ok(outcome),
+            }
+        }
+    }
+
+    /// Wait for deploy `hash` to execute; an error naming the registry error if it failed
+    pub fn succeed(&self, what: &str, hash: &str) -> Result<()> {
+        match self.outcome(what, hash)? {
+            None => Ok(()),
+            Some(e) => bail!("{what} reverted with {} ({}): {}", e.name(), e as u16, e.description()),
+        }
+    }
+
+    /// Wait for deploy `hash` to execute: `None` if it succeeded, the registry error it reverted with
+    pub fn outcome(&self, what: &str, hash: &str) -> Result<Option<RegistryError>> {
+        match self.executed(what, hash)?.error {
+            None => Ok(None),
+            Some(message) => RegistryError::from_revert(&message).map(Some).ok_or_else(|| anyhow!("{what} failed: {message}")),
+        }
+    }
+
+    /// Wait for deploy `hash` to succeed, returning the gas it consumed
+    pub fn gas(&self, what: &str, hash: &str) -> Result<u64> {
+        let executed = self.executed(what, hash)?;
+        if let Some(message) = executed.error {
+            bail!("{what} failed: {message}");
+        }
+        executed.gas.ok_or_else(|| anyhow!("{what} ({hash}) reports no gas"))
+    }
+
+    /// Wait for deploy `hash` to execute
+    fn executed(&self, what: &str, hash: &str) -> Result<Executed> {
+        let started = Clock::now();
+        loop {
+            if let Some(executed) = self.execution(hash) {
+                return Ok(executed);
+            }
+            if started.elapsed() > EXECUTION_TIMEOUT {
+                bail!("{what} ({hash}) wasn't executed within {}s", EXECUTION_TIMEOUT.as_secs());
+            }
+            thread::sleep(POLL);
+        }
+    }
+
+    /// `None` while deploy `hash` is pending
+    fn execution(&self, hash: &str) -> Option<Executed> {
+        let r = client(&["get-deploy", "--node-address", &self.address, hash]).ok()?;
+        let r = &r["result"];
+        let gas = |v: &Value| v.as_str().and_then(|g| g.parse().ok());
+        // Casper 1.x nodes list results per block, 2.0 nodes give the one
+        if let Some(e) = r["execution_results"].as_array().and_then(|v| v.first()) {
+            let (success, failure) = (&e["result"]["Success"], &e["result"]["Failure"]);
+            let error = failure["error_message"].as_str().map(String::from);
+            return Some(Executed { error, gas: gas(&success["cost"]).or_else(|| gas(&failure["cost"])) });
+        }
+        let result = &r["execution_info"]["execution_result"];
+        if result.is_null() {
+            return None;
+        }
+        if result["Version2"].is_null() {
+            let v1 = &result["Version1"];
+            let error = v1["Failure"]["error_message"].as_str().map(String::from);
+            return Some(Executed { error, gas: gas(&v1["Success"]["cost"]).or_else(|| gas(&v1["Failure"]["cost"])) });
+        }
+        let v2 = &result["Version2"];
+        Some(Executed { error: v2["error_message"].as_str().map(String::from), gas: gas(&v2["consumed"]) })
+    }
+
+    /// Value of `account`'s named key `name`, e.g. `hash-…`
+    fn named_key(&self, account: &TestKey, name: &str) -> Result<String> {
+        let r = client(&["get-account", "--node-address", &self.address, "--account-identifier", &account.public])?;
+        find_named_key(&r, name).ok_or_else(|| anyhow!("{} has no named key {name}", account.name))
+    }
+
+    /// `parsed` value of the registry's `d` dictionary item `key`; null if it isn't set
+    pub fn dictionary_item(&self, registry: &str, key: &str) -> Result<Value> {
+        let root = self.state_root_hash()?;
+        let r = client(&[
+            "get-dictionary-item", "--node-address", &self.address, "--state-root-hash", &root,
+            "--contract-hash", registry, "--dictionary-name", "d", "--dictionary-item-key", key,
+        ])
+        .unwrap_or(Value::Null);
+        Ok(r["result"]["stored_value"]["CLValue"]["parsed"].clone())
+    }
+}
+
+fn deploy_hash(r: &Value) -> Result<String> {
+    r["result"]["deploy_hash"].as_str().map(String::from).ok_or_else(|| anyhow!("casper-client returned no deploy hash"))
+}
+
+/// The `key` of the `{ name, key }` entry called `name`, wherever the account or entity JSON has its
+/// named keys
+fn find_named_key(v: &Value, name: &str) -> Option<String> {
+    match v {
+        Value::Object(o) if o.get("name").and_then(Value::as_str) == Some(name) => o.get("key")?.as_str().map(String::from),
+        Value::Object(o) => o.values().find_map(|v| find_named_key(v, name)),
+        Value::Array(a) => a.iter().find_map(|v| find_named_key(v, name)),
+        _ => None,
+    }
+}
@@ -0,0 +1,52 @@
+//! Named profiles: the backend and contract an account lives on, so commands on a machine set up by
+//! `restore` reach the same registry without repeating `--api`.
+//!
+//! Each profile is `~/.guardian/profiles/<name>.json`, selected with `--profile` or
+//! `GUARDIAN_PROFILE`. An explicit `--api` still wins over the profile's.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct Profile {
+    /// Backend API root
+    pub api: String,
+    pub chain_name: String,
+    /// Recovery registry contract hash
+    pub registry_hash: Option<String>,
+    /// Account hash hex of the account the profile was made for
+    pub account: Option<String>,
+}
+
+fn path(name: &str) -> Result<PathBuf> {
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        bail!("profile names may only use letters, digits, '-' and '_'");
+    }
+    let home = std::env::var_os("HOME").ok_or_else(|| anyhow!("HOME is not set"))?;
+    Ok(Path::new(&home).join(".guardian").join("profiles").join(format!("{name}.json")))
+}
+
+pub fn load(name: &str) -> Result<Profile> {
+    let path = path(name)?;
+    let json = fs::read_to_string(&path).with_context(|| format!("no profile {name} at {}", path.display()))?;
+    serde_json::from_str(&json).with_context(|| format!("reading {}", path.display()))
+}
+
+pub fn exists(name: &str) -> Result<bool> {
+    Ok(path(name)?.exists())
+}
+
+/// Write profile `name`, replacing any of that name
+pub fn save(name: &str, profile: &Profile) -> Result<PathBuf> {
+    let path = path(name)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, serde_json::to_string_pretty(profile)?)?;
+    Ok(path)
+}
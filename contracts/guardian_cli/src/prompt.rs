@@ -0,0 +1,75 @@
+//! Secrets and confirmations asked of the user.
+//!
+//! With `--no-input` nothing is asked on the terminal: secrets must be piped on stdin, and
+//! confirmations fail unless `--yes` answers them.
+
+use std::{
+    io::{self, BufRead, IsTerminal, Write},
+    sync::OnceLock,
+};
+
+use anyhow::{bail, Context, Result};
+
+struct Mode {
+    no_input: bool,
+    yes: bool,
+}
+
+static MODE: OnceLock<Mode> = OnceLock::new();
+
+/// Set once from the global `--no-input` and `--yes` flags
+pub fn configure(no_input: bool, yes: bool) {
+    let _ = MODE.set(Mode { no_input, yes });
+}
+
+fn mode() -> &'static Mode {
+    MODE.get_or_init(|| Mode { no_input: false, yes: false })
+}
+
+/// Read a secret without echoing it when a terminal is attached, else one line of stdin
+pub fn secret(prompt: &str) -> Result<String> {
+    if io::stdin().is_terminal() {
+        if mode().no_input {
+            bail!("--no-input: would ask for \"{}\"; pipe it on stdin instead", prompt.trim_end_matches([':', ' ']));
+        }
+        return rpassword::prompt_password(prompt).context("reading from the terminal");
+    }
+    let mut line = String::new();
+    io::stdin().lock().read_line(&mut line)?;
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+/// A new keystore password, asked twice on a terminal
+pub fn new_password() -> Result<String> {
+    new_secret("keystore password")
+}
+
+/// A new `what`, e.g. a password, asked twice on a terminal
+pub fn new_secret(what: &str) -> Result<String> {
+    let password = secret(&format!("New {what}: "))?;
+    if password.is_empty() {
+        bail!("the {what} can't be empty");
+    }
+    if io::stdin().is_terminal() && secret("Repeat password: ")? != password {
+        bail!("passwords don't match");
+    }
+    Ok(password)
+}
+
+/// Ask before doing `action`; `--yes` answers for the user, `--no-input` without it refuses
+pub fn confirm(action: &str) -> Result<()> {
+    if mode().yes {
+        return Ok(());
+    }
+    if mode().no_input || !io::stdin().is_terminal() {
+        bail!("{action} needs confirmation; pass --yes");
+    }
+    eprint!("{action}? [y/N] ");
+    io::stderr().flush()?;
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    if !matches!(answer.trim(), "y" | "Y" | "yes") {
+        bail!("cancelled");
+    }
+    Ok(())
+}
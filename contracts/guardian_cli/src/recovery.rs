@@ -0,0 +1,92 @@
+use anyhow::{anyhow, Result};
+use casper_types::{PublicKey, U256};
+use clap::Args;
+use guardian_signer::Signer;
+use guardian_types::{
+    payloads,
+    time::{Span, MS_PER_SECOND},
+};
+use serde_json::{json, Value};
+
+use crate::{
+    api::{account_hash, Api},
+    keystore::Keystore,
+    prompt,
+    sign::{self, key_hex},
+};
+
+#[derive(Args)]
+pub struct Approve {
+    /// Decimal recovery id
+    id: String,
+
+    /// Keystore key of the guardian, which also pays for the deploy
+    #[arg(long)]
+    key: String,
+
+    #[arg(long, default_value = "")]
+    memo: String,
+}
+
+#[derive(Args)]
+pub struct SignApproval {
+    /// Decimal recovery id
+    id: String,
+
+    /// Keystore key of the guardian
+    #[arg(long)]
+    key: String,
+
+    /// Must match the memo the relayer passes to `approve_signed`
+    #[arg(long, default_value = "")]
+    memo: String,
+}
+
+/// Approve a recovery with a deploy signed by the guardian's key
+pub fn approve(args: Approve, api: &Api, keystore: &Keystore) -> Result<()> {
+    let recovery = api.get(&format!("/recoveries/{}", args.id))?;
+    println!("account: {}", recovery["account"].as_str().unwrap_or_default());
+    println!("new key: {}", recovery["newKey"].as_str().unwrap_or("-"));
+    println!("status:  {}, {} approval(s)", recovery["status"].as_str().unwrap_or("unknown"), recovery["approvals"]);
+    prompt::confirm(&format!("Approve recovery {}", args.id))?;
+
+    let signer = sign::unlock(keystore, &args.key)?;
+    let mut call = vec![json!({ "name": "id", "type": "u256", "value": args.id })];
+    if !args.memo.is_empty() {
+        call.push(json!({ "name": "memo", "type": "string", "value": args.memo }));
+    }
+    println!("{}", submit_call(api, &signer, "approve", call)?);
+    Ok(())
+}
+
+/// Call `entry_point` with a deploy (or transaction) paid for and signed by `signer`, returning its
+/// hash
+pub fn submit_call(api: &Api, signer: &dyn Signer, entry_point: &str, args: Vec<Value>) -> Result<String> {
+    let prepared = api.prepare(&key_hex(signer.public_key()), entry_point, json!(args))?;
+    // Backends from before transactions have no `kind`
+    let kind = prepared["kind"].as_str().unwrap_or("deploy");
+    let mut deploy = prepared[kind].clone();
+    sign::sign_deploy(&mut deploy, signer)?;
+    api.submit(kind, &deploy)
+}
+
+/// `left` as days and hh:mm:ss, e.g. `1d 02:03:04`
+pub fn duration(left: Span) -> String {
+    let s = left.0 / MS_PER_SECOND;
+    format!("{}d {:02}:{:02}:{:02}", s / 86_400, s / 3600 % 24, s / 60 % 60, s % 60)
+}
+
+/// Print the guardian's signature over a recovery for a relayer's `approve_signed`
+pub fn sign_approval(args: SignApproval, api: &Api, keystore: &Keystore) -> Result<()> {
+    let recovery = api.get(&format!("/recoveries/{}", args.id))?;
+    let id = U256::from_dec_str(&args.id).map_err(|_| anyhow!("not a decimal recovery id: {}", args.id))?;
+    let account = account_hash(recovery["account"].as_str().unwrap_or_default())?;
+    let new_key = recovery["newKey"].as_str().ok_or_else(|| anyhow!("recovery {} has no new key", args.id))?;
+    let new_key = PublicKey::from_hex(new_key).map_err(|e| anyhow!("bad new key: {e}"))?;
+
+    let signer = sign::unlock(keystore, &args.key)?;
+    let signature = signer.sign_raw(&payloads::approval_payload(id, account, &new_key, &args.memo))?;
+    println!("guardian:  {}", key_hex(signer.public_key()));
+    println!("signature: {}", hex::encode(signature));
+    Ok(())
+}
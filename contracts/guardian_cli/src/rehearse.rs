@@ -0,0 +1,131 @@
+//! `guardian-cli rehearse`: a full recovery practised on a local NCTL network, since it can't be on
+//! a live one. The account's real guardian count, threshold and settings are mirrored onto a fresh
+//! registry with throwaway keys, and the rehearsal starts, approves and finalizes a recovery the way
+//! the owner's guardians would.
+//!
+//! The local network is driven through `nctl`; the backend is only asked for the real account's
+//! setup. Timelocks are shortened to `--max-wait` so a rehearsal of a week-long delay doesn't take
+//! a week.
+
+use std::{thread, time::Duration};
+
+use anyhow::{anyhow, bail, Result};
+use casper_types::PublicKey;
+use clap::Args;
+use guardian_types::{
+    ids, prefixes,
+    settings::AccountSettings,
+    time::{Span, MS_PER_SECOND},
+};
+use serde_json::json;
+
+use crate::{
+    api::{account_hash, Api},
+    doctor::Setup,
+    nctl::{self, step},
+    recovery,
+};
+
+#[derive(Args)]
+pub struct Rehearse {
+    /// Account whose setup to mirror: public key or account hash
+    account: String,
+
+    #[command(flatten)]
+    network: nctl::Network,
+
+    /// Longest the rehearsal waits on a timelock, in seconds; the delay and veto window are cut to it
+    #[arg(long, default_value_t = 60)]
+    max_wait: u64,
+}
+
+pub fn run(args: Rehearse, api: &Api) -> Result<()> {
+    let owner = account_hash(&args.account)?;
+    let real = Setup::read(api, owner)?;
+    let (count, threshold) = (real.count(), real.threshold);
+    if count == 0 {
+        bail!("account-hash-{} has no guardians to mirror", hex::encode(owner.value()));
+    }
+    if threshold as usize > count {
+        bail!("threshold {threshold} exceeds the {count} guardians: a recovery of this setup can never finish");
+    }
+    let settings = shortened(real.settings.unwrap_or_default(), Span::seconds(args.max_wait));
+    if !settings.is_valid() {
+        bail!("the account's settings, shortened to --max-wait, aren't valid; try a larger --max-wait");
+    }
+
+    println!("mirroring account-hash-{}: {count} guardians, threshold {threshold}", hex::encode(owner.value()));
+    if !real.contract_guardians.is_empty() || real.private > 0 {
+        println!("  contract and private guardians are rehearsed as account guardians");
+    }
+    println!(
+        "  delay {}, veto window {}, expiry {}",
+        recovery::duration(Span(settings.delay)),
+        recovery::duration(Span(settings.veto_window)),
+        recovery::duration(Span(settings.expiry)),
+    );
+
+    let local = args.network.connect()?;
+    let node = &local.node;
+
+    step("generating test keys");
+    let owner_key = local.key("owner")?;
+    let guardians = (1..=count).map(|i| local.key(&format!("guardian-{i}"))).collect::<Result<Vec<_>>>()?;
+    let new_key = local.key("new-key")?;
+
+    step(&format!("funding the owner and {count} guardians from the faucet"));
+    local.fund(&std::iter::once(&owner_key).chain(&guardians).collect::<Vec<_>>())?;
+
+    step("installing the registry");
+    let registry = local.install()?;
+    println!("  registry {registry}");
+
+    step("setting up the owner's guardians and settings");
+    local.set_up(&registry, &owner_key, &guardians, threshold, &settings)?;
+
+    step(&format!("{} starts a recovery to the new key", guardians[0].name));
+    let new_public = PublicKey::from_hex(&new_key.public).map_err(|e| anyhow!("bad test key: {e}"))?;
+    let id = ids::recovery_id(owner_key.hash, 0, ids::key_commitment(&new_public));
+    node.call(&guardians[0], &registry, "start_recovery", json!([
+        nctl::account_arg(&owner_key),
+        { "name": "new_key", "type": "PublicKey", "value": new_key.public },
+    ]))?;
+    println!("  recovery {id}");
+
+    let id_arg = nctl::id_args(id);
+    for (i, g) in guardians.iter().take(threshold as usize).enumerate() {
+        step(&format!("{} approves ({}/{threshold})", g.name, i + 1));
+        node.call(g, &registry, "approve", id_arg.clone())?;
+    }
+
+    let wait = Span(settings.delay.max(settings.veto_window));
+    step(&format!("waiting out the timelock, {}", recovery::duration(wait)));
+    thread::sleep(Duration::from_millis(wait.0));
+    step("finalizing");
+    if let Some(e) = node.finalize(&owner_key, &registry, id)? {
+        bail!("finalize reverted with {} ({}): {}", e.name(), e as u16, e.description());
+    }
+
+    let finalized = node.dictionary_item(&registry, &format!("{}{id}", prefixes::FINALIZED))?;
+    if finalized != json!(true) {
+        bail!("finalize succeeded but the registry doesn't mark recovery {id} finalized");
+    }
+    println!("\nrehearsal complete: recovery {id} finalized with {threshold} of {count} guardians");
+    println!("test keys are in {}", local.dir.display());
+    Ok(())
+}
+
+/// `settings` with the delay and veto window cut to `max`, and the windows they have to fit in cut
+/// to ten times that. Only what a plain recovery touches is kept.
+fn shortened(settings: AccountSettings, max: Span) -> AccountSettings {
+    let short = |v: u64| v.min(max.0);
+    let long = |v: u64| if v == 0 { 0 } else { v.min(max.0 * 10).max(max.0 * 2 + MS_PER_SECOND) };
+    AccountSettings {
+        expiry: long(settings.expiry),
+        delay: short(settings.delay),
+        max_attempts: settings.max_attempts,
+        veto_window: short(settings.veto_window),
+        approval_window: long(settings.approval_window),
+        ..AccountSettings::default()
+    }
+}
@@ -0,0 +1,129 @@
+//! `guardian-cli restore`: a recovery kit brought back on a new machine. Writes a profile pointing
+//! at the kit's backend and registry, checks the kit against the chain, lists every guardian with
+//! where they stand and how to reach them, and refreshes the owner's heartbeat if one is kept.
+
+use std::{
+    collections::BTreeSet,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{bail, Result};
+use casper_types::{account::AccountHash, Key};
+use clap::Args;
+use guardian_signer::Signer;
+use guardian_types::{
+    prefixes,
+    time::{Instant, Span},
+};
+use serde_json::json;
+
+use crate::{
+    api::{account_hash, Api},
+    keystore::Keystore,
+    kit,
+    profile::{self, Profile},
+    prompt,
+    recovery::{self, submit_call},
+    sign,
+};
+
+#[derive(Args)]
+pub struct Restore {
+    /// Kit file written by `backup`
+    kit: PathBuf,
+
+    /// Name of the profile to write; `account-<first 8 hex digits of the account hash>` by default
+    #[arg(long)]
+    name: Option<String>,
+
+    /// Keystore key of the owner, to send a heartbeat with
+    #[arg(long)]
+    key: Option<String>,
+}
+
+/// Restore from the kit. `api_root` is the backend given with `--api` or `--profile`, if any;
+/// otherwise the kit's is used.
+pub fn run(args: Restore, api_root: Option<&str>, keystore: &Keystore) -> Result<()> {
+    let kit = kit::open(&args.kit)?;
+    let owner = account_hash(&kit.account)?;
+    let root = api_root.unwrap_or(&kit.network.api).to_string();
+    let api = Api::new(&root);
+    let now = Instant(SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64);
+    println!("recovery kit of account-hash-{}, made {} ago", kit.account, recovery::duration(now.since(Instant(kit.created_at))));
+
+    let name = args.name.unwrap_or_else(|| format!("account-{}", &kit.account[..8]));
+    if profile::exists(&name)? {
+        prompt::confirm(&format!("Replace profile {name}"))?;
+    }
+    let profile = Profile {
+        api: root.clone(),
+        chain_name: kit.network.chain_name.clone(),
+        registry_hash: kit.network.registry_hash.clone(),
+        account: Some(kit.account.clone()),
+    };
+    let path = profile::save(&name, &profile)?;
+    println!("profile {name} written to {}; select it with --profile {name}", path.display());
+
+    println!("\nregistry at {root}");
+    if kit::drift(&kit, &api)? {
+        println!("the account's setup changed since the kit was made; what follows is the chain's");
+    } else {
+        println!("the account's setup matches the kit");
+    }
+
+    let key = |prefix: &str| format!("{prefix}{owner:?}");
+    let current: Vec<AccountHash> = api.registry_item(&key(prefixes::GUARDIANS))?.unwrap_or_default();
+    let current: BTreeSet<String> = current.iter().map(|g| hex::encode(g.value())).collect();
+    let terms: Vec<(Key, u64)> = api.registry_item(&key(prefixes::GUARDIAN_TERMS))?.unwrap_or_default();
+    let suspended: Vec<Key> = api.registry_item(&key(prefixes::SUSPENDED))?.unwrap_or_default();
+    let kept: BTreeSet<String> = kit.guardians.iter().cloned().collect();
+
+    println!("\nguardians");
+    for g in kept.union(&current) {
+        let as_key = Key::Account(account_hash(g)?);
+        let status = if !current.contains(g) {
+            "no longer a guardian"
+        } else if !kept.contains(g) {
+            "added since the kit was made"
+        } else if suspended.contains(&as_key) {
+            "suspended"
+        } else if terms.iter().any(|(k, until)| *k == as_key && Instant(*until).reached(now)) {
+            "term expired"
+        } else {
+            "active"
+        };
+        println!("  account-hash-{g}  {status}");
+        for c in kit.contacts.iter().filter(|c| c.guardian == *g) {
+            println!("    contact: {}", c.note);
+        }
+    }
+    println!("  reach each active guardian and confirm they still hold their key");
+    if !kit.notes.trim().is_empty() {
+        println!("\nnotes\n{}", kit.notes.trim_end());
+    }
+
+    let period = Span(api.registry_item(&key(prefixes::HEARTBEAT_PERIOD))?.unwrap_or(0));
+    if period.is_zero() {
+        return Ok(());
+    }
+    let last = Instant(api.registry_item(&key(prefixes::HEARTBEAT))?.unwrap_or(0));
+    if last.has_elapsed(period, now) {
+        println!("\nheartbeat missed: the account counts as inactive until the owner checks in");
+    } else {
+        println!("\nheartbeat due in {}", recovery::duration(last.plus(period).remaining(now)));
+    }
+    match args.key {
+        Some(name) => {
+            let signer = sign::unlock(keystore, &name)?;
+            if signer.public_key().to_account_hash() != owner {
+                bail!("key {name} isn't account-hash-{}'s key, so it can't send its heartbeat", kit.account);
+            }
+            prompt::confirm("Send a heartbeat")?;
+            let call = vec![json!({ "name": "account", "type": "account", "value": kit.account })];
+            println!("heartbeat: {}", submit_call(&api, &signer, "heartbeat", call)?);
+        }
+        None => println!("refresh it by running restore again with --key <owner key>"),
+    }
+    Ok(())
+}
@@ -0,0 +1,33 @@
+//! Signing with keystore keys.
+
+use anyhow::{anyhow, Result};
+use casper_types::{bytesrepr::ToBytes, PublicKey};
+use guardian_signer::{PemSigner, Signer};
+use serde_json::{json, Value};
+
+use crate::{keystore::Keystore, prompt};
+
+/// Signer of keystore key `name`, asking for its password
+pub fn unlock(keystore: &Keystore, name: &str) -> Result<PemSigner> {
+    let password = prompt::secret(&format!("Password for {name}: "))?;
+    Ok(PemSigner::from(keystore.unlock(name, &password)?))
+}
+
+pub fn key_hex(key: &PublicKey) -> String {
+    hex::encode(key.to_bytes().unwrap_or_default())
+}
+
+/// Add `signer`'s approval to a deploy's or TransactionV1's JSON, unless it already has one
+pub fn sign_deploy(deploy: &mut Value, signer: &dyn Signer) -> Result<()> {
+    let deploy = if deploy.get("Version1").is_some() { &mut deploy["Version1"] } else { deploy };
+    let hash = deploy["hash"].as_str().and_then(|h| hex::decode(h).ok()).ok_or_else(|| anyhow!("deploy has no hash"))?;
+    let key = key_hex(signer.public_key());
+    let signature = signer.sign(&hash)?;
+    let approval = json!({ "signer": key, "signature": hex::encode(signature.to_bytes()?) });
+    match deploy["approvals"].as_array_mut() {
+        Some(approvals) if approvals.iter().any(|a| a["signer"] == key.as_str()) => {}
+        Some(approvals) => approvals.push(approval),
+        None => deploy["approvals"] = json!([approval]),
+    }
+    Ok(())
+}
@@ -0,0 +1,98 @@
+//! Built-in guardian policy templates: list them, and set an account up from one with
+//! `init_from_template`, naming the guardian for each of the template's slots.
+
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, bail, Result};
+use clap::{Args, Subcommand};
+use guardian_signer::Signer;
+use guardian_types::{templates, time::Span, validation};
+use serde_json::json;
+
+use crate::{
+    api::{account_hash, Api},
+    keystore::Keystore,
+    prompt,
+    recovery::{duration, submit_call},
+    sign,
+};
+
+#[derive(Subcommand)]
+pub enum TemplateCommand {
+    /// Built-in templates with their slots, threshold and timings
+    List,
+    /// Set an account up from a template with a deploy from a keystore key
+    Apply(Apply),
+}
+
+#[derive(Args)]
+pub struct Apply {
+    /// Template name, as `template list` shows it
+    template: String,
+
+    /// Keystore key of the owner, or of a provider holding the owner's setup consent; pays for the deploy
+    #[arg(long)]
+    key: String,
+
+    /// Account to set up; the key's own account if not given
+    #[arg(long)]
+    account: Option<String>,
+
+    /// Guardian for one of the template's slots, as `<slot>=<guardian>`; one per slot
+    #[arg(long = "guardian", value_name = "SLOT=GUARDIAN")]
+    guardians: Vec<String>,
+}
+
+pub fn run(cmd: TemplateCommand, api: &Api, keystore: &Keystore) -> Result<()> {
+    match cmd {
+        TemplateCommand::List => {
+            for t in templates::ALL {
+                println!("{}\n  {}", t.name, t.description);
+                println!("  {} of {}: {}", t.threshold, t.slots.len(), t.slots.join(", "));
+                let s = &t.settings;
+                println!("  delay {}, veto window {}, expiry {}", duration(Span(s.delay)), duration(Span(s.veto_window)), duration(Span(s.expiry)));
+            }
+            Ok(())
+        }
+        TemplateCommand::Apply(args) => apply(args, api, keystore),
+    }
+}
+
+fn apply(args: Apply, api: &Api, keystore: &Keystore) -> Result<()> {
+    let template = templates::find(&args.template).ok_or_else(|| anyhow!("no template called {}; see `template list`", args.template))?;
+    let mut named = BTreeMap::new();
+    for entry in &args.guardians {
+        let (slot, guardian) = entry.split_once('=').ok_or_else(|| anyhow!("expected SLOT=GUARDIAN, got {entry}"))?;
+        if !template.slots.contains(&slot) {
+            bail!("{} has no slot {slot}; its slots are {}", template.name, template.slots.join(", "));
+        }
+        if named.insert(slot, account_hash(guardian)?).is_some() {
+            bail!("slot {slot} given twice");
+        }
+    }
+    let guards = template.slots.iter()
+        .map(|slot| named.get(slot).copied().ok_or_else(|| anyhow!("no guardian for slot {slot}")))
+        .collect::<Result<Vec<_>>>()?;
+
+    let signer = sign::unlock(keystore, &args.key)?;
+    let account = match &args.account {
+        Some(a) => account_hash(a)?,
+        None => signer.public_key().to_account_hash(),
+    };
+    validation::check_guardian_set(account, &guards, template.threshold)
+        .map_err(|_| anyhow!("the guardians must be distinct and none of them the account itself"))?;
+
+    println!("account:  {}", hex::encode(account.value()));
+    for (slot, guard) in template.slots.iter().zip(&guards) {
+        println!("{slot}: {}", hex::encode(guard.value()));
+    }
+    prompt::confirm(&format!("Set the account up from {}, {} of {}", template.name, template.threshold, guards.len()))?;
+
+    let call = vec![
+        json!({ "name": "account", "type": "account", "value": hex::encode(account.value()) }),
+        json!({ "name": "template", "type": "string", "value": template.name }),
+        json!({ "name": "guardians", "type": "account_list", "value": guards.iter().map(|g| hex::encode(g.value())).collect::<Vec<_>>() }),
+    ];
+    println!("{}", submit_call(api, &signer, "init_from_template", call)?);
+    Ok(())
+}
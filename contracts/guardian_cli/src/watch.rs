@@ -0,0 +1,272 @@
+//! `guardian-cli watch`: one recovery of an account followed from the event stream, printed as a
+//! timeline as it happens: started, approvals arriving, threshold reached, the timelock counting
+//! down and executable. The exit status says how it ended, for scripts.
+
+use std::{
+    collections::BTreeSet,
+    io::{self, IsTerminal, Write},
+    sync::mpsc::{self, RecvTimeoutError, Sender},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Result;
+use clap::Args;
+use guardian_types::time::{Instant, MS_PER_SECOND};
+use serde_json::Value;
+
+use crate::{
+    api::{account_hash, Api},
+    recovery,
+};
+
+/// Exit status once the recovery is executable (or finalized, with `--until-finalized`)
+pub const EXECUTABLE: i32 = 0;
+/// Exit status of a recovery that was cancelled or vetoed
+pub const CANCELLED: i32 = 2;
+/// Exit status of a recovery that expired before it was approved
+pub const EXPIRED: i32 = 3;
+/// Exit status when `--timeout` passes first
+pub const TIMED_OUT: i32 = 4;
+
+/// How often the countdown is redrawn
+const TICK: Duration = Duration::from_secs(1);
+/// Re-read the recovery at least this often, since the indexer can lag the event that woke us
+const REFRESH: Duration = Duration::from_secs(10);
+/// Retry delay after the event stream drops
+const RECONNECT: Duration = Duration::from_secs(5);
+
+#[derive(Args)]
+pub struct Watch {
+    /// Account to follow: public key or account hash
+    account: String,
+
+    /// Recovery to follow; by default the account's open one, or the next it starts
+    #[arg(long)]
+    id: Option<String>,
+
+    /// Keep watching past executable until the recovery is finalized
+    #[arg(long)]
+    until_finalized: bool,
+
+    /// Give up after this many seconds
+    #[arg(long)]
+    timeout: Option<u64>,
+}
+
+/// What has been printed of one recovery
+struct Timeline {
+    id: String,
+    threshold: u64,
+    started: bool,
+    approvers: BTreeSet<String>,
+    reached: bool,
+    executable: bool,
+    by_height: bool,
+    /// When `finalize` stops reverting with TooEarly, on the recovery's clock
+    executable_at: Option<u64>,
+    /// Blocks left of a height-timed timelock, at the newest indexed block
+    blocks_left: Option<u64>,
+    /// A countdown is on the terminal's current line
+    drawn: bool,
+    /// The countdown was printed since the last step, when not on a terminal
+    shown: bool,
+}
+
+/// Follow the recovery and return the exit status its outcome maps to
+pub fn run(args: Watch, api: &Api) -> Result<i32> {
+    let account = hex::encode(account_hash(&args.account)?.value());
+    let threshold = api.get(&format!("/accounts/{account}/config"))?["threshold"].as_u64().unwrap_or(0);
+    let deadline = args.timeout.map(|s| std::time::Instant::now() + Duration::from_secs(s));
+
+    let (tx, rx) = mpsc::channel();
+    let (stream, followed) = (api.clone(), account.clone());
+    thread::spawn(move || follow(stream, followed, tx));
+
+    let id = match args.id {
+        Some(id) => Some(id),
+        None => api.get(&format!("/recoveries?account={account}&limit=1"))?[0]["id"].as_str().map(String::from),
+    };
+    if id.is_none() {
+        println!("waiting for a recovery of account-hash-{account}");
+    }
+    let mut timeline = id.map(|id| Timeline::new(id, threshold));
+    let mut refreshed = std::time::Instant::now();
+    let mut stale = true;
+
+    loop {
+        if let Some(t) = timeline.as_mut() {
+            if stale {
+                if let Some(status) = t.update(api, args.until_finalized)? {
+                    return Ok(status);
+                }
+                (stale, refreshed) = (false, std::time::Instant::now());
+            }
+            if t.tick() && !args.until_finalized {
+                return Ok(EXECUTABLE);
+            }
+        }
+        if deadline.is_some_and(|d| std::time::Instant::now() >= d) {
+            if let Some(t) = timeline.as_mut() {
+                t.clear();
+            }
+            println!("timed out");
+            return Ok(TIMED_OUT);
+        }
+
+        match rx.recv_timeout(TICK) {
+            Ok(event) => {
+                let name = event["name"].as_str().unwrap_or_default();
+                let id = event["id"].as_str();
+                match &timeline {
+                    None if name.starts_with("RecoveryStarted") => {
+                        timeline = id.map(|id| Timeline::new(id.to_string(), threshold));
+                        stale = true;
+                    }
+                    Some(t) if id == Some(t.id.as_str()) => stale = true,
+                    _ => {}
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => stale |= refreshed.elapsed() >= REFRESH,
+            Err(RecvTimeoutError::Disconnected) => anyhow::bail!("event stream stopped"),
+        }
+    }
+}
+
+/// Forward `account`'s registry events, reconnecting whenever the stream drops
+fn follow(api: Api, account: String, tx: Sender<Value>) {
+    loop {
+        if let Ok(events) = api.events(&[account.clone()], &[]) {
+            for event in events {
+                if tx.send(event).is_err() {
+                    return;
+                }
+            }
+        }
+        thread::sleep(RECONNECT);
+    }
+}
+
+impl Timeline {
+    fn new(id: String, threshold: u64) -> Self {
+        Timeline {
+            id,
+            threshold,
+            started: false,
+            approvers: BTreeSet::new(),
+            reached: false,
+            executable: false,
+            by_height: false,
+            executable_at: None,
+            blocks_left: None,
+            drawn: false,
+            shown: false,
+        }
+    }
+
+    /// Print a step on its own line
+    fn step(&mut self, text: String) {
+        self.clear();
+        self.shown = false;
+        println!("{text}");
+    }
+
+    /// Wipe the countdown line, if one is drawn
+    fn clear(&mut self) {
+        if self.drawn {
+            print!("\r\x1b[2K");
+            self.drawn = false;
+        }
+    }
+
+    /// Re-read the recovery and print the steps it took since the last read. Returns the exit
+    /// status once it has ended.
+    fn update(&mut self, api: &Api, until_finalized: bool) -> Result<Option<i32>> {
+        let r = api.get(&format!("/recoveries/{}", self.id))?;
+        self.by_height = r["byHeight"].as_bool().unwrap_or(false);
+
+        if !self.started {
+            self.started = true;
+            let new_key = r["newKey"].as_str().unwrap_or("-");
+            self.step(format!("recovery {} started at block {}, new key {new_key}", self.id, r["startedHeight"]));
+        }
+        for a in r["approvalHistory"].as_array().into_iter().flatten() {
+            let guardian = a["guardian"].as_str().unwrap_or_default().to_string();
+            if self.approvers.insert(guardian.clone()) {
+                let count = self.approvers.len();
+                self.step(format!("approved by account-hash-{guardian} at block {} ({count}/{})", a["height"], self.threshold));
+            }
+        }
+
+        let timelock = &r["timelock"];
+        if !self.reached && timelock["thresholdReached"] == true {
+            self.reached = true;
+            self.step("threshold reached".into());
+        }
+        self.executable_at = timelock["executableAt"].as_u64();
+        self.blocks_left = if self.by_height { timelock["remaining"].as_u64() } else { None };
+
+        match r["status"].as_str().unwrap_or_default() {
+            "finalized" => {
+                self.step("finalized".into());
+                return Ok(Some(EXECUTABLE));
+            }
+            "vetoed" | "cancelled" => {
+                self.step(format!("{} by the owner or arbiter", r["status"].as_str().unwrap_or_default()));
+                return Ok(Some(CANCELLED));
+            }
+            _ => {}
+        }
+        if !self.reached && r["expiresAt"].as_u64().unwrap_or(0) > 0 && r["timeRemaining"] == 0 {
+            self.step("expired".into());
+            return Ok(Some(EXPIRED));
+        }
+        if until_finalized && self.executable {
+            self.draw("waiting for finalize".into());
+        }
+        Ok(None)
+    }
+
+    /// Advance the timelock countdown; true once the recovery is executable
+    fn tick(&mut self) -> bool {
+        let Some(at) = self.executable_at else { return false };
+        if self.executable {
+            return true;
+        }
+        let left = if self.by_height {
+            match self.blocks_left {
+                Some(0) => None,
+                Some(blocks) => Some(format!("{blocks} blocks")),
+                None => return false,
+            }
+        } else {
+            let now = Instant(SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64);
+            let left = Instant(at).remaining(now);
+            (left.0 >= MS_PER_SECOND).then(|| recovery::duration(left))
+        };
+        match left {
+            Some(left) => {
+                self.draw(format!("timelock: {left} left"));
+                false
+            }
+            None => {
+                self.executable = true;
+                self.step("executable: finalize can be called".into());
+                true
+            }
+        }
+    }
+
+    /// Redraw the countdown line in place; on anything but a terminal, print it once per step
+    fn draw(&mut self, text: String) {
+        let mut out = io::stdout();
+        if out.is_terminal() {
+            print!("\r\x1b[2K{text}");
+            let _ = out.flush();
+            self.drawn = true;
+        } else if !self.shown {
+            println!("{text}");
+            self.shown = true;
+        }
+    }
+}
@@ -0,0 +1,177 @@
+//! FROST (RFC 9591, Ed25519-SHA512) ceremonies for guardian groups.
+//!
+//! Guardians run a distributed key generation once to get a group key (registered with
+//! `set_frost_key`) and each keep their own key package. To approve a recovery, a coordinator
+//! collects round-one commitments, builds a signing package over
+//! `payloads::frost_approval_payload`, collects round-two signature shares and aggregates them into
+//! one Ed25519 signature for `approve_frost`. No party ever holds the group's secret key.
+//!
+//! Packages cross between parties as hex of their FROST serialization, keyed by each guardian's
+//! participant index (1-based, fixed at key generation). Secret packages and nonces stay with their
+//! owner and must not be reused.
+
+use std::{collections::BTreeMap, fmt};
+
+use casper_types::{bytesrepr::ToBytes, PublicKey};
+use frost_ed25519 as frost;
+use frost::{
+    keys::{dkg, KeyPackage, PublicKeyPackage},
+    round1::{SigningCommitments, SigningNonces},
+    round2::SignatureShare,
+    Identifier, SigningPackage,
+};
+
+#[derive(Debug)]
+pub struct Error(String);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<frost::Error> for Error {
+    fn from(e: frost::Error) -> Self {
+        Error(e.to_string())
+    }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Hex-encoded packages by participant index
+pub type Packages = BTreeMap<u16, String>;
+
+fn identifier(index: u16) -> Result<Identifier> {
+    Identifier::try_from(index).map_err(|_| Error(format!("participant index must be 1 or more, got {index}")))
+}
+
+fn decode<T>(what: &str, value: &str, parse: impl FnOnce(&[u8]) -> std::result::Result<T, frost::Error>) -> Result<T> {
+    let bytes = hex::decode(value).map_err(|_| Error(format!("{what} is not hex")))?;
+    parse(&bytes).map_err(|e| Error(format!("{what}: {e}")))
+}
+
+fn decode_all<T>(what: &str, packages: &Packages, parse: fn(&[u8]) -> std::result::Result<T, frost::Error>) -> Result<BTreeMap<Identifier, T>> {
+    packages.iter().map(|(index, value)| Ok((identifier(*index)?, decode(what, value, parse)?))).collect()
+}
+
+// ============================================================================
+// Distributed key generation
+// ============================================================================
+
+/// A participant's first DKG round: `secret` stays local, `package` goes to every other participant
+pub struct DkgRound1 {
+    pub secret: String,
+    pub package: String,
+}
+
+/// Start key generation as participant `index` of `max_signers`, any `min_signers` of whom can sign
+pub fn dkg_part1(index: u16, max_signers: u16, min_signers: u16) -> Result<DkgRound1> {
+    let (secret, package) = dkg::part1(identifier(index)?, max_signers, min_signers, rand::thread_rng())?;
+    Ok(DkgRound1 { secret: hex::encode(secret.serialize()?), package: hex::encode(package.serialize()?) })
+}
+
+/// A participant's second DKG round: `secret` stays local, `packages[j]` goes privately to participant `j`
+pub struct DkgRound2 {
+    pub secret: String,
+    pub packages: Packages,
+}
+
+/// Continue key generation with every other participant's round-one package
+pub fn dkg_part2(secret: &str, round1: &Packages) -> Result<DkgRound2> {
+    let secret = decode("round-one secret", secret, dkg::round1::SecretPackage::deserialize)?;
+    let (secret, outgoing) = dkg::part2(secret, &decode_all("round-one package", round1, dkg::round1::Package::deserialize)?)?;
+
+    let mut packages = Packages::new();
+    for index in round1.keys() {
+        if let Some(package) = outgoing.get(&identifier(*index)?) {
+            packages.insert(*index, hex::encode(package.serialize()?));
+        }
+    }
+    Ok(DkgRound2 { secret: hex::encode(secret.serialize()?), packages })
+}
+
+/// Outcome of key generation for one participant
+pub struct GroupKeys {
+    /// This participant's signing share; keep it secret
+    pub key_package: String,
+    /// Group and per-participant verifying keys, the same for everyone
+    pub public_key_package: String,
+    /// Casper hex of the group's Ed25519 key, for `set_frost_key`
+    pub group_key: String,
+}
+
+/// Finish key generation with the round-one packages of every other participant and the round-two
+/// packages they sent this one
+pub fn dkg_part3(secret: &str, round1: &Packages, round2: &Packages) -> Result<GroupKeys> {
+    let secret = decode("round-two secret", secret, dkg::round2::SecretPackage::deserialize)?;
+    let (key_package, public_key_package) = dkg::part3(
+        &secret,
+        &decode_all("round-one package", round1, dkg::round1::Package::deserialize)?,
+        &decode_all("round-two package", round2, dkg::round2::Package::deserialize)?,
+    )?;
+    Ok(GroupKeys {
+        key_package: hex::encode(key_package.serialize()?),
+        group_key: group_key_hex(&public_key_package)?,
+        public_key_package: hex::encode(public_key_package.serialize()?),
+    })
+}
+
+fn group_key_hex(package: &PublicKeyPackage) -> Result<String> {
+    let bytes: [u8; 32] = package
+        .verifying_key()
+        .serialize()?
+        .try_into()
+        .map_err(|_| Error("group key is not 32 bytes".into()))?;
+    let key = PublicKey::ed25519_from_bytes(bytes).map_err(|e| Error(e.to_string()))?;
+    Ok(hex::encode(key.to_bytes().map_err(|e| Error(e.to_string()))?))
+}
+
+/// Group key of a public key package, as Casper hex
+pub fn group_key(public_key_package: &str) -> Result<String> {
+    group_key_hex(&decode("public key package", public_key_package, PublicKeyPackage::deserialize)?)
+}
+
+// ============================================================================
+// Signing
+// ============================================================================
+
+/// A signer's round-one output: `nonces` stay local for round two, `commitments` go to the coordinator
+pub struct Commitment {
+    pub nonces: String,
+    pub commitments: String,
+}
+
+/// Round one: fresh nonces for one signing session
+pub fn commit(key_package: &str) -> Result<Commitment> {
+    let key_package = decode("key package", key_package, KeyPackage::deserialize)?;
+    let (nonces, commitments) = frost::round1::commit(key_package.signing_share(), &mut rand::thread_rng());
+    Ok(Commitment { nonces: hex::encode(nonces.serialize()?), commitments: hex::encode(commitments.serialize()?) })
+}
+
+/// Coordinator: the package every chosen signer signs, from at least `min_signers` commitments
+pub fn signing_package(commitments: &Packages, message: &[u8]) -> Result<String> {
+    let commitments = decode_all("commitments", commitments, SigningCommitments::deserialize)?;
+    Ok(hex::encode(SigningPackage::new(commitments, message).serialize()?))
+}
+
+/// Round two: this signer's share of the signature over the signing package's message
+pub fn sign(signing_package: &str, nonces: &str, key_package: &str) -> Result<String> {
+    let share = frost::round2::sign(
+        &decode("signing package", signing_package, SigningPackage::deserialize)?,
+        &decode("nonces", nonces, SigningNonces::deserialize)?,
+        &decode("key package", key_package, KeyPackage::deserialize)?,
+    )?;
+    Ok(hex::encode(share.serialize()))
+}
+
+/// Coordinator: the 64-byte aggregate Ed25519 signature, checked against the group key
+pub fn aggregate(signing_package: &str, shares: &Packages, public_key_package: &str) -> Result<Vec<u8>> {
+    let signature = frost::aggregate(
+        &decode("signing package", signing_package, SigningPackage::deserialize)?,
+        &decode_all("signature share", shares, SignatureShare::deserialize)?,
+        &decode("public key package", public_key_package, PublicKeyPackage::deserialize)?,
+    )?;
+    Ok(signature.serialize()?)
+}
@@ -0,0 +1,5 @@
+// Generates the `guardian.v1` messages and service from the proto; needs `protoc` on the PATH
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    tonic_build::compile_protos("proto/guardian/v1/guardian.proto")?;
+    Ok(())
+}
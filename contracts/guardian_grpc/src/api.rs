@@ -0,0 +1,129 @@
+//! The backend's `/api/v1`, which the unary methods answer from, and conversions of its JSON into
+//! the `guardian.v1` messages.
+
+use casper_types::{account::AccountHash, PublicKey};
+use serde_json::{json, Value};
+use tonic::{Code, Status};
+
+use crate::pb;
+
+pub struct Api {
+    base: String,
+    client: reqwest::Client,
+}
+
+impl Api {
+    pub fn new(base: &str) -> Self {
+        Api { base: base.trim_end_matches('/').to_string(), client: reqwest::Client::new() }
+    }
+
+    /// `data` of the `{ success, data | error }` envelope; the backend's 400 and 404 keep their
+    /// meaning as gRPC statuses
+    async fn send(&self, path: &str, body: Option<Value>) -> Result<Value, Status> {
+        let url = format!("{}{}", self.base, path);
+        let request = match body {
+            Some(body) => self.client.post(&url).json(&body),
+            None => self.client.get(&url),
+        };
+        let response = request.send().await.map_err(|e| Status::unavailable(format!("{url}: {e}")))?;
+        let status = response.status().as_u16();
+        let envelope: Value = response.json().await.map_err(|e| Status::internal(format!("reading {url}: {e}")))?;
+        if envelope["success"] == true {
+            return Ok(envelope["data"].clone());
+        }
+        let code = match status {
+            400 => Code::InvalidArgument,
+            404 => Code::NotFound,
+            _ => Code::Internal,
+        };
+        Err(Status::new(code, envelope["error"].as_str().unwrap_or("request failed")))
+    }
+
+    pub async fn config(&self, account: &str) -> Result<pb::AccountConfig, Status> {
+        let data = self.send(&format!("/accounts/{account}/config"), None).await?;
+        Ok(pb::AccountConfig {
+            account: text(&data["account"]),
+            threshold: data["threshold"].as_u64().unwrap_or_default() as u32,
+            guardians: data["guardians"].as_array().into_iter().flatten().map(text).collect(),
+        })
+    }
+
+    pub async fn recovery(&self, id: &str) -> Result<pb::Recovery, Status> {
+        Ok(recovery(&self.send(&format!("/recoveries/{id}"), None).await?))
+    }
+
+    pub async fn open_recoveries(&self, account: Option<&str>, limit: u32, offset: u32) -> Result<Vec<pb::Recovery>, Status> {
+        let account = account.map(|a| format!("&account={a}")).unwrap_or_default();
+        let data = self.send(&format!("/recoveries?limit={limit}&offset={offset}{account}"), None).await?;
+        Ok(data.as_array().into_iter().flatten().map(recovery).collect())
+    }
+
+    /// Unsigned registry call, built by the backend from the JSON-described arguments: a deploy, or
+    /// a TransactionV1 when the node runs Casper 2.0
+    pub async fn prepare(&self, request: pb::PrepareDeployRequest) -> Result<pb::PreparedDeploy, Status> {
+        let args = request
+            .args
+            .iter()
+            .map(|a| {
+                let value: Value = serde_json::from_str(&a.json_value)
+                    .map_err(|e| Status::invalid_argument(format!("argument {}: {e}", a.name)))?;
+                Ok(json!({ "name": a.name, "type": a.r#type, "value": value }))
+            })
+            .collect::<Result<Vec<_>, Status>>()?;
+        let mut body = json!({ "sender": request.sender, "entryPoint": request.entry_point, "args": args });
+        if !request.payment_amount.is_empty() {
+            body["paymentAmount"] = json!(request.payment_amount);
+        }
+        let data = self.send("/deploys/prepare", Some(body)).await?;
+        let kind = text(&data["kind"]);
+        let call = if kind == "transaction" { &data["transaction"] } else { &data["deploy"] };
+        Ok(pb::PreparedDeploy {
+            deploy_hash: text(&data["deployHash"]),
+            deploy_json: call.to_string(),
+            deploy_bytes: hex::decode(text(&data["bytes"])).map_err(|e| Status::internal(format!("deploy bytes: {e}")))?,
+            kind,
+        })
+    }
+
+    /// Submit a signed deploy or TransactionV1 (`{"Version1": ...}`), returning its hash
+    pub async fn submit(&self, deploy_json: &str) -> Result<String, Status> {
+        let call: Value = serde_json::from_str(deploy_json).map_err(|e| Status::invalid_argument(format!("deploy_json: {e}")))?;
+        let body = if call.get("Version1").is_some() { json!({ "transaction": call }) } else { json!({ "deploy": call }) };
+        let data = self.send("/deploys", Some(body)).await?;
+        Ok(text(&data["deployHash"]))
+    }
+}
+
+/// Account hash hex of a public key, or of an account hash given as hex or `account-hash-…`
+pub fn account_hash(value: &str) -> Result<String, Status> {
+    let hash = match AccountHash::from_formatted_str(value) {
+        Ok(hash) => hash,
+        Err(_) => match hex::decode(value) {
+            Ok(bytes) if bytes.len() == 32 => AccountHash::try_from(bytes.as_slice()).expect("32 bytes"),
+            _ => PublicKey::from_hex(value)
+                .map_err(|_| Status::invalid_argument(format!("not a public key or account hash: {value}")))?
+                .to_account_hash(),
+        },
+    };
+    Ok(hex::encode(hash.value()))
+}
+
+fn text(value: &Value) -> String {
+    value.as_str().unwrap_or_default().to_string()
+}
+
+fn recovery(data: &Value) -> pb::Recovery {
+    let number = |field: &str| data[field].as_u64().unwrap_or_default();
+    pb::Recovery {
+        id: text(&data["id"]),
+        account: text(&data["account"]),
+        new_key: text(&data["newKey"]),
+        status: text(&data["status"]),
+        approvals: number("approvals") as u32,
+        started_height: number("startedHeight"),
+        started_deploy: text(&data["startedDeploy"]),
+        expires_at: number("expiresAt"),
+        by_height: data["byHeight"].as_bool().unwrap_or_default(),
+        time_remaining: data["timeRemaining"].as_u64(),
+    }
+}
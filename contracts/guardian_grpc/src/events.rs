@@ -0,0 +1,325 @@
+//! Registry events as `RecoveryEvent` messages. An `__events` entry is read field by field with
+//! its schema from `guardian_types::events`, and each field lands in the message field
+//! `EVENT_FIELDS` places it in.
+
+use std::collections::BTreeMap;
+
+use casper_types::{
+    bytesrepr::{self, Bytes, FromBytes, ToBytes},
+    CLType, Key, PublicKey, U512,
+};
+use guardian_types::events;
+
+use crate::pb;
+
+/// `RecoveryEvent` fields set from the event's place in the chain rather than its own fields
+const ENVELOPE: &[&str] = &["name", "deploy_hash", "index"];
+
+/// Where each event field lands in `RecoveryEvent`, or `None` for those it leaves to the REST and
+/// SSE events. A new event field has to be placed here before the tests pass.
+const EVENT_FIELDS: &[(&str, Option<&str>)] = &[
+    ("id", Some("id")),
+    ("account", Some("account")),
+    ("guardian", Some("guardian")),
+    // A key rotation's old and new guardian
+    ("old", Some("guardian")),
+    ("new", Some("new_guardian")),
+    // The FROST group key stands in for the approving guardian
+    ("group_key", Some("guardian")),
+    ("new_key", Some("new_key")),
+    ("approvals", Some("approvals")),
+    // How many of a signed approval batch were counted
+    ("counted", Some("approvals")),
+    ("threshold", Some("threshold")),
+    ("vetoed", Some("vetoed")),
+    ("notify", Some("notify")),
+    ("tenant", Some("tenant")),
+    ("effective_at", None),
+    ("deadline", None),
+    ("outcomes", None),
+    ("reason", None),
+    ("flags", None),
+    ("counter", None),
+    ("count", None),
+    ("limit", None),
+    ("entry_point", None),
+    ("code", None),
+    ("arg", None),
+    ("item", None),
+    ("replacement", None),
+    ("caller", None),
+    ("fee", None),
+    ("contact", None),
+    ("policies", None),
+    ("fees", None),
+    ("operation", None),
+    ("payer", None),
+    ("amount", None),
+    ("destination", None),
+    ("admin", None),
+    ("member", None),
+    ("roles", None),
+    ("template", None),
+];
+
+/// An event field as it travels in the proto: hashes, keys, public keys and large integers are
+/// strings, as everywhere else in the backend
+#[derive(Debug, PartialEq)]
+enum Value {
+    Bool(bool),
+    Uint32(u32),
+    Uint64(u64),
+    Text(String),
+    Texts(Vec<String>),
+}
+
+/// Reads `__events` entries by the registry's event schemas
+pub struct Decoder {
+    schemas: BTreeMap<String, Vec<(String, CLType)>>,
+}
+
+impl Decoder {
+    pub fn new() -> Self {
+        Decoder { schemas: events::schemas().0 }
+    }
+
+    /// The event `bytes` (`event_<Name>` and its fields) hold, or `None` if it isn't one of the
+    /// registry's
+    pub fn decode(&self, bytes: &[u8], deploy_hash: &str, index: u64) -> Result<Option<pb::RecoveryEvent>, bytesrepr::Error> {
+        let (name, mut rest) = String::from_bytes(bytes)?;
+        let Some(schema) = name.strip_prefix("event_").and_then(|n| self.schemas.get(n)) else { return Ok(None) };
+        let mut event = pb::RecoveryEvent { deploy_hash: deploy_hash.into(), index, ..Default::default() };
+        for (field, cl_type) in schema {
+            let (value, remainder) = read(cl_type, rest)?;
+            rest = remainder;
+            if let Some((_, Some(target))) = EVENT_FIELDS.iter().find(|(f, _)| *f == field.as_str()) {
+                set(&mut event, target, value)?;
+            }
+        }
+        if !rest.is_empty() {
+            return Err(bytesrepr::Error::LeftOverBytes);
+        }
+        event.name = name["event_".len()..].into();
+        Ok(Some(event))
+    }
+}
+
+/// A value of `cl_type` at the front of `bytes`, and the bytes after it
+fn read<'a>(cl_type: &CLType, bytes: &'a [u8]) -> Result<(Value, &'a [u8]), bytesrepr::Error> {
+    Ok(match cl_type {
+        CLType::Bool => {
+            let (value, rest) = bool::from_bytes(bytes)?;
+            (Value::Bool(value), rest)
+        }
+        CLType::U8 => {
+            let (value, rest) = u8::from_bytes(bytes)?;
+            (Value::Uint32(value.into()), rest)
+        }
+        CLType::U32 => {
+            let (value, rest) = u32::from_bytes(bytes)?;
+            (Value::Uint32(value), rest)
+        }
+        CLType::U64 => {
+            let (value, rest) = u64::from_bytes(bytes)?;
+            (Value::Uint64(value), rest)
+        }
+        // The three share their encoding: a length byte, then that many little-endian bytes
+        CLType::U128 | CLType::U256 | CLType::U512 => {
+            let (value, rest) = U512::from_bytes(bytes)?;
+            (Value::Text(value.to_string()), rest)
+        }
+        CLType::String => {
+            let (value, rest) = String::from_bytes(bytes)?;
+            (Value::Text(value), rest)
+        }
+        CLType::Key => {
+            let (key, rest) = Key::from_bytes(bytes)?;
+            // Without the tag, as the backend writes keys
+            (Value::Text(hex::encode(&key.to_bytes()?[1..])), rest)
+        }
+        CLType::PublicKey => {
+            let (key, rest) = PublicKey::from_bytes(bytes)?;
+            (Value::Text(hex::encode(key.to_bytes()?)), rest)
+        }
+        CLType::ByteArray(len) => {
+            let len = *len as usize;
+            let raw = bytes.get(..len).ok_or(bytesrepr::Error::EarlyEndOfStream)?;
+            (Value::Text(hex::encode(raw)), &bytes[len..])
+        }
+        CLType::List(item) if **item == CLType::U8 => {
+            let (value, rest) = Bytes::from_bytes(bytes)?;
+            (Value::Text(hex::encode(value.as_slice())), rest)
+        }
+        CLType::List(item) => {
+            let (count, mut rest) = u32::from_bytes(bytes)?;
+            let mut items = Vec::new();
+            for _ in 0..count {
+                let (Value::Text(value), remainder) = read(item, rest)? else { return Err(bytesrepr::Error::Formatting) };
+                items.push(value);
+                rest = remainder;
+            }
+            (Value::Texts(items), rest)
+        }
+        _ => return Err(bytesrepr::Error::Formatting),
+    })
+}
+
+/// Put `value` in `event`'s `target` field
+fn set(event: &mut pb::RecoveryEvent, target: &str, value: Value) -> Result<(), bytesrepr::Error> {
+    match (target, value) {
+        ("id", Value::Text(v)) => event.id = v,
+        ("account", Value::Text(v)) => event.account = v,
+        ("guardian", Value::Text(v)) => event.guardian = v,
+        ("new_guardian", Value::Text(v)) => event.new_guardian = v,
+        ("new_key", Value::Text(v)) => event.new_key = v,
+        ("approvals", Value::Uint32(v)) => event.approvals = v,
+        ("threshold", Value::Uint32(v)) => event.threshold = v,
+        ("vetoed", Value::Bool(v)) => event.vetoed = v,
+        ("notify", Value::Texts(v)) => event.notify = v,
+        ("tenant", Value::Uint32(v)) => event.tenant = v,
+        _ => return Err(bytesrepr::Error::Formatting),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{account::AccountHash, U256};
+    use guardian_types::events::{Event, RecoveryApprovedV2};
+
+    use super::*;
+
+    const PROTO: &str = include_str!("../proto/guardian/v1/guardian.proto");
+
+    /// Fields of each top-level message in `proto` as (repeated, type, name). Reads the plain
+    /// `[repeated|optional] type name = n;` lines guardian.proto is written in.
+    fn messages(proto: &str) -> BTreeMap<String, Vec<(bool, String, String)>> {
+        let mut out = BTreeMap::new();
+        let mut current: Option<(String, Vec<_>)> = None;
+        for line in proto.lines() {
+            let line = line.split("//").next().unwrap_or_default().trim();
+            if let Some(name) = line.strip_prefix("message ").and_then(|l| l.strip_suffix('{')) {
+                current = Some((name.trim().to_string(), Vec::new()));
+            } else if line == "}" {
+                if let Some((name, fields)) = current.take() {
+                    out.insert(name, fields);
+                }
+            } else if let Some((_, fields)) = current.as_mut() {
+                let words: Vec<&str> = line.split_whitespace().collect();
+                let (repeated, words) = match words.as_slice() {
+                    ["repeated", rest @ ..] => (true, rest),
+                    ["optional", rest @ ..] => (false, rest),
+                    rest => (false, rest),
+                };
+                if let [ty, name, "=", ..] = words {
+                    fields.push((repeated, ty.to_string(), name.to_string()));
+                }
+            }
+        }
+        out
+    }
+
+    /// Proto type `read` turns a value of `cl_type` into, as (repeated, type)
+    fn proto_type(cl_type: &CLType) -> Option<(bool, &'static str)> {
+        Some(match cl_type {
+            CLType::Bool => (false, "bool"),
+            CLType::U8 | CLType::U32 => (false, "uint32"),
+            CLType::U64 => (false, "uint64"),
+            CLType::List(item) if **item == CLType::U8 => (false, "string"),
+            CLType::List(item) => match proto_type(item)? {
+                (false, "string") => (true, "string"),
+                _ => return None,
+            },
+            CLType::U128 | CLType::U256 | CLType::U512 | CLType::String | CLType::Key | CLType::PublicKey
+            | CLType::ByteArray(_) => (false, "string"),
+            _ => return None,
+        })
+    }
+
+    /// Every way `proto` has drifted from the registry's events; empty if it matches. Every event
+    /// field is placed and lands in a `RecoveryEvent` field of its type, every `RecoveryEvent`
+    /// field other than the envelope is fed by some event, and fields named like event fields in
+    /// the messages that mirror registry records carry that field's type.
+    fn drift(proto: &str) -> Vec<String> {
+        let mut problems = Vec::new();
+        let messages = messages(proto);
+        let event_fields = &messages["RecoveryEvent"];
+        let mut fed = Vec::new();
+        for (event, schema) in events::schemas().0 {
+            for (field, cl_type) in schema {
+                let expected = proto_type(&cl_type);
+                match (EVENT_FIELDS.iter().find(|(f, _)| *f == field), expected) {
+                    (None, _) => problems.push(format!("{event}.{field}: not placed in EVENT_FIELDS")),
+                    (Some((_, None)), _) => {}
+                    (Some(_), None) => problems.push(format!("{event}.{field}: {cl_type:?} has no proto form")),
+                    (Some((_, Some(target))), Some((repeated, ty))) => {
+                        fed.push(*target);
+                        match event_fields.iter().find(|(_, _, name)| name.as_str() == *target) {
+                            None => problems.push(format!("RecoveryEvent.{target}: missing, {event}.{field} lands in it")),
+                            Some(f) if (f.0, f.1.as_str()) != (repeated, ty) => {
+                                problems.push(format!("RecoveryEvent.{target}: is {}, {event}.{field} needs {ty}", f.1))
+                            }
+                            Some(_) => {}
+                        }
+                    }
+                }
+                let Some((repeated, ty)) = expected else { continue };
+                for message in ["AccountConfig", "Recovery"] {
+                    let same = messages[message].iter().find(|(_, _, name)| *name == field);
+                    if let Some(f) = same.filter(|f| (f.0, f.1.as_str()) != (repeated, ty)) {
+                        problems.push(format!("{message}.{field}: is {}, {event}.{field} needs {ty}", f.1));
+                    }
+                }
+            }
+        }
+        for (_, _, name) in event_fields {
+            if !ENVELOPE.contains(&name.as_str()) && !fed.contains(&name.as_str()) {
+                problems.push(format!("RecoveryEvent.{name}: no event field lands in it"));
+            }
+        }
+        problems.sort();
+        problems.dedup();
+        problems
+    }
+
+    #[test]
+    fn proto_matches_the_events() {
+        assert_eq!(drift(PROTO), Vec::<String>::new());
+    }
+
+    #[test]
+    fn drift_is_reported() {
+        let dropped = PROTO.replace("  repeated string notify = 12;\n", "");
+        assert!(drift(&dropped).iter().any(|p| p.starts_with("RecoveryEvent.notify: missing")));
+
+        let retyped = PROTO.replace("uint32 threshold = 2;", "string threshold = 2;");
+        assert!(drift(&retyped).iter().any(|p| p.starts_with("AccountConfig.threshold: is string")));
+
+        let stale = PROTO.replace("  repeated string notify = 12;\n", "  repeated string notify = 12;\n  string payload = 14;\n");
+        assert!(drift(&stale).contains(&"RecoveryEvent.payload: no event field lands in it".to_string()));
+    }
+
+    #[test]
+    fn decodes_an_event() {
+        let approved = RecoveryApprovedV2 {
+            id: U256::from(7),
+            account: AccountHash::new([1; 32]),
+            guardian: Key::Account(AccountHash::new([2; 32])),
+            approvals: 2,
+            notify: vec![[3; 32]],
+        };
+        let bytes = approved.event_bytes().unwrap();
+        let event = Decoder::new().decode(&bytes, "ab", 4).unwrap().unwrap();
+        assert_eq!(event.name, "RecoveryApprovedV2");
+        assert_eq!((event.deploy_hash.as_str(), event.index), ("ab", 4));
+        assert_eq!(event.id, "7");
+        assert_eq!(event.account, "01".repeat(32));
+        assert_eq!(event.guardian, "02".repeat(32));
+        assert_eq!(event.approvals, 2);
+        assert_eq!(event.notify, vec!["03".repeat(32)]);
+
+        assert_eq!(Decoder::new().decode(&bytes[..bytes.len() - 1], "ab", 4), Err(bytesrepr::Error::EarlyEndOfStream));
+        let unknown = "event_SomethingElseV1".to_bytes().unwrap();
+        assert_eq!(Decoder::new().decode(&unknown, "ab", 4), Ok(None));
+    }
+}
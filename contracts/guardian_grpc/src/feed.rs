@@ -0,0 +1,191 @@
+//! Registry events decoded live from the node's SSE stream, for `SubscribeRecoveryEvents`.
+//!
+//! The registry appends each event to its `__events` dictionary. A dictionary write shows up in
+//! the execution effects as a `CLType::Any` value holding the serialized dictionary value: the
+//! stored `CLValue`, the dictionary's seed URef address and the item key (the event's index).
+
+use std::time::Duration;
+
+use anyhow::{anyhow, Context, Result};
+use casper_types::{
+    bytesrepr::{Bytes, FromBytes},
+    CLValue, URef,
+};
+use futures_util::StreamExt;
+use serde_json::{json, Value};
+use tokio::sync::broadcast;
+
+use crate::{events::Decoder, pb};
+
+pub struct Feed {
+    client: reqwest::Client,
+    node: String,
+    events_url: String,
+    registry: String,
+    events_uref: Option<URef>,
+    decoder: Decoder,
+}
+
+impl Feed {
+    pub fn new(node: &str, events_url: &str, registry: &str, events_uref: Option<&str>) -> Result<Self> {
+        let events_uref = events_uref
+            .map(|u| URef::from_formatted_str(u).map_err(|e| anyhow!("bad events URef {u}: {e:?}")))
+            .transpose()?;
+        Ok(Feed {
+            client: reqwest::Client::new(),
+            node: node.into(),
+            events_url: events_url.into(),
+            registry: registry.trim_start_matches("hash-").into(),
+            events_uref,
+            decoder: Decoder::new(),
+        })
+    }
+
+    /// Follow the stream for good, sending every registry event on; reconnects with backoff
+    /// whenever the stream drops
+    pub async fn run(self, sender: broadcast::Sender<pb::RecoveryEvent>) -> Result<()> {
+        let seed = match self.events_uref {
+            Some(uref) => uref.addr(),
+            None => self.lookup_events_uref().await?.addr(),
+        };
+        let mut backoff = Duration::from_secs(1);
+        loop {
+            if let Err(e) = self.follow(&seed, &sender, &mut backoff).await {
+                eprintln!("event stream dropped, reconnecting: {e:#}");
+            }
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(Duration::from_secs(60));
+        }
+    }
+
+    /// The registry's `__events` named key, read from the contract on the node
+    async fn lookup_events_uref(&self) -> Result<URef> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "query_global_state",
+            "params": { "state_identifier": null, "key": format!("hash-{}", self.registry), "path": [] },
+        });
+        let response: Value = self.client.post(&self.node).json(&request).send().await?.json().await?;
+        let named_keys = response["result"]["stored_value"]["Contract"]["named_keys"]
+            .as_array()
+            .with_context(|| format!("hash-{} is not a contract: {}", self.registry, response["error"]))?;
+        let key = named_keys
+            .iter()
+            .find(|k| k["name"] == "__events")
+            .and_then(|k| k["key"].as_str())
+            .context("the registry has no __events named key")?;
+        URef::from_formatted_str(key).map_err(|e| anyhow!("bad __events URef {key}: {e:?}"))
+    }
+
+    async fn follow(&self, seed: &[u8; 32], sender: &broadcast::Sender<pb::RecoveryEvent>, backoff: &mut Duration) -> Result<()> {
+        let response = self.client.get(&self.events_url).send().await?.error_for_status()?;
+        let mut stream = response.bytes_stream();
+        *backoff = Duration::from_secs(1);
+
+        let mut buffer = String::new();
+        let mut data = String::new();
+        while let Some(chunk) = stream.next().await {
+            buffer.push_str(&String::from_utf8_lossy(&chunk?));
+            while let Some(newline) = buffer.find('\n') {
+                let line: String = buffer.drain(..=newline).collect();
+                let line = line.trim_end_matches(['\n', '\r']);
+                if line.is_empty() {
+                    if !data.is_empty() {
+                        self.message(&data, seed, sender);
+                    }
+                    data.clear();
+                } else if let Some(part) = line.strip_prefix("data:") {
+                    if !data.is_empty() {
+                        data.push('\n');
+                    }
+                    data.push_str(part.trim_start());
+                }
+            }
+        }
+        Err(anyhow!("event stream closed"))
+    }
+
+    /// Send on the registry events of one SSE message, in emission order
+    fn message(&self, data: &str, seed: &[u8; 32], sender: &broadcast::Sender<pb::RecoveryEvent>) {
+        let Ok(message) = serde_json::from_str::<Value>(data) else { return };
+        let (hash, result) = if let Some(deploy) = message.get("DeployProcessed") {
+            (deploy["deploy_hash"].as_str(), &deploy["execution_result"])
+        } else if let Some(transaction) = message.get("TransactionProcessed") {
+            let hash = &transaction["transaction_hash"];
+            (hash["Deploy"].as_str().or(hash["Version1"].as_str()), &transaction["execution_result"])
+        } else {
+            return;
+        };
+        for (index, bytes) in dictionary_writes(result, seed) {
+            // Entries that aren't registry events, or don't decode, are skipped
+            if let Ok(Some(event)) = self.decoder.decode(&bytes, hash.unwrap_or_default(), index) {
+                // Nobody subscribed is fine
+                let _ = sender.send(event);
+            }
+        }
+    }
+}
+
+/// (item index, stored bytes) of every item an execution result wrote to the dictionary seeded
+/// by `seed`, in index order. Reads the 1.x (`Success.effect.transforms`) and 2.x
+/// (`Version2.effects`) layouts.
+fn dictionary_writes(result: &Value, seed: &[u8; 32]) -> Vec<(u64, Vec<u8>)> {
+    let transforms = [&result["Success"], &result["Version1"]["Success"]]
+        .into_iter()
+        .flat_map(|success| success["effect"]["transforms"].as_array().into_iter().flatten())
+        .map(|t| &t["transform"]["WriteCLValue"]["bytes"]);
+    let effects = result["Version2"]["effects"].as_array().into_iter().flatten().map(|e| &e["kind"]["Write"]["CLValue"]["bytes"]);
+
+    let mut out: Vec<(u64, Vec<u8>)> = transforms
+        .chain(effects)
+        .filter_map(|bytes| hex::decode(bytes.as_str()?).ok())
+        .filter_map(|raw| {
+            let (value, rest) = CLValue::from_bytes(&raw).ok()?;
+            let (seed_addr, rest) = Bytes::from_bytes(rest).ok()?;
+            let (item_key, rest) = Bytes::from_bytes(rest).ok()?;
+            if !rest.is_empty() || seed_addr.as_slice() != seed {
+                return None;
+            }
+            let index = std::str::from_utf8(&item_key).ok()?.parse().ok()?;
+            // Events are stored as `Bytes`
+            let (event, _) = Bytes::from_bytes(value.inner_bytes()).ok()?;
+            Some((index, event.to_vec()))
+        })
+        .collect();
+    out.sort_by_key(|(index, _)| *index);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::bytesrepr::ToBytes;
+
+    use super::*;
+
+    /// The `bytes` a node reports for a write of `event` at `index` to the dictionary seeded by `seed`
+    fn write(seed: &[u8; 32], index: u64, event: &[u8]) -> String {
+        let stored = CLValue::from_t(Bytes::from(event.to_vec())).unwrap();
+        let mut raw = stored.to_bytes().unwrap();
+        raw.extend(Bytes::from(seed.to_vec()).to_bytes().unwrap());
+        raw.extend(Bytes::from(index.to_string().into_bytes()).to_bytes().unwrap());
+        hex::encode(raw)
+    }
+
+    #[test]
+    fn reads_writes_to_the_events_dictionary_only() {
+        let seed = [7; 32];
+        let result = json!({
+            "Success": { "effect": { "transforms": [
+                { "transform": { "WriteCLValue": { "bytes": write(&seed, 5, b"second") } } },
+                { "transform": { "WriteCLValue": { "bytes": write(&[8; 32], 1, b"elsewhere") } } },
+                { "transform": { "WriteCLValue": { "bytes": "00" } } },
+                { "transform": { "WriteCLValue": { "bytes": write(&seed, 4, b"first") } } },
+            ] } }
+        });
+        assert_eq!(dictionary_writes(&result, &seed), vec![(4, b"first".to_vec()), (5, b"second".to_vec())]);
+
+        let v2 = json!({ "Version2": { "effects": [{ "kind": { "Write": { "CLValue": { "bytes": write(&seed, 9, b"x") } } } }] } });
+        assert_eq!(dictionary_writes(&v2, &seed), vec![(9, b"x".to_vec())]);
+    }
+}
@@ -0,0 +1,132 @@
+//! `guardian-grpc`: the registry's gRPC service for exchanges and custodians
+//! (`proto/guardian/v1/guardian.proto`).
+//!
+//! Unary config, status and deploy methods go through the backend's `/api/v1`, so they answer
+//! from the same indexer and deploy builder as the REST API. `SubscribeRecoveryEvents` streams
+//! registry events decoded live from the node's SSE stream with `guardian_types::events`.
+
+mod api;
+mod events;
+mod feed;
+
+use std::pin::Pin;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use futures_util::{Stream, StreamExt};
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::BroadcastStream;
+use tonic::{transport::Server, Request, Response, Status};
+
+use crate::{api::Api, feed::Feed};
+
+pub mod pb {
+    tonic::include_proto!("guardian.v1");
+}
+
+use pb::guardian_recovery_server::{GuardianRecovery, GuardianRecoveryServer};
+
+#[derive(Parser)]
+#[command(name = "guardian-grpc", version, about = "SentinelX gRPC service")]
+struct Config {
+    /// Backend API root
+    #[arg(long, env = "GRPC_API", default_value = "http://localhost:3001/api/v1")]
+    api: String,
+
+    /// Address the gRPC service listens on
+    #[arg(long, env = "GRPC_LISTEN", default_value = "0.0.0.0:50051")]
+    listen: String,
+
+    /// Node JSON-RPC endpoint, to look up the registry's `__events` dictionary
+    #[arg(long, env = "GRPC_NODE", default_value = "http://localhost:7777/rpc")]
+    node: String,
+
+    /// Node SSE event stream
+    #[arg(long, env = "GRPC_EVENTS_URL", default_value = "http://localhost:9999/events/main")]
+    events_url: String,
+
+    /// Registry contract hash (hex, with or without `hash-`)
+    #[arg(long, env = "RECOVERY_REGISTRY_HASH")]
+    registry: String,
+
+    /// The registry's `__events` dictionary URef (`uref-…-007`); looked up on the node if unset
+    #[arg(long, env = "GRPC_EVENTS_UREF")]
+    events_uref: Option<String>,
+}
+
+struct Service {
+    api: Api,
+    events: broadcast::Sender<pb::RecoveryEvent>,
+}
+
+type EventStream = Pin<Box<dyn Stream<Item = Result<pb::RecoveryEvent, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl GuardianRecovery for Service {
+    async fn get_config(&self, request: Request<pb::GetConfigRequest>) -> Result<Response<pb::AccountConfig>, Status> {
+        let account = api::account_hash(&request.into_inner().account)?;
+        Ok(Response::new(self.api.config(&account).await?))
+    }
+
+    async fn get_recovery(&self, request: Request<pb::GetRecoveryRequest>) -> Result<Response<pb::Recovery>, Status> {
+        Ok(Response::new(self.api.recovery(&request.into_inner().id).await?))
+    }
+
+    async fn list_open_recoveries(
+        &self,
+        request: Request<pb::ListOpenRecoveriesRequest>,
+    ) -> Result<Response<pb::ListRecoveriesResponse>, Status> {
+        let request = request.into_inner();
+        let account = match request.account.as_str() {
+            "" => None,
+            account => Some(api::account_hash(account)?),
+        };
+        let limit = if request.limit == 0 { 20 } else { request.limit.min(100) };
+        let recoveries = self.api.open_recoveries(account.as_deref(), limit, request.offset).await?;
+        Ok(Response::new(pb::ListRecoveriesResponse { recoveries }))
+    }
+
+    async fn prepare_deploy(&self, request: Request<pb::PrepareDeployRequest>) -> Result<Response<pb::PreparedDeploy>, Status> {
+        Ok(Response::new(self.api.prepare(request.into_inner()).await?))
+    }
+
+    async fn submit_deploy(&self, request: Request<pb::SubmitDeployRequest>) -> Result<Response<pb::SubmitDeployResponse>, Status> {
+        let deploy_hash = self.api.submit(&request.into_inner().deploy_json).await?;
+        Ok(Response::new(pb::SubmitDeployResponse { deploy_hash }))
+    }
+
+    type SubscribeRecoveryEventsStream = EventStream;
+
+    async fn subscribe_recovery_events(
+        &self,
+        request: Request<pb::SubscribeRecoveryEventsRequest>,
+    ) -> Result<Response<EventStream>, Status> {
+        let accounts = request.into_inner().accounts.iter().map(String::as_str).map(api::account_hash).collect::<Result<Vec<_>, _>>()?;
+        // A subscriber too slow for the feed skips what it missed rather than holding the rest up
+        let events = BroadcastStream::new(self.events.subscribe()).filter_map(move |event| {
+            let event = event.ok().filter(|e| accounts.is_empty() || accounts.contains(&e.account));
+            async move { event.map(Ok) }
+        });
+        Ok(Response::new(Box::pin(events)))
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let config = Config::parse();
+    let addr = config.listen.parse().with_context(|| format!("bad --listen {}", config.listen))?;
+    let (events, _) = broadcast::channel(1024);
+
+    let feed = Feed::new(&config.node, &config.events_url, &config.registry, config.events_uref.as_deref())?;
+    let sender = events.clone();
+    tokio::spawn(async move {
+        if let Err(e) = feed.run(sender).await {
+            eprintln!("event feed stopped: {e:#}");
+        }
+    });
+
+    println!("serving guardian.v1.GuardianRecovery on {addr}");
+    let service = Service { api: Api::new(&config.api), events };
+    Server::builder().add_service(GuardianRecoveryServer::new(service)).serve(addr).await?;
+    Ok(())
+}
@@ -0,0 +1,85 @@
+from typing import Any, Optional
+
+class ApiError(Exception): ...
+
+class RevertError(ApiError):
+    code: int
+    name: str
+    description: str
+    deploy_hash: Optional[str]
+    entry_point: Optional[str]
+    args: Any
+
+class NonceRefused(ValueError): ...
+
+META_DOMAIN: bytes
+
+class Client:
+    def __init__(self, base_url: str, timeout: float = 30.0) -> None: ...
+    def config(self, account: str) -> dict[str, Any]: ...
+    def guarded_accounts(self, guardian: str, limit: int = 20, offset: int = 0) -> list[str]: ...
+    def recovery(self, id: str) -> dict[str, Any]: ...
+    def open_recoveries(self, account: Optional[str] = None, limit: int = 20, offset: int = 0) -> list[dict[str, Any]]: ...
+    def meta_nonce(self, guardian: str) -> int: ...
+    def relayers(self, account: str) -> list[str]: ...
+    def prepare_deploy(
+        self, sender: str, entry_point: str, args: list[dict[str, Any]], payment_amount: Optional[str] = None
+    ) -> dict[str, Any]: ...
+    def submit_deploy(self, deploy: dict[str, Any]) -> str: ...
+    def deploy_status(self, deploy_hash: str) -> dict[str, Any]: ...
+    def wait_for_deploy(self, deploy_hash: str, timeout: float = 300.0, interval: float = 5.0, check: bool = True) -> dict[str, Any]: ...
+
+class Signer:
+    @staticmethod
+    def from_pem(pem: str) -> Signer: ...
+    @staticmethod
+    def from_file(path: str) -> Signer: ...
+    @staticmethod
+    def pkcs11(module_path: str, token_label: str, pin: str, key_label: str, algorithm: str = "secp256k1") -> Signer: ...
+    @staticmethod
+    def aws_kms(key_id: str, region: Optional[str] = None) -> Signer: ...
+    @property
+    def public_key(self) -> str: ...
+    @property
+    def account_hash(self) -> str: ...
+    def sign(self, message: bytes) -> bytes: ...
+    def sign_deploy(self, deploy: dict[str, Any]) -> dict[str, Any]: ...
+    def sign_approval(self, id: str, account: str, new_key: str, memo: str = "") -> bytes: ...
+    def sign_meta_approval(
+        self, id: str, account: str, new_key: str, ledger: MetaNonces, chain_nonce: int, nonce: Optional[int] = None, memo: str = ""
+    ) -> tuple[int, bytes]: ...
+    def sign_rotation(self, account: str, nonce: int, old_key: str, new_key: str) -> bytes: ...
+
+SecretKey = Signer
+
+class MetaNonces:
+    def __init__(self) -> None: ...
+    @staticmethod
+    def from_bytes(bytes: bytes) -> MetaNonces: ...
+    def to_bytes(self) -> bytes: ...
+    def next_nonce(self, chain_nonce: int) -> int: ...
+    def check(self, chain_nonce: int, nonce: int, payload: bytes) -> None: ...
+    def prune(self, chain_nonce: int) -> None: ...
+    def issued(self) -> list[tuple[int, bytes]]: ...
+
+def account_hash(self) -> str: ...
+    def sign(self, message: bytes) -> bytes: ...
+    def sign_deploy(self, deploy: dict[str, Any]) -> dict[str, Any]: ...
+    def sign_approval(self, id: str, account: str, new_key: str, memo: str = "") -> bytes: ...
+    def sign_rotation(self, account: str, nonce: int, old_key: str, new_key: str) -> bytes: ...
+
+def account_hash(account: str) -> str: ...
+def recovery_id(account: str, nonce: int, new_key: str) -> str: ...
+def meta_approval_payload(id: str, account: str, new_key: str, nonce: int, memo: str = "") -> bytes: ...
+def validate_guardian_set(owner: str, guardians: list[str], threshold: int) -> None: ...
+def registry_error(code: int) -> Optional[tuple[str, str]]: ...
+def templates() -> list[dict[str, Any]]: ...
+def template_args(name: str, account: str, guardians: list[str]) -> list[dict[str, Any]]: ...
+
+def frost_dkg_part1(index: int, max_signers: int, min_signers: int) -> tuple[str, str]: ...
+def frost_dkg_part2(secret: str, round1: dict[int, str]) -> tuple[str, dict[int, str]]: ...
+def frost_dkg_part3(secret: str, round1: dict[int, str], round2: dict[int, str]) -> tuple[str, str, str]: ...
+def frost_commit(key_package: str) -> tuple[str, str]: ...
+def frost_signing_package(commitments: dict[int, str], id: str, account: str, new_key: str) -> str: ...
+def frost_sign(signing_package: str, nonces: str, key_package: str) -> str: ...
+def frost_aggregate(signing_package: str, shares: dict[int, str], public_key_package: str) -> bytes: ...
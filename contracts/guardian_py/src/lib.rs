@@ -0,0 +1,597 @@
+//! Python bindings (`guardian_recovery`), built with `make py-pkg`.
+//!
+//! `Client` reads configuration and recovery status from the backend's `/api/v1`, has it build
+//! unsigned deploys, submits them and polls them to completion. `Signer` signs deploys and
+//! guardian payloads offline with a PEM file, a PKCS#11 HSM or AWS KMS, so keys never leave the
+//! machine or module holding them. `MetaNonces` keeps a guardian's meta-approval nonces straight.
+//! Keys are hex public keys; accounts are a public key, an `account-hash-…` or `entity-account-…`
+//! string or raw account hash hex.
+
+use std::time::{Duration, Instant};
+
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{FromBytes, ToBytes},
+    PublicKey, U256,
+};
+#[cfg(feature = "aws-kms")]
+use guardian_signer::KmsSigner;
+#[cfg(feature = "pkcs11")]
+use guardian_signer::{KeyAlgorithm, Pkcs11Signer};
+use guardian_frost::Packages;
+use guardian_signer::{PemSigner, SignerError};
+use guardian_types::{
+    host, ids,
+    meta::{NonceError, NonceLedger},
+    payloads, templates, validation, GuardianError, RegistryError,
+};
+use pyo3::{
+    create_exception,
+    exceptions::{PyException, PyRuntimeError, PyTimeoutError, PyValueError},
+    prelude::*,
+    types::PyBytes,
+};
+use pythonize::{depythonize, pythonize};
+use serde_json::{json, Value};
+
+create_exception!(guardian_recovery, ApiError, PyException, "The backend rejected a request or could not be reached");
+create_exception!(guardian_recovery, RevertError, ApiError, "A deploy reverted with a registry error");
+create_exception!(guardian_recovery, NonceRefused, PyValueError, "A meta-approval nonce is stale, already signed or skips ahead");
+
+/// `RevertError` for a failed deploy's status, or None if it didn't fail with a registry error.
+/// Carries `code`, `name` and `description` of the error and `deploy_hash`, `entry_point` and
+/// `args` of the call.
+fn revert_error(py: Python<'_>, status: &Value) -> PyResult<Option<PyErr>> {
+    let message = status["errorMessage"].as_str().unwrap_or_default();
+    let Some(error) = RegistryError::from_revert(message) else { return Ok(None) };
+    let revert = &status["revert"];
+    let entry_point = revert["entryPoint"].as_str();
+    let text = match entry_point {
+        Some(ep) => format!("{ep} failed: {} ({}) - {}", error.name(), error as u16, error.description()),
+        None => format!("{} ({}) - {}", error.name(), error as u16, error.description()),
+    };
+
+    let err = RevertError::new_err(text);
+    let value = err.value_bound(py);
+    value.setattr("code", error as u16)?;
+    value.setattr("name", error.name())?;
+    value.setattr("description", error.description())?;
+    value.setattr("deploy_hash", status["deployHash"].as_str())?;
+    value.setattr("entry_point", entry_point)?;
+    value.setattr("args", pythonize(py, &revert["args"])?)?;
+    Ok(Some(err))
+}
+
+fn public_key(value: &str) -> PyResult<PublicKey> {
+    let bytes = hex::decode(value).map_err(|_| PyValueError::new_err(format!("Not hex: {value}")))?;
+    PublicKey::from_bytes(&bytes)
+        .ok()
+        .filter(|(_, rest)| rest.is_empty())
+        .map(|(key, _)| key)
+        .ok_or_else(|| PyValueError::new_err(format!("Not a public key: {value}")))
+}
+
+fn account_hash(value: &str) -> PyResult<AccountHash> {
+    let bad = || PyValueError::new_err(format!("Not an account hash: {value}"));
+    if value.contains('-') {
+        return host::formatted_account(value).ok_or_else(bad);
+    }
+    if value.len() == 64 {
+        let bytes = hex::decode(value).map_err(|_| bad())?;
+        return AccountHash::try_from(bytes.as_slice()).map_err(|_| bad());
+    }
+    Ok(public_key(value)?.to_account_hash())
+}
+
+fn recovery_id_arg(value: &str) -> PyResult<U256> {
+    U256::from_dec_str(value).map_err(|_| PyValueError::new_err(format!("Not a decimal recovery id: {value}")))
+}
+
+fn key_hex(key: &PublicKey) -> String {
+    hex::encode(key.to_bytes().unwrap_or_default())
+}
+
+// ============================================================================
+// Backend client
+// ============================================================================
+
+/// Client of the backend's versioned REST API
+#[pyclass(module = "guardian_recovery")]
+struct Client {
+    base: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base, path)
+    }
+
+    /// `data` of the `{ success, data | error }` envelope
+    fn send(&self, py: Python<'_>, request: ureq::Request, body: Option<Value>) -> PyResult<Value> {
+        let response = py.allow_threads(|| match body {
+            Some(body) => request.send_json(body),
+            None => request.call(),
+        });
+        let envelope: Value = match response {
+            Ok(r) | Err(ureq::Error::Status(_, r)) => r.into_json().map_err(|e| ApiError::new_err(e.to_string()))?,
+            Err(e) => return Err(ApiError::new_err(e.to_string())),
+        };
+        if envelope["success"] == true {
+            Ok(envelope["data"].clone())
+        } else {
+            Err(ApiError::new_err(envelope["error"].as_str().unwrap_or("Request failed").to_string()))
+        }
+    }
+
+    fn get(&self, py: Python<'_>, path: &str, query: &[(&str, String)]) -> PyResult<Value> {
+        let mut request = self.agent.get(&self.url(path));
+        for (name, value) in query {
+            request = request.query(name, value);
+        }
+        self.send(py, request, None)
+    }
+}
+
+#[pymethods]
+impl Client {
+    /// `base_url` is the API root, e.g. `http://localhost:3001/api/v1`
+    #[new]
+    #[pyo3(signature = (base_url, timeout=30.0))]
+    fn new(base_url: &str, timeout: f64) -> Self {
+        let agent = ureq::AgentBuilder::new().timeout(Duration::from_secs_f64(timeout)).build();
+        Client { base: base_url.trim_end_matches('/').to_string(), agent }
+    }
+
+    /// Threshold, guardians and recent configuration changes of an account
+    fn config<'py>(&self, py: Python<'py>, account: &str) -> PyResult<Bound<'py, PyAny>> {
+        let data = self.get(py, &format!("/accounts/{account}/config"), &[])?;
+        Ok(pythonize(py, &data)?)
+    }
+
+    /// Account hashes a key currently guards
+    #[pyo3(signature = (guardian, limit=20, offset=0))]
+    fn guarded_accounts<'py>(&self, py: Python<'py>, guardian: &str, limit: u32, offset: u32) -> PyResult<Bound<'py, PyAny>> {
+        let query = [("limit", limit.to_string()), ("offset", offset.to_string())];
+        let data = self.get(py, &format!("/guardians/{guardian}/accounts"), &query)?;
+        Ok(pythonize(py, &data)?)
+    }
+
+    /// Status and approvals of a recovery
+    fn recovery<'py>(&self, py: Python<'py>, id: &str) -> PyResult<Bound<'py, PyAny>> {
+        let data = self.get(py, &format!("/recoveries/{id}"), &[])?;
+        Ok(pythonize(py, &data)?)
+    }
+
+    /// Pending recoveries, of one account or all
+    #[pyo3(signature = (account=None, limit=20, offset=0))]
+    fn open_recoveries<'py>(&self, py: Python<'py>, account: Option<&str>, limit: u32, offset: u32) -> PyResult<Bound<'py, PyAny>> {
+        let mut query = vec![("limit", limit.to_string()), ("offset", offset.to_string())];
+        if let Some(account) = account {
+            query.push(("account", account.to_string()));
+        }
+        let data = self.get(py, "/recoveries", &query)?;
+        Ok(pythonize(py, &data)?)
+    }
+
+    /// Nonce the guardian's next meta-approval has to be signed with, as the registry has it now
+    fn meta_nonce(&self, py: Python<'_>, guardian: &str) -> PyResult<u64> {
+        let data = self.get(py, &format!("/guardians/{guardian}/meta-nonce"), &[])?;
+        data["nonce"].as_u64().ok_or_else(|| ApiError::new_err("Malformed meta nonce"))
+    }
+
+    /// Account hashes of the relayers allowed to submit meta-approvals for an account; empty if anyone may
+    fn relayers<'py>(&self, py: Python<'py>, account: &str) -> PyResult<Bound<'py, PyAny>> {
+        let data = self.get(py, &format!("/accounts/{account}/relayers"), &[])?;
+        Ok(pythonize(py, &data)?)
+    }
+
+    /// Unsigned deploy calling `entry_point` on the registry, or TransactionV1 on Casper 2.0 nodes.
+    /// `args` are `{name, type, value}` dicts as accepted by `POST /deploys/prepare`; returns
+    /// `{kind, deployHash, deploy or transaction, bytes}`.
+    #[pyo3(signature = (sender, entry_point, args, payment_amount=None))]
+    fn prepare_deploy<'py>(
+        &self,
+        py: Python<'py>,
+        sender: &str,
+        entry_point: &str,
+        args: &Bound<'py, PyAny>,
+        payment_amount: Option<&str>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let args: Value = depythonize(args)?;
+        let mut body = json!({ "sender": sender, "entryPoint": entry_point, "args": args });
+        if let Some(amount) = payment_amount {
+            body["paymentAmount"] = json!(amount);
+        }
+        let data = self.send(py, self.agent.post(&self.url("/deploys/prepare")), Some(body))?;
+        Ok(pythonize(py, &data)?)
+    }
+
+    /// Submit a signed deploy or TransactionV1 (`{"Version1": ...}`), returning its hash
+    fn submit_deploy(&self, py: Python<'_>, deploy: &Bound<'_, PyAny>) -> PyResult<String> {
+        let deploy: Value = depythonize(deploy)?;
+        let kind = if deploy.get("Version1").is_some() { "transaction" } else { "deploy" };
+        let data = self.send(py, self.agent.post(&self.url("/deploys")), Some(json!({ kind: deploy })))?;
+        Ok(data["deployHash"].as_str().unwrap_or_default().to_string())
+    }
+
+    /// `{deployHash, status, ...}` where status is pending, success or failed
+    fn deploy_status<'py>(&self, py: Python<'py>, deploy_hash: &str) -> PyResult<Bound<'py, PyAny>> {
+        let data = self.get(py, &format!("/deploys/{deploy_hash}"), &[])?;
+        Ok(pythonize(py, &data)?)
+    }
+
+    /// Poll a deploy every `interval` seconds until it executes; raises TimeoutError after `timeout`.
+    /// With `check`, a deploy the registry reverted raises RevertError instead of returning.
+    #[pyo3(signature = (deploy_hash, timeout=300.0, interval=5.0, check=true))]
+    fn wait_for_deploy<'py>(&self, py: Python<'py>, deploy_hash: &str, timeout: f64, interval: f64, check: bool) -> PyResult<Bound<'py, PyAny>> {
+        let deadline = Instant::now() + Duration::from_secs_f64(timeout);
+        loop {
+            // Not executed yet also shows up as a lookup error until the node has the deploy
+            if let Ok(data) = self.get(py, &format!("/deploys/{deploy_hash}"), &[]) {
+                if data["status"] != "pending" {
+                    if check {
+                        if let Some(err) = revert_error(py, &data)? {
+                            return Err(err);
+                        }
+                    }
+                    return Ok(pythonize(py, &data)?);
+                }
+            }
+            if Instant::now() >= deadline {
+                return Err(PyTimeoutError::new_err(format!("Deploy {deploy_hash} still pending after {timeout}s")));
+            }
+            py.allow_threads(|| std::thread::sleep(Duration::from_secs_f64(interval)));
+            py.check_signals()?;
+        }
+    }
+}
+
+// ============================================================================
+// Offline signing
+// ============================================================================
+
+/// Signs with one key: a local PEM file, a PKCS#11 HSM or AWS KMS
+#[pyclass(module = "guardian_recovery")]
+struct Signer {
+    inner: Box<dyn guardian_signer::Signer>,
+}
+
+fn signer_error(e: SignerError) -> PyErr {
+    match e {
+        SignerError::Key(_) => PyValueError::new_err(e.to_string()),
+        SignerError::Backend(_) => PyRuntimeError::new_err(e.to_string()),
+    }
+}
+
+impl Signer {
+    fn sign_raw(&self, msg: &[u8]) -> PyResult<Vec<u8>> {
+        self.inner.sign_raw(msg).map_err(signer_error)
+    }
+}
+
+#[pymethods]
+impl Signer {
+    #[staticmethod]
+    fn from_pem(pem: &str) -> PyResult<Self> {
+        Ok(Signer { inner: Box::new(PemSigner::from_pem(pem).map_err(signer_error)?) })
+    }
+
+    /// Load a `secret_key.pem` as written by casper-client keygen
+    #[staticmethod]
+    fn from_file(path: &str) -> PyResult<Self> {
+        Ok(Signer { inner: Box::new(PemSigner::from_file(path).map_err(signer_error)?) })
+    }
+
+    /// Key pair labelled `key_label` in a PKCS#11 token; `algorithm` is "ed25519" or "secp256k1"
+    #[cfg(feature = "pkcs11")]
+    #[staticmethod]
+    #[pyo3(signature = (module_path, token_label, pin, key_label, algorithm="secp256k1"))]
+    fn pkcs11(module_path: &str, token_label: &str, pin: &str, key_label: &str, algorithm: &str) -> PyResult<Self> {
+        let algorithm = match algorithm {
+            "ed25519" => KeyAlgorithm::Ed25519,
+            "secp256k1" => KeyAlgorithm::Secp256k1,
+            other => return Err(PyValueError::new_err(format!("Unknown key algorithm {other}"))),
+        };
+        let signer = Pkcs11Signer::open(module_path, token_label, pin, key_label, algorithm).map_err(signer_error)?;
+        Ok(Signer { inner: Box::new(signer) })
+    }
+
+    /// ECC_SECG_P256K1 key in AWS KMS, using the default credential chain
+    #[cfg(feature = "aws-kms")]
+    #[staticmethod]
+    #[pyo3(signature = (key_id, region=None))]
+    fn aws_kms(py: Python<'_>, key_id: &str, region: Option<&str>) -> PyResult<Self> {
+        let signer = py.allow_threads(|| KmsSigner::new(key_id, region)).map_err(signer_error)?;
+        Ok(Signer { inner: Box::new(signer) })
+    }
+
+    /// Hex public key
+    #[getter]
+    fn public_key(&self) -> String {
+        key_hex(self.inner.public_key())
+    }
+
+    /// Hex account hash
+    #[getter]
+    fn account_hash(&self) -> String {
+        hex::encode(self.inner.public_key().to_account_hash().value())
+    }
+
+    /// Raw signature over `message` (64 bytes), as the registry verifies signed payloads
+    fn sign<'py>(&self, py: Python<'py>, message: &[u8]) -> PyResult<Bound<'py, PyBytes>> {
+        let sig = py.allow_threads(|| self.sign_raw(message))?;
+        Ok(PyBytes::new_bound(py, &sig))
+    }
+
+    /// Copy of a deploy or TransactionV1 (as returned by `prepare_deploy`, wrapped in `deploy` or
+    /// `transaction` or not) with this key's approval added
+    fn sign_deploy<'py>(&self, py: Python<'py>, deploy: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+        let mut value: Value = depythonize(deploy)?;
+        let inner = match ["deploy", "transaction"].into_iter().find(|k| value.get(k).is_some()) {
+            Some(k) => &mut value[k],
+            None => &mut value,
+        };
+        let inner = if inner.get("Version1").is_some() { &mut inner["Version1"] } else { inner };
+        let hash = inner["hash"].as_str().and_then(|h| hex::decode(h).ok()).ok_or_else(|| PyValueError::new_err("Deploy has no hash"))?;
+
+        let signer = key_hex(self.inner.public_key());
+        let signature = py.allow_threads(|| self.inner.sign(&hash)).map_err(signer_error)?;
+        let approval = json!({ "signer": signer, "signature": hex::encode(signature.to_bytes().unwrap_or_default()) });
+        match inner["approvals"].as_array_mut() {
+            Some(approvals) if approvals.iter().any(|a| a["signer"] == signer.as_str()) => {}
+            Some(approvals) => approvals.push(approval),
+            None => inner["approvals"] = json!([approval]),
+        }
+        Ok(pythonize(py, &value)?)
+    }
+
+    /// Signature for `approve_signed`: this guardian approving recovery `id` of `account`
+    #[pyo3(signature = (id, account, new_key, memo=String::new()))]
+    fn sign_approval<'py>(&self, py: Python<'py>, id: &str, account: &str, new_key: &str, memo: String) -> PyResult<Bound<'py, PyBytes>> {
+        let msg = payloads::approval_payload(recovery_id_arg(id)?, account_hash(account)?, &public_key(new_key)?, &memo);
+        let sig = py.allow_threads(|| self.sign_raw(&msg))?;
+        Ok(PyBytes::new_bound(py, &sig))
+    }
+
+    /// `(nonce, signature)` for a relayer's `approve_meta`, signed with the next nonce `ledger`
+    /// has free past `chain_nonce` (`Client.meta_nonce`), or `nonce` if given, and recorded in
+    /// `ledger`. Raises NonceRefused rather than sign with a nonce the registry has moved past, one
+    /// already signed for another approval, or one past the next free nonce.
+    #[pyo3(signature = (id, account, new_key, ledger, chain_nonce, nonce=None, memo=String::new()))]
+    #[allow(clippy::too_many_arguments)]
+    fn sign_meta_approval<'py>(
+        &self,
+        py: Python<'py>,
+        id: &str,
+        account: &str,
+        new_key: &str,
+        mut ledger: PyRefMut<'_, MetaNonces>,
+        chain_nonce: u64,
+        nonce: Option<u64>,
+        memo: String,
+    ) -> PyResult<(u64, Bound<'py, PyBytes>)> {
+        let nonce = nonce.unwrap_or_else(|| ledger.inner.next(chain_nonce));
+        let msg = payloads::meta_approval_payload(recovery_id_arg(id)?, account_hash(account)?, &public_key(new_key)?, &memo, nonce);
+        ledger.inner.check(chain_nonce, nonce, &msg).map_err(nonce_refused)?;
+        let sig = py.allow_threads(|| self.sign_raw(&msg))?;
+        ledger.inner.record(chain_nonce, nonce, msg).map_err(nonce_refused)?;
+        Ok((nonce, PyBytes::new_bound(py, &sig)))
+    }
+
+    /// Signature for `rotate_my_key`; both the old and the new key sign the same rotation
+    fn sign_rotation<'py>(&self, py: Python<'py>, account: &str, nonce: u64, old_key: &str, new_key: &str) -> PyResult<Bound<'py, PyBytes>> {
+        let msg = payloads::rotation_payload(account_hash(account)?, nonce, &public_key(old_key)?, &public_key(new_key)?);
+        let sig = py.allow_threads(|| self.sign_raw(&msg))?;
+        Ok(PyBytes::new_bound(py, &sig))
+    }
+}
+
+// ============================================================================
+// Meta-approval nonces
+// ============================================================================
+
+fn nonce_refused(e: NonceError) -> PyErr {
+    NonceRefused::new_err(e.description())
+}
+
+/// Nonces one guardian has signed meta-approvals with. Keep it across sessions with `to_bytes`
+/// and `from_bytes`, and use one per guardian key.
+#[pyclass(module = "guardian_recovery")]
+#[derive(Default)]
+struct MetaNonces {
+    inner: NonceLedger,
+}
+
+#[pymethods]
+impl MetaNonces {
+    #[new]
+    fn new() -> Self {
+        MetaNonces::default()
+    }
+
+    #[staticmethod]
+    fn from_bytes(bytes: &[u8]) -> PyResult<Self> {
+        match NonceLedger::from_bytes(bytes) {
+            Ok((inner, rest)) if rest.is_empty() => Ok(MetaNonces { inner }),
+            _ => Err(PyValueError::new_err("Not an encoded nonce ledger")),
+        }
+    }
+
+    fn to_bytes<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new_bound(py, &self.inner.to_bytes().unwrap_or_default())
+    }
+
+    /// Nonce to sign the next meta-approval with, given the guardian's nonce on chain
+    fn next_nonce(&self, chain_nonce: u64) -> u64 {
+        self.inner.next(chain_nonce)
+    }
+
+    /// Raises NonceRefused unless `payload` may be signed with `nonce`
+    fn check(&self, chain_nonce: u64, nonce: u64, payload: &[u8]) -> PyResult<()> {
+        self.inner.check(chain_nonce, nonce, payload).map_err(nonce_refused)
+    }
+
+    /// Forget nonces the registry has used or voided
+    fn prune(&mut self, chain_nonce: u64) {
+        self.inner.prune(chain_nonce);
+    }
+
+    /// (nonce, payload) of each meta-approval signed and not yet pruned, lowest nonce first
+    fn issued<'py>(&self, py: Python<'py>) -> Vec<(u64, Bound<'py, PyBytes>)> {
+        self.inner.issued().map(|(nonce, payload)| (nonce, PyBytes::new_bound(py, payload))).collect()
+    }
+}
+
+/// Bytes a guardian signs for a relayer's `approve_meta`: `META_DOMAIN` then the bytesrepr fields
+#[pyfunction]
+#[pyo3(signature = (id, account, new_key, nonce, memo=String::new()))]
+fn meta_approval_payload<'py>(py: Python<'py>, id: &str, account: &str, new_key: &str, nonce: u64, memo: String) -> PyResult<Bound<'py, PyBytes>> {
+    let msg = payloads::meta_approval_payload(recovery_id_arg(id)?, account_hash(account)?, &public_key(new_key)?, &memo, nonce);
+    Ok(PyBytes::new_bound(py, &msg))
+}
+
+// ============================================================================
+// Local helpers
+// ============================================================================
+
+/// Account hash hex of a public key or account hash
+#[pyfunction(name = "account_hash")]
+fn account_hash_hex(account: &str) -> PyResult<String> {
+    Ok(hex::encode(account_hash(account)?.value()))
+}
+
+/// Decimal id `start_recovery` will assign to `account`'s recovery number `nonce` to `new_key`
+#[pyfunction]
+fn recovery_id(account: &str, nonce: u64, new_key: &str) -> PyResult<String> {
+    let commitment = ids::key_commitment(&public_key(new_key)?);
+    Ok(ids::recovery_id(account_hash(account)?, nonce, commitment).to_string())
+}
+
+/// Raises ValueError unless `set_up` would accept `guardians` for `owner` with `threshold`
+#[pyfunction]
+fn validate_guardian_set(owner: &str, guardians: Vec<String>, threshold: u8) -> PyResult<()> {
+    let guards = guardians.iter().map(|g| account_hash(g)).collect::<PyResult<Vec<_>>>()?;
+    validation::check_guardian_set(account_hash(owner)?, &guards, threshold).map_err(|error| match error {
+        GuardianError::InvalidThreshold => PyValueError::new_err("Threshold must be between 1 and the number of guardians"),
+        _ => PyValueError::new_err("Need at least 2 distinct guardians, none of them the owner"),
+    })
+}
+
+/// Built-in guardian policy templates as `{name, description, slots, threshold}` dicts; `slots` says
+/// what each guardian stands for, in the order `init_from_template` takes them
+#[pyfunction(name = "templates")]
+fn list_templates(py: Python<'_>) -> PyResult<Bound<'_, PyAny>> {
+    let list: Vec<Value> = templates::ALL.iter()
+        .map(|t| json!({ "name": t.name, "description": t.description, "slots": t.slots, "threshold": t.threshold }))
+        .collect();
+    Ok(pythonize(py, &list)?)
+}
+
+/// `prepare_deploy` args for `init_from_template` setting `account` up from template `name`, with
+/// `guardians` filling its slots in order. Raises ValueError if the registry would refuse them.
+#[pyfunction]
+fn template_args<'py>(py: Python<'py>, name: &str, account: &str, guardians: Vec<String>) -> PyResult<Bound<'py, PyAny>> {
+    let template = templates::find(name).ok_or_else(|| PyValueError::new_err(format!("No template called {name}")))?;
+    if guardians.len() != template.slots.len() {
+        return Err(PyValueError::new_err(format!("{name} takes {} guardians: {}", template.slots.len(), template.slots.join(", "))));
+    }
+    validate_guardian_set(account, guardians.clone(), template.threshold)?;
+    let args = json!([
+        { "name": "account", "type": "account", "value": account_hash_hex(account)? },
+        { "name": "template", "type": "string", "value": name },
+        { "name": "guardians", "type": "account_list", "value": guardians },
+    ]);
+    Ok(pythonize(py, &args)?)
+}
+
+/// (name, description) of a registry revert code, or None if the registry doesn't use it
+#[pyfunction]
+fn registry_error(code: u16) -> Option<(&'static str, &'static str)> {
+    RegistryError::from_code(code).map(|e| (e.name(), e.description()))
+}
+
+// ============================================================================
+// FROST guardian groups
+// ============================================================================
+
+fn frost_error(e: guardian_frost::Error) -> PyErr {
+    PyValueError::new_err(e.to_string())
+}
+
+/// DKG round one as participant `index`: `{secret, package}`; broadcast `package`
+#[pyfunction]
+fn frost_dkg_part1(index: u16, max_signers: u16, min_signers: u16) -> PyResult<(String, String)> {
+    let round = guardian_frost::dkg_part1(index, max_signers, min_signers).map_err(frost_error)?;
+    Ok((round.secret, round.package))
+}
+
+/// DKG round two from everyone else's round-one packages: `(secret, {index: package})`; send each
+/// package privately to its participant
+#[pyfunction]
+fn frost_dkg_part2(secret: &str, round1: Packages) -> PyResult<(String, Packages)> {
+    let round = guardian_frost::dkg_part2(secret, &round1).map_err(frost_error)?;
+    Ok((round.secret, round.packages))
+}
+
+/// Finish DKG: `(key_package, public_key_package, group_key)`; register `group_key` with `set_frost_key`
+#[pyfunction]
+fn frost_dkg_part3(secret: &str, round1: Packages, round2: Packages) -> PyResult<(String, String, String)> {
+    let keys = guardian_frost::dkg_part3(secret, &round1, &round2).map_err(frost_error)?;
+    Ok((keys.key_package, keys.public_key_package, keys.group_key))
+}
+
+/// Signing round one: `(nonces, commitments)`; send `commitments` to the coordinator
+#[pyfunction]
+fn frost_commit(key_package: &str) -> PyResult<(String, String)> {
+    let commitment = guardian_frost::commit(key_package).map_err(frost_error)?;
+    Ok((commitment.nonces, commitment.commitments))
+}
+
+/// Coordinator: signing package approving recovery `id` of `account` to `new_key`
+#[pyfunction]
+fn frost_signing_package(commitments: Packages, id: &str, account: &str, new_key: &str) -> PyResult<String> {
+    let msg = payloads::frost_approval_payload(recovery_id_arg(id)?, account_hash(account)?, &public_key(new_key)?);
+    guardian_frost::signing_package(&commitments, &msg).map_err(frost_error)
+}
+
+/// Signing round two: this guardian's signature share
+#[pyfunction]
+fn frost_sign(signing_package: &str, nonces: &str, key_package: &str) -> PyResult<String> {
+    guardian_frost::sign(signing_package, nonces, key_package).map_err(frost_error)
+}
+
+/// Coordinator: aggregate signature to pass as `approve_frost`'s `signature`
+#[pyfunction]
+fn frost_aggregate<'py>(py: Python<'py>, signing_package: &str, shares: Packages, public_key_package: &str) -> PyResult<Bound<'py, PyBytes>> {
+    let sig = guardian_frost::aggregate(signing_package, &shares, public_key_package).map_err(frost_error)?;
+    Ok(PyBytes::new_bound(py, &sig))
+}
+
+#[pymodule]
+fn guardian_recovery(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Client>()?;
+    m.add_class::<Signer>()?;
+    m.add_class::<MetaNonces>()?;
+    // Name of `Signer` before it gained HSM and KMS backends
+    m.add("SecretKey", m.getattr("Signer")?)?;
+    m.add("ApiError", m.py().get_type_bound::<ApiError>())?;
+    m.add("RevertError", m.py().get_type_bound::<RevertError>())?;
+    m.add("NonceRefused", m.py().get_type_bound::<NonceRefused>())?;
+    m.add("META_DOMAIN", PyBytes::new_bound(m.py(), payloads::META_DOMAIN))?;
+    m.add_function(wrap_pyfunction!(registry_error, m)?)?;
+    m.add_function(wrap_pyfunction!(account_hash_hex, m)?)?;
+    m.add_function(wrap_pyfunction!(recovery_id, m)?)?;
+    m.add_function(wrap_pyfunction!(meta_approval_payload, m)?)?;
+    m.add_function(wrap_pyfunction!(validate_guardian_set, m)?)?;
+    m.add_function(wrap_pyfunction!(list_templates, m)?)?;
+    m.add_function(wrap_pyfunction!(template_args, m)?)?;
+    m.add_function(wrap_pyfunction!(frost_dkg_part1, m)?)?;
+    m.add_function(wrap_pyfunction!(frost_dkg_part2, m)?)?;
+    m.add_function(wrap_pyfunction!(frost_dkg_part3, m)?)?;
+    m.add_function(wrap_pyfunction!(frost_commit, m)?)?;
+    m.add_function(wrap_pyfunction!(frost_signing_package, m)?)?;
+    m.add_function(wrap_pyfunction!(frost_sign, m)?)?;
+    m.add_function(wrap_pyfunction!(frost_aggregate, m)?)?;
+    Ok(())
+}
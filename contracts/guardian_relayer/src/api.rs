@@ -0,0 +1,101 @@
+//! The parts of the backend's `/api/v1` the relayer reads registry state and submits deploys through.
+
+use anyhow::{anyhow, Context, Result};
+use casper_types::account::AccountHash;
+use serde_json::{json, Value};
+
+pub struct Api {
+    base: String,
+}
+
+/// Why a backend request failed
+pub enum ApiError {
+    /// The backend answered 404
+    NotFound,
+    Other(anyhow::Error),
+}
+
+impl From<anyhow::Error> for ApiError {
+    fn from(error: anyhow::Error) -> Self {
+        ApiError::Other(error)
+    }
+}
+
+impl Api {
+    pub fn new(base: &str) -> Self {
+        Api { base: base.trim_end_matches('/').to_string() }
+    }
+
+    /// `data` of the `{ success, data | error }` envelope, or the backend's error message
+    fn send(&self, path: &str, body: Option<Value>) -> Result<Value, ApiError> {
+        let url = format!("{}{}", self.base, path);
+        let response = match body {
+            Some(body) => ureq::post(&url).send_json(body),
+            None => ureq::get(&url).call(),
+        };
+        let (status, envelope): (u16, Value) = match response {
+            Ok(r) | Err(ureq::Error::Status(_, r)) => (r.status(), r.into_json().with_context(|| format!("reading {url}"))?),
+            Err(e) => return Err(anyhow!("{url}: {e}").into()),
+        };
+        if envelope["success"] == true {
+            Ok(envelope["data"].clone())
+        } else if status == 404 {
+            Err(ApiError::NotFound)
+        } else {
+            Err(anyhow!("{}", envelope["error"].as_str().unwrap_or("request failed")).into())
+        }
+    }
+
+    pub fn get(&self, path: &str) -> Result<Value, ApiError> {
+        self.send(path, None)
+    }
+
+    pub fn post(&self, path: &str, body: Value) -> Result<Value, ApiError> {
+        self.send(path, Some(body))
+    }
+
+    /// Account hashes of `account`'s current guardians
+    pub fn guardians(&self, account: &AccountHash) -> Result<Vec<AccountHash>, ApiError> {
+        let config = self.get(&format!("/accounts/{}/config", hex::encode(account.value())))?;
+        hashes(&config["guardians"])
+    }
+
+    /// Relayers `account` allows to submit its meta-approvals; empty if anyone may
+    pub fn relayers(&self, account: &AccountHash) -> Result<Vec<AccountHash>, ApiError> {
+        hashes(&self.get(&format!("/accounts/{}/relayers", hex::encode(account.value())))?)
+    }
+
+    /// Nonce `guardian`'s next meta-approval has to be signed with
+    pub fn meta_nonce(&self, guardian: &AccountHash) -> Result<u64, ApiError> {
+        let data = self.get(&format!("/guardians/{}/meta-nonce", hex::encode(guardian.value())))?;
+        data["nonce"].as_u64().ok_or_else(|| anyhow!("bad meta nonce {}", data["nonce"]).into())
+    }
+
+    /// Unsigned `relay_approvals` deploy (or transaction, on Casper 2.0 nodes) of `sender` for
+    /// `approvals`
+    pub fn prepare_relay(&self, sender: &str, approvals: Value, memo: &str, payment: Option<&str>) -> Result<Value, ApiError> {
+        let mut body = json!({ "sender": sender, "approvals": approvals, "memo": memo });
+        if let Some(payment) = payment {
+            body["paymentAmount"] = json!(payment);
+        }
+        self.post("/deploys/prepare-relay", body)
+    }
+
+    /// Submit a signed deploy or transaction, as `kind` says, returning its hash
+    pub fn submit(&self, kind: &str, deploy: &Value) -> Result<String, ApiError> {
+        let data = self.post("/deploys", json!({ kind: deploy }))?;
+        Ok(data["deployHash"].as_str().unwrap_or_default().to_string())
+    }
+}
+
+fn hashes(list: &Value) -> Result<Vec<AccountHash>, ApiError> {
+    list.as_array()
+        .into_iter()
+        .flatten()
+        .map(|h| {
+            let hex = h.as_str().unwrap_or_default();
+            let bytes = hex::decode(hex).ok().filter(|b| b.len() == 32).ok_or_else(|| anyhow!("bad account hash {hex}"))?;
+            Ok(AccountHash::try_from(bytes.as_slice()).map_err(|_| anyhow!("bad account hash {hex}"))?)
+        })
+        .collect()
+}
@@ -0,0 +1,104 @@
+//! Sends queued submissions as `relay_approvals` deploys signed by the operator, and follows the
+//! deploys until they execute.
+
+use std::{thread, time::Duration};
+
+use anyhow::anyhow;
+use casper_types::{bytesrepr::ToBytes, PublicKey};
+use guardian_signer::Signer;
+use serde_json::{json, Value};
+
+use crate::{
+    api::ApiError,
+    queue::{Status, Submission},
+    Relayer,
+};
+
+pub fn run(relayer: &Relayer) {
+    let interval = Duration::from_secs(relayer.config.batch_interval);
+    loop {
+        thread::sleep(interval);
+        follow(relayer);
+        let batch = relayer.queue.lock().unwrap().next_batch(relayer.config.batch_size);
+        if !batch.is_empty() {
+            send(relayer, &batch);
+        }
+    }
+}
+
+/// Settle submissions whose deploy has executed
+fn follow(relayer: &Relayer) {
+    let submitted = relayer.queue.lock().unwrap().submitted();
+    for (deploy_hash, batch) in submitted {
+        let Ok(status) = relayer.api.get(&format!("/deploys/{deploy_hash}")) else { continue };
+        let settled = match status["status"].as_str() {
+            // The session skips an approval whose nonce another relayer used first, so on success
+            // every one of them is on chain
+            Some("success") => {
+                relayer.metrics.relayed(batch.len());
+                Status::Relayed { deploy_hash }
+            }
+            Some("failed") => {
+                relayer.metrics.deploy_failed(batch.len());
+                let error = status["revert"]["name"].as_str().or(status["errorMessage"].as_str()).unwrap_or("execution failed");
+                Status::Failed { deploy_hash: Some(deploy_hash), error: error.to_string() }
+            }
+            _ => continue,
+        };
+        let mut queue = relayer.queue.lock().unwrap();
+        for s in &batch {
+            queue.set_status(&s.key(), settled.clone());
+        }
+    }
+}
+
+fn send(relayer: &Relayer, batch: &[Submission]) {
+    let status = match submit(relayer, batch) {
+        Ok(deploy_hash) => {
+            relayer.metrics.deploy_sent();
+            Status::Submitted { deploy_hash }
+        }
+        Err(e) => {
+            relayer.metrics.deploy_failed(batch.len());
+            let error = match e {
+                ApiError::NotFound => "backend has no relay endpoint".to_string(),
+                ApiError::Other(e) => e.to_string(),
+            };
+            eprintln!("relay deploy of {} approval(s) failed: {error}", batch.len());
+            Status::Failed { deploy_hash: None, error }
+        }
+    };
+    let mut queue = relayer.queue.lock().unwrap();
+    for s in batch {
+        queue.set_status(&s.key(), status.clone());
+    }
+}
+
+/// Prepare, sign and submit one deploy of `batch`, returning its hash
+fn submit(relayer: &Relayer, batch: &[Submission]) -> Result<String, ApiError> {
+    let hex_key = |key: &PublicKey| key.to_bytes().map(hex::encode).map_err(|e| anyhow!("{e}"));
+    let approvals: Vec<Value> = batch
+        .iter()
+        .map(|s| {
+            Ok(json!({
+                "id": s.id.to_string(),
+                "guardian": hex_key(&s.guardian)?,
+                "nonce": s.nonce,
+                "signature": hex::encode(&s.signature),
+            }))
+        })
+        .collect::<anyhow::Result<_>>()?;
+    let operator = &relayer.operator;
+    let sender = hex_key(operator.public_key())?;
+    let prepared = relayer.api.prepare_relay(&sender, json!(approvals), &batch[0].memo, relayer.config.payment.as_deref())?;
+
+    let kind = prepared["kind"].as_str().unwrap_or("deploy");
+    let mut deploy = prepared[kind].clone();
+    // A TransactionV1 keeps its hash and approvals under `Version1`
+    let inner = if deploy.get("Version1").is_some() { &mut deploy["Version1"] } else { &mut deploy };
+    let hash = inner["hash"].as_str().and_then(|h| hex::decode(h).ok()).ok_or_else(|| anyhow!("deploy has no hash"))?;
+    let signature = operator.sign(&hash).map_err(|e| anyhow!("{e}"))?;
+    let signature = signature.to_bytes().map_err(|e| anyhow!("{e}"))?;
+    inner["approvals"] = json!([{ "signer": sender, "signature": hex::encode(signature) }]);
+    relayer.api.submit(kind, &deploy)
+}
@@ -0,0 +1,91 @@
+//! `guardian-relayer`: pays the gas for guardians' meta-approvals, so guardians needn't hold CSPR.
+//!
+//! Guardians (or their wallets) POST approvals signed over `payloads::meta_approval_payload` to
+//! `/approvals`. Each is checked against the registry state the backend's `/api/v1` reports and
+//! queued; every `--batch-interval` the queue goes out as one `relay_approvals` session deploy
+//! signed and paid for by the operator key. `/approvals/<submission>` reports where a submission
+//! is and `/metrics` serves Prometheus counters.
+
+mod api;
+mod batcher;
+mod metrics;
+mod queue;
+mod server;
+mod validate;
+
+use std::{
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use guardian_signer::{PemSigner, Signer};
+
+use crate::{api::Api, metrics::Metrics, queue::Queue};
+
+#[derive(Parser)]
+#[command(name = "guardian-relayer", version, about = "SentinelX meta-approval relayer")]
+pub struct Config {
+    /// Backend API root
+    #[arg(long, env = "RELAYER_API", default_value = "http://localhost:3001/api/v1")]
+    api: String,
+
+    /// Address the HTTP service listens on
+    #[arg(long, env = "RELAYER_LISTEN", default_value = "127.0.0.1:8088")]
+    listen: String,
+
+    /// Secret key PEM of the operator account, which signs and pays for every relay deploy
+    #[arg(long, env = "RELAYER_OPERATOR_KEY")]
+    operator_key: String,
+
+    /// Most approvals in one deploy
+    #[arg(long, env = "RELAYER_BATCH_SIZE", default_value_t = 10)]
+    batch_size: usize,
+
+    /// Seconds between deploys
+    #[arg(long, env = "RELAYER_BATCH_INTERVAL", default_value_t = 15)]
+    batch_interval: u64,
+
+    /// Payment per deploy in motes; the backend's session payment by default
+    #[arg(long, env = "RELAYER_PAYMENT")]
+    payment: Option<String>,
+
+    /// Most approvals accepted for recoveries of one account per `--rate-window`
+    #[arg(long, env = "RELAYER_RATE_LIMIT", default_value_t = 20)]
+    rate_limit: usize,
+
+    /// Rate limit window in seconds
+    #[arg(long, env = "RELAYER_RATE_WINDOW", default_value_t = 3600)]
+    rate_window: u64,
+}
+
+/// What the HTTP service and the batcher share
+pub struct Relayer {
+    pub api: Api,
+    pub operator: PemSigner,
+    pub queue: Mutex<Queue>,
+    pub metrics: Metrics,
+    pub config: Config,
+}
+
+fn main() -> Result<()> {
+    let config = Config::parse();
+    if config.batch_size == 0 {
+        return Err(anyhow!("--batch-size must be at least 1"));
+    }
+    let operator = PemSigner::from_file(&config.operator_key)?;
+    let relayer = Arc::new(Relayer {
+        api: Api::new(&config.api),
+        queue: Mutex::new(Queue::new(config.rate_limit, Duration::from_secs(config.rate_window))),
+        metrics: Metrics::default(),
+        operator,
+        config,
+    });
+    println!("operator: {}", relayer.operator.public_key().to_account_hash().to_formatted_string());
+
+    let worker = relayer.clone();
+    thread::spawn(move || batcher::run(&worker));
+    server::run(&relayer)
+}
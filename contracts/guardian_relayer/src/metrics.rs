@@ -0,0 +1,66 @@
+//! Prometheus metrics of the relayer, served in the text exposition format.
+
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+};
+
+#[derive(Default)]
+pub struct Metrics {
+    accepted: AtomicU64,
+    /// Rejected submissions by reason
+    rejected: Mutex<BTreeMap<&'static str, u64>>,
+    deploys: AtomicU64,
+    deploy_failures: AtomicU64,
+    relayed: AtomicU64,
+    failed: AtomicU64,
+}
+
+impl Metrics {
+    pub fn accepted(&self) {
+        self.accepted.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn rejected(&self, reason: &'static str) {
+        *self.rejected.lock().unwrap().entry(reason).or_default() += 1;
+    }
+
+    pub fn deploy_sent(&self) {
+        self.deploys.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// A deploy that couldn't be sent or failed to execute, and the approvals in it
+    pub fn deploy_failed(&self, approvals: usize) {
+        self.deploy_failures.fetch_add(1, Ordering::Relaxed);
+        self.failed.fetch_add(approvals as u64, Ordering::Relaxed);
+    }
+
+    pub fn relayed(&self, approvals: usize) {
+        self.relayed.fetch_add(approvals as u64, Ordering::Relaxed);
+    }
+
+    pub fn render(&self, queued: usize) -> String {
+        let mut out = String::new();
+        let mut metric = |name: &str, kind: &str, help: &str, values: &[(String, u64)]| {
+            let _ = writeln!(out, "# HELP {name} {help}\n# TYPE {name} {kind}");
+            for (labels, value) in values {
+                let _ = writeln!(out, "{name}{labels} {value}");
+            }
+        };
+        let one = |counter: &AtomicU64| [(String::new(), counter.load(Ordering::Relaxed))];
+        metric("guardian_relayer_submissions_accepted_total", "counter", "Meta-approvals validated and queued", &one(&self.accepted));
+        let rejected: Vec<(String, u64)> =
+            self.rejected.lock().unwrap().iter().map(|(reason, n)| (format!("{{reason=\"{reason}\"}}"), *n)).collect();
+        metric("guardian_relayer_submissions_rejected_total", "counter", "Meta-approvals turned away, by reason", &rejected);
+        metric("guardian_relayer_queued", "gauge", "Meta-approvals waiting for the next deploy", &[(String::new(), queued as u64)]);
+        metric("guardian_relayer_deploys_total", "counter", "Relay deploys sent", &one(&self.deploys));
+        metric("guardian_relayer_deploy_failures_total", "counter", "Relay deploys that couldn't be sent or failed to execute", &one(&self.deploy_failures));
+        metric("guardian_relayer_approvals_relayed_total", "counter", "Meta-approvals on chain after their deploy executed", &one(&self.relayed));
+        metric("guardian_relayer_approvals_failed_total", "counter", "Meta-approvals in deploys that failed", &one(&self.failed));
+        out
+    }
+}
@@ -0,0 +1,139 @@
+//! Accepted submissions, their status, and the per-account rate limit.
+
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+use casper_types::{account::AccountHash, PublicKey, U256};
+use serde::Serialize;
+
+/// A guardian's meta-approval as posted to `/approvals`
+#[derive(Clone)]
+pub struct Submission {
+    pub id: U256,
+    pub guardian: PublicKey,
+    pub nonce: u64,
+    /// 64 bytes, without the algorithm tag byte
+    pub signature: Vec<u8>,
+    pub memo: String,
+}
+
+impl Submission {
+    /// `<guardian account hash>-<nonce>`: a guardian signs each nonce once, so this names the
+    /// submission whichever relayer it went to
+    pub fn key(&self) -> String {
+        format!("{}-{}", hex::encode(self.guardian.to_account_hash().value()), self.nonce)
+    }
+}
+
+#[derive(Clone, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum Status {
+    Queued,
+    /// In a deploy that hasn't executed yet
+    Submitted { deploy_hash: String },
+    /// The deploy executed and the guardian's nonce moved past this approval, so it is on chain
+    /// (through this deploy, or another relayer's that got there first)
+    Relayed { deploy_hash: String },
+    Failed { deploy_hash: Option<String>, error: String },
+}
+
+impl Status {
+    fn in_flight(&self) -> bool {
+        matches!(self, Status::Queued | Status::Submitted { .. })
+    }
+}
+
+pub struct Entry {
+    pub submission: Submission,
+    pub status: Status,
+}
+
+pub struct Queue {
+    entries: BTreeMap<String, Entry>,
+    /// Keys of queued entries, oldest first
+    waiting: VecDeque<String>,
+    /// When approvals for each account's recoveries were accepted, within the window
+    accepted: HashMap<AccountHash, VecDeque<Instant>>,
+    rate_limit: usize,
+    rate_window: Duration,
+}
+
+impl Queue {
+    pub fn new(rate_limit: usize, rate_window: Duration) -> Self {
+        Queue { entries: BTreeMap::new(), waiting: VecDeque::new(), accepted: HashMap::new(), rate_limit, rate_window }
+    }
+
+    pub fn status(&self, key: &str) -> Option<&Status> {
+        self.entries.get(key).map(|e| &e.status)
+    }
+
+    /// Nonce `guardian`'s next submission needs: past the chain's and past any of theirs in flight
+    pub fn next_nonce(&self, guardian: &PublicKey, chain: u64) -> u64 {
+        self.entries
+            .values()
+            .filter(|e| e.submission.guardian == *guardian && e.status.in_flight())
+            .map(|e| e.submission.nonce + 1)
+            .fold(chain, u64::max)
+    }
+
+    /// True if approvals for `account`'s recoveries may be accepted now
+    pub fn within_rate(&mut self, account: AccountHash) -> bool {
+        let window = self.rate_window;
+        let times = self.accepted.entry(account).or_default();
+        while times.front().is_some_and(|t| t.elapsed() > window) {
+            times.pop_front();
+        }
+        times.len() < self.rate_limit
+    }
+
+    /// Queue a validated submission, counting it against `account`'s rate limit. Replaces a failed
+    /// entry of the same key, since the guardian's nonce hasn't moved.
+    pub fn push(&mut self, account: AccountHash, submission: Submission) -> String {
+        let key = submission.key();
+        self.accepted.entry(account).or_default().push_back(Instant::now());
+        self.waiting.push_back(key.clone());
+        self.entries.insert(key.clone(), Entry { submission, status: Status::Queued });
+        key
+    }
+
+    /// Up to `max` queued submissions sharing the oldest one's memo, as `relay_approvals` takes one
+    /// memo per deploy, in nonce order per guardian
+    pub fn next_batch(&mut self, max: usize) -> Vec<Submission> {
+        let Some(memo) = self.waiting.front().map(|k| self.entries[k].submission.memo.clone()) else { return Vec::new() };
+        let mut batch = Vec::new();
+        self.waiting.retain(|key| {
+            let submission = &self.entries[key].submission;
+            if batch.len() < max && submission.memo == memo {
+                batch.push(submission.clone());
+                false
+            } else {
+                true
+            }
+        });
+        batch.sort_by(|a, b| (a.guardian.to_account_hash(), a.nonce).cmp(&(b.guardian.to_account_hash(), b.nonce)));
+        batch
+    }
+
+    pub fn set_status(&mut self, key: &str, status: Status) {
+        if let Some(entry) = self.entries.get_mut(key) {
+            entry.status = status;
+        }
+    }
+
+    /// Deploys sent but not yet executed, with the submissions in each
+    pub fn submitted(&self) -> BTreeMap<String, Vec<Submission>> {
+        let mut deploys: BTreeMap<String, Vec<Submission>> = BTreeMap::new();
+        for entry in self.entries.values() {
+            if let Status::Submitted { deploy_hash } = &entry.status {
+                deploys.entry(deploy_hash.clone()).or_default().push(entry.submission.clone());
+            }
+        }
+        deploys
+    }
+
+    pub fn waiting(&self) -> usize {
+        self.waiting.len()
+    }
+}
@@ -0,0 +1,109 @@
+//! HTTP service: `POST /approvals`, `GET /approvals/<submission>`, `GET /metrics`, `GET /health`.
+//!
+//! Responses use the backend's `{ success, data | error }` envelope.
+
+use anyhow::{anyhow, Result};
+use casper_types::PublicKey;
+use guardian_signer::Signer;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::{
+    queue::Submission,
+    validate::{self, Rejection},
+    Relayer,
+};
+
+/// Body of `POST /approvals`
+#[derive(Deserialize)]
+struct Posted {
+    /// Decimal recovery id
+    id: String,
+    /// Guardian public key hex
+    guardian: String,
+    nonce: u64,
+    /// Signature hex, without the algorithm tag byte
+    signature: String,
+    #[serde(default)]
+    memo: String,
+}
+
+pub fn run(relayer: &Relayer) -> Result<()> {
+    let server = Server::http(&relayer.config.listen).map_err(|e| anyhow!("{}: {e}", relayer.config.listen))?;
+    println!("listening on {}", relayer.config.listen);
+    for request in server.incoming_requests() {
+        handle(relayer, request);
+    }
+    Ok(())
+}
+
+fn handle(relayer: &Relayer, mut request: Request) {
+    let url = request.url().to_string();
+    let (status, body) = match (request.method(), url.as_str()) {
+        (Method::Post, "/approvals") => {
+            let mut text = String::new();
+            let posted = std::io::Read::read_to_string(request.as_reader(), &mut text)
+                .map_err(|e| e.to_string())
+                .and_then(|_| serde_json::from_str::<Posted>(&text).map_err(|e| e.to_string()));
+            match posted.and_then(submission) {
+                Ok(s) => accept(relayer, s),
+                Err(e) => (400, failure(&e)),
+            }
+        }
+        (Method::Get, path) if path.starts_with("/approvals/") => {
+            match relayer.queue.lock().unwrap().status(&path["/approvals/".len()..]) {
+                Some(status) => (200, json!({ "success": true, "data": status })),
+                None => (404, failure("unknown submission")),
+            }
+        }
+        (Method::Get, "/metrics") => {
+            let text = relayer.metrics.render(relayer.queue.lock().unwrap().waiting());
+            let content_type = Header::from_bytes("Content-Type", "text/plain; version=0.0.4").unwrap();
+            let _ = request.respond(Response::from_string(text).with_header(content_type));
+            return;
+        }
+        (Method::Get, "/health") => {
+            let operator = relayer.operator.public_key().to_account_hash().to_formatted_string();
+            (200, json!({ "success": true, "data": { "operator": operator } }))
+        }
+        _ => (404, failure("not found")),
+    };
+    let content_type = Header::from_bytes("Content-Type", "application/json").unwrap();
+    let _ = request.respond(Response::from_string(body.to_string()).with_status_code(status).with_header(content_type));
+}
+
+fn submission(p: Posted) -> Result<Submission, String> {
+    Ok(Submission {
+        id: validate::recovery_id(&p.id).ok_or_else(|| format!("not a decimal recovery id: {}", p.id))?,
+        guardian: PublicKey::from_hex(&p.guardian).map_err(|_| format!("not a public key: {}", p.guardian))?,
+        nonce: p.nonce,
+        signature: hex::decode(&p.signature).map_err(|_| "signature is not hex".to_string())?,
+        memo: p.memo,
+    })
+}
+
+/// Validate and queue `s`, answering 202 with its submission key
+fn accept(relayer: &Relayer, s: Submission) -> (u16, Value) {
+    let operator = relayer.operator.public_key().to_account_hash();
+    match validate::check(&relayer.api, &relayer.queue, operator, &s) {
+        Ok(account) => {
+            relayer.metrics.accepted();
+            let key = relayer.queue.lock().unwrap().push(account, s);
+            (202, json!({ "success": true, "data": { "submission": key, "status": "queued" } }))
+        }
+        Err(rejection) => {
+            relayer.metrics.rejected(rejection.reason());
+            let status = match rejection {
+                Rejection::RateLimited => 429,
+                Rejection::Backend(_) => 502,
+                _ => 422,
+            };
+            (status, failure(&rejection.message()))
+        }
+    }
+}
+
+fn failure(error: &str) -> Value {
+    json!({ "success": false, "error": error })
+}
@@ -0,0 +1,112 @@
+//! Checks a submission against registry state before it is queued, so the operator doesn't pay
+//! for deploys the registry would revert.
+
+use std::sync::Mutex;
+
+use casper_types::{account::AccountHash, crypto, PublicKey, Signature, U256};
+use guardian_types::{payloads, RegistryError};
+use serde_json::Value;
+
+use crate::{
+    api::{Api, ApiError},
+    queue::{Queue, Submission},
+};
+
+/// Why a submission was turned away
+pub enum Rejection {
+    /// The registry would revert the approval with this error
+    Registry(RegistryError),
+    /// The recovery isn't pending any more
+    NotPending(String),
+    /// Too many approvals for the account's recoveries lately
+    RateLimited,
+    /// The backend couldn't be asked
+    Backend(String),
+}
+
+impl Rejection {
+    /// Label of the rejection in metrics
+    pub fn reason(&self) -> &'static str {
+        match self {
+            Rejection::Registry(e) => e.name(),
+            Rejection::NotPending(_) => "NotPending",
+            Rejection::RateLimited => "RateLimited",
+            Rejection::Backend(_) => "Backend",
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            Rejection::Registry(e) => format!("{}: {}", e.name(), e.description()),
+            Rejection::NotPending(status) => format!("recovery is {status}, not pending"),
+            Rejection::RateLimited => "too many approvals for this account's recoveries; try again later".to_string(),
+            Rejection::Backend(e) => format!("backend unavailable: {e}"),
+        }
+    }
+}
+
+impl From<ApiError> for Rejection {
+    fn from(error: ApiError) -> Self {
+        match error {
+            ApiError::NotFound => Rejection::Registry(RegistryError::NotFound),
+            ApiError::Other(e) => Rejection::Backend(e.to_string()),
+        }
+    }
+}
+
+/// Check `s` as `approve_meta` would for a deploy from `operator`, taking the guardian's
+/// submissions already in `queue` as done. Returns the account whose recovery it approves.
+pub fn check(api: &Api, queue: &Mutex<Queue>, operator: AccountHash, s: &Submission) -> Result<AccountHash, Rejection> {
+    let recovery = api.get(&format!("/recoveries/{}", s.id))?;
+    let status = recovery["status"].as_str().unwrap_or_default();
+    if status != "pending" {
+        return Err(Rejection::NotPending(status.to_string()));
+    }
+    let account = account_hash(&recovery["account"]).ok_or(Rejection::Registry(RegistryError::NotFound))?;
+    if !queue.lock().unwrap().within_rate(account) {
+        return Err(Rejection::RateLimited);
+    }
+
+    let relayers = api.relayers(&account)?;
+    if !relayers.is_empty() && !relayers.contains(&operator) {
+        return Err(Rejection::Registry(RegistryError::NotRelayer));
+    }
+    let guardian = s.guardian.to_account_hash();
+    if !api.guardians(&account)?.contains(&guardian) {
+        return Err(Rejection::Registry(RegistryError::NotGuardian));
+    }
+    let chain = api.meta_nonce(&guardian)?;
+    if s.nonce != queue.lock().unwrap().next_nonce(&s.guardian, chain) {
+        return Err(Rejection::Registry(RegistryError::BadNonce));
+    }
+
+    let new_key = recovery["newKey"].as_str().and_then(|k| PublicKey::from_hex(k).ok());
+    let new_key = new_key.ok_or(Rejection::Registry(RegistryError::NotFound))?;
+    let msg = payloads::meta_approval_payload(s.id, account, &new_key, &s.memo, s.nonce);
+    if !verify(&s.guardian, &msg, &s.signature) {
+        return Err(Rejection::Registry(RegistryError::BadSignature));
+    }
+    Ok(account)
+}
+
+/// True if `sig`, without its algorithm tag byte, is `key`'s signature over `msg`
+fn verify(key: &PublicKey, msg: &[u8], sig: &[u8]) -> bool {
+    let Ok(bytes) = <[u8; 64]>::try_from(sig) else { return false };
+    let signature = match key {
+        PublicKey::Ed25519(_) => Signature::ed25519(bytes),
+        PublicKey::Secp256k1(_) => Signature::secp256k1(bytes),
+        _ => return false,
+    };
+    signature.is_ok_and(|signature| crypto::verify(msg, &signature, key).is_ok())
+}
+
+fn account_hash(value: &Value) -> Option<AccountHash> {
+    let hex = value.as_str()?;
+    let bytes = hex::decode(hex.trim_start_matches("account-hash-")).ok()?;
+    AccountHash::try_from(bytes.as_slice()).ok()
+}
+
+/// Recovery id of a decimal string
+pub fn recovery_id(value: &str) -> Option<U256> {
+    U256::from_dec_str(value).ok()
+}
@@ -0,0 +1,127 @@
+//! Prints a JSON Schema document for every recovery registry entry point (arguments and return
+//! value) and every error code, built from the same definitions the contract installs.
+//!
+//! `make schema` writes it to `pkg/schema/recovery_registry.schema.json`. Each schema carries the
+//! exact CLType under `x-cl-type`; large integers are decimal strings and byte arrays hex, as the
+//! backend and casper-client take them.
+//!
+//! With `--errors-ts` it prints the error codes as a TypeScript module instead, which
+//! `make errors-ts` writes to the backend for decoding reverts.
+
+use casper_types::{ApiError, CLType};
+use guardian_types::{entry_points::registry_entry_points, RegistryError};
+use serde_json::{json, Map, Value};
+
+fn hex_bytes(len: Option<u32>) -> Value {
+    match len {
+        Some(n) => json!({ "type": "string", "pattern": format!("^[0-9a-fA-F]{{{}}}$", n * 2) }),
+        None => json!({ "type": "string", "pattern": "^([0-9a-fA-F]{2})*$" }),
+    }
+}
+
+fn uint(bits: u32) -> Value {
+    json!({ "type": "integer", "minimum": 0, "maximum": (1u64 << bits) - 1 })
+}
+
+/// JSON Schema of a value of `cl_type`, without the `x-cl-type` annotation
+fn schema(cl_type: &CLType) -> Value {
+    match cl_type {
+        CLType::Bool => json!({ "type": "boolean" }),
+        CLType::I32 => json!({ "type": "integer", "minimum": i32::MIN, "maximum": i32::MAX }),
+        CLType::I64 => json!({ "type": "integer" }),
+        CLType::U8 => uint(8),
+        CLType::U32 => uint(32),
+        CLType::U64 => json!({ "type": "integer", "minimum": 0 }),
+        CLType::U128 | CLType::U256 | CLType::U512 => json!({ "type": "string", "pattern": "^[0-9]+$" }),
+        CLType::Unit => json!({ "type": "null" }),
+        CLType::String => json!({ "type": "string" }),
+        CLType::Key => json!({ "type": "string", "description": "Formatted key, e.g. account-hash-… or hash-…" }),
+        CLType::URef => json!({ "type": "string", "pattern": "^uref-[0-9a-f]{64}-[0-7]{3}$" }),
+        CLType::PublicKey => json!({ "type": "string", "pattern": "^(01[0-9a-fA-F]{64}|02[0-9a-fA-F]{66})$" }),
+        CLType::ByteArray(n) => hex_bytes(Some(*n)),
+        // Vec<u8> (signatures, proofs) travels as hex
+        CLType::List(item) if **item == CLType::U8 => hex_bytes(None),
+        CLType::List(item) => json!({ "type": "array", "items": schema(item) }),
+        CLType::Option(inner) => json!({ "anyOf": [schema(inner), { "type": "null" }] }),
+        CLType::Result { ok, err } => json!({
+            "oneOf": [
+                { "type": "object", "properties": { "Ok": schema(ok) }, "required": ["Ok"] },
+                { "type": "object", "properties": { "Err": schema(err) }, "required": ["Err"] },
+            ],
+        }),
+        CLType::Map { key, value } => json!({
+            "type": "array",
+            "items": { "type": "object", "properties": { "key": schema(key), "value": schema(value) }, "required": ["key", "value"] },
+        }),
+        CLType::Tuple1(items) => tuple(items.iter().map(|t| &**t)),
+        CLType::Tuple2(items) => tuple(items.iter().map(|t| &**t)),
+        CLType::Tuple3(items) => tuple(items.iter().map(|t| &**t)),
+        CLType::Any => json!({ "description": "Serialized bytesrepr (hex); layout documented with the type in guardian-types" }),
+    }
+}
+
+fn tuple<'a>(items: impl Iterator<Item = &'a CLType>) -> Value {
+    let items: Vec<Value> = items.map(schema).collect();
+    json!({ "type": "array", "prefixItems": items, "minItems": items.len(), "maxItems": items.len() })
+}
+
+fn annotated(cl_type: &CLType) -> Value {
+    let mut value = schema(cl_type);
+    value["x-cl-type"] = serde_json::to_value(cl_type).unwrap_or(Value::Null);
+    value
+}
+
+/// `REGISTRY_ERRORS` of backend/src/types/registry-errors.ts
+fn errors_ts() -> String {
+    let mut out = String::from(
+        "// Generated by `make errors-ts` from guardian-types' RegistryError. Do not edit.\n\n\
+         export interface RegistryErrorInfo {\n    name: string;\n    description: string;\n}\n\n\
+         /** Registry `ApiError::User` codes */\n\
+         export const REGISTRY_ERRORS: Record<number, RegistryErrorInfo> = {\n",
+    );
+    for &e in RegistryError::ALL {
+        let description = serde_json::to_string(e.description()).unwrap_or_default();
+        out.push_str(&format!("    {}: {{ name: '{}', description: {} }},\n", e as u16, e.name(), description));
+    }
+    out.push_str("};\n");
+    out
+}
+
+fn main() {
+    if std::env::args().any(|a| a == "--errors-ts") {
+        print!("{}", errors_ts());
+        return;
+    }
+
+    let mut entry_points = Map::new();
+    let mut eps = registry_entry_points().take_entry_points();
+    eps.sort_by(|a, b| a.name().cmp(b.name()));
+    for ep in eps {
+        let args: Map<String, Value> = ep.args().iter().map(|p| (p.name().to_string(), annotated(p.cl_type()))).collect();
+        entry_points.insert(
+            ep.name().to_string(),
+            json!({
+                // The registry reads some arguments with try_get_named_arg, so none are marked required
+                "args": { "type": "object", "properties": args, "additionalProperties": false },
+                "returns": annotated(ep.ret()),
+            }),
+        );
+    }
+
+    let errors: Vec<Value> = RegistryError::ALL
+        .iter()
+        .map(|&e| {
+            let api: u32 = ApiError::from(e).into();
+            json!({ "code": e as u16, "apiError": api, "name": e.name(), "description": e.description() })
+        })
+        .collect();
+
+    let document = json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "recovery_registry",
+        "description": "Entry point arguments and return values of the SentinelX recovery registry",
+        "entryPoints": entry_points,
+        "errors": errors,
+    });
+    println!("{}", serde_json::to_string_pretty(&document).unwrap_or_default());
+}
@@ -0,0 +1,65 @@
+//! secp256k1 keys in AWS KMS (`ECC_SECG_P256K1`, signing with `ECDSA_SHA_256`).
+
+use aws_sdk_kms::{
+    primitives::Blob,
+    types::{MessageType, SigningAlgorithmSpec},
+    Client,
+};
+use casper_types::{PublicKey, Signature};
+use k256::{ecdsa::Signature as EcSignature, pkcs8::DecodePublicKey};
+use tokio::runtime::Runtime;
+
+use crate::{digest, secp256k1_signature, Signer, SignerError};
+
+/// KMS key used through the default AWS credential chain (environment, profile, instance role)
+pub struct KmsSigner {
+    client: Client,
+    key_id: String,
+    public: PublicKey,
+    runtime: Runtime,
+}
+
+fn backend(e: impl std::fmt::Display) -> SignerError {
+    SignerError::Backend(e.to_string())
+}
+
+impl KmsSigner {
+    /// Use the key with `key_id` (id, ARN or alias); `region` overrides the configured one
+    pub fn new(key_id: &str, region: Option<&str>) -> Result<Self, SignerError> {
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().map_err(backend)?;
+        let mut loader = aws_config::defaults(aws_config::BehaviorVersion::latest());
+        if let Some(region) = region {
+            loader = loader.region(aws_config::Region::new(region.to_string()));
+        }
+        let client = Client::new(&runtime.block_on(loader.load()));
+
+        let response = runtime
+            .block_on(client.get_public_key().key_id(key_id).send())
+            .map_err(|e| SignerError::Key(format!("{key_id}: {e}")))?;
+        let der = response.public_key().ok_or_else(|| SignerError::Key(format!("{key_id} returned no public key")))?;
+        let key = k256::PublicKey::from_public_key_der(der.as_ref())
+            .map_err(|_| SignerError::Key(format!("{key_id} is not an ECC_SECG_P256K1 key")))?;
+        let public = crate::secp256k1_public_key(&key.to_sec1_bytes())?;
+
+        Ok(KmsSigner { client, key_id: key_id.to_string(), public, runtime })
+    }
+}
+
+impl Signer for KmsSigner {
+    fn public_key(&self) -> &PublicKey {
+        &self.public
+    }
+
+    fn sign(&self, msg: &[u8]) -> Result<Signature, SignerError> {
+        let request = self
+            .client
+            .sign()
+            .key_id(&self.key_id)
+            .message(Blob::new(digest(msg)))
+            .message_type(MessageType::Digest)
+            .signing_algorithm(SigningAlgorithmSpec::EcdsaSha256);
+        let response = self.runtime.block_on(request.send()).map_err(backend)?;
+        let der = response.signature().ok_or_else(|| backend("KMS returned no signature"))?;
+        secp256k1_signature(EcSignature::from_der(der.as_ref()).map_err(backend)?)
+    }
+}
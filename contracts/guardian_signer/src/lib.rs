@@ -0,0 +1,112 @@
+//! Signers for deploy approvals and guardian payloads.
+//!
+//! A [`Signer`] produces Casper signatures without exposing its key: [`PemSigner`] holds a local
+//! secret key, while [`Pkcs11Signer`] (feature `pkcs11`) and [`KmsSigner`] (feature `aws-kms`) ask
+//! an HSM or AWS KMS to sign, so key material never leaves the secure module.
+
+use std::fmt;
+
+use casper_types::{bytesrepr::ToBytes, crypto, PublicKey, SecretKey, Signature};
+use k256::ecdsa::Signature as EcSignature;
+use sha2::{Digest, Sha256};
+
+#[cfg(feature = "aws-kms")]
+mod kms;
+#[cfg(feature = "pkcs11")]
+mod pkcs11;
+
+#[cfg(feature = "aws-kms")]
+pub use kms::KmsSigner;
+#[cfg(feature = "pkcs11")]
+pub use pkcs11::{KeyAlgorithm, Pkcs11Signer};
+
+#[derive(Debug)]
+pub enum SignerError {
+    /// The key couldn't be loaded or found
+    Key(String),
+    /// The backend refused or failed to sign
+    Backend(String),
+}
+
+impl fmt::Display for SignerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SignerError::Key(msg) => write!(f, "signing key unavailable: {msg}"),
+            SignerError::Backend(msg) => write!(f, "signing failed: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for SignerError {}
+
+/// Something that signs with one Casper key
+///
+/// `sign` takes the message itself, as Casper does: Ed25519 signs it directly and secp256k1 signs
+/// its SHA-256 digest. Deploys are approved by signing the 32-byte deploy hash.
+pub trait Signer: Send + Sync {
+    fn public_key(&self) -> &PublicKey;
+
+    fn sign(&self, msg: &[u8]) -> Result<Signature, SignerError>;
+
+    /// Signature without the algorithm tag byte (64 bytes), as the registry's `approve_signed` and
+    /// `rotate_my_key` take it
+    fn sign_raw(&self, msg: &[u8]) -> Result<Vec<u8>, SignerError> {
+        let mut bytes = self.sign(msg)?.to_bytes().map_err(|e| SignerError::Backend(e.to_string()))?;
+        Ok(bytes.split_off(1))
+    }
+}
+
+/// Secret key held in memory, loaded from a casper-client `secret_key.pem`
+pub struct PemSigner {
+    secret: SecretKey,
+    public: PublicKey,
+}
+
+impl PemSigner {
+    pub fn from_pem(pem: &str) -> Result<Self, SignerError> {
+        let secret = SecretKey::from_pem(pem).map_err(|e| SignerError::Key(e.to_string()))?;
+        let public = PublicKey::from(&secret);
+        Ok(PemSigner { secret, public })
+    }
+
+    pub fn from_file(path: &str) -> Result<Self, SignerError> {
+        let pem = std::fs::read_to_string(path).map_err(|e| SignerError::Key(format!("{path}: {e}")))?;
+        PemSigner::from_pem(&pem)
+    }
+}
+
+impl From<SecretKey> for PemSigner {
+    fn from(secret: SecretKey) -> Self {
+        let public = PublicKey::from(&secret);
+        PemSigner { secret, public }
+    }
+}
+
+impl Signer for PemSigner {
+    fn public_key(&self) -> &PublicKey {
+        &self.public
+    }
+
+    fn sign(&self, msg: &[u8]) -> Result<Signature, SignerError> {
+        Ok(crypto::sign(msg, &self.secret, &self.public))
+    }
+}
+
+/// SHA-256 digest that secp256k1 backends sign in place of the message
+pub(crate) fn digest(msg: &[u8]) -> [u8; 32] {
+    Sha256::digest(msg).into()
+}
+
+/// Casper secp256k1 signature from an ECDSA signature, in the low-S form verifiers require
+pub(crate) fn secp256k1_signature(sig: EcSignature) -> Result<Signature, SignerError> {
+    let sig = sig.normalize_s().unwrap_or(sig);
+    let bytes: [u8; 64] = sig.to_bytes().into();
+    Signature::secp256k1(bytes).map_err(|e| SignerError::Backend(e.to_string()))
+}
+
+/// Casper secp256k1 public key from any SEC1 encoding
+pub(crate) fn secp256k1_public_key(sec1: &[u8]) -> Result<PublicKey, SignerError> {
+    let key = k256::PublicKey::from_sec1_bytes(sec1).map_err(|e| SignerError::Key(e.to_string()))?;
+    let compressed = k256::elliptic_curve::sec1::ToEncodedPoint::to_encoded_point(&key, true);
+    PublicKey::secp256k1_from_bytes(compressed.as_bytes()).map_err(|e| SignerError::Key(e.to_string()))
+}
@@ -0,0 +1,110 @@
+//! Keys in a PKCS#11 token (HSMs, SoftHSM, YubiHSM), found by label.
+
+use std::sync::Mutex;
+
+use casper_types::{PublicKey, Signature};
+use cryptoki::{
+    context::{CInitializeArgs, Pkcs11},
+    mechanism::Mechanism,
+    object::{Attribute, AttributeType, ObjectClass, ObjectHandle},
+    session::{Session, UserType},
+    types::AuthPin,
+};
+use k256::ecdsa::Signature as EcSignature;
+
+use crate::{digest, secp256k1_public_key, secp256k1_signature, Signer, SignerError};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyAlgorithm {
+    Ed25519,
+    Secp256k1,
+}
+
+/// Key pair whose private half stays in the token; public and private objects share `key_label`
+pub struct Pkcs11Signer {
+    session: Mutex<Session>,
+    key: ObjectHandle,
+    algorithm: KeyAlgorithm,
+    public: PublicKey,
+    // Keeps the library loaded for as long as the session is open
+    _context: Pkcs11,
+}
+
+fn backend(e: impl std::fmt::Display) -> SignerError {
+    SignerError::Backend(e.to_string())
+}
+
+/// Contents of a DER OCTET STRING, or `bytes` if it isn't one (tokens differ on CKA_EC_POINT)
+fn unwrap_octet_string(bytes: &[u8]) -> &[u8] {
+    match bytes {
+        [0x04, len, rest @ ..] if *len as usize == rest.len() && *len < 0x80 => rest,
+        [0x04, 0x81, len, rest @ ..] if *len as usize == rest.len() => rest,
+        _ => bytes,
+    }
+}
+
+impl Pkcs11Signer {
+    /// Log in to the token labelled `token_label` through the module at `module_path` and use the
+    /// key pair labelled `key_label`
+    pub fn open(module_path: &str, token_label: &str, pin: &str, key_label: &str, algorithm: KeyAlgorithm) -> Result<Self, SignerError> {
+        let context = Pkcs11::new(module_path).map_err(|e| SignerError::Key(format!("{module_path}: {e}")))?;
+        context.initialize(CInitializeArgs::OsThreads).map_err(backend)?;
+
+        let slot = context
+            .get_slots_with_token()
+            .map_err(backend)?
+            .into_iter()
+            .find(|slot| context.get_token_info(*slot).is_ok_and(|info| info.label().trim_end() == token_label))
+            .ok_or_else(|| SignerError::Key(format!("no token labelled {token_label}")))?;
+        let session = context.open_ro_session(slot).map_err(backend)?;
+        session.login(UserType::User, Some(&AuthPin::new(pin.into()))).map_err(|e| SignerError::Key(e.to_string()))?;
+
+        let find = |class: ObjectClass| -> Result<ObjectHandle, SignerError> {
+            session
+                .find_objects(&[Attribute::Class(class), Attribute::Label(key_label.as_bytes().to_vec())])
+                .map_err(backend)?
+                .into_iter()
+                .next()
+                .ok_or_else(|| SignerError::Key(format!("no key labelled {key_label}")))
+        };
+        let key = find(ObjectClass::PRIVATE_KEY)?;
+        let public_object = find(ObjectClass::PUBLIC_KEY)?;
+
+        let point = match session.get_attributes(public_object, &[AttributeType::EcPoint]).map_err(backend)?.pop() {
+            Some(Attribute::EcPoint(point)) => point,
+            _ => return Err(SignerError::Key(format!("{key_label} has no EC point"))),
+        };
+        let point = unwrap_octet_string(&point);
+        let public = match algorithm {
+            KeyAlgorithm::Ed25519 => {
+                let bytes: [u8; 32] = point.try_into().map_err(|_| SignerError::Key("Ed25519 point is not 32 bytes".into()))?;
+                PublicKey::ed25519_from_bytes(bytes).map_err(|e| SignerError::Key(e.to_string()))?
+            }
+            KeyAlgorithm::Secp256k1 => secp256k1_public_key(point)?,
+        };
+
+        Ok(Pkcs11Signer { session: Mutex::new(session), key, algorithm, public, _context: context })
+    }
+}
+
+impl Signer for Pkcs11Signer {
+    fn public_key(&self) -> &PublicKey {
+        &self.public
+    }
+
+    fn sign(&self, msg: &[u8]) -> Result<Signature, SignerError> {
+        let session = self.session.lock().map_err(|_| backend("session lock poisoned"))?;
+        match self.algorithm {
+            KeyAlgorithm::Ed25519 => {
+                let sig = session.sign(&Mechanism::Eddsa, self.key, msg).map_err(backend)?;
+                let bytes: [u8; 64] = sig.as_slice().try_into().map_err(|_| backend("Ed25519 signature is not 64 bytes"))?;
+                Signature::ed25519(bytes).map_err(backend)
+            }
+            KeyAlgorithm::Secp256k1 => {
+                // CKM_ECDSA signs a prehashed message and returns r ‖ s
+                let sig = session.sign(&Mechanism::Ecdsa, self.key, &digest(msg)).map_err(backend)?;
+                secp256k1_signature(EcSignature::from_slice(&sig).map_err(backend)?)
+            }
+        }
+    }
+}
@@ -0,0 +1,296 @@
+//! JavaScript/TypeScript bindings for guardian-types, built with `make wasm-pkg`.
+//!
+//! Wallets use these to derive recovery ids, build the exact bytes guardians sign, encode account
+//! settings and check a guardian set before submitting anything. Keys are hex public keys
+//! (with their algorithm tag byte); accounts are a public key, an `account-hash-…` or
+//! `entity-account-…` string or raw account hash hex. Recovery ids are decimal strings.
+
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{FromBytes, ToBytes},
+    PublicKey, U256,
+};
+use guardian_types::{
+    host, ids,
+    meta::{NonceError, NonceLedger},
+    payloads,
+    settings::AccountSettings,
+    templates, validation, GuardianError,
+};
+use wasm_bindgen::prelude::*;
+
+fn public_key(value: &str) -> Result<PublicKey, JsError> {
+    let bytes = hex::decode(value).map_err(|_| JsError::new(&format!("Not hex: {value}")))?;
+    PublicKey::from_bytes(&bytes)
+        .ok()
+        .filter(|(_, rest)| rest.is_empty())
+        .map(|(key, _)| key)
+        .ok_or_else(|| JsError::new(&format!("Not a public key: {value}")))
+}
+
+fn account_hash(value: &str) -> Result<AccountHash, JsError> {
+    if value.contains('-') {
+        return host::formatted_account(value).ok_or_else(|| JsError::new(&format!("Not an account hash: {value}")));
+    }
+    if value.len() == 64 {
+        let bytes = hex::decode(value).map_err(|_| JsError::new(&format!("Not hex: {value}")))?;
+        return AccountHash::try_from(bytes.as_slice()).map_err(|_| JsError::new(&format!("Not an account hash: {value}")));
+    }
+    Ok(public_key(value)?.to_account_hash())
+}
+
+fn recovery_id_arg(value: &str) -> Result<U256, JsError> {
+    U256::from_dec_str(value).map_err(|_| JsError::new(&format!("Not a decimal recovery id: {value}")))
+}
+
+/// Account hash hex of a public key or account hash
+#[wasm_bindgen(js_name = accountHash)]
+pub fn account_hash_hex(account: &str) -> Result<String, JsError> {
+    Ok(hex::encode(account_hash(account)?.value()))
+}
+
+/// blake2b of the proposed key's bytesrepr encoding, as the registry stores it
+#[wasm_bindgen(js_name = keyCommitment)]
+pub fn key_commitment(new_key: &str) -> Result<Vec<u8>, JsError> {
+    Ok(ids::key_commitment(&public_key(new_key)?).to_vec())
+}
+
+/// Decimal id `start_recovery` will assign to `account`'s recovery number `nonce` to `new_key`
+#[wasm_bindgen(js_name = recoveryId)]
+pub fn recovery_id(account: &str, nonce: u64, new_key: &str) -> Result<String, JsError> {
+    let commitment = ids::key_commitment(&public_key(new_key)?);
+    Ok(ids::recovery_id(account_hash(account)?, nonce, commitment).to_string())
+}
+
+/// Bytes a guardian signs for `approve_signed`
+#[wasm_bindgen(js_name = approvalPayload)]
+pub fn approval_payload(id: &str, account: &str, new_key: &str, memo: String) -> Result<Vec<u8>, JsError> {
+    Ok(payloads::approval_payload(recovery_id_arg(id)?, account_hash(account)?, &public_key(new_key)?, &memo))
+}
+
+/// Bytes a guardian signs for a relayer's `approve_meta`; `nonce` is the guardian's meta nonce.
+/// Sign through `MetaNonces` to avoid reusing or skipping one.
+#[wasm_bindgen(js_name = metaApprovalPayload)]
+pub fn meta_approval_payload(id: &str, account: &str, new_key: &str, memo: String, nonce: u64) -> Result<Vec<u8>, JsError> {
+    Ok(payloads::meta_approval_payload(recovery_id_arg(id)?, account_hash(account)?, &public_key(new_key)?, &memo, nonce))
+}
+
+/// Domain tag every `metaApprovalPayload` starts with
+#[wasm_bindgen(js_name = metaDomain)]
+pub fn meta_domain() -> Vec<u8> {
+    payloads::META_DOMAIN.to_vec()
+}
+
+/// Bytes both the old and the new key sign for `rotate_my_key`; `nonce` is the account's rotation count
+#[wasm_bindgen(js_name = rotationPayload)]
+pub fn rotation_payload(account: &str, nonce: u64, old_key: &str, new_key: &str) -> Result<Vec<u8>, JsError> {
+    Ok(payloads::rotation_payload(account_hash(account)?, nonce, &public_key(old_key)?, &public_key(new_key)?))
+}
+
+/// Message a FROST guardian group signs for `approve_frost`
+#[wasm_bindgen(js_name = frostApprovalPayload)]
+pub fn frost_approval_payload(id: &str, account: &str, new_key: &str) -> Result<Vec<u8>, JsError> {
+    Ok(payloads::frost_approval_payload(recovery_id_arg(id)?, account_hash(account)?, &public_key(new_key)?))
+}
+
+/// Throws unless `set_up` would accept `guardians` for `owner` with `threshold`
+#[wasm_bindgen(js_name = validateGuardianSet)]
+pub fn validate_guardian_set(owner: &str, guardians: Vec<String>, threshold: u8) -> Result<(), JsError> {
+    let guards = guardians.iter().map(|g| account_hash(g)).collect::<Result<Vec<_>, _>>()?;
+    validation::check_guardian_set(account_hash(owner)?, &guards, threshold).map_err(|error| match error {
+        GuardianError::InvalidThreshold => JsError::new("Threshold must be between 1 and the number of guardians"),
+        _ => JsError::new("Need at least 2 distinct guardians, none of them the owner"),
+    })
+}
+
+/// Account settings as passed to `update_settings` and stored by the registry
+#[wasm_bindgen]
+#[derive(Clone, Copy, Default)]
+pub struct Settings {
+    pub expiry: u64,
+    pub delay: u64,
+    #[wasm_bindgen(js_name = maxAttempts)]
+    pub max_attempts: u8,
+    #[wasm_bindgen(js_name = vetoWindow)]
+    pub veto_window: u64,
+    #[wasm_bindgen(js_name = approvalWindow)]
+    pub approval_window: u64,
+    #[wasm_bindgen(js_name = byHeight)]
+    pub by_height: bool,
+    #[wasm_bindgen(js_name = rotationWindow)]
+    pub rotation_window: u64,
+    #[wasm_bindgen(js_name = freezePeriod)]
+    pub freeze_period: u64,
+    #[wasm_bindgen(js_name = gracePeriod)]
+    pub grace_period: u64,
+    #[wasm_bindgen(js_name = graceWeight)]
+    pub grace_weight: u8,
+    pub cooldown: u64,
+}
+
+impl From<Settings> for AccountSettings {
+    fn from(s: Settings) -> Self {
+        AccountSettings {
+            expiry: s.expiry, delay: s.delay, max_attempts: s.max_attempts, veto_window: s.veto_window,
+            approval_window: s.approval_window, by_height: s.by_height, rotation_window: s.rotation_window,
+            freeze_period: s.freeze_period, grace_period: s.grace_period, grace_weight: s.grace_weight,
+            cooldown: s.cooldown,
+        }
+    }
+}
+
+impl From<AccountSettings> for Settings {
+    fn from(s: AccountSettings) -> Self {
+        Settings {
+            expiry: s.expiry, delay: s.delay, max_attempts: s.max_attempts, veto_window: s.veto_window,
+            approval_window: s.approval_window, by_height: s.by_height, rotation_window: s.rotation_window,
+            freeze_period: s.freeze_period, grace_period: s.grace_period, grace_weight: s.grace_weight,
+            cooldown: s.cooldown,
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl Settings {
+    /// All-zero settings, every feature disabled
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Settings {
+        Settings::default()
+    }
+
+    /// Decode the registry's bytesrepr encoding
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Settings, JsError> {
+        match AccountSettings::from_bytes(bytes) {
+            Ok((settings, rest)) if rest.is_empty() => Ok(settings.into()),
+            _ => Err(JsError::new("Not encoded account settings")),
+        }
+    }
+
+    /// bytesrepr encoding, as stored by the registry
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        AccountSettings::from(*self).to_bytes().unwrap_or_default()
+    }
+
+    /// False if `update_settings` would reject these
+    #[wasm_bindgen(js_name = isValid)]
+    pub fn is_valid(&self) -> bool {
+        AccountSettings::from(*self).is_valid()
+    }
+}
+
+/// A built-in guardian policy template, as `init_from_template` applies it
+#[wasm_bindgen]
+pub struct Template {
+    inner: &'static templates::Template,
+}
+
+#[wasm_bindgen]
+impl Template {
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> String {
+        self.inner.name.to_string()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn description(&self) -> String {
+        self.inner.description.to_string()
+    }
+
+    /// What each guardian stands for, in the order `init_from_template` takes them
+    #[wasm_bindgen(getter)]
+    pub fn slots(&self) -> Vec<String> {
+        self.inner.slots.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn threshold(&self) -> u8 {
+        self.inner.threshold
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn settings(&self) -> Settings {
+        self.inner.settings.into()
+    }
+
+    /// Throws unless `init_from_template` would accept `guardians`, in slot order, for `owner`
+    pub fn validate(&self, owner: &str, guardians: Vec<String>) -> Result<(), JsError> {
+        if guardians.len() != self.inner.slots.len() {
+            return Err(JsError::new(&format!("{} takes {} guardians: {}", self.inner.name, self.inner.slots.len(), self.inner.slots.join(", "))));
+        }
+        validate_guardian_set(owner, guardians, self.inner.threshold)
+    }
+}
+
+/// Names of the built-in templates
+#[wasm_bindgen(js_name = templateNames)]
+pub fn template_names() -> Vec<String> {
+    templates::ALL.iter().map(|t| t.name.to_string()).collect()
+}
+
+/// Built-in template called `name`
+#[wasm_bindgen]
+pub fn template(name: &str) -> Result<Template, JsError> {
+    templates::find(name).map(|inner| Template { inner }).ok_or_else(|| JsError::new(&format!("No template called {name}")))
+}
+
+fn nonce_refused(e: NonceError) -> JsError {
+    JsError::new(e.description())
+}
+
+/// Nonces one guardian has signed meta-approvals with, so a wallet never signs with a nonce the
+/// registry has moved past, one already signed for another approval, or one past the next free
+/// nonce. Keep it across sessions with `toBytes`/`fromBytes`, one per guardian key, and pass the
+/// guardian's nonce on chain (`GET /api/v1/guardians/{guardian}/meta-nonce`) as `chainNonce`.
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct MetaNonces {
+    inner: NonceLedger,
+}
+
+#[wasm_bindgen]
+impl MetaNonces {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> MetaNonces {
+        MetaNonces::default()
+    }
+
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<MetaNonces, JsError> {
+        match NonceLedger::from_bytes(bytes) {
+            Ok((inner, rest)) if rest.is_empty() => Ok(MetaNonces { inner }),
+            _ => Err(JsError::new("Not an encoded nonce ledger")),
+        }
+    }
+
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.inner.to_bytes().unwrap_or_default()
+    }
+
+    /// Nonce to sign the next meta-approval with
+    #[wasm_bindgen(js_name = nextNonce)]
+    pub fn next_nonce(&self, chain_nonce: u64) -> u64 {
+        self.inner.next(chain_nonce)
+    }
+
+    /// Payload to sign for approving recovery `id` with the next free nonce (or `nonce`), recorded
+    /// as signed; throws instead if that nonce is refused. The nonce is the payload's last 8 bytes.
+    pub fn issue(&mut self, id: &str, account: &str, new_key: &str, memo: String, chain_nonce: u64, nonce: Option<u64>) -> Result<Vec<u8>, JsError> {
+        let nonce = nonce.unwrap_or_else(|| self.inner.next(chain_nonce));
+        let msg = meta_approval_payload(id, account, new_key, memo, nonce)?;
+        self.inner.record(chain_nonce, nonce, msg.clone()).map_err(nonce_refused)?;
+        Ok(msg)
+    }
+
+    /// Throws unless `payload` may be signed with `nonce`
+    pub fn check(&self, chain_nonce: u64, nonce: u64, payload: &[u8]) -> Result<(), JsError> {
+        self.inner.check(chain_nonce, nonce, payload).map_err(nonce_refused)
+    }
+
+    /// Forget nonces the registry has used or voided
+    pub fn prune(&mut self, chain_nonce: u64) {
+        self.inner.prune(chain_nonce);
+    }
+}
@@ -0,0 +1,67 @@
+//! Call helpers for contracts gating actions on guardianship in the recovery registry, and the
+//! `RecoveryModule` trait for contracts plugging into its recoveries.
+//!
+//! These wrap the registry's versioned guardianship queries (`GUARDIANSHIP_VERSION`). Their names,
+//! arguments and return types are only ever added to, never changed; a breaking change ships as
+//! new `_v2` entry points alongside the old ones. `quorum_certificate` reads the record the registry
+//! keeps of each finalized recovery, for contracts such as the recovery executor that act on them.
+//!
+//! ```ignore
+//! if !guardian_interface::is_guardian_of(registry, account, Key::Hash(self_package)) {
+//!     runtime::revert(ApiError::PermissionDenied);
+//! }
+//! ```
+
+#![no_std]
+
+extern crate alloc;
+#[cfg(not(feature = "casper-2"))]
+extern crate casper_contract_1 as casper_contract;
+#[cfg(not(feature = "casper-2"))]
+extern crate casper_types_1 as casper_types;
+
+pub mod module;
+
+pub use module::RecoveryModule;
+
+use alloc::vec::Vec;
+use casper_contract::contract_api::runtime;
+use casper_types::{account::AccountHash, contracts::ContractPackageHash, runtime_args, Key, PublicKey, U256};
+
+pub use guardian_types::interfaces::{GUARDIANSHIP_QUERIES, GUARDIANSHIP_VERSION};
+
+pub const ENTRY_IS_GUARDIAN_OF: &str = "is_guardian_of_v1";
+pub const ENTRY_GUARDIAN_COUNT: &str = "guardian_count_v1";
+pub const ENTRY_HAS_MIN_GUARDIANS: &str = "has_min_guardians_v1";
+pub const ENTRY_GUARDIANSHIP_VERSION: &str = "guardianship_version";
+pub const ENTRY_QUORUM_CERTIFICATE: &str = "get_quorum_certificate";
+
+/// A finalized recovery as the registry certifies it: ((id, account, new key), each counted approval
+/// as (guardian, weight, time), (finalized at, arbiter broke a tie)). A weight is what the account's
+/// approval policy counted the approval for, at most 255.
+pub type QuorumCertificate = ((U256, AccountHash, PublicKey), Vec<(Key, u8, u64)>, (u64, bool));
+
+/// True if `who` (an account as `Key::Account`, or a contract package as `Key::Hash`) is a guardian of `account`
+pub fn is_guardian_of(registry: ContractPackageHash, account: AccountHash, who: Key) -> bool {
+    runtime::call_versioned_contract(registry, None, ENTRY_IS_GUARDIAN_OF, runtime_args! { "account" => account, "guardian" => who })
+}
+
+/// Number of guardians `account` has across all guardian kinds; zero if it has none
+pub fn guardian_count(registry: ContractPackageHash, account: AccountHash) -> u32 {
+    runtime::call_versioned_contract(registry, None, ENTRY_GUARDIAN_COUNT, runtime_args! { "account" => account })
+}
+
+/// True if `account` has at least `min` guardians
+pub fn has_min_guardians(registry: ContractPackageHash, account: AccountHash, min: u32) -> bool {
+    runtime::call_versioned_contract(registry, None, ENTRY_HAS_MIN_GUARDIANS, runtime_args! { "account" => account, "min" => min })
+}
+
+/// Highest guardianship query version the registry serves
+pub fn guardianship_version(registry: ContractPackageHash) -> u32 {
+    runtime::call_versioned_contract(registry, None, ENTRY_GUARDIANSHIP_VERSION, runtime_args! {})
+}
+
+/// Quorum certificate of recovery `id`, once it has finalized
+pub fn quorum_certificate(registry: ContractPackageHash, id: U256) -> Option<QuorumCertificate> {
+    runtime::call_versioned_contract(registry, None, ENTRY_QUORUM_CERTIFICATE, runtime_args! { "id" => id })
+}
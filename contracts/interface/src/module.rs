@@ -0,0 +1,82 @@
+//! The `RecoveryModule` trait for contracts that plug into an account's recoveries (see
+//! `guardian_types::modules`). Implement the hooks a module cares about, install
+//! `guardian_types::entry_points::module_entry_points()` next to the module's own entry points, and
+//! let `recovery_module!` export the hooks:
+//!
+//! ```ignore
+//! struct BusinessHours;
+//!
+//! impl RecoveryModule for BusinessHours {
+//!     fn on_initiate(&self, _id: U256, _account: AccountHash, _new_key: &PublicKey) -> Result<(), u16> {
+//!         if in_business_hours(runtime::get_blocktime()) { Ok(()) } else { Err(1) }
+//!     }
+//! }
+//!
+//! guardian_interface::recovery_module!(BusinessHours);
+//! ```
+//!
+//! Hooks run inside the registry's call, so the registry's own records are already written, and an
+//! `Err(code)` reverts the whole step with `ApiError::User(code)`. Hooks should check the caller is
+//! the registry package they expect before trusting their arguments.
+
+use casper_types::{account::AccountHash, Key, PublicKey, U256};
+
+#[doc(hidden)]
+pub use casper_contract::contract_api::runtime as __runtime;
+#[doc(hidden)]
+pub use casper_types::ApiError as __ApiError;
+
+/// Hooks of one recovery module; each allows the step unless overridden
+pub trait RecoveryModule {
+    /// Recovery `id` of `account` to `new_key` was started
+    fn on_initiate(&self, _id: U256, _account: AccountHash, _new_key: &PublicKey) -> Result<(), u16> {
+        Ok(())
+    }
+
+    /// `guardian`'s approval of recovery `id` was counted
+    fn on_approve(&self, _id: U256, _account: AccountHash, _guardian: Key) -> Result<(), u16> {
+        Ok(())
+    }
+
+    /// Recovery `id` was finalized, so the account's keys are about to be rotated
+    fn on_execute(&self, _id: U256, _account: AccountHash) -> Result<(), u16> {
+        Ok(())
+    }
+}
+
+/// Export the hook entry points of the `RecoveryModule` value `$module`
+#[macro_export]
+macro_rules! recovery_module {
+    ($module:expr) => {
+        #[no_mangle]
+        pub extern "C" fn module_on_initiate_v1() {
+            use $crate::module::__runtime as runtime;
+            let r = $crate::RecoveryModule::on_initiate(
+                &$module,
+                runtime::get_named_arg("id"),
+                runtime::get_named_arg("account"),
+                &runtime::get_named_arg("new_key"),
+            );
+            if let Err(code) = r { runtime::revert($crate::module::__ApiError::User(code)); }
+        }
+
+        #[no_mangle]
+        pub extern "C" fn module_on_approve_v1() {
+            use $crate::module::__runtime as runtime;
+            let r = $crate::RecoveryModule::on_approve(
+                &$module,
+                runtime::get_named_arg("id"),
+                runtime::get_named_arg("account"),
+                runtime::get_named_arg("guardian"),
+            );
+            if let Err(code) = r { runtime::revert($crate::module::__ApiError::User(code)); }
+        }
+
+        #[no_mangle]
+        pub extern "C" fn module_on_execute_v1() {
+            use $crate::module::__runtime as runtime;
+            let r = $crate::RecoveryModule::on_execute(&$module, runtime::get_named_arg("id"), runtime::get_named_arg("account"));
+            if let Err(code) = r { runtime::revert($crate::module::__ApiError::User(code)); }
+        }
+    };
+}
@@ -0,0 +1,201 @@
+//! Recovery Executor Contract
+//!
+//! Carries out recoveries the registry has finalized, so the registry stays pure bookkeeping and
+//! either side can be upgraded or replaced without the other:
+//! 1. Anyone `queue`s a finalized recovery; the executor reads its quorum certificate from the
+//!    registry and starts its own timelock
+//! 2. Once the timelock has passed, the recovery_key_rotation session of the recovered account calls
+//!    `execute`, which hands back the new key exactly once, and rotates the account's keys
+//!
+//! The admin who installed the executor can point it at another registry package or change the
+//! timelock; recoveries already queued keep the time they were queued with.
+
+#![no_std]
+#![no_main]
+
+#[cfg(not(target_arch = "wasm32"))]
+compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
+
+#[cfg(not(feature = "casper-2"))]
+extern crate casper_contract_1 as casper_contract;
+#[cfg(not(feature = "casper-2"))]
+extern crate casper_types_1 as casper_types;
+
+extern crate alloc;
+
+use alloc::{format, string::ToString};
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{FromBytes, ToBytes},
+    contracts::{ContractPackageHash, NamedKeys},
+    ApiError, CLTyped, CLValue, Key, PublicKey, URef, U256,
+};
+use guardian_types::{entry_points, time::{Instant, Span}};
+
+const DICT: &str = "d";
+const ARG_REGISTRY: &str = "registry";
+const ARG_TIMELOCK: &str = "timelock";
+/// Default time between queueing a finalized recovery and executing it, in ms
+const DEFAULT_TIMELOCK: Span = Span::days(1);
+
+// Custom errors
+#[repr(u16)]
+enum ExecutorError {
+    MissingDict = 1,
+    NotAdmin = 2,
+    BadRegistry = 3,
+    NotFinalized = 4,
+    AlreadyQueued = 5,
+    NotQueued = 6,
+    NotAccount = 7,
+    Timelocked = 8,
+    AlreadyExecuted = 9,
+}
+
+impl From<ExecutorError> for ApiError {
+    fn from(error: ExecutorError) -> Self {
+        ApiError::User(error as u16)
+    }
+}
+
+fn get_dict() -> URef {
+    runtime::get_key(DICT)
+        .unwrap_or_revert_with(ExecutorError::MissingDict)
+        .into_uref()
+        .unwrap_or_revert_with(ExecutorError::MissingDict)
+}
+
+fn read<T: CLTyped + FromBytes>(k: &str) -> Option<T> {
+    storage::dictionary_get(get_dict(), k).unwrap_or(None)
+}
+
+fn write<T: CLTyped + ToBytes>(k: &str, v: T) {
+    storage::dictionary_put(get_dict(), k, v);
+}
+
+fn now() -> Instant {
+    Instant(runtime::get_blocktime().into())
+}
+
+fn registry() -> ContractPackageHash {
+    match runtime::get_key(ARG_REGISTRY) {
+        Some(Key::Hash(addr)) => ContractPackageHash::new(addr),
+        _ => runtime::revert(ExecutorError::BadRegistry),
+    }
+}
+
+fn timelock_uref() -> URef {
+    runtime::get_key(ARG_TIMELOCK).and_then(|k| k.into_uref()).unwrap_or_revert()
+}
+
+fn only_admin() {
+    if runtime::get_key("admin") != Some(Key::Account(runtime::get_caller())) {
+        runtime::revert(ExecutorError::NotAdmin);
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn init_storage() {
+    storage::new_dictionary(DICT).unwrap_or_revert();
+}
+
+/// Queue finalized recovery `id` for execution. Returns the block time from which `execute` succeeds.
+#[no_mangle]
+pub extern "C" fn queue() {
+    let id: U256 = runtime::get_named_arg("id");
+
+    let ((_, acc, new_key), _, _) = guardian_interface::quorum_certificate(registry(), id)
+        .unwrap_or_revert_with(ExecutorError::NotFinalized);
+    if read::<(AccountHash, PublicKey, u64)>(&format!("q{}", id)).is_some() { runtime::revert(ExecutorError::AlreadyQueued); }
+
+    let timelock: u64 = storage::read(timelock_uref()).unwrap_or_revert().unwrap_or_revert();
+    let ready = now().plus(Span(timelock)).0;
+    write(&format!("q{}", id), (acc, new_key, ready));
+    runtime::ret(CLValue::from_t(ready).unwrap_or_revert());
+}
+
+/// Claim queued recovery `id` for the rotation session of its account, once its timelock has passed.
+/// Returns the new key to rotate to; each recovery executes once.
+#[no_mangle]
+pub extern "C" fn execute() {
+    let id: U256 = runtime::get_named_arg("id");
+
+    let (acc, new_key, ready) = read::<(AccountHash, PublicKey, u64)>(&format!("q{}", id))
+        .unwrap_or_revert_with(ExecutorError::NotQueued);
+    // Only the recovered account itself, i.e. its rotation session, may act on the recovery
+    if runtime::get_caller() != acc { runtime::revert(ExecutorError::NotAccount); }
+    if !Instant(ready).reached(now()) { runtime::revert(ExecutorError::Timelocked); }
+    let key = format!("x{}", id);
+    if read::<u64>(&key).is_some() { runtime::revert(ExecutorError::AlreadyExecuted); }
+
+    write(&key, now().0);
+    runtime::ret(CLValue::from_t(new_key).unwrap_or_revert());
+}
+
+/// Returns (account, block time executable from, block time executed or 0) of recovery `id`, if queued
+#[no_mangle]
+pub extern "C" fn get_execution() {
+    let id: U256 = runtime::get_named_arg("id");
+    let queued = read::<(AccountHash, PublicKey, u64)>(&format!("q{}", id))
+        .map(|(acc, _, ready)| (acc, ready, read::<u64>(&format!("x{}", id)).unwrap_or(0)));
+    runtime::ret(CLValue::from_t(queued).unwrap_or_revert());
+}
+
+/// Point the executor at another registry package (`Key::Hash`), e.g. after replacing the registry
+#[no_mangle]
+pub extern "C" fn set_registry() {
+    only_admin();
+    let registry: Key = runtime::get_named_arg(ARG_REGISTRY);
+    if !matches!(registry, Key::Hash(_)) { runtime::revert(ExecutorError::BadRegistry); }
+    runtime::put_key(ARG_REGISTRY, registry);
+}
+
+/// Timelock for recoveries queued from now on, in ms
+#[no_mangle]
+pub extern "C" fn set_timelock() {
+    only_admin();
+    let timelock: u64 = runtime::get_named_arg(ARG_TIMELOCK);
+    storage::write(timelock_uref(), timelock);
+}
+
+/// Returns (registry package, timelock, admin)
+#[no_mangle]
+pub extern "C" fn get_executor_config() {
+    let timelock: u64 = storage::read(timelock_uref()).unwrap_or_revert().unwrap_or_revert();
+    let admin = runtime::get_key("admin").unwrap_or_revert();
+    runtime::ret(CLValue::from_t((Key::Hash(registry().value()), timelock, admin)).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let registry: Key = runtime::get_named_arg(ARG_REGISTRY);
+    if !matches!(registry, Key::Hash(_)) { runtime::revert(ExecutorError::BadRegistry); }
+    let timelock: u64 = runtime::try_get_named_arg(ARG_TIMELOCK).unwrap_or(DEFAULT_TIMELOCK.0);
+
+    let mut keys = NamedKeys::new();
+    keys.insert(ARG_REGISTRY.to_string(), registry);
+    keys.insert(ARG_TIMELOCK.to_string(), storage::new_uref(timelock).into());
+    keys.insert("admin".to_string(), Key::Account(runtime::get_caller()));
+
+    // Upgradable through the access key, unlike the registry
+    #[cfg(feature = "casper-2")]
+    let (hash, _) = storage::new_contract(
+        entry_points::executor_entry_points().into(),
+        Some(keys),
+        Some("recovery_executor_package".to_string()),
+        Some("recovery_executor_access".to_string()),
+        None,
+    );
+    #[cfg(not(feature = "casper-2"))]
+    let (hash, _) = storage::new_contract(
+        entry_points::executor_entry_points(),
+        Some(keys),
+        Some("recovery_executor_package".to_string()),
+        Some("recovery_executor_access".to_string()),
+    );
+    runtime::put_key("recovery_executor_contract_hash", Key::Hash(hash.value()));
+}
@@ -3,9 +3,13 @@
 //! This contract performs a complete account key rotation for recovery:
 //! 1. Adds a new associated key with specified weight
 //! 2. Updates thresholds to give the new key control
-//! 3. Removes the old (lost) associated key
+//! 3. Removes the old (lost) associated key, or with `old_key_weight` keeps it at that reduced
+//!    weight for the account's grace period (see the registry's `get_grace`/`finalize_removal`)
 //!
-//! All operations happen in a single deploy, requiring multi-sig from guardians.
+//! All operations happen in a single deploy, requiring multi-sig from guardians. With `executor` and
+//! `recovery_id` the session first claims the recovery from the recovery executor contract, which
+//! checks the registry finalized it and its timelock has passed, and rotates only to the key the
+//! registry certified.
 
 #![no_std]
 #![no_main]
@@ -13,12 +17,18 @@
 #[cfg(not(target_arch = "wasm32"))]
 compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
 
+#[cfg(not(feature = "casper-2"))]
+extern crate casper_contract_1 as casper_contract;
+#[cfg(not(feature = "casper-2"))]
+extern crate casper_types_1 as casper_types;
+
 extern crate alloc;
 
 use casper_contract::contract_api::{account, runtime};
 use casper_contract::unwrap_or_revert::UnwrapOrRevert;
 use casper_types::account::{ActionType, Weight};
-use casper_types::{ApiError, Key};
+use casper_types::contracts::ContractPackageHash;
+use casper_types::{runtime_args, ApiError, Key, PublicKey, U256};
 
 // Runtime argument names
 const ARG_NEW_KEY: &str = "new_key";
@@ -26,6 +36,9 @@ const ARG_NEW_KEY_WEIGHT: &str = "new_key_weight";
 const ARG_OLD_KEY: &str = "old_key";
 const ARG_DEPLOYMENT_THRESHOLD: &str = "deployment_threshold";
 const ARG_KEY_MANAGEMENT_THRESHOLD: &str = "key_management_threshold";
+const ARG_OLD_KEY_WEIGHT: &str = "old_key_weight";
+const ARG_EXECUTOR: &str = "executor";
+const ARG_RECOVERY_ID: &str = "recovery_id";
 
 // Custom errors
 #[repr(u16)]
@@ -35,6 +48,9 @@ enum RecoveryError {
     AddKeyFailed = 3,
     UpdateThresholdsFailed = 4,
     RemoveKeyFailed = 5,
+    UpdateKeyFailed = 6,
+    /// `new_key` isn't the key the executor released for the recovery
+    NotCertifiedKey = 7,
 }
 
 impl From<RecoveryError> for ApiError {
@@ -51,6 +67,7 @@ pub extern "C" fn call() {
     let old_key: Key = runtime::get_named_arg(ARG_OLD_KEY);
     let deployment_threshold: u8 = runtime::get_named_arg(ARG_DEPLOYMENT_THRESHOLD);
     let key_management_threshold: u8 = runtime::get_named_arg(ARG_KEY_MANAGEMENT_THRESHOLD);
+    let old_key_weight: u8 = runtime::try_get_named_arg(ARG_OLD_KEY_WEIGHT).unwrap_or(0);
 
     // 2. Validate keys are account hashes
     let new_account_hash = match new_key {
@@ -63,6 +80,15 @@ pub extern "C" fn call() {
         _ => runtime::revert(RecoveryError::InvalidOldKey),
     };
 
+    // Claim the recovery from the executor, which reverts unless it is finalized and past its timelock
+    if let Some(executor) = runtime::try_get_named_arg::<Key>(ARG_EXECUTOR) {
+        let Key::Hash(executor) = executor else { runtime::revert(ApiError::InvalidArgument) };
+        let executor = ContractPackageHash::new(executor);
+        let id: U256 = runtime::get_named_arg(ARG_RECOVERY_ID);
+        let certified: PublicKey = runtime::call_versioned_contract(executor, None, "execute", runtime_args! { "id" => id });
+        if certified.to_account_hash() != new_account_hash { runtime::revert(RecoveryError::NotCertifiedKey); }
+    }
+
     // Step 1: Add the new key with specified weight
     // This gives the new key permission to participate in account operations
     account::add_associated_key(new_account_hash, Weight::new(new_key_weight))
@@ -77,7 +103,15 @@ pub extern "C" fn call() {
     account::set_action_threshold(ActionType::Deployment, Weight::new(deployment_threshold))
         .unwrap_or_revert_with(RecoveryError::UpdateThresholdsFailed);
 
-    // Step 3: Remove the old key
+    // Step 3: Remove the old key, or reduce it to its grace weight
+    // During a grace period the old key stays below the thresholds so a mistaken recovery can be
+    // unwound; it is removed later with the remove_associated_key session
+    if old_key_weight > 0 {
+        account::update_associated_key(old_account_hash, Weight::new(old_key_weight))
+            .unwrap_or_revert_with(RecoveryError::UpdateKeyFailed);
+        return;
+    }
+
     // The old (lost) key is removed from the account
     // Note: Using try pattern as removal might fail if key doesn't exist
     let _ = account::remove_associated_key(old_account_hash);
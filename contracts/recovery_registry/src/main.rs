@@ -2,46 +2,109 @@
 #![no_main]
 
 extern crate alloc;
+#[cfg(not(feature = "casper-2"))]
+extern crate casper_contract_1 as casper_contract;
+#[cfg(not(feature = "casper-2"))]
+extern crate casper_types_1 as casper_types;
 
-use alloc::{vec::Vec, vec, boxed::Box, format};
-use alloc::string::ToString;
+mod sig;
+mod verify;
+
+use alloc::{collections::BTreeSet, vec::Vec, vec, format};
+use core::sync::atomic::{AtomicU64, Ordering};
+use alloc::string::{String, ToString};
+use guardian_types::{
+    counters, deprecations, entry_points, events::{self, Event}, fees, host, ids, interfaces, lifecycle, migrations, modules,
+    payloads,
+    policy::{self, ApprovalPolicy, Tally}, prefixes, reputation, roles,
+    settings::AccountSettings, templates, time::{Instant, Span}, validation, GuardianError,
+    RegistryError as Err,
+};
 use casper_contract::{
-    contract_api::{runtime, storage},
+    contract_api::{runtime, storage, system},
+    ext_ffi,
     unwrap_or_revert::UnwrapOrRevert,
 };
 use casper_types::{
     account::AccountHash, 
-    contracts::{EntryPoint, EntryPoints},
-    EntryPointAccess,
-    EntryPointType, 
+    contracts::{ContractHash, ContractPackageHash, NamedKeys},
     ApiError,
     Key, 
-    Parameter,
     PublicKey, 
     U256,
+    U512,
     URef,
     CLTyped,
     bytesrepr::{FromBytes, ToBytes},
-    CLType,
     CLValue,
+    RuntimeArgs,
+    runtime_args,
 };
-
-#[repr(u16)]
-enum Err {
-    NotOwner = 1,
-    AlreadyInit = 2,
-    BadGuardians = 3,
-    BadThreshold = 4,
-    NotGuardian = 5,
-    RecoveryExists = 6,
-    NotFound = 7,
-    AlreadyApproved = 8,
-    NotApproved = 9,
-    NotInit = 10,
-    MissingDict = 11,
-}
+#[cfg(any(feature = "events", feature = "policies"))]
+use casper_types::bytesrepr::Bytes;
 
 const DICT: &str = "d";
+/// Named key of the (decommissioned at, sunset end) URef, once the admin has decommissioned the registry
+const DECOMMISSION: &str = "decommission";
+/// Contract purse protocol fees are collected into
+const FEE_PURSE: &str = "fee_purse";
+/// Contract purse recovery deposits are held in until settled
+const DEPOSIT_PURSE: &str = "deposit_purse";
+/// Contract purse guardians' bonds are held in
+#[cfg(feature = "bonding")]
+const BOND_PURSE: &str = "bond_purse";
+/// Contract purse accounts' reward balances are held in
+const REWARD_PURSE: &str = "reward_purse";
+/// Named key present on registries installed with `strict_args`
+const STRICT_ARGS: &str = "strict_args";
+/// Optional modules this build of the registry implements
+const INTERFACES: u64 = interfaces::mask(&[
+    interfaces::SIGNED_APPROVALS,
+    interfaces::INHERITANCE,
+    #[cfg(feature = "bonding")]
+    interfaces::BONDING,
+    interfaces::DELEGATION,
+    interfaces::PRIVATE_GUARDIANS,
+    interfaces::CONTRACT_GUARDIANS,
+    interfaces::REWARDS,
+    interfaces::ARBITER,
+    interfaces::CONFIRMATION,
+    interfaces::GUARDIANSHIP_QUERIES,
+    interfaces::FROST_APPROVALS,
+    interfaces::FLAGGING,
+    #[cfg(feature = "policies")]
+    interfaces::CATEGORY_QUORUMS,
+    #[cfg(feature = "policies")]
+    interfaces::APPROVAL_POLICIES,
+    interfaces::RECOVERY_MODULES,
+    #[cfg(feature = "events")]
+    interfaces::EVENTS,
+    interfaces::META_APPROVALS,
+    interfaces::GUARDIAN_DIRECTORY,
+    interfaces::PROTOCOL_FEES,
+    interfaces::TENANTS,
+    interfaces::ACCOUNT_ROLES,
+    interfaces::POLICY_TEMPLATES,
+]);
+/// Key policy bits: which `PublicKey` algorithms an account accepts for its guardians
+const KEY_ED25519: u8 = 1;
+const KEY_SECP256K1: u8 = 2;
+/// Shortest configuration freeze after a malicious veto, whatever the account's settings say, so an
+/// attacker holding the owner key can't switch it off first
+const MIN_FREEZE: Span = Span::days(3);
+/// Most watch-only parties an account can have notified
+const MAX_WATCHERS: usize = 16;
+/// Most members an account can grant roles to
+const MAX_ROLE_MEMBERS: usize = 16;
+/// Most relayers an account can allowlist for meta-approvals
+const MAX_RELAYERS: usize = 16;
+/// Most entries `init_guardians_batch` takes in one call, to stay well inside the gas limit
+const MAX_BATCH: usize = 50;
+/// Most listings `find_guardians` returns in one call
+const MAX_DIRECTORY_PAGE: u32 = 50;
+/// How long approvers of a vetoed recovery have to contest a slash before it can execute
+#[cfg(feature = "bonding")]
+const SLASH_DISPUTE_WINDOW: Span = Span::days(7);
 
 fn get_dict() -> URef {
     runtime::get_key(DICT)
@@ -50,227 +113,3451 @@ fn get_dict() -> URef {
         .unwrap_or_revert_with(ApiError::User(Err::MissingDict as u16))
 }
 
+/// Tenant of the current call once `tenant()` has read it; `u64::MAX` until then
+static TENANT: AtomicU64 = AtomicU64::new(u64::MAX);
+
+/// Tenant the current call works on, from its optional `tenant` argument; 0, the registry's own
+/// records, without one. Reverts with `NoTenant` for a tenant that hasn't been created.
+fn tenant() -> u32 {
+    let known = TENANT.load(Ordering::Relaxed);
+    if known != u64::MAX { return known as u32; }
+    let t: u32 = runtime::try_get_named_arg(entry_points::ARG_TENANT).unwrap_or(0);
+    if t != 0 && read_unscoped::<AccountHash>(&format!("{}{}", prefixes::TENANT_ADMIN, t)).is_none() {
+        runtime::revert(ApiError::User(Err::NoTenant as u16));
+    }
+    TENANT.store(t as u64, Ordering::Relaxed);
+    t
+}
+
+/// Record `k` of the call's tenant
 fn read<T: CLTyped + FromBytes>(k: &str) -> Option<T> {
-    storage::dictionary_get(get_dict(), k).unwrap_or(None)
+    read_unscoped(&prefixes::scoped(tenant(), k))
 }
 
 fn write<T: CLTyped + ToBytes>(k: &str, v: T) {
-    storage::dictionary_put(get_dict(), k, v);
+    write_unscoped(&prefixes::scoped(tenant(), k), v);
 }
 
-#[no_mangle]
-pub extern "C" fn init_guardians() {
-    let acc: AccountHash = runtime::get_named_arg("account");
-    let guards: Vec<AccountHash> = runtime::get_named_arg("guardians");
-    let thresh: u8 = runtime::get_named_arg("threshold");
+/// Dictionary item `key` as is, for the tenant table every tenant shares
+fn read_unscoped<T: CLTyped + FromBytes>(key: &str) -> Option<T> {
+    storage::dictionary_get(get_dict(), key).unwrap_or(None)
+}
 
-    if runtime::get_caller() != acc { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
-    if guards.len() < 2 { runtime::revert(ApiError::User(Err::BadGuardians as u16)); }
-    if thresh == 0 || thresh as usize > guards.len() { runtime::revert(ApiError::User(Err::BadThreshold as u16)); }
+/// Every change to registry state goes through here, so none gets past the end of a decommissioning
+fn write_unscoped<T: CLTyped + ToBytes>(key: &str, v: T) {
+    if runtime::has_key(DECOMMISSION) && lifecycle() == lifecycle::DECOMMISSIONED {
+        runtime::revert(ApiError::User(Err::Decommissioned as u16));
+    }
+    storage::dictionary_put(get_dict(), key, v);
+}
 
-    let k = format!("i{:?}", acc);
-    if read::<bool>(&k).unwrap_or(false) { runtime::revert(ApiError::User(Err::AlreadyInit as u16)); }
+/// Named key `name` of the call's tenant, for contract state kept outside the dictionary such as
+/// its purses, so no tenant's funds can pay out another's
+fn tenant_key(name: &str) -> String {
+    match tenant() {
+        0 => name.to_string(),
+        t => format!("{}_{}", name, t),
+    }
+}
 
-    write(&format!("g{:?}", acc), guards.clone());
-    write(&format!("t{:?}", acc), thresh);
-    write(&k, true);
+/// (decommissioned at, sunset end) once the admin has decommissioned the registry
+fn decommissioning() -> Option<(u64, u64)> {
+    let uref = runtime::get_key(DECOMMISSION)?.into_uref().unwrap_or_revert();
+    storage::read(uref).unwrap_or_revert()
+}
 
-    // Add reverse mapping: for each guardian, add this account to their protected list
-    for guard in &guards {
-        let key = format!("ga{:?}", guard);
-        let mut protected: Vec<AccountHash> = read(&key).unwrap_or(vec![]);
-        if !protected.contains(&acc) {
-            protected.push(acc);
-            write(&key, protected);
-        }
+/// The registry's `guardian_types::lifecycle` state
+fn lifecycle() -> u8 {
+    lifecycle::state(now().0, decommissioning().map(|(_, end)| end))
+}
+
+/// Reverts unless the registry still takes new accounts and recoveries
+fn taking_new() {
+    if lifecycle() != lifecycle::ACTIVE { runtime::revert(ApiError::User(Err::Decommissioned as u16)); }
+}
+
+/// Append `e` to the CES event log set up by `init_storage`
+#[cfg(feature = "events")]
+fn emit<E: Event>(e: E) {
+    let uref = |name: &str| runtime::get_key(name).and_then(|k| k.into_uref()).unwrap_or_revert();
+    let len_ref = uref("__events_length");
+    let len: u32 = storage::read(len_ref).unwrap_or_revert().unwrap_or_revert();
+    let bytes: Bytes = e.event_bytes().unwrap_or_revert().into();
+    storage::dictionary_put(uref("__events"), &len.to_string(), bytes);
+    storage::write(len_ref, len + 1);
+}
+
+/// Builds without the `events` feature keep no event log
+#[cfg(not(feature = "events"))]
+fn emit<E: Event>(_: E) {}
+
+/// Record that `entry_point` skipped `who`'s part of the call with `code` instead of reverting.
+/// `arg` indexes the entry point's parameters and `item` the offending element of a list argument.
+/// Failures that revert can't be recorded: the revert discards the event log with everything else.
+fn rejected(entry_point: &str, acc: AccountHash, who: Key, code: u32, arg: u8, item: usize) {
+    emit(events::RejectedV2 { tenant: tenant(), entry_point: entry_point.into(), account: acc, guardian: who, code, arg, item: item as u32 });
+}
+
+/// Tell integrators still calling `entry_point` what replaced it, if `deprecations` lists it
+fn deprecated(entry_point: &str) {
+    if let Some(replacement) = deprecations::replacement(entry_point) {
+        emit(events::EntryPointDeprecatedV1 {
+            entry_point: entry_point.into(), replacement: replacement.into(), caller: runtime::get_caller(),
+        });
     }
 }
 
-#[no_mangle]
-pub extern "C" fn start_recovery() {
-    let acc: AccountHash = runtime::get_named_arg("account");
-    let nk: PublicKey = runtime::get_named_arg("new_key");
+/// Whether the call passed an argument `name`, whatever its type
+fn has_arg(name: &str) -> bool {
+    let mut size = 0usize;
+    unsafe { ext_ffi::casper_get_named_arg_size(name.as_ptr(), name.len(), &mut size) == 0 }
+}
 
-    if !read::<bool>(&format!("i{:?}", acc)).unwrap_or(false) { runtime::revert(ApiError::User(Err::NotInit as u16)); }
-    if read::<U256>(&format!("a{:?}", acc)).is_some() { runtime::revert(ApiError::User(Err::RecoveryExists as u16)); }
+/// On registries installed with `strict_args`, reverts with `UnexpectedArgument` if the call passes
+/// an argument that some registry entry point takes but `entry_point` doesn't. Casper can't list a
+/// call's arguments, so each known name is probed; a name no entry point takes goes unnoticed.
+fn check_args(entry_point: &str) {
+    if !runtime::has_key(STRICT_ARGS) { return; }
+    let eps = entry_points::registry_entry_points();
+    let own: BTreeSet<String> = eps.get(entry_point)
+        .map(|ep| ep.args().iter().map(|p| p.name().to_string()).collect())
+        .unwrap_or_default();
+    let known: BTreeSet<String> = eps.take_entry_points().iter()
+        .flat_map(|ep| ep.args().iter().map(|p| p.name().to_string()))
+        .filter(|name| name != entry_points::ARG_TENANT)
+        .collect();
+    if known.difference(&own).any(|name| has_arg(name)) {
+        runtime::revert(ApiError::User(Err::UnexpectedArgument as u16));
+    }
+}
 
-    let id = read::<U256>("c").unwrap_or(U256::zero()) + 1;
-    write("c", id);
-    write(&format!("ra{}", id), acc);
-    write(&format!("rk{}", id), nk);
-    write(&format!("rc{}", id), 0u8);
-    write(&format!("ro{}", id), false);
-    write(&format!("a{:?}", acc), id);
+/// True while `acc`'s configuration is locked after a recovery was vetoed as malicious
+fn frozen(acc: AccountHash) -> bool {
+    !Instant(read(&format!("{}{:?}", prefixes::FROZEN_UNTIL, acc)).unwrap_or(0)).reached(now())
+}
 
-    // Add reverse mapping: for each guardian, add this recovery ID to their active recoveries list
-    let guards: Vec<AccountHash> = read(&format!("g{:?}", acc)).unwrap_or(vec![]);
-    for guard in &guards {
-        let key = format!("gr{:?}", guard);
-        let mut recoveries: Vec<U256> = read(&key).unwrap_or(vec![]);
-        if !recoveries.contains(&id) {
-            recoveries.push(id);
-            write(&key, recoveries);
+/// Watch-only parties to notify of `acc`'s recoveries
+fn watchers(acc: AccountHash) -> Vec<[u8; 32]> {
+    read(&format!("{}{:?}", prefixes::WATCHERS, acc)).unwrap_or_default()
+}
+
+/// Who `acc`'s recovery events notify: its watchers, then its auditors that are accounts
+fn notify(acc: AccountHash) -> Vec<[u8; 32]> {
+    let mut list = watchers(acc);
+    for (member, _) in members(acc).into_iter().filter(|(_, r)| r & roles::AUDITOR != 0) {
+        if let Key::Account(a) = member {
+            if !list.contains(&a.value()) { list.push(a.value()); }
         }
     }
+    list
+}
 
-    runtime::ret(CLValue::from_t(id).unwrap_or_revert());
+/// Contract-owned purse stored under `name`, created on first use
+fn purse(name: &str) -> URef {
+    if let Some(k) = runtime::get_key(name) { return k.into_uref().unwrap_or_revert(); }
+    let p = system::create_purse();
+    runtime::put_key(name, Key::URef(p));
+    p
 }
 
-#[no_mangle]
-pub extern "C" fn approve() {
-    let id: U256 = runtime::get_named_arg("id");
-    let caller = runtime::get_caller();
+/// The fee schedule, indexed by `fees::*`; all zero until the admin sets one
+fn fee_schedule() -> Vec<U512> {
+    let mut schedule: Vec<U512> = read(prefixes::FEE_SCHEDULE).unwrap_or_default();
+    schedule.resize(fees::COUNT, U512::zero());
+    schedule
+}
 
-    let acc: AccountHash = read(&format!("ra{}", id)).unwrap_or_revert_with(ApiError::User(Err::NotFound as u16));
-    let guards: Vec<AccountHash> = read(&format!("g{:?}", acc)).unwrap_or_revert_with(ApiError::User(Err::NotGuardian as u16));
+/// Collect the scheduled fee for `operation` on `acc` from the call's `purse` into the fee purse.
+/// A zero fee takes nothing and needs no purse. Errors are `Err` codes.
+fn charge(operation: u8, acc: AccountHash) -> Result<(), u16> {
+    let amount = fee_schedule()[operation as usize];
+    if amount.is_zero() { return Ok(()); }
+    let src: URef = runtime::try_get_named_arg("purse").ok_or(Err::FeeRequired as u16)?;
+    system::transfer_from_purse_to_purse(src, purse(&tenant_key(FEE_PURSE)), amount, None).map_err(|_| Err::FeeRequired as u16)?;
+    emit(events::FeeChargedV2 { tenant: tenant(), operation, account: acc, payer: runtime::get_caller(), amount });
+    Ok(())
+}
 
-    if !guards.contains(&caller) { runtime::revert(ApiError::User(Err::NotGuardian as u16)); }
+/// Motes collected in the fee purse and not withdrawn yet
+fn fee_balance() -> U512 {
+    runtime::get_key(&tenant_key(FEE_PURSE))
+        .and_then(|k| k.into_uref())
+        .map_or(U512::zero(), |p| system::get_purse_balance(p).unwrap_or_default())
+}
+
+/// True if the caller administers the call's tenant: the account that installed the registry for
+/// tenant 0, the tenant's own admin otherwise
+fn caller_is_admin() -> bool {
+    match tenant() {
+        0 => caller_is_registry_admin(),
+        t => read_unscoped::<AccountHash>(&format!("{}{}", prefixes::TENANT_ADMIN, t)) == Some(runtime::get_caller()),
+    }
+}
 
-    let ak = format!("rp{}_{:?}", id, caller);
-    if read::<bool>(&ak).unwrap_or(false) { runtime::revert(ApiError::User(Err::AlreadyApproved as u16)); }
+/// True if the caller is the account that installed the registry, whatever the call's tenant
+fn caller_is_registry_admin() -> bool {
+    runtime::get_key("admin") == Some(Key::Account(runtime::get_caller()))
+}
 
-    write(&ak, true);
-    let cnt: u8 = read(&format!("rc{}", id)).unwrap_or(0) + 1;
-    write(&format!("rc{}", id), cnt);
+fn closed(id: U256) -> bool {
+    read::<bool>(&format!("{}{}", prefixes::FINALIZED, id)).unwrap_or(false) || read::<bool>(&format!("{}{}", prefixes::CANCELLED, id)).unwrap_or(false) || expired(id)
+}
 
-    let thresh: u8 = read(&format!("t{:?}", acc)).unwrap_or(2);
-    if cnt >= thresh { write(&format!("ro{}", id), true); }
+/// True once recovery `id` has outlived the account's configured lifetime
+fn expired(id: U256) -> bool {
+    read::<u64>(&format!("{}{}", prefixes::EXPIRES_AT, id)).map_or(false, |deadline| Instant(deadline).reached(tick(id)))
 }
 
-#[no_mangle]
-pub extern "C" fn is_approved() {
-    let id: U256 = runtime::get_named_arg("id");
-    runtime::ret(CLValue::from_t(read::<bool>(&format!("ro{}", id)).unwrap_or(false)).unwrap_or_revert());
+/// Lifetime recovery counts of `acc`, indexed by `counters::*`
+fn counts(acc: AccountHash) -> Vec<u32> {
+    let mut c: Vec<u32> = read(&format!("{}{:?}", prefixes::LIFETIME_COUNTS, acc)).unwrap_or_default();
+    c.resize(counters::COUNT, 0);
+    c
 }
 
-#[no_mangle]
-pub extern "C" fn finalize() {
-    let id: U256 = runtime::get_named_arg("id");
-    if !read::<bool>(&format!("ro{}", id)).unwrap_or(false) { runtime::revert(ApiError::User(Err::NotApproved as u16)); }
+/// Count one more recovery (`id`) under `counter`, warning if that takes it past the account's
+/// anomaly limit
+fn bump(id: U256, acc: AccountHash, counter: u8) {
+    let mut c = counts(acc);
+    let count = c[counter as usize].saturating_add(1);
+    c[counter as usize] = count;
+    write(&format!("{}{:?}", prefixes::LIFETIME_COUNTS, acc), c);
 
-    // Get the target account for this recovery
-    let acc: AccountHash = read(&format!("ra{}", id)).unwrap_or_revert_with(ApiError::User(Err::NotFound as u16));
-    
-    // Remove this recovery ID from each guardian's active recoveries list
-    let guards: Vec<AccountHash> = read(&format!("g{:?}", acc)).unwrap_or(vec![]);
+    let limits: Vec<u32> = read(&format!("{}{:?}", prefixes::ANOMALY_LIMITS, acc)).unwrap_or_default();
+    if let Some(&limit) = limits.get(counter as usize).filter(|l| **l > 0 && count > **l) {
+        emit(events::RecoveryAnomalyV2 { tenant: tenant(), id, account: acc, counter, count, limit, notify: notify(acc) });
+    }
+}
+
+/// `acc`'s latest recovery if it expired without being finalized or cancelled, and so isn't counted yet
+fn uncounted_expiry(acc: AccountHash) -> Option<U256> {
+    let id: U256 = read(&format!("{}{:?}", prefixes::ACTIVE_RECOVERY, acc))?;
+    let ended = read::<bool>(&format!("{}{}", prefixes::FINALIZED, id)).unwrap_or(false) || read::<bool>(&format!("{}{}", prefixes::CANCELLED, id)).unwrap_or(false);
+    (!ended && expired(id)).then_some(id)
+}
+
+/// Call hook `entry` of each recovery module `acc` plugged in, in order; any of them may revert
+fn run_modules(acc: AccountHash, entry: &str, args: RuntimeArgs) {
+    let mods: Vec<Key> = read(&format!("{}{:?}", prefixes::MODULES, acc)).unwrap_or_default();
+    for m in mods {
+        let Key::Hash(pkg) = m else { runtime::revert(ApiError::User(Err::BadModule as u16)) };
+        runtime::call_versioned_contract::<()>(ContractPackageHash::new(pkg), None, entry, args.clone());
+    }
+}
+
+/// Reverts unless the caller is the arbiter of the account behind recovery `id`
+fn only_arbiter(id: U256) -> AccountHash {
+    let acc: AccountHash = read(&format!("{}{}", prefixes::RECOVERY_ACCOUNT, id)).unwrap_or_revert_with(ApiError::User(Err::NotFound as u16));
+    let arbiter = read::<Option<AccountHash>>(&format!("{}{:?}", prefixes::ARBITER, acc)).flatten();
+    if arbiter != Some(runtime::get_caller()) { runtime::revert(ApiError::User(Err::NotArbiter as u16)); }
+    if closed(id) { runtime::revert(ApiError::User(Err::NotFound as u16)); }
+    acc
+}
+
+/// Package of the contract that called this entry point directly, normalized to `Key::Hash`
+#[cfg(feature = "casper-2")]
+fn caller_package() -> Option<Key> {
+    use casper_types::PackageHash;
+    let info = runtime::get_immediate_caller().ok()?;
+    // CallerInfo field 2 holds a legacy contract package, field 1 an addressable-entity package
+    let legacy = info.get_field_by_index(2).and_then(|v| v.clone().into_t::<Option<ContractPackageHash>>().ok()).flatten();
+    if let Some(pkg) = legacy { return Some(Key::Hash(pkg.value())); }
+    info.get_field_by_index(1)?.clone().into_t::<Option<PackageHash>>().ok()?.map(|p| Key::Hash(p.value()))
+}
+
+/// Package of the contract that called this entry point directly, normalized to `Key::Hash`
+#[cfg(not(feature = "casper-2"))]
+fn caller_package() -> Option<Key> {
+    use casper_types::system::CallStackElement;
+    // The last element is this call; the one before it made it
+    let stack = runtime::get_call_stack();
+    match stack.len().checked_sub(2).and_then(|i| stack.get(i))? {
+        CallStackElement::StoredContract { contract_package_hash, .. } => Some(Key::Hash(contract_package_hash.value())),
+        _ => None,
+    }
+}
+
+/// `acc`'s role members as (member, `roles` bits), in the order first granted
+fn members(acc: AccountHash) -> Vec<(Key, u8)> {
+    read(&format!("{}{:?}", prefixes::ROLES, acc)).unwrap_or_default()
+}
+
+/// True if the caller is `acc`'s owner or a member holding `role`. A member account matches the
+/// caller, a member contract the package that made the call.
+fn holds(acc: AccountHash, role: u8) -> bool {
+    if runtime::get_caller() == acc { return true; }
+    let (caller, package) = (Key::Account(runtime::get_caller()), caller_package());
+    members(acc).iter().any(|(m, r)| r & role != 0 && (*m == caller || Some(*m) == package))
+}
+
+/// Contract packages acting as guardians of `acc`, alongside the account guardians in `g`
+fn contract_guardians(acc: AccountHash) -> Vec<Key> {
+    read(&format!("{}{:?}", prefixes::CONTRACT_GUARDIANS, acc)).unwrap_or(vec![])
+}
+
+/// Guardian of `acc` whose unexpired delegation currently names `delegate`
+fn delegator(acc: AccountHash, delegate: AccountHash) -> Option<AccountHash> {
+    let guard: AccountHash = read::<Option<AccountHash>>(&format!("{}{:?}_{:?}", prefixes::DELEGATOR, acc, delegate)).flatten()?;
+    let (pk, until): (PublicKey, u64) = read::<Option<(PublicKey, u64)>>(&format!("{}{:?}_{:?}", prefixes::DELEGATION, acc, guard)).flatten()?;
+    (pk.to_account_hash() == delegate && !Instant(until).reached(now())).then_some(guard)
+}
+
+fn clear_delegation(acc: AccountHash, guard: AccountHash) {
+    let key = format!("{}{:?}_{:?}", prefixes::DELEGATION, acc, guard);
+    if let Some((pk, _)) = read::<Option<(PublicKey, u64)>>(&key).flatten() {
+        write::<Option<AccountHash>>(&format!("{}{:?}_{:?}", prefixes::DELEGATOR, acc, pk.to_account_hash()), None);
+        write::<Option<(PublicKey, u64)>>(&key, None);
+    }
+}
+
+fn key_bit(pk: &PublicKey) -> u8 {
+    match pk {
+        PublicKey::Ed25519(_) => KEY_ED25519,
+        PublicKey::Secp256k1(_) => KEY_SECP256K1,
+        _ => 0,
+    }
+}
+
+/// True if `acc` has no key policy, or `keys` are, in order, the public keys behind `guards`, each of
+/// an algorithm the policy allows. Account hashes alone don't reveal the algorithm.
+fn keys_ok(acc: AccountHash, guards: &[AccountHash], keys: &[PublicKey]) -> bool {
+    let policy: u8 = read(&format!("{}{:?}", prefixes::KEY_POLICY, acc)).unwrap_or(0);
+    policy == 0 || (keys.len() == guards.len() && keys.iter().zip(guards).all(|(k, g)| k.to_account_hash() == *g && policy & key_bit(k) != 0))
+}
+
+/// Reverts unless the `guardian_keys` argument satisfies `keys_ok`
+fn check_keys(acc: AccountHash, guards: &[AccountHash]) {
+    let keys: Vec<PublicKey> = runtime::try_get_named_arg("guardian_keys").unwrap_or_default();
+    if !keys_ok(acc, guards, &keys) { runtime::revert(ApiError::User(Err::BadKeyType as u16)); }
+}
+
+fn guardian_count(acc: AccountHash) -> usize {
+    let committed = read::<Option<([u8; 32], u32)>>(&format!("{}{:?}", prefixes::MERKLE_ROOT, acc)).flatten().map_or(0, |(_, n)| n as usize);
+    let hashed = read::<Vec<[u8; 32]>>(&format!("{}{:?}", prefixes::HASHED_GUARDIANS, acc)).map_or(0, |h| h.len());
+    read::<Vec<AccountHash>>(&format!("{}{:?}", prefixes::GUARDIANS, acc)).map_or(0, |g| g.len()) + contract_guardians(acc).len() + committed + hashed
+}
+
+/// True while `acc` has a recovery that hasn't been finalized or vetoed
+fn pending(acc: AccountHash) -> bool {
+    read::<U256>(&format!("{}{:?}", prefixes::ACTIVE_RECOVERY, acc)).map_or(false, |id| !closed(id))
+}
+
+/// Vetoed recovery whose slash proposal is still open for `acc`
+#[cfg(feature = "bonding")]
+fn open_slash(acc: AccountHash) -> Option<U256> {
+    read::<Option<U256>>(&format!("{}{:?}", prefixes::OPEN_SLASH, acc)).flatten()
+}
+
+/// Guardians who have approved recovery `id`
+fn approvers(id: U256, acc: AccountHash) -> Vec<AccountHash> {
+    let guards: Vec<AccountHash> = read(&format!("{}{:?}", prefixes::GUARDIANS, acc)).unwrap_or(vec![]);
+    guards.into_iter().filter(|g| read::<bool>(&format!("{}{}_{:?}", prefixes::APPROVED, id, g)).unwrap_or(false)).collect()
+}
+
+/// Release recovery `id`'s anti-spam deposit, if still held, to `to`
+fn settle_deposit(id: U256, to: AccountHash) {
+    let key = format!("{}{}", prefixes::RECOVERY_DEPOSIT, id);
+    let held: U512 = read(&key).unwrap_or_default();
+    if held.is_zero() { return; }
+    write(&key, U512::zero());
+    system::transfer_from_purse_to_account(purse(&tenant_key(DEPOSIT_PURSE)), to, held, None).unwrap_or_revert();
+}
+
+/// Swap guardian `from` of `acc` for `to` in place, carrying over their bond and share record
+fn rotate(acc: AccountHash, from: AccountHash, to: AccountHash) {
+    let mut guards: Vec<AccountHash> = read(&format!("{}{:?}", prefixes::GUARDIANS, acc)).unwrap_or_default();
+    guards.iter_mut().filter(|g| **g == from).for_each(|g| *g = to);
+    write(&format!("{}{:?}", prefixes::GUARDIANS, acc), guards);
+
+    let mut protected: Vec<AccountHash> = read(&format!("{}{:?}", prefixes::PROTECTED_ACCOUNTS, from)).unwrap_or(vec![]);
+    protected.retain(|a| *a != acc);
+    write(&format!("{}{:?}", prefixes::PROTECTED_ACCOUNTS, from), protected);
+    let mut protected: Vec<AccountHash> = read(&format!("{}{:?}", prefixes::PROTECTED_ACCOUNTS, to)).unwrap_or(vec![]);
+    protected.push(acc);
+    write(&format!("{}{:?}", prefixes::PROTECTED_ACCOUNTS, to), protected);
+    // The account's open recovery now waits on the new key
+    if let Some(id) = read::<U256>(&format!("{}{:?}", prefixes::ACTIVE_RECOVERY, acc)).filter(|id| !closed(*id)) {
+        let mut recoveries: Vec<U256> = read(&format!("{}{:?}", prefixes::GUARDIAN_RECOVERIES, from)).unwrap_or(vec![]);
+        recoveries.retain(|&r| r != id);
+        write(&format!("{}{:?}", prefixes::GUARDIAN_RECOVERIES, from), recoveries);
+        track(to, id);
+    }
+
+    #[cfg(feature = "bonding")]
+    {
+        let posted: U512 = read(&format!("{}{:?}_{:?}", prefixes::BOND, acc, from)).unwrap_or_default();
+        if !posted.is_zero() {
+            write(&format!("{}{:?}_{:?}", prefixes::BOND, acc, from), U512::zero());
+            write(&format!("{}{:?}_{:?}", prefixes::BOND, acc, to), posted);
+        }
+    }
+    // The share moves along but has to be attested again from the new key
+    if let Some((hash, uri, _)) = read::<Option<([u8; 32], String, u64)>>(&format!("{}{:?}_{:?}", prefixes::SHARE, acc, from)).flatten() {
+        write::<Option<([u8; 32], String, u64)>>(&format!("{}{:?}_{:?}", prefixes::SHARE, acc, from), None);
+        write(&format!("{}{:?}_{:?}", prefixes::SHARE, acc, to), Some((hash, uri, 0u64)));
+    }
+    let mut cats = categories(acc);
+    cats.iter_mut().filter(|(k, _)| *k == Key::Account(from)).for_each(|(k, _)| *k = Key::Account(to));
+    write(&format!("{}{:?}", prefixes::CATEGORIES, acc), cats);
+    if let Some(p) = read::<ApprovalPolicy>(&format!("{}{:?}", prefixes::APPROVAL_POLICY, acc)) {
+        write(&format!("{}{:?}", prefixes::APPROVAL_POLICY, acc), p.rotated(&Key::Account(from), &Key::Account(to)));
+    }
+    let mut terms: Vec<(Key, u64)> = read(&format!("{}{:?}", prefixes::GUARDIAN_TERMS, acc)).unwrap_or_default();
+    terms.iter_mut().filter(|(k, _)| *k == Key::Account(from)).for_each(|(k, _)| *k = Key::Account(to));
+    write(&format!("{}{:?}", prefixes::GUARDIAN_TERMS, acc), terms);
+    let mut suspended: Vec<Key> = read(&format!("{}{:?}", prefixes::SUSPENDED, acc)).unwrap_or_default();
+    suspended.iter_mut().filter(|k| **k == Key::Account(from)).for_each(|k| *k = Key::Account(to));
+    write(&format!("{}{:?}", prefixes::SUSPENDED, acc), suspended);
+    clear_delegation(acc, from);
+    emit(events::GuardianKeyRotatedV2 { tenant: tenant(), account: acc, old: from, new: to });
+}
+
+/// Add recovery `id` to `guard`'s active recoveries list, dropping ones that expired untracked
+fn track(guard: AccountHash, id: U256) {
+    let key = format!("{}{:?}", prefixes::GUARDIAN_RECOVERIES, guard);
+    let mut recoveries: Vec<U256> = read(&key).unwrap_or(vec![]);
+    recoveries.retain(|&r| !closed(r));
+    if !recoveries.contains(&id) {
+        recoveries.push(id);
+    }
+    write(&key, recoveries);
+}
+
+/// Drop a finished recovery from each guardian's active recoveries list
+fn untrack(id: U256, acc: AccountHash) {
+    let guards: Vec<AccountHash> = read(&format!("{}{:?}", prefixes::GUARDIANS, acc)).unwrap_or(vec![]);
     for guard in &guards {
-        let key = format!("gr{:?}", guard);
+        let key = format!("{}{:?}", prefixes::GUARDIAN_RECOVERIES, guard);
         let mut recoveries: Vec<U256> = read(&key).unwrap_or(vec![]);
         recoveries.retain(|&r| r != id);
         write(&key, recoveries);
     }
+}
 
-    // Clear the active recovery mapping for this account
-    // Note: Casper dictionary doesn't have delete, so we write a zero value
-    // The active recovery check in start_recovery uses is_some(), so we need to handle this
-    // For now, we mark it as finalized by setting a special flag
-    write(&format!("rf{}", id), true); // Recovery finalized flag
+fn now() -> Instant {
+    Instant(runtime::get_blocktime().into())
+}
+
+fn settings(acc: AccountHash) -> AccountSettings {
+    read(&format!("{}{:?}", prefixes::SETTINGS, acc)).unwrap_or_default()
+}
+
+/// Current reading of the clock `cfg` measures its settings with
+fn clock(cfg: &AccountSettings) -> Instant {
+    if cfg.by_height { Instant(runtime::get_block_height()) } else { now() }
+}
+
+/// Reading of `cfg`'s clock before which no new recovery of `acc` may start: the last finalized
+/// recovery plus the cool-down, 0 if there's none to wait out
+fn cooldown_end(acc: AccountHash, cfg: &AccountSettings) -> Instant {
+    if cfg.cooldown == 0 { return Instant(0); }
+    let Some((at, height)) = read::<(u64, u64)>(&format!("{}{:?}", prefixes::LAST_COMPLETED, acc)) else { return Instant(0) };
+    Instant(if cfg.by_height { height } else { at }).plus(Span(cfg.cooldown))
+}
+
+/// Current reading of recovery `id`'s clock, fixed when it started, for its expiry, delay, veto and
+/// approval windows
+fn tick(id: U256) -> Instant {
+    if read::<bool>(&format!("{}{}", prefixes::BY_HEIGHT, id)).unwrap_or(false) { Instant(runtime::get_block_height()) } else { now() }
+}
+
+/// Mark recovery `id` as having reached its threshold, opening the owner's veto window
+fn reach_threshold(id: U256) {
+    write(&format!("{}{}", prefixes::THRESHOLD_REACHED, id), true);
+    write(&format!("{}{}", prefixes::APPROVED_AT, id), tick(id).0);
+    settle_deposit(id, read(&format!("{}{}", prefixes::INITIATOR, id)).unwrap_or_revert());
+}
+
+/// True once an opted-in owner has missed their heartbeat for the configured period
+fn inactive(acc: AccountHash) -> bool {
+    let period: u64 = read(&format!("{}{:?}", prefixes::HEARTBEAT_PERIOD, acc)).unwrap_or(0);
+    if period == 0 { return false; }
+    let last: u64 = read(&format!("{}{:?}", prefixes::HEARTBEAT, acc)).unwrap_or(0);
+    Instant(last).has_elapsed(Span(period), now())
+}
+
+/// `acc`'s approval policy: the one set with `set_approval_policy`, or for accounts configured before
+/// policies the one their threshold and decay schedule imply
+#[cfg(feature = "policies")]
+fn approval_policy(acc: AccountHash) -> ApprovalPolicy {
+    if let Some(p) = read::<ApprovalPolicy>(&format!("{}{:?}", prefixes::APPROVAL_POLICY, acc)) { return p; }
+    let threshold: u8 = read(&format!("{}{:?}", prefixes::THRESHOLD, acc)).unwrap_or(2);
+    let steps: Vec<(u64, u8)> = read(&format!("{}{:?}", prefixes::THRESHOLD_DECAY, acc)).unwrap_or(vec![]);
+    if steps.is_empty() { ApprovalPolicy::Threshold { threshold } } else { ApprovalPolicy::Decaying { threshold, steps } }
+}
+
+/// Without the `policies` feature every account approves by its plain threshold
+#[cfg(not(feature = "policies"))]
+fn approval_policy(acc: AccountHash) -> ApprovalPolicy {
+    ApprovalPolicy::Threshold { threshold: read(&format!("{}{:?}", prefixes::THRESHOLD, acc)).unwrap_or(2) }
+}
+
+/// Check `p` against `acc`'s current guardians and make it the account's policy, keeping `t` in step
+#[cfg(feature = "policies")]
+fn store_policy(acc: AccountHash, p: ApprovalPolicy) {
+    let keys = guardian_keys(acc);
+    let hidden = guardian_count(acc).saturating_sub(keys.len());
+    if let Err(e) = p.check(&keys, hidden, &categories(acc)) { runtime::revert(ApiError::User(e as u16)); }
+    if let Some(t) = p.count_threshold() { write(&format!("{}{:?}", prefixes::THRESHOLD, acc), t); }
+    write(&format!("{}{:?}", prefixes::APPROVAL_POLICY, acc), p);
+}
+
+/// Ceiling on any requirement while the owner is inactive: the dead-man threshold
+fn inactive_cap(acc: AccountHash) -> Option<u32> {
+    if !inactive(acc) { return None; }
+    read::<u8>(&format!("{}{:?}", prefixes::HEARTBEAT_THRESHOLD, acc)).map(u32::from)
+}
+
+/// How long recovery `id` has been open, in ms
+fn age(id: U256) -> u64 {
+    let started = read(&format!("{}{}", prefixes::STARTED_AT, id)).map_or(now(), Instant);
+    now().since(started).0
+}
+
+/// Guardians of `acc` whose term has expired or who are suspended
+fn inactive_guardians(acc: AccountHash) -> Vec<Key> {
+    let terms: Vec<(Key, u64)> = read(&format!("{}{:?}", prefixes::GUARDIAN_TERMS, acc)).unwrap_or_default();
+    let mut out: Vec<Key> = read(&format!("{}{:?}", prefixes::SUSPENDED, acc)).unwrap_or_default();
+    out.extend(terms.into_iter().filter(|(_, until)| Instant(*until).reached(now())).map(|(k, _)| k));
+    out
+}
+
+/// What recovery `id` is asked against its account's policy
+fn standing<'a>(id: U256, approvers: &'a [Key], categories: &'a [(Key, u8)], inactive: &'a [Key]) -> Tally<'a> {
+    Tally {
+        approvers,
+        extra: tally(id).saturating_sub(approvers.len() as u8),
+        age: age(id),
+        base: read::<u8>(&format!("{}{}", prefixes::REQUIRED, id)).map(u32::from),
+        cap: None,
+        categories,
+        inactive,
+    }
+}
+
+/// Requirement currently in force for recovery `id` (approvals, or weight under a weighted policy):
+/// the policy's (or recovery type's) threshold after any decay, lowered to the dead-man threshold
+/// while the owner is inactive. Under a fractional policy, the share of active weight required in
+/// whole percent, rounded up.
+fn threshold(id: U256, acc: AccountHash) -> u8 {
+    let p = approval_policy(acc);
+    if let ApprovalPolicy::Fractional { threshold_bps, .. } = &p { return policy::percent(*threshold_bps as u32, policy::BPS, true); }
+    let required = p.required(read::<u8>(&format!("{}{}", prefixes::REQUIRED, id)).map(u32::from), age(id), &[]);
+    inactive_cap(acc).map_or(required, |c| c.min(required)).min(u8::MAX as u32) as u8
+}
+
+/// What recovery `id`'s counted approvals add up to under its account's policy; under a fractional
+/// policy their share of active weight in whole percent, rounded down
+fn score(id: U256, acc: AccountHash) -> u32 {
+    let (p, approvers, idle) = (approval_policy(acc), counted(id), inactive_guardians(acc));
+    if let ApprovalPolicy::Fractional { .. } = &p { return policy::percent(p.score(&approvers, &idle), p.active_weight(&idle), false) as u32; }
+    let extra = tally(id).saturating_sub(approvers.len() as u8);
+    p.score(&approvers, &idle) + extra as u32
+}
+
+/// Inheritance applies once the owner has gone inactive with at least one heir designated
+fn inheritable(acc: AccountHash) -> bool {
+    inactive(acc) && read::<Vec<AccountHash>>(&format!("{}{:?}", prefixes::HEIRS, acc)).map_or(false, |h| !h.is_empty())
+}
+
+/// Who a guardian call on `acc`'s recovery counts for: the calling contract guardian's package, or
+/// the guardian account the caller votes as
+fn ballot(acc: AccountHash) -> Key {
+    match caller_package().filter(|p| contract_guardians(acc).contains(p)) {
+        Some(pkg) => pkg,
+        None => Key::Account(verify::for_account(acc).voter(acc, runtime::get_caller()).unwrap_or_revert_with(ApiError::User(Err::NotGuardian as u16))),
+    }
+}
+
+/// Dictionary key under which `who`'s record of kind `p` on recovery `id` is kept: its vote
+/// (`prefixes::APPROVED` or `REJECTED`), or the approvers its flag counted against (`FLAGGED_APPROVERS`)
+fn vote_key(p: &str, id: U256, who: Key) -> String {
+    match who {
+        Key::Account(a) => format!("{}{}_{:?}", p, id, a),
+        k => format!("{}{}_{:?}", p, id, k),
+    }
+}
+
+fn voted(id: U256, who: Key) -> bool {
+    read::<bool>(&vote_key(prefixes::APPROVED, id, who)).unwrap_or(false) || read::<bool>(&vote_key(prefixes::REJECTED, id, who)).unwrap_or(false)
+}
+
+/// Record `who`'s vote with an optional memo, reverting if they already voted either way
+fn cast(id: U256, who: Key, approve: bool) {
+    let memo: String = runtime::try_get_named_arg("memo").unwrap_or_default();
+    if memo.len() > 140 { runtime::revert(ApiError::User(Err::BadMemo as u16)); }
+    if voted(id, who) { runtime::revert(ApiError::User(Err::AlreadyApproved as u16)); }
+
+    write(&vote_key(if approve { prefixes::APPROVED } else { prefixes::REJECTED }, id, who), true);
+    let mut log: Vec<(Key, bool, String)> = read(&format!("{}{}", prefixes::VOTE_LOG, id)).unwrap_or_default();
+    log.push((who, approve, memo));
+    write(&format!("{}{}", prefixes::VOTE_LOG, id), log);
+}
+
+/// Approvals counting toward recovery `id`: all of them, or with a rolling window only those cast within it
+fn tally(id: U256) -> u8 {
+    let window: u64 = read(&format!("{}{}", prefixes::APPROVAL_WINDOW, id)).unwrap_or(0);
+    if window == 0 { return read(&format!("{}{}", prefixes::APPROVAL_COUNT, id)).unwrap_or(0); }
+    let timeline: Vec<(Key, u64)> = read(&format!("{}{}", prefixes::TIMELINE, id)).unwrap_or_default();
+    timeline.iter().filter(|(_, at)| !Instant(*at).has_elapsed(Span(window), tick(id))).count() as u8
+}
+
+/// Guardians whose approvals make up `tally(id)`; an arbiter's tie-break is counted but has no key
+fn counted(id: U256) -> Vec<Key> {
+    let window: u64 = read(&format!("{}{}", prefixes::APPROVAL_WINDOW, id)).unwrap_or(0);
+    let timeline: Vec<(Key, u64)> = read(&format!("{}{}", prefixes::TIMELINE, id)).unwrap_or_default();
+    timeline.into_iter().filter(|(_, at)| window == 0 || !Instant(*at).has_elapsed(Span(window), tick(id))).map(|(who, _)| who).collect()
+}
+
+/// Guardians listed by key (account guardians, then contract guardians); hidden and Merkle-committed
+/// guardians can't be given a category or weight
+fn guardian_keys(acc: AccountHash) -> Vec<Key> {
+    let guards: Vec<AccountHash> = read(&format!("{}{:?}", prefixes::GUARDIANS, acc)).unwrap_or_default();
+    guards.into_iter().map(Key::Account).chain(contract_guardians(acc)).collect()
+}
+
+fn categories(acc: AccountHash) -> Vec<(Key, u8)> {
+    read(&format!("{}{:?}", prefixes::CATEGORIES, acc)).unwrap_or_default()
+}
+
+/// Approvals recovery `id` still lacks per category
+fn category_shortfall(id: U256, acc: AccountHash) -> Vec<(u8, u8)> {
+    approval_policy(acc).shortfall(&counted(id), &categories(acc))
+}
+
+/// True if recovery `id`'s account policy counts it as approved
+fn quorum(id: U256, acc: AccountHash) -> bool {
+    let (approvers, cats, idle) = (counted(id), categories(acc), inactive_guardians(acc));
+    let tally = Tally { cap: inactive_cap(acc), ..standing(id, &approvers, &cats, &idle) };
+    approval_policy(acc).approved(&tally)
+}
+
+fn approved(id: U256, acc: AccountHash) -> bool {
+    read::<bool>(&format!("{}{}", prefixes::THRESHOLD_REACHED, id)).unwrap_or(false) || quorum(id, acc)
+}
+
+/// Count `who`'s approval of recovery `id`. A resubmitted approval gets AlreadyApproved even once the
+/// recovery has moved on.
+fn record_approval(id: U256, acc: AccountHash, who: Key) {
+    if voted(id, who) { runtime::revert(ApiError::User(Err::AlreadyApproved as u16)); }
+    if read::<bool>(&format!("{}{}", prefixes::CANCELLED, id)).unwrap_or(false) { runtime::revert(ApiError::User(Err::Vetoed as u16)); }
+    if expired(id) { runtime::revert(ApiError::User(Err::Expired as u16)); }
+    if let Some(e) = approval_blocker(id, acc, who) { runtime::revert(ApiError::User(e as u16)); }
+    count_approval(id, acc, who);
+}
+
+/// Why `who` can't approve recovery `id` of `acc` themselves, if anything
+fn approval_blocker(id: U256, acc: AccountHash, who: Key) -> Option<Err> {
+    if voted(id, who) { return Some(Err::AlreadyApproved); }
+    if read::<bool>(&format!("{}{}", prefixes::FINALIZED, id)).unwrap_or(false) { return Some(Err::Finalized); }
+    #[cfg(feature = "bonding")]
+    if let Key::Account(voter) = who {
+        let bond: U512 = read(&format!("{}{:?}", prefixes::BOND_REQUIRED, acc)).unwrap_or_default();
+        if !bond.is_zero() && read::<U512>(&format!("{}{:?}_{:?}", prefixes::BOND, acc, voter)).unwrap_or_default() < bond {
+            return Some(Err::BondRequired);
+        }
+    }
+    None
+}
+
+fn count_approval(id: U256, acc: AccountHash, who: Key) {
+    cast(id, who, true);
+    let cnt: u8 = read(&format!("{}{}", prefixes::APPROVAL_COUNT, id)).unwrap_or(0) + 1;
+    write(&format!("{}{}", prefixes::APPROVAL_COUNT, id), cnt);
+    emit(events::RecoveryApprovedV3 { tenant: tenant(), id, account: acc, guardian: who, approvals: cnt, notify: notify(acc) });
+    run_modules(acc, modules::ON_APPROVE, runtime_args! { "id" => id, "account" => acc, "guardian" => who });
+    let mut timeline: Vec<(Key, u64)> = read(&format!("{}{}", prefixes::TIMELINE, id)).unwrap_or_default();
+    timeline.push((who, tick(id).0));
+    write(&format!("{}{}", prefixes::TIMELINE, id), timeline);
+    if let Key::Account(g) = who {
+        record_stat(g, reputation::APPROVALS);
+        let started = read(&format!("{}{}", prefixes::STARTED_AT, id)).map_or(now(), Instant);
+        if started.within(reputation::APPROVAL_SLA, now()) { record_stat(g, reputation::APPROVALS_IN_SLA); }
+    }
+
+    if quorum(id, acc) { reach_threshold(id); }
+}
+
+/// True if the caller is `acc`'s owner, or the provider holding `acc`'s unused setup consent
+fn may_set_up(acc: AccountHash) -> bool {
+    if runtime::get_caller() == acc { return true; }
+    let Some((provider, _, used)) = read::<Option<(Key, u64, bool)>>(&format!("{}{:?}", prefixes::SETUP_CONSENT, acc)).flatten() else { return false };
+    !used && (provider == Key::Account(runtime::get_caller()) || Some(provider) == caller_package())
+}
+
+/// Mark a provider's setup consent for `acc` used; the owner setting up their own account leaves it be
+fn use_consent(acc: AccountHash) {
+    if runtime::get_caller() == acc { return; }
+    let key = format!("{}{:?}", prefixes::SETUP_CONSENT, acc);
+    if let Some((provider, at, _)) = read::<Option<(Key, u64, bool)>>(&key).flatten() {
+        write(&key, Some((provider, at, true)));
+    }
+}
+
+/// Register `guards` with threshold `thresh` for `acc`, writing nothing unless every check passes.
+/// Errors are `Err` codes.
+fn set_up(acc: AccountHash, guards: Vec<AccountHash>, thresh: u8, keys: &[PublicKey]) -> Result<(), u16> {
+    if !may_set_up(acc) { return Result::Err(Err::NotOwner as u16); }
+    match validation::check_guardian_set(acc, &guards, thresh) {
+        Ok(()) => {}
+        Result::Err(GuardianError::InvalidThreshold) => return Result::Err(Err::BadThreshold as u16),
+        Result::Err(_) => return Result::Err(Err::BadGuardians as u16),
+    }
+    if !keys_ok(acc, &guards, keys) { return Result::Err(Err::BadKeyType as u16); }
+
+    let k = format!("{}{:?}", prefixes::INITIALIZED, acc);
+    if read::<bool>(&k).unwrap_or(false) { return Result::Err(Err::AlreadyInit as u16); }
+    charge(fees::SETUP, acc)?;
+
+    use_consent(acc);
+    write(&format!("{}{:?}", prefixes::GUARDIANS, acc), guards.clone());
+    write(&format!("{}{:?}", prefixes::THRESHOLD, acc), thresh);
+    write(&k, true);
+    emit(events::GuardiansInitializedV2 { tenant: tenant(), account: acc, threshold: thresh });
+
+    // Add reverse mapping: for each guardian, add this account to their protected list
+    for guard in &guards {
+        let key = format!("{}{:?}", prefixes::PROTECTED_ACCOUNTS, guard);
+        let mut protected: Vec<AccountHash> = read(&key).unwrap_or(vec![]);
+        if !protected.contains(&acc) {
+            protected.push(acc);
+            write(&key, protected);
+        }
+    }
+    Ok(())
 }
 
 #[no_mangle]
-pub extern "C" fn get_guardians() {
+pub extern "C" fn init_guardians() {
+    check_args("init_guardians");
     let acc: AccountHash = runtime::get_named_arg("account");
-    let g: Vec<AccountHash> = read(&format!("g{:?}", acc)).unwrap_or_revert_with(ApiError::User(Err::NotInit as u16));
-    runtime::ret(CLValue::from_t(g).unwrap_or_revert());
+    let guards: Vec<AccountHash> = runtime::get_named_arg("guardians");
+    let thresh: u8 = runtime::get_named_arg("threshold");
+    let keys: Vec<PublicKey> = runtime::try_get_named_arg("guardian_keys").unwrap_or_default();
+
+    taking_new();
+    if let Result::Err(code) = set_up(acc, guards, thresh, &keys) { runtime::revert(ApiError::User(code)); }
 }
 
+/// `init_guardians` from the built-in `guardian_types::templates` template `template`: `guardians`
+/// fill its slots in order, and the template's threshold and settings apply. Pays the setup fee.
 #[no_mangle]
-pub extern "C" fn has_guardians() {
+pub extern "C" fn init_from_template() {
+    check_args("init_from_template");
     let acc: AccountHash = runtime::get_named_arg("account");
-    runtime::ret(CLValue::from_t(read::<bool>(&format!("i{:?}", acc)).unwrap_or(false)).unwrap_or_revert());
+    let name: String = runtime::get_named_arg("template");
+    let guards: Vec<AccountHash> = runtime::get_named_arg("guardians");
+    let keys: Vec<PublicKey> = runtime::try_get_named_arg("guardian_keys").unwrap_or_default();
+
+    taking_new();
+    let template = templates::find(&name).unwrap_or_revert_with(ApiError::User(Err::BadTemplate as u16));
+    if guards.len() != template.slots.len() { runtime::revert(ApiError::User(Err::BadTemplate as u16)); }
+    if let Result::Err(code) = set_up(acc, guards, template.threshold, &keys) { runtime::revert(ApiError::User(code)); }
+    write(&format!("{}{:?}", prefixes::SETTINGS, acc), template.settings);
+    emit(events::TemplateAppliedV1 { tenant: tenant(), account: acc, template: name });
 }
 
+/// Bulk `init_guardians` for onboarding many accounts, each entry (account, guardians, threshold)
+/// needing its owner's setup consent for the caller. Entries succeed or fail on their own; returns
+/// (account, 0 or the `Err` code it failed with) per entry. Accounts with a key policy can't be
+/// set up this way. Each entry pays the setup fee from `purse`, so it has to hold enough for all.
 #[no_mangle]
-pub extern "C" fn init_storage() {
-    storage::new_dictionary(DICT).unwrap_or_revert();
+pub extern "C" fn init_guardians_batch() {
+    check_args("init_guardians_batch");
+    let entries: Vec<(AccountHash, Vec<AccountHash>, u8)> = runtime::get_named_arg("entries");
+    if entries.is_empty() || entries.len() > MAX_BATCH { runtime::revert(ApiError::User(Err::BadBatch as u16)); }
+    taking_new();
+
+    // CLType has no u16, so codes go out widened
+    let results: Vec<(AccountHash, u32)> = entries
+        .into_iter()
+        .map(|(acc, guards, thresh)| (acc, set_up(acc, guards, thresh, &[]).err().unwrap_or(0).into()))
+        .collect();
+    runtime::ret(CLValue::from_t(results).unwrap_or_revert());
 }
 
-/// Get all active recovery IDs for a guardian
+/// Privacy mode: like `init_guardians`, but takes `guardian_digest`s computed off-chain so guardian
+/// identities never reach global state
 #[no_mangle]
-pub extern "C" fn get_recoveries_for_guardian() {
-    let guardian: AccountHash = runtime::get_named_arg("guardian");
-    let recoveries: Vec<U256> = read(&format!("gr{:?}", guardian)).unwrap_or(vec![]);
-    runtime::ret(CLValue::from_t(recoveries).unwrap_or_revert());
+pub extern "C" fn init_private_guardians() {
+    check_args("init_private_guardians");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let digests: Vec<[u8; 32]> = runtime::get_named_arg("guardian_hashes");
+    let thresh: u8 = runtime::get_named_arg("threshold");
+
+    taking_new();
+    if !may_set_up(acc) { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if digests.len() < 2 || (1..digests.len()).any(|i| digests[..i].contains(&digests[i])) { runtime::revert(ApiError::User(Err::BadGuardians as u16)); }
+    if thresh == 0 || thresh as usize > digests.len() { runtime::revert(ApiError::User(Err::BadThreshold as u16)); }
+
+    let k = format!("{}{:?}", prefixes::INITIALIZED, acc);
+    if read::<bool>(&k).unwrap_or(false) { runtime::revert(ApiError::User(Err::AlreadyInit as u16)); }
+    if let Result::Err(code) = charge(fees::SETUP, acc) { runtime::revert(ApiError::User(code)); }
+
+    use_consent(acc);
+    write(&format!("{}{:?}", prefixes::GUARDIANS, acc), Vec::<AccountHash>::new());
+    write(&format!("{}{:?}", prefixes::HASHED_GUARDIANS, acc), digests);
+    write(&format!("{}{:?}", prefixes::VERIFIER, acc), verify::HASHED);
+    write(&format!("{}{:?}", prefixes::THRESHOLD, acc), thresh);
+    write(&k, true);
+    emit(events::GuardiansInitializedV2 { tenant: tenant(), account: acc, threshold: thresh });
 }
 
-/// Get all accounts that a guardian protects
+/// Let wallet `provider` (an account, or a contract package as `Key::Hash`) run one guardian setup
+/// for `account` during onboarding; `None` withdraws an unused consent. The consent, when it was
+/// given and whether it was used stay on record.
 #[no_mangle]
-pub extern "C" fn get_protected_accounts() {
-    let guardian: AccountHash = runtime::get_named_arg("guardian");
-    let accounts: Vec<AccountHash> = read(&format!("ga{:?}", guardian)).unwrap_or(vec![]);
-    runtime::ret(CLValue::from_t(accounts).unwrap_or_revert());
+pub extern "C" fn authorize_setup() {
+    check_args("authorize_setup");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let provider: Option<Key> = runtime::get_named_arg("provider");
+
+    taking_new();
+    if runtime::get_caller() != acc { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if read::<bool>(&format!("{}{:?}", prefixes::INITIALIZED, acc)).unwrap_or(false) { runtime::revert(ApiError::User(Err::AlreadyInit as u16)); }
+    let provider = provider.map(|p| match host::stored_key(p) {
+        p @ (Key::Account(_) | Key::Hash(_)) => p,
+        _ => runtime::revert(ApiError::User(Err::BadProvider as u16)),
+    });
+    write(&format!("{}{:?}", prefixes::SETUP_CONSENT, acc), provider.map(|p| (p, now().0, false)));
 }
 
+/// Returns (provider, consented at, used) for `account`'s setup consent, if any
 #[no_mangle]
-pub extern "C" fn call() {
-    let mut eps = EntryPoints::new();
-
-    eps.add_entry_point(EntryPoint::new(
-        "init_storage",
-        vec![],
-        CLType::Unit, EntryPointAccess::Public, EntryPointType::Called,
-    ));
-
-    eps.add_entry_point(EntryPoint::new(
-        "init_guardians",
-        vec![
-            Parameter::new("account", CLType::ByteArray(32)),
-            Parameter::new("guardians", CLType::List(Box::new(CLType::ByteArray(32)))),
-            Parameter::new("threshold", CLType::U8),
-        ],
-        CLType::Unit, EntryPointAccess::Public, EntryPointType::Called,
-    ));
-
-    eps.add_entry_point(EntryPoint::new(
-        "start_recovery",
-        vec![
-            Parameter::new("account", CLType::ByteArray(32)),
-            Parameter::new("new_key", CLType::PublicKey),
-        ],
-        CLType::U256, EntryPointAccess::Public, EntryPointType::Called,
-    ));
-
-    eps.add_entry_point(EntryPoint::new(
-        "approve", vec![Parameter::new("id", CLType::U256)],
-        CLType::Unit, EntryPointAccess::Public, EntryPointType::Called,
-    ));
-
-    eps.add_entry_point(EntryPoint::new(
-        "is_approved", vec![Parameter::new("id", CLType::U256)],
-        CLType::Bool, EntryPointAccess::Public, EntryPointType::Called,
-    ));
-
-    eps.add_entry_point(EntryPoint::new(
-        "finalize", vec![Parameter::new("id", CLType::U256)],
-        CLType::Unit, EntryPointAccess::Public, EntryPointType::Called,
-    ));
-
-    eps.add_entry_point(EntryPoint::new(
-        "get_guardians", vec![Parameter::new("account", CLType::ByteArray(32))],
-        CLType::List(Box::new(CLType::ByteArray(32))), EntryPointAccess::Public, EntryPointType::Called,
-    ));
-
-    eps.add_entry_point(EntryPoint::new(
-        "has_guardians", vec![Parameter::new("account", CLType::ByteArray(32))],
-        CLType::Bool, EntryPointAccess::Public, EntryPointType::Called,
-    ));
-
-    eps.add_entry_point(EntryPoint::new(
-        "get_recoveries_for_guardian", vec![Parameter::new("guardian", CLType::ByteArray(32))],
-        CLType::List(Box::new(CLType::U256)), EntryPointAccess::Public, EntryPointType::Called,
-    ));
-
-    eps.add_entry_point(EntryPoint::new(
-        "get_protected_accounts", vec![Parameter::new("guardian", CLType::ByteArray(32))],
-        CLType::List(Box::new(CLType::ByteArray(32))), EntryPointAccess::Public, EntryPointType::Called,
-    ));
-
-    let (hash, _) = storage::new_locked_contract(
-        eps.into(), 
-        None, 
-        Some("recovery_registry_contract".to_string()), 
-        Some("recovery_registry_package".to_string()),
-        None
+pub extern "C" fn get_setup_consent() {
+    check_args("get_setup_consent");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let c: Option<(Key, u64, bool)> = read::<Option<(Key, u64, bool)>>(&format!("{}{:?}", prefixes::SETUP_CONSENT, acc)).flatten();
+    runtime::ret(CLValue::from_t(c).unwrap_or_revert());
+}
+
+/// True if `guardian` is a listed or privacy-mode guardian of `account` (Merkle members need a proof)
+#[no_mangle]
+pub extern "C" fn is_guardian() {
+    check_args("is_guardian");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let who: AccountHash = runtime::get_named_arg("guardian");
+    let listed = read::<Vec<AccountHash>>(&format!("{}{:?}", prefixes::GUARDIANS, acc)).is_some_and(|g| g.contains(&who));
+    runtime::ret(CLValue::from_t(listed || verify::hidden(acc, who)).unwrap_or_revert());
+}
+
+/// Like `init_guardians`, but only a Merkle root of the guardian set (see `merkle_member`) and its
+/// size are stored; guardians pass a membership `proof` when approving
+#[no_mangle]
+pub extern "C" fn init_merkle_guardians() {
+    check_args("init_merkle_guardians");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let root: [u8; 32] = runtime::get_named_arg("root");
+    let size: u32 = runtime::get_named_arg("size");
+    let thresh: u8 = runtime::get_named_arg("threshold");
+
+    taking_new();
+    if !may_set_up(acc) { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if size < 2 { runtime::revert(ApiError::User(Err::BadGuardians as u16)); }
+    if thresh == 0 || thresh as u32 > size { runtime::revert(ApiError::User(Err::BadThreshold as u16)); }
+
+    let k = format!("{}{:?}", prefixes::INITIALIZED, acc);
+    if read::<bool>(&k).unwrap_or(false) { runtime::revert(ApiError::User(Err::AlreadyInit as u16)); }
+    if let Result::Err(code) = charge(fees::SETUP, acc) { runtime::revert(ApiError::User(code)); }
+
+    use_consent(acc);
+    write(&format!("{}{:?}", prefixes::GUARDIANS, acc), Vec::<AccountHash>::new());
+    write(&format!("{}{:?}", prefixes::MERKLE_ROOT, acc), Some((root, size)));
+    write(&format!("{}{:?}", prefixes::VERIFIER, acc), verify::MERKLE);
+    write(&format!("{}{:?}", prefixes::THRESHOLD, acc), thresh);
+    write(&k, true);
+    emit(events::GuardiansInitializedV2 { tenant: tenant(), account: acc, threshold: thresh });
+}
+
+/// Replace the committed guardian set outside of any pending recovery
+#[no_mangle]
+pub extern "C" fn set_guardian_root() {
+    check_args("set_guardian_root");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let root: [u8; 32] = runtime::get_named_arg("root");
+    let size: u32 = runtime::get_named_arg("size");
+
+    if !holds(acc, roles::CONFIG_ADMIN) { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::Frozen as u16)); }
+    if read::<Option<([u8; 32], u32)>>(&format!("{}{:?}", prefixes::MERKLE_ROOT, acc)).flatten().is_none() { runtime::revert(ApiError::User(Err::NotInit as u16)); }
+    if pending(acc) { runtime::revert(ApiError::User(Err::RecoveryExists as u16)); }
+    write(&format!("{}{:?}", prefixes::MERKLE_ROOT, acc), Some((root, size)));
+
+    if guardian_count(acc) < 2 { runtime::revert(ApiError::User(Err::BadGuardians as u16)); }
+    if let Err(e) = approval_policy(acc).check(&[], guardian_count(acc), &[]) { runtime::revert(ApiError::User(e as u16)); }
+}
+
+/// Returns the committed (root, size) of `account`'s guardian set, if it uses one
+#[no_mangle]
+pub extern "C" fn get_guardian_root() {
+    check_args("get_guardian_root");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let r: Option<([u8; 32], u32)> = read::<Option<([u8; 32], u32)>>(&format!("{}{:?}", prefixes::MERKLE_ROOT, acc)).flatten();
+    runtime::ret(CLValue::from_t(r).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn start_recovery() {
+    check_args("start_recovery");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let nk: PublicKey = runtime::get_named_arg("new_key");
+    let kind: Option<String> = runtime::try_get_named_arg("recovery_type");
+
+    taking_new();
+    if !read::<bool>(&format!("{}{:?}", prefixes::INITIALIZED, acc)).unwrap_or(false) { runtime::revert(ApiError::User(Err::NotInit as u16)); }
+    // Accounts that name recovery initiators take recoveries from them alone
+    if members(acc).iter().any(|(_, r)| r & roles::RECOVERY_INITIATOR != 0) && !holds(acc, roles::RECOVERY_INITIATOR) {
+        runtime::revert(ApiError::User(Err::NotInitiator as u16));
+    }
+    if pending(acc) { runtime::revert(ApiError::User(Err::RecoveryExists as u16)); }
+    let cfg = settings(acc);
+    let attempts: u8 = read(&format!("{}{:?}", prefixes::ATTEMPTS, acc)).unwrap_or(0);
+    if cfg.max_attempts > 0 && attempts >= cfg.max_attempts { runtime::revert(ApiError::User(Err::TooManyAttempts as u16)); }
+    if !cooldown_end(acc, &cfg).reached(clock(&cfg)) { runtime::revert(ApiError::User(Err::CoolingDown as u16)); }
+
+    // In inheritance mode the new key must be one an heir registered in advance
+    if inheritable(acc) {
+        let heirs: Vec<AccountHash> = read(&format!("{}{:?}", prefixes::HEIRS, acc)).unwrap_or(vec![]);
+        let ok = heirs.iter().any(|h| read::<Option<PublicKey>>(&format!("{}{:?}_{:?}", prefixes::HEIR_KEY, acc, h)).flatten().as_ref() == Some(&nk));
+        if !ok { runtime::revert(ApiError::User(Err::NotHeirKey as u16)); }
+    }
+
+    // Clients can derive the ID up front from the account's nonce and the proposed key
+    let nonce: u64 = read(&format!("{}{:?}", prefixes::RECOVERY_NONCE, acc)).unwrap_or(0);
+    let id = ids::recovery_id(acc, nonce, ids::key_commitment(&nk));
+    if read::<AccountHash>(&format!("{}{}", prefixes::RECOVERY_ACCOUNT, id)).is_some() { runtime::revert(ApiError::User(Err::RecoveryExists as u16)); }
+
+    // Initiators lock the owner's configured deposit, passed in as `purse` via the `funded_call` session
+    let deposit: U512 = read(&format!("{}{:?}", prefixes::DEPOSIT_REQUIRED, acc)).unwrap_or_default();
+    if !deposit.is_zero() {
+        let src: URef = runtime::try_get_named_arg("purse").unwrap_or_revert_with(ApiError::User(Err::DepositRequired as u16));
+        system::transfer_from_purse_to_purse(src, purse(&tenant_key(DEPOSIT_PURSE)), deposit, None)
+            .unwrap_or_revert_with(ApiError::User(Err::DepositRequired as u16));
+        write(&format!("{}{}", prefixes::RECOVERY_DEPOSIT, id), deposit);
+    }
+
+    write(&format!("{}{:?}", prefixes::RECOVERY_NONCE, acc), nonce + 1);
+    write(&format!("{}{}", prefixes::INITIATOR, id), runtime::get_caller());
+    write(&format!("{}{}", prefixes::RECOVERY_ACCOUNT, id), acc);
+    write(&format!("{}{}", prefixes::NEW_KEY, id), nk.clone());
+    write(&format!("{}{}", prefixes::APPROVAL_COUNT, id), 0u8);
+    write(&format!("{}{:?}", prefixes::ATTEMPTS, acc), attempts.saturating_add(1));
+    // Snapshot the timing settings so they can't be changed under a pending recovery
+    if cfg.approval_window > 0 { write(&format!("{}{}", prefixes::APPROVAL_WINDOW, id), cfg.approval_window); }
+    if cfg.by_height { write(&format!("{}{}", prefixes::BY_HEIGHT, id), true); }
+    if cfg.delay > 0 { write(&format!("{}{}", prefixes::UNLOCK_AT, id), clock(&cfg).plus(Span(cfg.delay)).0); }
+    if cfg.veto_window > 0 { write(&format!("{}{}", prefixes::VETO_WINDOW, id), cfg.veto_window); }
+    write(&format!("{}{}", prefixes::THRESHOLD_REACHED, id), false);
+    write(&format!("{}{}", prefixes::STARTED_AT, id), now().0);
+    // Snapshot the confirmation policy so it can't be changed under a pending recovery
+    if let Some(policy) = read::<Option<(u64, u8, u64)>>(&format!("{}{:?}", prefixes::CONFIRMATION_POLICY, acc)).flatten() {
+        write(&format!("{}{}", prefixes::CONFIRMATION_ROUND, id), policy);
+    }
+    if cfg.expiry > 0 { write(&format!("{}{}", prefixes::EXPIRES_AT, id), clock(&cfg).plus(Span(cfg.expiry)).0); }
+    if let Some(kind) = kind {
+        // Snapshot the type's threshold so later policy edits can't move a pending recovery
+        let types: Vec<(String, u8)> = read(&format!("{}{:?}", prefixes::RECOVERY_TYPES, acc)).unwrap_or(vec![]);
+        let t = types.iter().find(|(n, _)| *n == kind).map(|(_, t)| *t).unwrap_or_revert_with(ApiError::User(Err::BadType as u16));
+        write(&format!("{}{}", prefixes::RECOVERY_TYPE, id), kind);
+        write(&format!("{}{}", prefixes::REQUIRED, id), t);
+    }
+    if let Some(prev) = uncounted_expiry(acc) { bump(prev, acc, counters::EXPIRED); }
+    write(&format!("{}{:?}", prefixes::ACTIVE_RECOVERY, acc), id);
+
+    // Add reverse mapping: for each guardian, add this recovery ID to their active recoveries list
+    let guards: Vec<AccountHash> = read(&format!("{}{:?}", prefixes::GUARDIANS, acc)).unwrap_or(vec![]);
+    for guard in &guards {
+        track(*guard, id);
+    }
+
+    emit(events::RecoveryStartedV3 { tenant: tenant(), id, account: acc, new_key: nk.clone(), notify: notify(acc) });
+    bump(id, acc, counters::STARTED);
+    run_modules(acc, modules::ON_INITIATE, runtime_args! { "id" => id, "account" => acc, "new_key" => nk });
+    runtime::ret(CLValue::from_t(id).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn approve() {
+    check_args("approve");
+    let id: U256 = runtime::get_named_arg("id");
+
+    let acc: AccountHash = read(&format!("{}{}", prefixes::RECOVERY_ACCOUNT, id)).unwrap_or_revert_with(ApiError::User(Err::NotFound as u16));
+
+    // Contract guardians approve by calling in directly; their approval is keyed by package.
+    record_approval(id, acc, ballot(acc));
+}
+
+/// Relay a guardian's off-chain signature over `sig::approval_payload` as their approval, so the
+/// guardian needn't hold CSPR or sign a deploy themselves
+#[no_mangle]
+pub extern "C" fn approve_signed() {
+    check_args("approve_signed");
+    let id: U256 = runtime::get_named_arg("id");
+    let guardian: PublicKey = runtime::get_named_arg("guardian");
+    let signature: Vec<u8> = runtime::get_named_arg("signature");
+
+    let acc: AccountHash = read(&format!("{}{}", prefixes::RECOVERY_ACCOUNT, id)).unwrap_or_revert_with(ApiError::User(Err::NotFound as u16));
+    let guards: Vec<AccountHash> = read(&format!("{}{:?}", prefixes::GUARDIANS, acc)).unwrap_or_default();
+    if !guards.contains(&guardian.to_account_hash()) { runtime::revert(ApiError::User(Err::NotGuardian as u16)); }
+
+    let nk: PublicKey = read(&format!("{}{}", prefixes::NEW_KEY, id)).unwrap_or_revert();
+    let memo: String = runtime::try_get_named_arg("memo").unwrap_or_default();
+    if !sig::verify(&guardian, &sig::approval_payload(id, acc, &nk, &memo), &signature) {
+        runtime::revert(ApiError::User(Err::BadSignature as u16));
+    }
+    record_approval(id, acc, Key::Account(guardian.to_account_hash()));
+
+    // Keep the signature so anyone can re-check the quorum against `sig::approval_payload` later
+    let mut proofs: Vec<(PublicKey, Vec<u8>, String)> = read(&format!("{}{}", prefixes::SIGNED_PROOFS, id)).unwrap_or_default();
+    proofs.push((guardian, signature, memo));
+    write(&format!("{}{}", prefixes::SIGNED_PROOFS, id), proofs);
+}
+
+/// Submit a guardian's approval signed over `sig::meta_approval_payload` with their current meta
+/// nonce, the relayer paying the gas so the guardian needn't hold CSPR. Each signature counts once:
+/// the nonce moves on when it is accepted. If `account` allowlists relayers only they may submit.
+#[no_mangle]
+pub extern "C" fn approve_meta() {
+    check_args("approve_meta");
+    let id: U256 = runtime::get_named_arg("id");
+    let guardian: PublicKey = runtime::get_named_arg("guardian");
+    let nonce: u64 = runtime::get_named_arg("nonce");
+    let signature: Vec<u8> = runtime::get_named_arg("signature");
+
+    let acc: AccountHash = read(&format!("{}{}", prefixes::RECOVERY_ACCOUNT, id)).unwrap_or_revert_with(ApiError::User(Err::NotFound as u16));
+    let relayers = relayers(acc);
+    if !relayers.is_empty() && !relayers.contains(&runtime::get_caller()) { runtime::revert(ApiError::User(Err::NotRelayer as u16)); }
+    let guards: Vec<AccountHash> = read(&format!("{}{:?}", prefixes::GUARDIANS, acc)).unwrap_or_default();
+    let gh = guardian.to_account_hash();
+    if !guards.contains(&gh) { runtime::revert(ApiError::User(Err::NotGuardian as u16)); }
+    if nonce != meta_nonce(gh) { runtime::revert(ApiError::User(Err::BadNonce as u16)); }
+
+    let nk: PublicKey = read(&format!("{}{}", prefixes::NEW_KEY, id)).unwrap_or_revert();
+    let memo: String = runtime::try_get_named_arg("memo").unwrap_or_default();
+    if !sig::verify(&guardian, &sig::meta_approval_payload(id, acc, &nk, &memo, nonce), &signature) {
+        runtime::revert(ApiError::User(Err::BadSignature as u16));
+    }
+    write(&format!("{}{:?}", prefixes::META_NONCE, gh), nonce + 1);
+    record_approval(id, acc, Key::Account(gh));
+}
+
+/// Nonce `guardian`'s next meta-approval has to be signed with
+fn meta_nonce(guardian: AccountHash) -> u64 {
+    read(&format!("{}{:?}", prefixes::META_NONCE, guardian)).unwrap_or(0)
+}
+
+/// Returns the nonce `guardian`'s next meta-approval has to be signed with
+#[no_mangle]
+pub extern "C" fn get_meta_nonce() {
+    check_args("get_meta_nonce");
+    let guardian: AccountHash = runtime::get_named_arg("guardian");
+    runtime::ret(CLValue::from_t(meta_nonce(guardian)).unwrap_or_revert());
+}
+
+/// Guardian voids every meta-approval they signed but no relayer has submitted yet, by moving their
+/// nonce past them
+#[no_mangle]
+pub extern "C" fn void_meta_approvals() {
+    check_args("void_meta_approvals");
+    let guardian = runtime::get_caller();
+    write(&format!("{}{:?}", prefixes::META_NONCE, guardian), meta_nonce(guardian) + 1);
+}
+
+fn relayers(acc: AccountHash) -> Vec<AccountHash> {
+    read(&format!("{}{:?}", prefixes::RELAYERS, acc)).unwrap_or_default()
+}
+
+/// Limit who may submit meta-approvals for `account`'s recoveries to `relayers`, so no one else can
+/// spend guardians' signatures or front-run the account's relayer. An empty list lets anyone relay.
+#[no_mangle]
+pub extern "C" fn set_relayers() {
+    check_args("set_relayers");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let list: Vec<AccountHash> = runtime::get_named_arg("relayers");
+
+    if !holds(acc, roles::CONFIG_ADMIN) { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::Frozen as u16)); }
+    if list.len() > MAX_RELAYERS || list.iter().enumerate().any(|(i, r)| list[..i].contains(r)) {
+        runtime::revert(ApiError::User(Err::NotRelayer as u16));
+    }
+    write(&format!("{}{:?}", prefixes::RELAYERS, acc), list);
+}
+
+/// Returns the relayers allowed to submit meta-approvals for `account`; empty if anyone may
+#[no_mangle]
+pub extern "C" fn get_relayers() {
+    check_args("get_relayers");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    runtime::ret(CLValue::from_t(relayers(acc)).unwrap_or_revert());
+}
+
+/// Register `account`'s FROST group key: the Ed25519 key its guardians produced in a distributed key
+/// generation at the threshold they agreed on. `None` removes it. The key is tied to the current
+/// guardian set and stops working once that changes.
+#[no_mangle]
+pub extern "C" fn set_frost_key() {
+    check_args("set_frost_key");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let key: Option<PublicKey> = runtime::get_named_arg("group_key");
+
+    if !holds(acc, roles::CONFIG_ADMIN) { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::Frozen as u16)); }
+    let guards: Vec<AccountHash> = read(&format!("{}{:?}", prefixes::GUARDIANS, acc)).unwrap_or_revert_with(ApiError::User(Err::NotInit as u16));
+    if pending(acc) { runtime::revert(ApiError::User(Err::RecoveryExists as u16)); }
+    if key.as_ref().is_some_and(|k| !matches!(k, PublicKey::Ed25519(_))) { runtime::revert(ApiError::User(Err::BadKeyType as u16)); }
+    write(&format!("{}{:?}", prefixes::FROST_KEY, acc), key.map(|k| (k, guards)));
+}
+
+/// Returns `account`'s FROST group key, if one is registered and still matches its guardian set
+#[no_mangle]
+pub extern "C" fn get_frost_key() {
+    check_args("get_frost_key");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    runtime::ret(CLValue::from_t(frost_key(acc)).unwrap_or_revert());
+}
+
+fn frost_key(acc: AccountHash) -> Option<PublicKey> {
+    let (key, snapshot) = read::<Option<(PublicKey, Vec<AccountHash>)>>(&format!("{}{:?}", prefixes::FROST_KEY, acc)).flatten()?;
+    let guards: Vec<AccountHash> = read(&format!("{}{:?}", prefixes::GUARDIANS, acc)).unwrap_or_default();
+    (guards == snapshot).then_some(key)
+}
+
+/// Approve recovery `id` outright with the guardian group's FROST aggregate signature over
+/// `payloads::frost_approval_payload`, in place of individual approvals. Anyone may relay it.
+#[no_mangle]
+pub extern "C" fn approve_frost() {
+    check_args("approve_frost");
+    let id: U256 = runtime::get_named_arg("id");
+    let signature: Vec<u8> = runtime::get_named_arg("signature");
+
+    let acc: AccountHash = read(&format!("{}{}", prefixes::RECOVERY_ACCOUNT, id)).unwrap_or_revert_with(ApiError::User(Err::NotFound as u16));
+    if read::<bool>(&format!("{}{}", prefixes::FINALIZED, id)).unwrap_or(false) { runtime::revert(ApiError::User(Err::Finalized as u16)); }
+    if read::<bool>(&format!("{}{}", prefixes::CANCELLED, id)).unwrap_or(false) { runtime::revert(ApiError::User(Err::Vetoed as u16)); }
+    if expired(id) { runtime::revert(ApiError::User(Err::Expired as u16)); }
+    if approved(id, acc) { runtime::revert(ApiError::User(Err::AlreadyApproved as u16)); }
+    // A group signature doesn't show which guardians took part, so neither weights nor categories
+    if matches!(approval_policy(acc), ApprovalPolicy::Weighted { .. } | ApprovalPolicy::CategoryQuorum { .. } | ApprovalPolicy::Fractional { .. }) {
+        runtime::revert(ApiError::User(Err::BadPolicy as u16));
+    }
+
+    let key = frost_key(acc).unwrap_or_revert_with(ApiError::User(Err::NoFrostKey as u16));
+    let nk: PublicKey = read(&format!("{}{}", prefixes::NEW_KEY, id)).unwrap_or_revert();
+    if !sig::verify(&key, &payloads::frost_approval_payload(id, acc, &nk), &signature) {
+        runtime::revert(ApiError::User(Err::BadSignature as u16));
+    }
+
+    // Kept so anyone can re-check the group approval later
+    write(&format!("{}{}", prefixes::FROST_APPROVAL, id), (key.clone(), signature));
+    emit(events::RecoveryFrostApprovedV2 { tenant: tenant(), id, account: acc, group_key: key, notify: notify(acc) });
+    reach_threshold(id);
+}
+
+/// Relay many guardians' signed approvals of recovery `id` in one deploy. Each (guardian key,
+/// signature) pair is checked as `approve_signed` would, against the same `memo` for all, and every
+/// valid one is counted; an invalid item is skipped rather than failing the batch, with a
+/// `RejectedV2` naming its position in `approvals`. Returns and emits each guardian's outcome: 0 if
+/// counted, otherwise the `Err` code `approve_signed` would have reverted with.
+#[no_mangle]
+pub extern "C" fn submit_signed_approvals() {
+    check_args("submit_signed_approvals");
+    let id: U256 = runtime::get_named_arg("id");
+    let items: Vec<(PublicKey, Vec<u8>)> = runtime::get_named_arg("approvals");
+    if items.is_empty() || items.len() > MAX_BATCH { runtime::revert(ApiError::User(Err::BadBatch as u16)); }
+
+    let acc: AccountHash = read(&format!("{}{}", prefixes::RECOVERY_ACCOUNT, id)).unwrap_or_revert_with(ApiError::User(Err::NotFound as u16));
+    if read::<bool>(&format!("{}{}", prefixes::CANCELLED, id)).unwrap_or(false) { runtime::revert(ApiError::User(Err::Vetoed as u16)); }
+    if expired(id) { runtime::revert(ApiError::User(Err::Expired as u16)); }
+
+    let guards: Vec<AccountHash> = read(&format!("{}{:?}", prefixes::GUARDIANS, acc)).unwrap_or_default();
+    let nk: PublicKey = read(&format!("{}{}", prefixes::NEW_KEY, id)).unwrap_or_revert();
+    let memo: String = runtime::try_get_named_arg("memo").unwrap_or_default();
+    let payload = sig::approval_payload(id, acc, &nk, &memo);
+
+    let mut proofs: Vec<(PublicKey, Vec<u8>, String)> = read(&format!("{}{}", prefixes::SIGNED_PROOFS, id)).unwrap_or_default();
+    let mut outcomes: Vec<(PublicKey, u32)> = Vec::new();
+    for (item, (guardian, signature)) in items.into_iter().enumerate() {
+        let who = Key::Account(guardian.to_account_hash());
+        let outcome = if !guards.contains(&guardian.to_account_hash()) {
+            Err::NotGuardian as u32
+        } else if !sig::verify(&guardian, &payload, &signature) {
+            Err::BadSignature as u32
+        } else if let Some(e) = approval_blocker(id, acc, who) {
+            e as u32
+        } else {
+            count_approval(id, acc, who);
+            proofs.push((guardian.clone(), signature, memo.clone()));
+            0
+        };
+        if outcome != 0 { rejected("submit_signed_approvals", acc, who, outcome, 1, item); }
+        outcomes.push((guardian, outcome));
+    }
+    write(&format!("{}{}", prefixes::SIGNED_PROOFS, id), proofs);
+
+    let counted = outcomes.iter().filter(|(_, code)| *code == 0).count() as u8;
+    emit(events::SignedApprovalsSubmittedV2 { tenant: tenant(), id, account: acc, counted, outcomes: outcomes.clone(), notify: notify(acc) });
+    runtime::ret(CLValue::from_t(outcomes).unwrap_or_revert());
+}
+
+/// Returns (guardian key, signature, memo) for each signed approval of recovery `id`, in the order relayed
+#[no_mangle]
+pub extern "C" fn get_approval_proofs() {
+    check_args("get_approval_proofs");
+    let id: U256 = runtime::get_named_arg("id");
+    let proofs: Vec<(PublicKey, Vec<u8>, String)> = read(&format!("{}{}", prefixes::SIGNED_PROOFS, id)).unwrap_or_default();
+    runtime::ret(CLValue::from_t(proofs).unwrap_or_revert());
+}
+
+/// Guardian records their objection to recovery `id`, with an optional memo. Rejections are advisory:
+/// they don't cancel the recovery, but they stop the guardian from approving it later.
+#[no_mangle]
+pub extern "C" fn reject() {
+    check_args("reject");
+    let id: U256 = runtime::get_named_arg("id");
+    let acc: AccountHash = read(&format!("{}{}", prefixes::RECOVERY_ACCOUNT, id)).unwrap_or_revert_with(ApiError::User(Err::NotFound as u16));
+    if closed(id) { runtime::revert(ApiError::User(Err::NotFound as u16)); }
+    cast(id, ballot(acc), false);
+}
+
+/// (state: 0 open, 1 approved, 2 finalized, 3 cancelled, 4 expired; (approvals, threshold); each
+/// vote as (guardian, approved, memo) in the order cast) of recovery `id`
+fn recovery_status(id: U256) -> (u8, (u32, u8), Vec<(Key, bool, String)>) {
+    let acc: AccountHash = read(&format!("{}{}", prefixes::RECOVERY_ACCOUNT, id)).unwrap_or_revert_with(ApiError::User(Err::NotFound as u16));
+    let state: u8 = if read::<bool>(&format!("{}{}", prefixes::FINALIZED, id)).unwrap_or(false) { 2 }
+        else if read::<bool>(&format!("{}{}", prefixes::CANCELLED, id)).unwrap_or(false) { 3 }
+        else if expired(id) { 4 }
+        else if approved(id, acc) { 1 }
+        else { 0 };
+    let log: Vec<(Key, bool, String)> = read(&format!("{}{}", prefixes::VOTE_LOG, id)).unwrap_or_default();
+    (state, (score(id, acc), threshold(id, acc)), log)
+}
+
+/// Returns (state: 0 open, 1 approved, 2 finalized, 3 cancelled, 4 expired; (approvals, threshold);
+/// each vote as (guardian, approved, memo) in the order cast). Deprecated for
+/// `get_recovery_status_v2`: approvals are capped at 255 and the expiry is missing.
+#[no_mangle]
+pub extern "C" fn get_recovery_status() {
+    check_args("get_recovery_status");
+    deprecated("get_recovery_status");
+    let id: U256 = runtime::get_named_arg("id");
+    let (state, (approvals, thresh), log) = recovery_status(id);
+    let cnt = approvals.min(u8::MAX as u32) as u8;
+    runtime::ret(CLValue::from_t((state, (cnt, thresh), log)).unwrap_or_revert());
+}
+
+/// Returns (state as `get_recovery_status`; (approvals, threshold, expiry); votes as
+/// `get_recovery_status`). Approvals are the policy's full score and the expiry is a block time in
+/// ms, or a block height for height-timed recoveries.
+#[no_mangle]
+pub extern "C" fn get_recovery_status_v2() {
+    check_args("get_recovery_status_v2");
+    let id: U256 = runtime::get_named_arg("id");
+    let (state, (approvals, thresh), log) = recovery_status(id);
+    let expiry: u64 = read(&format!("{}{}", prefixes::EXPIRES_AT, id)).unwrap_or(0);
+    runtime::ret(CLValue::from_t((state, (approvals, thresh, expiry), log)).unwrap_or_revert());
+}
+
+/// Returns (deprecated entry point, replacement) for every entry point `deprecations` lists
+#[no_mangle]
+pub extern "C" fn get_deprecations() {
+    check_args("get_deprecations");
+    let list: Vec<(String, String)> = deprecations::DEPRECATED.iter().map(|(old, new)| ((*old).into(), (*new).into())).collect();
+    runtime::ret(CLValue::from_t(list).unwrap_or_revert());
+}
+
+/// Dry run of a recovery of `account` started now (optionally of `recovery_type`) that `approvers`
+/// approve at once. Returns (would finalize, threshold now, earliest block time — or height, for
+/// height-timed accounts — `finalize` would succeed; 0 if never), allowing for threshold decay and a
+/// confirmation round the same guardians pass. Decay and confirmation delays are in ms and aren't
+/// placed on a height clock. Non-guardians and duplicates in `approvers` are ignored. Nothing is written.
+#[no_mangle]
+pub extern "C" fn simulate_recovery() {
+    check_args("simulate_recovery");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let approvers: Vec<Key> = runtime::get_named_arg("approvers");
+    let kind: Option<String> = runtime::try_get_named_arg("recovery_type");
+
+    if !read::<bool>(&format!("{}{:?}", prefixes::INITIALIZED, acc)).unwrap_or(false) { runtime::revert(ApiError::User(Err::NotInit as u16)); }
+    taking_new();
+
+    let guards: Vec<AccountHash> = read(&format!("{}{:?}", prefixes::GUARDIANS, acc)).unwrap_or_default();
+    let contracts = contract_guardians(acc);
+    let mut counted: Vec<Key> = vec![];
+    for who in approvers {
+        let ok = match who {
+            Key::Account(a) => guards.contains(&a) || verify::hidden(acc, a),
+            Key::Hash(_) => contracts.contains(&who),
+            _ => false,
+        };
+        if ok && !counted.contains(&who) { counted.push(who); }
+    }
+    let cnt = counted.len().min(u8::MAX as usize) as u8;
+    let policy = approval_policy(acc);
+    let idle = inactive_guardians(acc);
+    let score = policy.score(&counted, &idle);
+    let categories_met = policy.shortfall(&counted, &categories(acc)).is_empty();
+
+    let base: Option<u32> = kind.map(|kind| {
+        let types: Vec<(String, u8)> = read(&format!("{}{:?}", prefixes::RECOVERY_TYPES, acc)).unwrap_or(vec![]);
+        types.iter().find(|(n, _)| *n == kind).map(|(_, t)| *t as u32).unwrap_or_revert_with(ApiError::User(Err::BadType as u16))
+    });
+    let fractional = matches!(policy, ApprovalPolicy::Fractional { .. });
+    let cap = inactive_cap(acc).filter(|_| !fractional);
+    let required = |age: u64| { let r = policy.required(base.filter(|_| !fractional), age, &idle); cap.map_or(r, |c| c.min(r)) };
+    let thresh = match &policy {
+        ApprovalPolicy::Fractional { threshold_bps, .. } => policy::percent(*threshold_bps as u32, policy::BPS, true),
+        _ => required(0).min(u8::MAX as u32) as u8,
+    };
+
+    // Earliest age at which the (possibly decayed) requirement is met, then the owner's veto window,
+    // the settings delay and any confirmation round have to pass
+    let cfg = settings(acc);
+    let decayed = match &policy {
+        ApprovalPolicy::Decaying { steps, .. } if !cfg.by_height => steps.iter().map(|(a, _)| *a).find(|a| score >= required(*a)),
+        _ => None,
+    };
+    let reached = if score >= required(0) { Some(0) } else { decayed };
+    let mut after = reached.filter(|_| categories_met).map(|a| a.saturating_add(cfg.veto_window).max(cfg.delay));
+    if let Some((delay, quorum, _)) = read::<Option<(u64, u8, u64)>>(&format!("{}{:?}", prefixes::CONFIRMATION_POLICY, acc)).flatten() {
+        let delay = if cfg.by_height { 0 } else { delay };
+        after = after.filter(|_| cnt >= quorum).map(|a| a.max(delay));
+    }
+    if cfg.expiry > 0 { after = after.filter(|a| *a < cfg.expiry); }
+    // Still cooling down from the last recovery, so it couldn't be started now
+    if !cooldown_end(acc, &cfg).reached(clock(&cfg)) { after = None; }
+
+    let at = after.map_or(0, |a| clock(&cfg).plus(Span(a)).0);
+    runtime::ret(CLValue::from_t((after.is_some(), thresh, at)).unwrap_or_revert());
+}
+
+/// Nonce the next recovery of `account` will be derived with (see `guardian_types::ids`)
+#[no_mangle]
+pub extern "C" fn get_recovery_nonce() {
+    check_args("get_recovery_nonce");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    runtime::ret(CLValue::from_t(read::<u64>(&format!("{}{:?}", prefixes::RECOVERY_NONCE, acc)).unwrap_or(0)).unwrap_or_revert());
+}
+
+/// True if `guardian` (an account or contract guardian's package) already voted on recovery `id`
+#[no_mangle]
+pub extern "C" fn has_voted() {
+    check_args("has_voted");
+    let id: U256 = runtime::get_named_arg("id");
+    let who: Key = runtime::get_named_arg("guardian");
+    runtime::ret(CLValue::from_t(voted(id, who)).unwrap_or_revert());
+}
+
+/// Returns each approval of recovery `id` as (guardian, block time or height for height-timed
+/// recoveries) in the order they landed
+#[no_mangle]
+pub extern "C" fn get_approval_timeline() {
+    check_args("get_approval_timeline");
+    let id: U256 = runtime::get_named_arg("id");
+    let timeline: Vec<(Key, u64)> = read(&format!("{}{}", prefixes::TIMELINE, id)).unwrap_or_default();
+    runtime::ret(CLValue::from_t(timeline).unwrap_or_revert());
+}
+
+/// Only approvals cast within the last `window` ms (blocks, if timed by height) count toward new recoveries
+/// of `account` (zero disables)
+#[no_mangle]
+pub extern "C" fn set_approval_window() {
+    check_args("set_approval_window");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let window: u64 = runtime::get_named_arg("window");
+
+    if !holds(acc, roles::CONFIG_ADMIN) { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::Frozen as u16)); }
+    let mut cfg = settings(acc);
+    cfg.approval_window = window;
+    write(&format!("{}{:?}", prefixes::SETTINGS, acc), cfg);
+}
+
+#[no_mangle]
+pub extern "C" fn is_approved() {
+    check_args("is_approved");
+    let id: U256 = runtime::get_named_arg("id");
+    let ok = read::<AccountHash>(&format!("{}{}", prefixes::RECOVERY_ACCOUNT, id)).map_or(false, |acc| approved(id, acc));
+    runtime::ret(CLValue::from_t(ok).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn finalize() {
+    check_args("finalize");
+    let id: U256 = runtime::get_named_arg("id");
+
+    // Get the target account for this recovery
+    let acc: AccountHash = read(&format!("{}{}", prefixes::RECOVERY_ACCOUNT, id)).unwrap_or_revert_with(ApiError::User(Err::NotFound as u16));
+    if read::<bool>(&format!("{}{}", prefixes::CANCELLED, id)).unwrap_or(false) { runtime::revert(ApiError::User(Err::Vetoed as u16)); }
+    if read::<bool>(&format!("{}{}", prefixes::FINALIZED, id)).unwrap_or(false) { runtime::revert(ApiError::User(Err::Finalized as u16)); }
+    if expired(id) { runtime::revert(ApiError::User(Err::Expired as u16)); }
+    if !approved(id, acc) { runtime::revert(ApiError::User(Err::NotApproved as u16)); }
+    if let Some((_, quorum, _)) = read::<(u64, u8, u64)>(&format!("{}{}", prefixes::CONFIRMATION_ROUND, id)) {
+        if read::<u8>(&format!("{}{}", prefixes::CONFIRMATIONS, id)).unwrap_or(0) < quorum { runtime::revert(ApiError::User(Err::NotConfirmed as u16)); }
+    }
+    let unlock = Instant(read(&format!("{}{}", prefixes::UNLOCK_AT, id)).unwrap_or(0));
+    let veto_until = Instant(read(&format!("{}{}", prefixes::APPROVED_AT, id)).unwrap_or(0))
+        .plus(Span(read(&format!("{}{}", prefixes::VETO_WINDOW, id)).unwrap_or(0)));
+    if !unlock.max(veto_until).reached(tick(id)) { runtime::revert(ApiError::User(Err::TooEarly as u16)); }
+    if held(id, acc) { runtime::revert(ApiError::User(Err::Flagged as u16)); }
+    if let Result::Err(code) = charge(fees::FINALIZE, acc) { runtime::revert(ApiError::User(code)); }
+    write(&format!("{}{:?}", prefixes::ATTEMPTS, acc), 0u8);
+    
+    // Remove this recovery ID from each guardian's active recoveries list
+    untrack(id, acc);
+
+    settle_deposit(id, read(&format!("{}{}", prefixes::INITIATOR, id)).unwrap_or_revert());
+
+    // Pay the configured reward to each approving guardian while the account's reward balance lasts
+    let reward: U512 = read(&format!("{}{:?}", prefixes::REWARD, acc)).unwrap_or_default();
+    let mut balance: U512 = read(&format!("{}{:?}", prefixes::REWARD_BALANCE, acc)).unwrap_or_default();
+    if !reward.is_zero() {
+        for guard in approvers(id, acc) {
+            if balance < reward { break; }
+            balance -= reward;
+            system::transfer_from_purse_to_account(purse(&tenant_key(REWARD_PURSE)), guard, reward, None).unwrap_or_revert();
+        }
+        write(&format!("{}{:?}", prefixes::REWARD_BALANCE, acc), balance);
+    }
+
+    // Clear the active recovery mapping for this account
+    // Note: Casper dictionary doesn't have delete, so we write a zero value
+    // The active recovery check in start_recovery uses is_some(), so we need to handle this
+    // For now, we mark it as finalized by setting a special flag
+    write(&format!("{}{}", prefixes::FINALIZED, id), true); // Recovery finalized flag
+    // Both clocks, since the cool-down is read on whichever the settings use when the next one starts
+    write(&format!("{}{:?}", prefixes::LAST_COMPLETED, acc), (now().0, runtime::get_block_height()));
+    certify(id, acc);
+    // The rotation session keeps the old key at the grace weight until this deadline
+    let cfg = settings(acc);
+    if cfg.grace_period > 0 {
+        write(&format!("{}{}", prefixes::GRACE, id), (tick(id).plus(Span(cfg.grace_period)).0, cfg.grace_weight));
+    }
+    emit(events::RecoveryFinalizedV3 { tenant: tenant(), id, account: acc, notify: notify(acc) });
+    bump(id, acc, counters::FINALIZED);
+    run_modules(acc, modules::ON_EXECUTE, runtime_args! { "id" => id, "account" => acc });
+}
+
+/// Write-once quorum certificate for finalized recovery `id`: ((id, account, new key), each counted
+/// approval as (guardian, weight, time), (finalized at, arbiter broke a tie)). Kept apart from the
+/// working recovery keys so it outlives them.
+fn certify(id: U256, acc: AccountHash) {
+    let key = format!("{}{}", prefixes::QUORUM_CERTIFICATE, id);
+    if read::<Certificate>(&key).is_some() { return; }
+
+    let window = Span(read(&format!("{}{}", prefixes::APPROVAL_WINDOW, id)).unwrap_or(0));
+    let approved_at = read(&format!("{}{}", prefixes::APPROVED_AT, id)).map_or_else(|| tick(id), Instant);
+    let timeline: Vec<(Key, u64)> = read(&format!("{}{}", prefixes::TIMELINE, id)).unwrap_or_default();
+    // Approvals that counted when the threshold was reached, each weighing what the account's policy
+    // counts it for: 1, or the guardian's `Weighted` or `Fractional` weight (basis points past 255
+    // are recorded as 255)
+    let (policy, idle) = (approval_policy(acc), inactive_guardians(acc));
+    let quorum: Vec<(Key, u8, u64)> = timeline.into_iter()
+        .filter(|(_, at)| window.is_zero() || !Instant(*at).has_elapsed(window, approved_at))
+        .map(|(who, at)| (who, policy.score(&[who], &idle).min(u8::MAX as u32) as u8, at))
+        .collect();
+    let nk: PublicKey = read(&format!("{}{}", prefixes::NEW_KEY, id)).unwrap_or_revert();
+    let arbitrated = read::<bool>(&format!("{}{}", prefixes::ARBITER_APPROVED, id)).unwrap_or(false);
+    write(&key, ((id, acc, nk), quorum, (tick(id).0, arbitrated)));
+}
+
+type Certificate = ((U256, AccountHash, PublicKey), Vec<(Key, u8, u64)>, (u64, bool));
+
+/// Closes the grace period of finalized recovery `id` once its deadline has passed, after which the
+/// old key should be removed from the account
+#[no_mangle]
+pub extern "C" fn finalize_removal() {
+    check_args("finalize_removal");
+    let id: U256 = runtime::get_named_arg("id");
+    let acc: AccountHash = read(&format!("{}{}", prefixes::RECOVERY_ACCOUNT, id)).unwrap_or_revert_with(ApiError::User(Err::NotFound as u16));
+    if !holds(acc, roles::CONFIG_ADMIN) { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    let (deadline, _): (u64, u8) = read(&format!("{}{}", prefixes::GRACE, id)).unwrap_or_revert_with(ApiError::User(Err::NotFound as u16));
+    if read::<bool>(&format!("{}{}", prefixes::GRACE_REMOVED, id)).unwrap_or(false) { runtime::revert(ApiError::User(Err::Finalized as u16)); }
+    if !Instant(deadline).reached(tick(id)) { runtime::revert(ApiError::User(Err::TooEarly as u16)); }
+    write(&format!("{}{}", prefixes::GRACE_REMOVED, id), true);
+}
+
+/// Returns (grace deadline, old key weight, removal finalized) for recovery `id`, if it left the old
+/// key a grace period
+#[no_mangle]
+pub extern "C" fn get_grace() {
+    check_args("get_grace");
+    let id: U256 = runtime::get_named_arg("id");
+    let grace = read::<(u64, u8)>(&format!("{}{}", prefixes::GRACE, id))
+        .map(|(deadline, weight)| (deadline, weight, read::<bool>(&format!("{}{}", prefixes::GRACE_REMOVED, id)).unwrap_or(false)));
+    runtime::ret(CLValue::from_t(grace).unwrap_or_revert());
+}
+
+/// Returns the quorum certificate of recovery `id` (see `certify`), once it has finalized
+#[no_mangle]
+pub extern "C" fn get_quorum_certificate() {
+    check_args("get_quorum_certificate");
+    let id: U256 = runtime::get_named_arg("id");
+    runtime::ret(CLValue::from_t(read::<Certificate>(&format!("{}{}", prefixes::QUORUM_CERTIFICATE, id))).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn get_guardians() {
+    check_args("get_guardians");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let g: Vec<AccountHash> = read(&format!("{}{:?}", prefixes::GUARDIANS, acc)).unwrap_or_revert_with(ApiError::User(Err::NotInit as u16));
+    runtime::ret(CLValue::from_t(g).unwrap_or_revert());
+}
+
+/// Returns (guardians, threshold, recovery instructions pointer)
+#[no_mangle]
+pub extern "C" fn get_guardian_config() {
+    check_args("get_guardian_config");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let g: Vec<AccountHash> = read(&format!("{}{:?}", prefixes::GUARDIANS, acc)).unwrap_or_revert_with(ApiError::User(Err::NotInit as u16));
+    let t: u8 = read(&format!("{}{:?}", prefixes::THRESHOLD, acc)).unwrap_or(2);
+    let ins: Option<([u8; 32], String)> = read::<Option<([u8; 32], String)>>(&format!("{}{:?}", prefixes::INSTRUCTIONS, acc)).flatten();
+    runtime::ret(CLValue::from_t((g, t, ins)).unwrap_or_revert());
+}
+
+/// Point guardians at encrypted recovery instructions by content `hash` and `uri` (e.g. an IPFS CID)
+#[no_mangle]
+pub extern "C" fn set_instructions() {
+    check_args("set_instructions");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let hash: [u8; 32] = runtime::get_named_arg("hash");
+    let uri: String = runtime::get_named_arg("uri");
+
+    if !holds(acc, roles::CONFIG_ADMIN) { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::Frozen as u16)); }
+    if uri.len() > 256 { runtime::revert(ApiError::User(Err::BadUri as u16)); }
+    write(&format!("{}{:?}", prefixes::INSTRUCTIONS, acc), Some((hash, uri)));
+}
+
+#[no_mangle]
+pub extern "C" fn clear_instructions() {
+    check_args("clear_instructions");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    if !holds(acc, roles::CONFIG_ADMIN) { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::Frozen as u16)); }
+    write::<Option<([u8; 32], String)>>(&format!("{}{:?}", prefixes::INSTRUCTIONS, acc), None);
+}
+
+#[no_mangle]
+pub extern "C" fn get_contract_guardians() {
+    check_args("get_contract_guardians");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    runtime::ret(CLValue::from_t(contract_guardians(acc)).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn has_guardians() {
+    check_args("has_guardians");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    runtime::ret(CLValue::from_t(read::<bool>(&format!("{}{:?}", prefixes::INITIALIZED, acc)).unwrap_or(false)).unwrap_or_revert());
+}
+
+/// Opt into the dead-man switch: after `period` ms without a heartbeat, `threshold` approvals suffice.
+/// A zero period opts back out.
+#[no_mangle]
+pub extern "C" fn configure_heartbeat() {
+    check_args("configure_heartbeat");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let period: u64 = runtime::get_named_arg("period");
+    let thresh: u8 = runtime::get_named_arg("threshold");
+
+    if !holds(acc, roles::CONFIG_ADMIN) { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::Frozen as u16)); }
+    if !read::<bool>(&format!("{}{:?}", prefixes::INITIALIZED, acc)).unwrap_or(false) { runtime::revert(ApiError::User(Err::NotInit as u16)); }
+    let full: u8 = read(&format!("{}{:?}", prefixes::THRESHOLD, acc)).unwrap_or(2);
+    if period > 0 && (thresh == 0 || thresh > full) { runtime::revert(ApiError::User(Err::BadThreshold as u16)); }
+
+    write(&format!("{}{:?}", prefixes::HEARTBEAT_PERIOD, acc), period);
+    write(&format!("{}{:?}", prefixes::HEARTBEAT_THRESHOLD, acc), thresh);
+    write(&format!("{}{:?}", prefixes::HEARTBEAT, acc), now().0);
+}
+
+/// Owner proof-of-life, restarting the inactivity clock
+#[no_mangle]
+pub extern "C" fn heartbeat() {
+    check_args("heartbeat");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    if runtime::get_caller() != acc { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if !read::<bool>(&format!("{}{:?}", prefixes::INITIALIZED, acc)).unwrap_or(false) { runtime::revert(ApiError::User(Err::NotInit as u16)); }
+    write(&format!("{}{:?}", prefixes::HEARTBEAT, acc), now().0);
+}
+
+#[no_mangle]
+pub extern "C" fn is_inactive() {
+    check_args("is_inactive");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    runtime::ret(CLValue::from_t(inactive(acc)).unwrap_or_revert());
+}
+
+/// Set the decay schedule: (age in ms, threshold) steps, each lowering the threshold once a pending
+/// recovery is at least that old. An empty schedule disables decay.
+#[cfg(feature = "policies")]
+#[no_mangle]
+pub extern "C" fn set_threshold_decay() {
+    check_args("set_threshold_decay");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let schedule: Vec<(u64, u8)> = runtime::get_named_arg("schedule");
+
+    if !holds(acc, roles::CONFIG_ADMIN) { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::Frozen as u16)); }
+    if !read::<bool>(&format!("{}{:?}", prefixes::INITIALIZED, acc)).unwrap_or(false) { runtime::revert(ApiError::User(Err::NotInit as u16)); }
+
+    // Shorthand for a decaying policy: steps strictly later and strictly lower than the one before,
+    // starting below the base threshold
+    let threshold: u8 = read(&format!("{}{:?}", prefixes::THRESHOLD, acc)).unwrap_or(2);
+    match approval_policy(acc) {
+        ApprovalPolicy::Threshold { .. } | ApprovalPolicy::Decaying { .. } => {}
+        _ => runtime::revert(ApiError::User(Err::BadPolicy as u16)),
+    }
+    let p = if schedule.is_empty() { ApprovalPolicy::Threshold { threshold } } else { ApprovalPolicy::Decaying { threshold, steps: schedule } };
+    store_policy(acc, p);
+}
+
+#[cfg(feature = "policies")]
+#[no_mangle]
+pub extern "C" fn get_threshold_decay() {
+    check_args("get_threshold_decay");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let schedule: Vec<(u64, u8)> = match approval_policy(acc) {
+        ApprovalPolicy::Decaying { steps, .. } => steps,
+        _ => vec![],
+    };
+    runtime::ret(CLValue::from_t(schedule).unwrap_or_revert());
+}
+
+/// Approvals currently required for recovery `id`, after decay and inactivity reductions
+#[no_mangle]
+pub extern "C" fn get_current_threshold() {
+    check_args("get_current_threshold");
+    let id: U256 = runtime::get_named_arg("id");
+    let acc: AccountHash = read(&format!("{}{}", prefixes::RECOVERY_ACCOUNT, id)).unwrap_or_revert_with(ApiError::User(Err::NotFound as u16));
+    runtime::ret(CLValue::from_t(threshold(id, acc)).unwrap_or_revert());
+}
+
+/// Define (or with a zero threshold, remove) a named recovery type, e.g. "device_rotation" needing 2
+/// approvals and "takeover" needing 4, selectable via `recovery_type` in `start_recovery`
+#[no_mangle]
+pub extern "C" fn set_recovery_type() {
+    check_args("set_recovery_type");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let name: String = runtime::get_named_arg("name");
+    let thresh: u8 = runtime::get_named_arg("threshold");
+
+    if !holds(acc, roles::CONFIG_ADMIN) { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::Frozen as u16)); }
+    if !read::<bool>(&format!("{}{:?}", prefixes::INITIALIZED, acc)).unwrap_or(false) { runtime::revert(ApiError::User(Err::NotInit as u16)); }
+    if name.is_empty() || name.len() > 32 { runtime::revert(ApiError::User(Err::BadType as u16)); }
+    if thresh as usize > guardian_count(acc) { runtime::revert(ApiError::User(Err::BadThreshold as u16)); }
+
+    let key = format!("{}{:?}", prefixes::RECOVERY_TYPES, acc);
+    let mut types: Vec<(String, u8)> = read(&key).unwrap_or(vec![]);
+    types.retain(|(n, _)| *n != name);
+    if thresh > 0 { types.push((name, thresh)); }
+    write(&key, types);
+}
+
+#[no_mangle]
+pub extern "C" fn get_recovery_types() {
+    check_args("get_recovery_types");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let types: Vec<(String, u8)> = read(&format!("{}{:?}", prefixes::RECOVERY_TYPES, acc)).unwrap_or(vec![]);
+    runtime::ret(CLValue::from_t(types).unwrap_or_revert());
+}
+
+/// Require each guardian of `account` to lock `amount` motes before their approvals count (zero disables)
+#[cfg(feature = "bonding")]
+#[no_mangle]
+pub extern "C" fn set_guardian_bond() {
+    check_args("set_guardian_bond");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let amount: U512 = runtime::get_named_arg("amount");
+
+    if !holds(acc, roles::CONFIG_ADMIN) { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::Frozen as u16)); }
+    if !read::<bool>(&format!("{}{:?}", prefixes::INITIALIZED, acc)).unwrap_or(false) { runtime::revert(ApiError::User(Err::NotInit as u16)); }
+    write(&format!("{}{:?}", prefixes::BOND_REQUIRED, acc), amount);
+}
+
+/// Guardian accepts by locking the outstanding bond from `purse` (see the `funded_call` session)
+#[cfg(feature = "bonding")]
+#[no_mangle]
+pub extern "C" fn post_bond() {
+    check_args("post_bond");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let src: URef = runtime::get_named_arg("purse");
+    let caller = runtime::get_caller();
+
+    let guards: Vec<AccountHash> = read(&format!("{}{:?}", prefixes::GUARDIANS, acc)).unwrap_or_revert_with(ApiError::User(Err::NotInit as u16));
+    if !guards.contains(&caller) { runtime::revert(ApiError::User(Err::NotGuardian as u16)); }
+
+    let bond: U512 = read(&format!("{}{:?}", prefixes::BOND_REQUIRED, acc)).unwrap_or_default();
+    let key = format!("{}{:?}_{:?}", prefixes::BOND, acc, caller);
+    let posted: U512 = read(&key).unwrap_or_default();
+    if posted >= bond { runtime::revert(ApiError::User(Err::BadBond as u16)); }
+
+    system::transfer_from_purse_to_purse(src, purse(&tenant_key(BOND_PURSE)), bond - posted, None)
+        .unwrap_or_revert_with(ApiError::User(Err::BadBond as u16));
+    write(&key, bond);
+}
+
+/// Returns (required bond, amount `guardian` has locked)
+#[cfg(feature = "bonding")]
+#[no_mangle]
+pub extern "C" fn get_bond() {
+    check_args("get_bond");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let guard: AccountHash = runtime::get_named_arg("guardian");
+    let bond: U512 = read(&format!("{}{:?}", prefixes::BOND_REQUIRED, acc)).unwrap_or_default();
+    let posted: U512 = read(&format!("{}{:?}_{:?}", prefixes::BOND, acc, guard)).unwrap_or_default();
+    runtime::ret(CLValue::from_t((bond, posted)).unwrap_or_revert());
+}
+
+/// Restrict `account`'s guardians (and their delegates) to the key algorithms in `allowed`
+/// (`KEY_ED25519` | `KEY_SECP256K1`; zero lifts the restriction). Once set, `init_guardians` and
+/// `add_guardian` need the guardians' `guardian_keys`; setting it on an initialized account re-checks
+/// the listed guardians the same way. Merkle and privacy-mode members can't be checked on-chain.
+#[no_mangle]
+pub extern "C" fn set_key_policy() {
+    check_args("set_key_policy");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let allowed: u8 = runtime::get_named_arg("allowed");
+
+    if !holds(acc, roles::CONFIG_ADMIN) { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::Frozen as u16)); }
+    if pending(acc) { runtime::revert(ApiError::User(Err::RecoveryExists as u16)); }
+    if allowed & !(KEY_ED25519 | KEY_SECP256K1) != 0 { runtime::revert(ApiError::User(Err::BadKeyType as u16)); }
+
+    write(&format!("{}{:?}", prefixes::KEY_POLICY, acc), allowed);
+    let guards: Vec<AccountHash> = read(&format!("{}{:?}", prefixes::GUARDIANS, acc)).unwrap_or_default();
+    if !guards.is_empty() { check_keys(acc, &guards); }
+}
+
+#[no_mangle]
+pub extern "C" fn get_key_policy() {
+    check_args("get_key_policy");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    runtime::ret(CLValue::from_t(read::<u8>(&format!("{}{:?}", prefixes::KEY_POLICY, acc)).unwrap_or(0)).unwrap_or_revert());
+}
+
+/// Select how `account`'s guardians prove membership when approving: `verify::CALLER`,
+/// `verify::MERKLE` or `verify::HASHED`. The backing guardian set must already exist.
+#[no_mangle]
+pub extern "C" fn set_verifier() {
+    check_args("set_verifier");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let kind: u8 = runtime::get_named_arg("verifier");
+
+    if !holds(acc, roles::CONFIG_ADMIN) { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::Frozen as u16)); }
+    if !read::<bool>(&format!("{}{:?}", prefixes::INITIALIZED, acc)).unwrap_or(false) { runtime::revert(ApiError::User(Err::NotInit as u16)); }
+    if pending(acc) { runtime::revert(ApiError::User(Err::RecoveryExists as u16)); }
+    if !verify::available(acc, kind) { runtime::revert(ApiError::User(Err::BadVerifier as u16)); }
+    write(&format!("{}{:?}", prefixes::VERIFIER, acc), kind);
+}
+
+#[no_mangle]
+pub extern "C" fn get_verifier() {
+    check_args("get_verifier");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    runtime::ret(CLValue::from_t(verify::kind(acc)).unwrap_or_revert());
+}
+
+/// Guardian lets `delegate` approve on their behalf for `account` until block time `until`
+#[no_mangle]
+pub extern "C" fn delegate_guardianship() {
+    check_args("delegate_guardianship");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let delegate: PublicKey = runtime::get_named_arg("delegate");
+    let until: u64 = runtime::get_named_arg("until");
+    let caller = runtime::get_caller();
+
+    let guards: Vec<AccountHash> = read(&format!("{}{:?}", prefixes::GUARDIANS, acc)).unwrap_or_revert_with(ApiError::User(Err::NotInit as u16));
+    if !guards.contains(&caller) { runtime::revert(ApiError::User(Err::NotGuardian as u16)); }
+
+    let dh = delegate.to_account_hash();
+    if dh == acc || guards.contains(&dh) || Instant(until).reached(now()) { runtime::revert(ApiError::User(Err::BadDelegate as u16)); }
+    let policy: u8 = read(&format!("{}{:?}", prefixes::KEY_POLICY, acc)).unwrap_or(0);
+    if policy != 0 && policy & key_bit(&delegate) == 0 { runtime::revert(ApiError::User(Err::BadKeyType as u16)); }
+    if delegator(acc, dh).is_some_and(|g| g != caller) { runtime::revert(ApiError::User(Err::BadDelegate as u16)); }
+
+    clear_delegation(acc, caller);
+    write(&format!("{}{:?}_{:?}", prefixes::DELEGATOR, acc, dh), Some(caller));
+    write(&format!("{}{:?}_{:?}", prefixes::DELEGATION, acc, caller), Some((delegate, until)));
+}
+
+#[no_mangle]
+pub extern "C" fn revoke_delegation() {
+    check_args("revoke_delegation");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    clear_delegation(acc, runtime::get_caller());
+}
+
+/// Returns the (delegate, expiry) `guardian` has set for `account`, if any
+#[no_mangle]
+pub extern "C" fn get_delegation() {
+    check_args("get_delegation");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let guard: AccountHash = runtime::get_named_arg("guardian");
+    let d: Option<(PublicKey, u64)> = read::<Option<(PublicKey, u64)>>(&format!("{}{:?}_{:?}", prefixes::DELEGATION, acc, guard)).flatten();
+    runtime::ret(CLValue::from_t(d).unwrap_or_revert());
+}
+
+/// Add a guardian outside of any pending recovery: an account (`Key::Account`) or a contract
+/// package (`Key::Hash`) that approves through cross-contract calls
+#[no_mangle]
+pub extern "C" fn add_guardian() {
+    check_args("add_guardian");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let guard: Key = runtime::get_named_arg("guardian");
+
+    if !holds(acc, roles::CONFIG_ADMIN) { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::Frozen as u16)); }
+    let guards: Vec<AccountHash> = read(&format!("{}{:?}", prefixes::GUARDIANS, acc)).unwrap_or_revert_with(ApiError::User(Err::NotInit as u16));
+    if pending(acc) { runtime::revert(ApiError::User(Err::RecoveryExists as u16)); }
+
+    match host::stored_key(guard) {
+        Key::Account(g) => enroll(acc, guards, g),
+        Key::Hash(pkg) => {
+            let mut contracts = contract_guardians(acc);
+            if contracts.contains(&Key::Hash(pkg)) { runtime::revert(ApiError::User(Err::BadGuardians as u16)); }
+            contracts.push(Key::Hash(pkg));
+            write(&format!("{}{:?}", prefixes::CONTRACT_GUARDIANS, acc), contracts);
+            emit(events::GuardianAddedV2 { tenant: tenant(), account: acc, guardian: Key::Hash(pkg) });
+        }
+        _ => runtime::revert(ApiError::User(Err::BadGuardians as u16)),
+    }
+}
+
+/// Make account `g` a guardian of `acc` after `guards`, unless it is the owner, already one of them
+/// or outside the account's key policy
+fn enroll(acc: AccountHash, mut guards: Vec<AccountHash>, g: AccountHash) {
+    if g == acc || guards.contains(&g) { runtime::revert(ApiError::User(Err::BadGuardians as u16)); }
+    check_keys(acc, &[g]);
+    guards.push(g);
+    write(&format!("{}{:?}", prefixes::GUARDIANS, acc), guards);
+
+    let key = format!("{}{:?}", prefixes::PROTECTED_ACCOUNTS, g);
+    let mut protected: Vec<AccountHash> = read(&key).unwrap_or(vec![]);
+    protected.push(acc);
+    write(&key, protected);
+    emit(events::GuardianAddedV2 { tenant: tenant(), account: acc, guardian: Key::Account(g) });
+}
+
+/// `acc`'s pending invitations as (guardian, deadline), expired ones included until swept
+fn invitations(acc: AccountHash) -> Vec<(AccountHash, u64)> {
+    read(&format!("{}{:?}", prefixes::INVITATIONS, acc)).unwrap_or_default()
+}
+
+/// Drop the invitations in `invites` whose deadline has passed, emitting `InvitationExpiredV2` for
+/// each, and return how many
+fn drop_expired(acc: AccountHash, invites: &mut Vec<(AccountHash, u64)>) -> u32 {
+    let (before, at) = (invites.len(), now());
+    invites.retain(|&(guardian, deadline)| {
+        let live = !Instant(deadline).reached(at);
+        if !live { emit(events::InvitationExpiredV2 { tenant: tenant(), account: acc, guardian, deadline }); }
+        live
+    });
+    (before - invites.len()) as u32
+}
+
+/// Invite account `guardian` to guard `account` until `deadline` (block time, ms). They become a
+/// guardian by calling `accept_invitation` before then; inviting them again moves the deadline.
+/// Lapsed invitations are swept on the way.
+#[no_mangle]
+pub extern "C" fn invite_guardian() {
+    check_args("invite_guardian");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let guard: AccountHash = runtime::get_named_arg("guardian");
+    let deadline: u64 = runtime::get_named_arg("deadline");
+
+    if !holds(acc, roles::CONFIG_ADMIN) { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::Frozen as u16)); }
+    let guards: Vec<AccountHash> = read(&format!("{}{:?}", prefixes::GUARDIANS, acc)).unwrap_or_revert_with(ApiError::User(Err::NotInit as u16));
+    if guard == acc || guards.contains(&guard) { runtime::revert(ApiError::User(Err::BadGuardians as u16)); }
+    if Instant(deadline).reached(now()) { runtime::revert(ApiError::User(Err::InvitationExpired as u16)); }
+
+    let mut invites = invitations(acc);
+    drop_expired(acc, &mut invites);
+    invites.retain(|(g, _)| *g != guard);
+    invites.push((guard, deadline));
+    write(&format!("{}{:?}", prefixes::INVITATIONS, acc), invites);
+    emit(events::GuardianInvitedV2 { tenant: tenant(), account: acc, guardian: guard, deadline });
+}
+
+/// The caller accepts their invitation to guard `account`, joining its guardians on the terms of
+/// `add_guardian`: not while a recovery is pending or the account is frozen, and with
+/// `guardian_keys` under a key policy. A lapsed invitation reverts with `InvitationExpired` and
+/// stays until swept.
+#[no_mangle]
+pub extern "C" fn accept_invitation() {
+    check_args("accept_invitation");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let guard = runtime::get_caller();
+
+    let mut invites = invitations(acc);
+    let i = invites.iter().position(|(g, _)| *g == guard).unwrap_or_revert_with(ApiError::User(Err::NoInvitation as u16));
+    if Instant(invites[i].1).reached(now()) { runtime::revert(ApiError::User(Err::InvitationExpired as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::Frozen as u16)); }
+    let guards: Vec<AccountHash> = read(&format!("{}{:?}", prefixes::GUARDIANS, acc)).unwrap_or_revert_with(ApiError::User(Err::NotInit as u16));
+    if pending(acc) { runtime::revert(ApiError::User(Err::RecoveryExists as u16)); }
+
+    invites.remove(i);
+    drop_expired(acc, &mut invites);
+    write(&format!("{}{:?}", prefixes::INVITATIONS, acc), invites);
+    enroll(acc, guards, guard);
+}
+
+/// Drop `account`'s lapsed invitations, emitting `InvitationExpiredV2` for each. Anyone may call.
+/// Returns how many were dropped.
+#[no_mangle]
+pub extern "C" fn sweep_invitations() {
+    check_args("sweep_invitations");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let mut invites = invitations(acc);
+    let dropped = drop_expired(acc, &mut invites);
+    if dropped > 0 { write(&format!("{}{:?}", prefixes::INVITATIONS, acc), invites); }
+    runtime::ret(CLValue::from_t(dropped).unwrap_or_revert());
+}
+
+/// Returns `account`'s pending invitations as (guardian, deadline), lapsed ones included until swept
+#[no_mangle]
+pub extern "C" fn get_invitations() {
+    check_args("get_invitations");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    runtime::ret(CLValue::from_t(invitations(acc)).unwrap_or_revert());
+}
+
+/// Remove a guardian outside of any pending recovery, refunding an account guardian's bond
+#[no_mangle]
+pub extern "C" fn remove_guardian() {
+    check_args("remove_guardian");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let guard: Key = runtime::get_named_arg("guardian");
+
+    if !holds(acc, roles::CONFIG_ADMIN) { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::Frozen as u16)); }
+    let mut guards: Vec<AccountHash> = read(&format!("{}{:?}", prefixes::GUARDIANS, acc)).unwrap_or_revert_with(ApiError::User(Err::NotInit as u16));
+    if pending(acc) { runtime::revert(ApiError::User(Err::RecoveryExists as u16)); }
+    #[cfg(feature = "bonding")]
+    if open_slash(acc).is_some() { runtime::revert(ApiError::User(Err::SlashPending as u16)); }
+
+    let thresh: u8 = read(&format!("{}{:?}", prefixes::THRESHOLD, acc)).unwrap_or(2);
+    let remaining = guardian_count(acc).saturating_sub(1);
+    if remaining < 2 { runtime::revert(ApiError::User(Err::BadGuardians as u16)); }
+    if thresh as usize > remaining { runtime::revert(ApiError::User(Err::BadThreshold as u16)); }
+    let removed = host::stored_key(guard);
+    let keys = guardian_keys(acc);
+    let hidden = guardian_count(acc).saturating_sub(keys.len());
+    let left: Vec<Key> = keys.into_iter().filter(|k| *k != removed).collect();
+    let cats: Vec<(Key, u8)> = categories(acc).into_iter().filter(|(k, _)| *k != removed).collect();
+    let p = approval_policy(acc).without(&removed);
+    if let Err(e) = p.check(&left, hidden, &cats) { runtime::revert(ApiError::User(e as u16)); }
+    if read::<ApprovalPolicy>(&format!("{}{:?}", prefixes::APPROVAL_POLICY, acc)).is_some() { write(&format!("{}{:?}", prefixes::APPROVAL_POLICY, acc), p); }
+    write(&format!("{}{:?}", prefixes::CATEGORIES, acc), cats);
+    let mut terms: Vec<(Key, u64)> = read(&format!("{}{:?}", prefixes::GUARDIAN_TERMS, acc)).unwrap_or_default();
+    terms.retain(|(k, _)| *k != removed);
+    write(&format!("{}{:?}", prefixes::GUARDIAN_TERMS, acc), terms);
+    let mut suspended: Vec<Key> = read(&format!("{}{:?}", prefixes::SUSPENDED, acc)).unwrap_or_default();
+    suspended.retain(|k| *k != removed);
+    write(&format!("{}{:?}", prefixes::SUSPENDED, acc), suspended);
+
+    let guard = match removed {
+        Key::Account(g) if guards.contains(&g) => g,
+        Key::Hash(pkg) if contract_guardians(acc).contains(&Key::Hash(pkg)) => {
+            let mut contracts = contract_guardians(acc);
+            contracts.retain(|c| *c != Key::Hash(pkg));
+            write(&format!("{}{:?}", prefixes::CONTRACT_GUARDIANS, acc), contracts);
+            emit(events::GuardianRemovedV2 { tenant: tenant(), account: acc, guardian: Key::Hash(pkg) });
+            return;
+        }
+        _ => runtime::revert(ApiError::User(Err::NotGuardian as u16)),
+    };
+
+    guards.retain(|g| *g != guard);
+    write(&format!("{}{:?}", prefixes::GUARDIANS, acc), guards);
+    emit(events::GuardianRemovedV2 { tenant: tenant(), account: acc, guardian: Key::Account(guard) });
+
+    let key = format!("{}{:?}", prefixes::PROTECTED_ACCOUNTS, guard);
+    let mut protected: Vec<AccountHash> = read(&key).unwrap_or(vec![]);
+    protected.retain(|a| *a != acc);
+    write(&key, protected);
+
+    #[cfg(feature = "bonding")]
+    {
+        let bk = format!("{}{:?}_{:?}", prefixes::BOND, acc, guard);
+        let posted: U512 = read(&bk).unwrap_or_default();
+        if !posted.is_zero() {
+            write(&bk, U512::zero());
+            system::transfer_from_purse_to_account(purse(&tenant_key(BOND_PURSE)), guard, posted, None).unwrap_or_revert();
+        }
+    }
+}
+
+/// Guardian of `account` moves to a new key, e.g. after losing their device. Both keys sign
+/// `sig::rotation_payload`, so anyone may relay it. With a rotation window in the account's settings
+/// the swap waits for `complete_rotation`, and the owner can `veto_rotation` in the meantime.
+#[no_mangle]
+pub extern "C" fn rotate_my_key() {
+    check_args("rotate_my_key");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let old: PublicKey = runtime::get_named_arg("old_key");
+    let new: PublicKey = runtime::get_named_arg("new_key");
+    let old_sig: Vec<u8> = runtime::get_named_arg("old_signature");
+    let new_sig: Vec<u8> = runtime::get_named_arg("new_signature");
+
+    let guards: Vec<AccountHash> = read(&format!("{}{:?}", prefixes::GUARDIANS, acc)).unwrap_or_revert_with(ApiError::User(Err::NotInit as u16));
+    let (from, to) = (old.to_account_hash(), new.to_account_hash());
+    if !guards.contains(&from) { runtime::revert(ApiError::User(Err::NotGuardian as u16)); }
+    if to == acc || guards.contains(&to) { runtime::revert(ApiError::User(Err::BadGuardians as u16)); }
+    if !keys_ok(acc, &[to], core::slice::from_ref(&new)) { runtime::revert(ApiError::User(Err::BadKeyType as u16)); }
+    if pending(acc) { runtime::revert(ApiError::User(Err::RecoveryExists as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::Frozen as u16)); }
+
+    let nonce: u64 = read(&format!("{}{:?}", prefixes::ROTATION_NONCE, acc)).unwrap_or(0);
+    let msg = sig::rotation_payload(acc, nonce, &old, &new);
+    if !sig::verify(&old, &msg, &old_sig) || !sig::verify(&new, &msg, &new_sig) { runtime::revert(ApiError::User(Err::BadSignature as u16)); }
+    write(&format!("{}{:?}", prefixes::ROTATION_NONCE, acc), nonce + 1);
+
+    let cfg = settings(acc);
+    if cfg.rotation_window == 0 { return rotate(acc, from, to); }
+    let at = clock(&cfg).plus(Span(cfg.rotation_window)).0;
+    write(&format!("{}{:?}_{:?}", prefixes::PENDING_ROTATION, acc, from), Some((new.clone(), at)));
+    emit(events::GuardianRotationRequestedV2 { tenant: tenant(), account: acc, guardian: from, new_key: new, effective_at: at });
+}
+
+/// Apply `guardian`'s staged key rotation once the owner's veto window has passed
+#[no_mangle]
+pub extern "C" fn complete_rotation() {
+    check_args("complete_rotation");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let guard: AccountHash = runtime::get_named_arg("guardian");
+
+    let key = format!("{}{:?}_{:?}", prefixes::PENDING_ROTATION, acc, guard);
+    let (new, at): (PublicKey, u64) = read::<Option<(PublicKey, u64)>>(&key).flatten().unwrap_or_revert_with(ApiError::User(Err::NotFound as u16));
+    if !Instant(at).reached(clock(&settings(acc))) { runtime::revert(ApiError::User(Err::TooEarly as u16)); }
+    if pending(acc) { runtime::revert(ApiError::User(Err::RecoveryExists as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::Frozen as u16)); }
+
+    // The guardian set may have changed while the rotation waited
+    let guards: Vec<AccountHash> = read(&format!("{}{:?}", prefixes::GUARDIANS, acc)).unwrap_or_default();
+    let to = new.to_account_hash();
+    if !guards.contains(&guard) || guards.contains(&to) { runtime::revert(ApiError::User(Err::BadGuardians as u16)); }
+
+    write::<Option<(PublicKey, u64)>>(&key, None);
+    rotate(acc, guard, to);
+}
+
+/// Owner cancels `guardian`'s staged key rotation
+#[no_mangle]
+pub extern "C" fn veto_rotation() {
+    check_args("veto_rotation");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let guard: AccountHash = runtime::get_named_arg("guardian");
+
+    if !holds(acc, roles::CONFIG_ADMIN) { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    let key = format!("{}{:?}_{:?}", prefixes::PENDING_ROTATION, acc, guard);
+    if read::<Option<(PublicKey, u64)>>(&key).flatten().is_none() { runtime::revert(ApiError::User(Err::NotFound as u16)); }
+    write::<Option<(PublicKey, u64)>>(&key, None);
+}
+
+/// Returns (new key, effective at) of `guardian`'s staged key rotation, if any
+#[no_mangle]
+pub extern "C" fn get_pending_rotation() {
+    check_args("get_pending_rotation");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let guard: AccountHash = runtime::get_named_arg("guardian");
+    let r: Option<(PublicKey, u64)> = read::<Option<(PublicKey, u64)>>(&format!("{}{:?}_{:?}", prefixes::PENDING_ROTATION, acc, guard)).flatten();
+    runtime::ret(CLValue::from_t(r).unwrap_or_revert());
+}
+
+/// Amount paid to each approving guardian when a recovery of `account` finalizes (zero disables)
+#[no_mangle]
+pub extern "C" fn set_guardian_reward() {
+    check_args("set_guardian_reward");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let amount: U512 = runtime::get_named_arg("amount");
+
+    if !holds(acc, roles::CONFIG_ADMIN) { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::Frozen as u16)); }
+    if !read::<bool>(&format!("{}{:?}", prefixes::INITIALIZED, acc)).unwrap_or(false) { runtime::revert(ApiError::User(Err::NotInit as u16)); }
+    write(&format!("{}{:?}", prefixes::REWARD, acc), amount);
+}
+
+/// Move the whole balance of `purse` into `account`'s reward balance (see the `funded_call` session)
+#[no_mangle]
+pub extern "C" fn fund_rewards() {
+    check_args("fund_rewards");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let src: URef = runtime::get_named_arg("purse");
+
+    if !read::<bool>(&format!("{}{:?}", prefixes::INITIALIZED, acc)).unwrap_or(false) { runtime::revert(ApiError::User(Err::NotInit as u16)); }
+    let amount = system::get_purse_balance(src).unwrap_or_default();
+    system::transfer_from_purse_to_purse(src, purse(&tenant_key(REWARD_PURSE)), amount, None).unwrap_or_revert();
+
+    let key = format!("{}{:?}", prefixes::REWARD_BALANCE, acc);
+    let balance: U512 = read(&key).unwrap_or_default();
+    write(&key, balance + amount);
+}
+
+/// Owner takes back unused reward funds; blocked while a recovery is pending so rewards can't be pulled mid-flight
+#[no_mangle]
+pub extern "C" fn withdraw_rewards() {
+    check_args("withdraw_rewards");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let amount: U512 = runtime::get_named_arg("amount");
+
+    if runtime::get_caller() != acc { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::Frozen as u16)); }
+    if pending(acc) { runtime::revert(ApiError::User(Err::RecoveryExists as u16)); }
+
+    let key = format!("{}{:?}", prefixes::REWARD_BALANCE, acc);
+    let balance: U512 = read(&key).unwrap_or_default();
+    if amount > balance { runtime::revert(ApiError::User(Err::InsufficientRewards as u16)); }
+
+    write(&key, balance - amount);
+    system::transfer_from_purse_to_account(purse(&tenant_key(REWARD_PURSE)), acc, amount, None).unwrap_or_revert();
+}
+
+/// Returns (reward per approving guardian, remaining reward balance)
+#[no_mangle]
+pub extern "C" fn get_rewards() {
+    check_args("get_rewards");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let reward: U512 = read(&format!("{}{:?}", prefixes::REWARD, acc)).unwrap_or_default();
+    let balance: U512 = read(&format!("{}{:?}", prefixes::REWARD_BALANCE, acc)).unwrap_or_default();
+    runtime::ret(CLValue::from_t((reward, balance)).unwrap_or_revert());
+}
+
+/// Owner cancels a pending recovery they didn't ask for, recording who approved it. Flagging it
+/// `malicious` forfeits the initiator's deposit to the owner instead of refunding it.
+#[no_mangle]
+pub extern "C" fn veto() {
+    check_args("veto");
+    let id: U256 = runtime::get_named_arg("id");
+    let malicious: bool = runtime::try_get_named_arg("malicious").unwrap_or(false);
+    let acc: AccountHash = read(&format!("{}{}", prefixes::RECOVERY_ACCOUNT, id)).unwrap_or_revert_with(ApiError::User(Err::NotFound as u16));
+
+    if runtime::get_caller() != acc { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if closed(id) { runtime::revert(ApiError::User(Err::NotFound as u16)); }
+
+    write(&format!("{}{}", prefixes::VETOED_APPROVERS, id), approvers(id, acc));
+    write(&format!("{}{}", prefixes::CANCELLED, id), true);
+    untrack(id, acc);
+
+    let to = if malicious { acc } else { read(&format!("{}{}", prefixes::INITIATOR, id)).unwrap_or_revert() };
+    settle_deposit(id, to);
+    // A malicious veto means someone is fighting over the account: stop either side from reshaping the
+    // guardian set, threshold or policies for a while
+    if malicious {
+        let until = now().plus(Span(settings(acc).freeze_period).max(MIN_FREEZE)).0;
+        write(&format!("{}{:?}", prefixes::FROZEN_UNTIL, acc), until.max(read(&format!("{}{:?}", prefixes::FROZEN_UNTIL, acc)).unwrap_or(0)));
+    }
+    emit(events::RecoveryCancelledV3 { tenant: tenant(), id, account: acc, vetoed: true, notify: notify(acc) });
+    bump(id, acc, counters::VETOED);
+}
+
+/// Guardians' flags on recovery `id` as (guardian, reason), in the order raised
+fn flags(id: U256) -> Vec<(Key, u8)> {
+    read(&format!("{}{}", prefixes::FLAGS, id)).unwrap_or_default()
+}
+
+/// True while recovery `id` has at least the account's flag limit of flags and the arbiter hasn't
+/// dismissed them
+fn held(id: U256, acc: AccountHash) -> bool {
+    let limit = read::<Option<u8>>(&format!("{}{:?}", prefixes::FLAG_LIMIT, acc)).flatten();
+    limit.map_or(false, |l| flags(id).len() >= l as usize) && !read::<bool>(&format!("{}{}", prefixes::FLAGS_DISMISSED, id)).unwrap_or(false)
+}
+
+/// Guardian flags pending recovery `id` as suspected fraud with a `guardian_types::flags` reason.
+/// Any guardian may flag, approvers included; with a flag policy enough flags hold finalization.
+#[no_mangle]
+pub extern "C" fn flag_recovery() {
+    check_args("flag_recovery");
+    let id: U256 = runtime::get_named_arg("id");
+    let reason: u8 = runtime::get_named_arg("reason");
+    let acc: AccountHash = read(&format!("{}{}", prefixes::RECOVERY_ACCOUNT, id)).unwrap_or_revert_with(ApiError::User(Err::NotFound as u16));
+    if closed(id) { runtime::revert(ApiError::User(Err::NotFound as u16)); }
+
+    let who = ballot(acc);
+    let mut list = flags(id);
+    if list.iter().any(|(k, _)| *k == who) { runtime::revert(ApiError::User(Err::BadFlag as u16)); }
+    list.push((who, reason));
+    let count = list.len().min(u8::MAX as usize) as u8;
+    write(&format!("{}{}", prefixes::FLAGS, id), list);
+    // Kept so withdrawing the flag takes back exactly what it counted
+    let charged: Vec<AccountHash> = counted(id).into_iter().filter(|k| *k != who)
+        .filter_map(|k| match k { Key::Account(g) => Some(g), _ => None })
+        .filter(|g| record_stat(*g, reputation::FLAGGED))
+        .collect();
+    write(&vote_key(prefixes::FLAGGED_APPROVERS, id, who), charged);
+    emit(events::RecoveryFlaggedV2 { tenant: tenant(), id, account: acc, guardian: who, reason, flags: count, notify: notify(acc) });
+}
+
+/// Guardian takes back their flag on recovery `id`
+#[no_mangle]
+pub extern "C" fn withdraw_flag() {
+    check_args("withdraw_flag");
+    let id: U256 = runtime::get_named_arg("id");
+    let acc: AccountHash = read(&format!("{}{}", prefixes::RECOVERY_ACCOUNT, id)).unwrap_or_revert_with(ApiError::User(Err::NotFound as u16));
+    if closed(id) { runtime::revert(ApiError::User(Err::NotFound as u16)); }
+
+    let who = ballot(acc);
+    let mut list = flags(id);
+    let before = list.len();
+    list.retain(|(k, _)| *k != who);
+    if list.len() == before { runtime::revert(ApiError::User(Err::BadFlag as u16)); }
+    let count = list.len() as u8;
+    write(&format!("{}{}", prefixes::FLAGS, id), list);
+    let key = vote_key(prefixes::FLAGGED_APPROVERS, id, who);
+    for g in read::<Vec<AccountHash>>(&key).unwrap_or_default() { retract_stat(g, reputation::FLAGGED); }
+    write(&key, Vec::<AccountHash>::new());
+    emit(events::RecoveryFlagWithdrawnV2 { tenant: tenant(), id, account: acc, guardian: who, flags: count });
+}
+
+/// Returns (each flag as (guardian, reason), whether the arbiter dismissed them)
+#[no_mangle]
+pub extern "C" fn get_flags() {
+    check_args("get_flags");
+    let id: U256 = runtime::get_named_arg("id");
+    let dismissed = read::<bool>(&format!("{}{}", prefixes::FLAGS_DISMISSED, id)).unwrap_or(false);
+    runtime::ret(CLValue::from_t((flags(id), dismissed)).unwrap_or_revert());
+}
+
+/// Hold finalization of `account`'s recoveries while `limit` or more guardians flag them; `None`
+/// leaves flags advisory. Can't change while a recovery is pending.
+#[no_mangle]
+pub extern "C" fn set_flag_policy() {
+    check_args("set_flag_policy");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let limit: Option<u8> = runtime::get_named_arg("limit");
+
+    if !holds(acc, roles::CONFIG_ADMIN) { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::Frozen as u16)); }
+    if !read::<bool>(&format!("{}{:?}", prefixes::INITIALIZED, acc)).unwrap_or(false) { runtime::revert(ApiError::User(Err::NotInit as u16)); }
+    if pending(acc) { runtime::revert(ApiError::User(Err::RecoveryExists as u16)); }
+    if limit == Some(0) { runtime::revert(ApiError::User(Err::BadFlag as u16)); }
+    write(&format!("{}{:?}", prefixes::FLAG_LIMIT, acc), limit);
+}
+
+#[no_mangle]
+pub extern "C" fn get_flag_policy() {
+    check_args("get_flag_policy");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let limit: Option<u8> = read::<Option<u8>>(&format!("{}{:?}", prefixes::FLAG_LIMIT, acc)).flatten();
+    runtime::ret(CLValue::from_t(limit).unwrap_or_revert());
+}
+
+/// Require `quorum` guardians to re-confirm an approved recovery within `window` ms once `delay` ms
+/// have passed since it started; `None` turns the second round off for future recoveries
+#[no_mangle]
+pub extern "C" fn set_confirmation_policy() {
+    check_args("set_confirmation_policy");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let policy: Option<(u64, u8, u64)> = runtime::get_named_arg("policy");
+
+    if !holds(acc, roles::CONFIG_ADMIN) { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::Frozen as u16)); }
+    if !read::<bool>(&format!("{}{:?}", prefixes::INITIALIZED, acc)).unwrap_or(false) { runtime::revert(ApiError::User(Err::NotInit as u16)); }
+    if let Some((_, quorum, window)) = policy {
+        let thresh: u8 = read(&format!("{}{:?}", prefixes::THRESHOLD, acc)).unwrap_or(2);
+        if quorum == 0 || quorum > thresh || window == 0 { runtime::revert(ApiError::User(Err::BadThreshold as u16)); }
+    }
+    write(&format!("{}{:?}", prefixes::CONFIRMATION_POLICY, acc), policy);
+}
+
+#[no_mangle]
+pub extern "C" fn get_confirmation_policy() {
+    check_args("get_confirmation_policy");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let policy: Option<(u64, u8, u64)> = read::<Option<(u64, u8, u64)>>(&format!("{}{:?}", prefixes::CONFIRMATION_POLICY, acc)).flatten();
+    runtime::ret(CLValue::from_t(policy).unwrap_or_revert());
+}
+
+/// Second-round "still sure?" vote on an approved recovery, open only inside its confirmation window
+#[no_mangle]
+pub extern "C" fn confirm() {
+    check_args("confirm");
+    let id: U256 = runtime::get_named_arg("id");
+    let acc: AccountHash = read(&format!("{}{}", prefixes::RECOVERY_ACCOUNT, id)).unwrap_or_revert_with(ApiError::User(Err::NotFound as u16));
+
+    if closed(id) { runtime::revert(ApiError::User(Err::NotFound as u16)); }
+    if !approved(id, acc) { runtime::revert(ApiError::User(Err::NotApproved as u16)); }
+    let (delay, _, window): (u64, u8, u64) = read(&format!("{}{}", prefixes::CONFIRMATION_ROUND, id)).unwrap_or_revert_with(ApiError::User(Err::NotFound as u16));
+    let opens = Instant(read(&format!("{}{}", prefixes::STARTED_AT, id)).unwrap_or_revert()).plus(Span(delay));
+    if !opens.within(Span(window), now()) { runtime::revert(ApiError::User(Err::ConfirmWindow as u16)); }
+
+    let voter = verify::for_account(acc).voter(acc, runtime::get_caller()).unwrap_or_revert_with(ApiError::User(Err::NotGuardian as u16));
+    let ck = format!("{}{}_{:?}", prefixes::CONFIRMATIONS, id, voter);
+    if read::<bool>(&ck).unwrap_or(false) { runtime::revert(ApiError::User(Err::AlreadyApproved as u16)); }
+
+    write(&ck, true);
+    write(&format!("{}{}", prefixes::CONFIRMATIONS, id), read::<u8>(&format!("{}{}", prefixes::CONFIRMATIONS, id)).unwrap_or(0) + 1);
+}
+
+/// How long in ms (blocks, if timed by height) a new recovery for `account` stays open before expiring
+/// (zero disables)
+#[no_mangle]
+pub extern "C" fn set_recovery_lifetime() {
+    check_args("set_recovery_lifetime");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let lifetime: u64 = runtime::get_named_arg("lifetime");
+
+    if !holds(acc, roles::CONFIG_ADMIN) { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::Frozen as u16)); }
+    let mut cfg = settings(acc);
+    cfg.expiry = lifetime;
+    if !cfg.is_valid() { runtime::revert(ApiError::User(Err::BadSettings as u16)); }
+    write(&format!("{}{:?}", prefixes::SETTINGS, acc), cfg);
+}
+
+/// Replace all of `account`'s timing and limit settings (see `AccountSettings`); they apply to
+/// recoveries started afterwards. Also resets the count of unfinalized recovery attempts.
+#[no_mangle]
+pub extern "C" fn update_settings() {
+    check_args("update_settings");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let expiry: u64 = runtime::get_named_arg("expiry");
+    let delay: u64 = runtime::get_named_arg("delay");
+    let max_attempts: u8 = runtime::get_named_arg("max_attempts");
+    let veto_window: u64 = runtime::get_named_arg("veto_window");
+    let approval_window: u64 = runtime::get_named_arg("approval_window");
+    let by_height: bool = runtime::try_get_named_arg("by_height").unwrap_or(false);
+    let rotation_window: u64 = runtime::try_get_named_arg("rotation_window").unwrap_or(0);
+    let freeze_period: u64 = runtime::try_get_named_arg("freeze_period").unwrap_or(0);
+    let grace_period: u64 = runtime::try_get_named_arg("grace_period").unwrap_or(0);
+    let grace_weight: u8 = runtime::try_get_named_arg("grace_weight").unwrap_or(0);
+    let cooldown: u64 = runtime::try_get_named_arg("cooldown").unwrap_or(0);
+
+    if !holds(acc, roles::CONFIG_ADMIN) { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::Frozen as u16)); }
+    if !read::<bool>(&format!("{}{:?}", prefixes::INITIALIZED, acc)).unwrap_or(false) { runtime::revert(ApiError::User(Err::NotInit as u16)); }
+    let cfg = AccountSettings {
+        expiry, delay, max_attempts, veto_window, approval_window, by_height, rotation_window, freeze_period,
+        grace_period, grace_weight, cooldown,
+    };
+    if !cfg.is_valid() { runtime::revert(ApiError::User(Err::BadSettings as u16)); }
+    // The arbiter extends a pending recovery by the current expiry, which must be in its units
+    if pending(acc) && by_height != settings(acc).by_height { runtime::revert(ApiError::User(Err::RecoveryExists as u16)); }
+
+    write(&format!("{}{:?}", prefixes::SETTINGS, acc), cfg);
+    write(&format!("{}{:?}", prefixes::ATTEMPTS, acc), 0u8);
+}
+
+/// Returns ((expiry, delay, max attempts), (veto window, approval window, rotation window),
+/// (timed by height, unfinalized attempts so far, freeze period))
+#[no_mangle]
+pub extern "C" fn get_settings() {
+    check_args("get_settings");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let cfg = settings(acc);
+    let attempts: u8 = read(&format!("{}{:?}", prefixes::ATTEMPTS, acc)).unwrap_or(0);
+    let limits = (cfg.expiry, cfg.delay, cfg.max_attempts);
+    let windows = (cfg.veto_window, cfg.approval_window, cfg.rotation_window);
+    runtime::ret(CLValue::from_t((limits, windows, (cfg.by_height, attempts, cfg.freeze_period))).unwrap_or_revert());
+}
+
+/// Plug recovery module contracts (package hashes as `Key::Hash`) into `account`'s recoveries,
+/// replacing any before; an empty list removes them. Their hooks (see `guardian_types::modules`) run
+/// in this order.
+#[no_mangle]
+pub extern "C" fn set_recovery_modules() {
+    check_args("set_recovery_modules");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let mods: Vec<Key> = runtime::get_named_arg("modules");
+
+    if !holds(acc, roles::CONFIG_ADMIN) { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::Frozen as u16)); }
+    if !read::<bool>(&format!("{}{:?}", prefixes::INITIALIZED, acc)).unwrap_or(false) { runtime::revert(ApiError::User(Err::NotInit as u16)); }
+    if pending(acc) { runtime::revert(ApiError::User(Err::RecoveryExists as u16)); }
+    let mods: Vec<Key> = mods.into_iter().map(host::stored_key).collect();
+    if mods.len() > modules::MAX_MODULES
+        || mods.iter().enumerate().any(|(i, m)| !matches!(m, Key::Hash(_)) || mods[..i].contains(m))
+    {
+        runtime::revert(ApiError::User(Err::BadModule as u16));
+    }
+    write(&format!("{}{:?}", prefixes::MODULES, acc), mods);
+}
+
+#[no_mangle]
+pub extern "C" fn get_recovery_modules() {
+    check_args("get_recovery_modules");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let mods: Vec<Key> = read(&format!("{}{:?}", prefixes::MODULES, acc)).unwrap_or_default();
+    runtime::ret(CLValue::from_t(mods).unwrap_or_revert());
+}
+
+/// Returns `account`'s lifetime recovery counts and its anomaly limits, both indexed by
+/// `guardian_types::counters` (a limit of 0, or none listed, means no limit)
+#[no_mangle]
+pub extern "C" fn get_recovery_counts() {
+    check_args("get_recovery_counts");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let mut c = counts(acc);
+    if uncounted_expiry(acc).is_some() { c[counters::EXPIRED as usize] += 1; }
+    let limits: Vec<u32> = read(&format!("{}{:?}", prefixes::ANOMALY_LIMITS, acc)).unwrap_or_default();
+    runtime::ret(CLValue::from_t((c, limits)).unwrap_or_revert());
+}
+
+/// Emit `RecoveryAnomalyV2` whenever a lifetime counter of `account` goes past its entry in
+/// `limits` (indexed by `guardian_types::counters`; 0 for no limit), so monitoring can escalate
+#[no_mangle]
+pub extern "C" fn set_anomaly_limits() {
+    check_args("set_anomaly_limits");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let limits: Vec<u32> = runtime::get_named_arg("limits");
+
+    if !holds(acc, roles::CONFIG_ADMIN) { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::Frozen as u16)); }
+    if !read::<bool>(&format!("{}{:?}", prefixes::INITIALIZED, acc)).unwrap_or(false) { runtime::revert(ApiError::User(Err::NotInit as u16)); }
+    if limits.len() > counters::COUNT { runtime::revert(ApiError::User(Err::BadSettings as u16)); }
+    write(&format!("{}{:?}", prefixes::ANOMALY_LIMITS, acc), limits);
+}
+
+/// Block time (or height, for height-timed accounts) before which no new recovery of `account` can
+/// start; 0 if it isn't cooling down from a finalized one
+#[no_mangle]
+pub extern "C" fn get_cooldown() {
+    check_args("get_cooldown");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let cfg = settings(acc);
+    let end = cooldown_end(acc, &cfg);
+    runtime::ret(CLValue::from_t(if end.reached(clock(&cfg)) { 0 } else { end.0 }).unwrap_or_revert());
+}
+
+/// Block time until which `account`'s configuration is frozen (0 if never frozen)
+#[no_mangle]
+pub extern "C" fn get_freeze() {
+    check_args("get_freeze");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    runtime::ret(CLValue::from_t(read::<u64>(&format!("{}{:?}", prefixes::FROZEN_UNTIL, acc)).unwrap_or(0)).unwrap_or_revert());
+}
+
+/// Name (or with `None`, remove) the arbiter who may break deadlocks on `account`'s recoveries.
+/// The arbiter can't be the owner or a guardian and can't be changed while a recovery is pending.
+#[no_mangle]
+pub extern "C" fn set_arbiter() {
+    check_args("set_arbiter");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let arbiter: Option<AccountHash> = runtime::get_named_arg("arbiter");
+
+    if !holds(acc, roles::CONFIG_ADMIN) { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::Frozen as u16)); }
+    if !read::<bool>(&format!("{}{:?}", prefixes::INITIALIZED, acc)).unwrap_or(false) { runtime::revert(ApiError::User(Err::NotInit as u16)); }
+    if pending(acc) { runtime::revert(ApiError::User(Err::RecoveryExists as u16)); }
+    if let Some(a) = arbiter {
+        if a == acc || verify::for_account(acc).voter(acc, a).is_some() { runtime::revert(ApiError::User(Err::BadArbiter as u16)); }
+    }
+    write(&format!("{}{:?}", prefixes::ARBITER, acc), arbiter);
+}
+
+#[no_mangle]
+pub extern "C" fn get_arbiter() {
+    check_args("get_arbiter");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let a: Option<AccountHash> = read::<Option<AccountHash>>(&format!("{}{:?}", prefixes::ARBITER, acc)).flatten();
+    runtime::ret(CLValue::from_t(a).unwrap_or_revert());
+}
+
+/// Assign guardians of `account` to categories (family, friends, institutions, ...), replacing any
+/// earlier assignment. Only guardians listed by key can be assigned.
+#[cfg(feature = "policies")]
+#[no_mangle]
+pub extern "C" fn set_guardian_categories() {
+    check_args("set_guardian_categories");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let cats: Vec<(Key, u8)> = runtime::get_named_arg("categories");
+
+    if !holds(acc, roles::CONFIG_ADMIN) { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::Frozen as u16)); }
+    if !read::<bool>(&format!("{}{:?}", prefixes::INITIALIZED, acc)).unwrap_or(false) { runtime::revert(ApiError::User(Err::NotInit as u16)); }
+    if pending(acc) { runtime::revert(ApiError::User(Err::RecoveryExists as u16)); }
+
+    let guards = guardian_keys(acc);
+    let cats: Vec<(Key, u8)> = cats.into_iter().map(|(k, c)| (host::stored_key(k), c)).collect();
+    for (i, (who, _)) in cats.iter().enumerate() {
+        if !guards.contains(who) || cats[..i].iter().any(|(k, _)| k == who) { runtime::revert(ApiError::User(Err::NotGuardian as u16)); }
+    }
+    let hidden = guardian_count(acc).saturating_sub(guards.len());
+    if let Err(e) = approval_policy(acc).check(&guards, hidden, &cats) { runtime::revert(ApiError::User(e as u16)); }
+    write(&format!("{}{:?}", prefixes::CATEGORIES, acc), cats);
+}
+
+/// Shorthand for a category quorum policy: at least `minimum` approvals from each listed category on
+/// top of the threshold (or with `None`, back to a plain threshold). Every minimum has to be reachable
+/// by the guardians assigned now.
+#[cfg(feature = "policies")]
+#[no_mangle]
+pub extern "C" fn set_category_policy() {
+    check_args("set_category_policy");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let mins: Option<Vec<(u8, u8)>> = runtime::get_named_arg("minimums");
+
+    if !holds(acc, roles::CONFIG_ADMIN) { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::Frozen as u16)); }
+    if !read::<bool>(&format!("{}{:?}", prefixes::INITIALIZED, acc)).unwrap_or(false) { runtime::revert(ApiError::User(Err::NotInit as u16)); }
+    if pending(acc) { runtime::revert(ApiError::User(Err::RecoveryExists as u16)); }
+    let threshold: u8 = read(&format!("{}{:?}", prefixes::THRESHOLD, acc)).unwrap_or(2);
+    match (approval_policy(acc), mins) {
+        (ApprovalPolicy::Threshold { .. } | ApprovalPolicy::CategoryQuorum { .. }, Some(minimums)) => {
+            store_policy(acc, ApprovalPolicy::CategoryQuorum { threshold, minimums });
+        }
+        (ApprovalPolicy::CategoryQuorum { .. }, None) => store_policy(acc, ApprovalPolicy::Threshold { threshold }),
+        (_, None) => {}
+        _ => runtime::revert(ApiError::User(Err::BadPolicy as u16)),
+    }
+}
+
+/// Returns (each categorized guardian as (guardian, category), the category minimums if set)
+#[cfg(feature = "policies")]
+#[no_mangle]
+pub extern "C" fn get_category_policy() {
+    check_args("get_category_policy");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let mins = match approval_policy(acc) {
+        ApprovalPolicy::CategoryQuorum { minimums, .. } => Some(minimums),
+        _ => None,
+    };
+    runtime::ret(CLValue::from_t((categories(acc), mins)).unwrap_or_revert());
+}
+
+/// Replace `account`'s approval policy with the bytesrepr-encoded `guardian_types::policy::ApprovalPolicy`
+/// in `policy`, checked against the current guardians. Count-based policies also set the threshold.
+#[cfg(feature = "policies")]
+#[no_mangle]
+pub extern "C" fn set_approval_policy() {
+    check_args("set_approval_policy");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let encoded: Bytes = runtime::get_named_arg("policy");
+
+    if !holds(acc, roles::CONFIG_ADMIN) { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::Frozen as u16)); }
+    if !read::<bool>(&format!("{}{:?}", prefixes::INITIALIZED, acc)).unwrap_or(false) { runtime::revert(ApiError::User(Err::NotInit as u16)); }
+    if pending(acc) { runtime::revert(ApiError::User(Err::RecoveryExists as u16)); }
+    let p = match ApprovalPolicy::from_bytes(&encoded) {
+        Ok((p, rest)) if rest.is_empty() => p,
+        _ => runtime::revert(ApiError::User(Err::BadPolicy as u16)),
+    };
+    store_policy(acc, p);
+}
+
+/// `account`'s approval policy, bytesrepr-encoded
+#[cfg(feature = "policies")]
+#[no_mangle]
+pub extern "C" fn get_approval_policy() {
+    check_args("get_approval_policy");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let encoded: Bytes = approval_policy(acc).to_bytes().unwrap_or_revert().into();
+    runtime::ret(CLValue::from_t(encoded).unwrap_or_revert());
+}
+
+/// Owner checks shared by guardian terms and suspensions; returns `guardian` as listed
+#[cfg(feature = "policies")]
+fn listed_guardian(acc: AccountHash, guard: Key) -> Key {
+    if !holds(acc, roles::CONFIG_ADMIN) { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::Frozen as u16)); }
+    if !read::<bool>(&format!("{}{:?}", prefixes::INITIALIZED, acc)).unwrap_or(false) { runtime::revert(ApiError::User(Err::NotInit as u16)); }
+    if pending(acc) { runtime::revert(ApiError::User(Err::RecoveryExists as u16)); }
+    let guard = host::stored_key(guard);
+    if !guardian_keys(acc).contains(&guard) { runtime::revert(ApiError::User(Err::NotGuardian as u16)); }
+    guard
+}
+
+/// End `guardian`'s term at block time `until` (ms), or with `None` make it open-ended. Past its
+/// term a guardian's weight no longer counts under a fractional policy.
+#[cfg(feature = "policies")]
+#[no_mangle]
+pub extern "C" fn set_guardian_term() {
+    check_args("set_guardian_term");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let until: Option<u64> = runtime::get_named_arg("until");
+    let guard = listed_guardian(acc, runtime::get_named_arg("guardian"));
+
+    let mut terms: Vec<(Key, u64)> = read(&format!("{}{:?}", prefixes::GUARDIAN_TERMS, acc)).unwrap_or_default();
+    terms.retain(|(k, _)| *k != guard);
+    if let Some(until) = until { terms.push((guard, until)); }
+    write(&format!("{}{:?}", prefixes::GUARDIAN_TERMS, acc), terms);
+}
+
+/// Suspend `guardian` (or lift its suspension): while suspended its weight doesn't count under a
+/// fractional policy
+#[cfg(feature = "policies")]
+#[no_mangle]
+pub extern "C" fn suspend_guardian() {
+    check_args("suspend_guardian");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let suspended: bool = runtime::get_named_arg("suspended");
+    let guard = listed_guardian(acc, runtime::get_named_arg("guardian"));
+
+    let mut list: Vec<Key> = read(&format!("{}{:?}", prefixes::SUSPENDED, acc)).unwrap_or_default();
+    list.retain(|k| *k != guard);
+    if suspended { list.push(guard); }
+    write(&format!("{}{:?}", prefixes::SUSPENDED, acc), list);
+}
+
+/// Returns (`guardian`'s term end if it has one, whether it's suspended)
+#[cfg(feature = "policies")]
+#[no_mangle]
+pub extern "C" fn get_guardian_term() {
+    check_args("get_guardian_term");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let guard: Key = runtime::get_named_arg("guardian");
+    let guard = host::stored_key(guard);
+    let terms: Vec<(Key, u64)> = read(&format!("{}{:?}", prefixes::GUARDIAN_TERMS, acc)).unwrap_or_default();
+    let suspended: Vec<Key> = read(&format!("{}{:?}", prefixes::SUSPENDED, acc)).unwrap_or_default();
+    let until = terms.iter().find(|(k, _)| *k == guard).map(|(_, t)| *t);
+    runtime::ret(CLValue::from_t((until, suspended.contains(&guard))).unwrap_or_revert());
+}
+
+/// Approvals recovery `id` still needs from each category, as (category, missing); empty once every
+/// minimum is met
+#[cfg(feature = "policies")]
+#[no_mangle]
+pub extern "C" fn get_category_shortfall() {
+    check_args("get_category_shortfall");
+    let id: U256 = runtime::get_named_arg("id");
+    let acc: AccountHash = read(&format!("{}{}", prefixes::RECOVERY_ACCOUNT, id)).unwrap_or_revert_with(ApiError::User(Err::NotFound as u16));
+    runtime::ret(CLValue::from_t(category_shortfall(id, acc)).unwrap_or_revert());
+}
+
+/// Arbiter pushes an expiring recovery's deadline back by one lifetime, once per recovery
+#[no_mangle]
+pub extern "C" fn arbiter_extend() {
+    check_args("arbiter_extend");
+    let id: U256 = runtime::get_named_arg("id");
+    let acc = only_arbiter(id);
+
+    let deadline: u64 = read(&format!("{}{}", prefixes::EXPIRES_AT, id)).unwrap_or_revert_with(ApiError::User(Err::NotFound as u16));
+    if read::<bool>(&format!("{}{}", prefixes::ARBITER_EXTENDED, id)).unwrap_or(false) { runtime::revert(ApiError::User(Err::ArbiterUsed as u16)); }
+
+    write(&format!("{}{}", prefixes::EXPIRES_AT, id), Instant(deadline).plus(Span(settings(acc).expiry)).0);
+    write(&format!("{}{}", prefixes::ARBITER_EXTENDED, id), true);
+}
+
+/// Arbiter cancels a disputed recovery. Unlike `veto` no approvers are recorded, so nobody can be slashed.
+#[no_mangle]
+pub extern "C" fn arbiter_cancel() {
+    check_args("arbiter_cancel");
+    let id: U256 = runtime::get_named_arg("id");
+    let acc = only_arbiter(id);
+
+    write(&format!("{}{}", prefixes::CANCELLED, id), true);
+    untrack(id, acc);
+    settle_deposit(id, read(&format!("{}{}", prefixes::INITIATOR, id)).unwrap_or_revert());
+    emit(events::RecoveryCancelledV3 { tenant: tenant(), id, account: acc, vetoed: false, notify: notify(acc) });
+}
+
+/// Arbiter casts the single approval that breaks a tie one short of threshold; finalizing is still
+/// left to `finalize`
+#[no_mangle]
+pub extern "C" fn arbiter_approve() {
+    check_args("arbiter_approve");
+    let id: U256 = runtime::get_named_arg("id");
+    let acc = only_arbiter(id);
+
+    if read::<bool>(&format!("{}{}", prefixes::ARBITER_APPROVED, id)).unwrap_or(false) { runtime::revert(ApiError::User(Err::ArbiterUsed as u16)); }
+    let cnt: u8 = read(&format!("{}{}", prefixes::APPROVAL_COUNT, id)).unwrap_or(0);
+    // A tie-break carries no weight to make up a share of it
+    if matches!(approval_policy(acc), ApprovalPolicy::Fractional { .. }) { runtime::revert(ApiError::User(Err::BadArbiter as u16)); }
+    if approved(id, acc) || score(id, acc) + 1 != threshold(id, acc) as u32 { runtime::revert(ApiError::User(Err::BadArbiter as u16)); }
+    // The tie-break stands in for no category
+    if !category_shortfall(id, acc).is_empty() { runtime::revert(ApiError::User(Err::BadArbiter as u16)); }
+
+    write(&format!("{}{}", prefixes::ARBITER_APPROVED, id), true);
+    write(&format!("{}{}", prefixes::APPROVAL_COUNT, id), cnt + 1);
+    reach_threshold(id);
+}
+
+/// Arbiter rules that the flags on recovery `id` don't stand, releasing the hold on finalizing it.
+/// To uphold them instead, the arbiter uses `arbiter_cancel`.
+#[no_mangle]
+pub extern "C" fn arbiter_dismiss_flags() {
+    check_args("arbiter_dismiss_flags");
+    let id: U256 = runtime::get_named_arg("id");
+    only_arbiter(id);
+
+    if read::<bool>(&format!("{}{}", prefixes::FLAGS_DISMISSED, id)).unwrap_or(false) { runtime::revert(ApiError::User(Err::ArbiterUsed as u16)); }
+    write(&format!("{}{}", prefixes::FLAGS_DISMISSED, id), true);
+}
+
+/// Deposit in motes a recovery initiator must lock for `account` (zero disables)
+#[no_mangle]
+pub extern "C" fn set_recovery_deposit() {
+    check_args("set_recovery_deposit");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let amount: U512 = runtime::get_named_arg("amount");
+
+    if !holds(acc, roles::CONFIG_ADMIN) { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::Frozen as u16)); }
+    if !read::<bool>(&format!("{}{:?}", prefixes::INITIALIZED, acc)).unwrap_or(false) { runtime::revert(ApiError::User(Err::NotInit as u16)); }
+    write(&format!("{}{:?}", prefixes::DEPOSIT_REQUIRED, acc), amount);
+}
+
+#[no_mangle]
+pub extern "C" fn get_recovery_deposit() {
+    check_args("get_recovery_deposit");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let amount: U512 = read(&format!("{}{:?}", prefixes::DEPOSIT_REQUIRED, acc)).unwrap_or_default();
+    runtime::ret(CLValue::from_t(amount).unwrap_or_revert());
+}
+
+/// Propose slashing `bps` basis points of the bonds of everyone who approved vetoed recovery `id`
+#[cfg(feature = "bonding")]
+#[no_mangle]
+pub extern "C" fn propose_slash() {
+    check_args("propose_slash");
+    let id: U256 = runtime::get_named_arg("id");
+    let bps: u32 = runtime::get_named_arg("bps");
+    let evidence: String = runtime::get_named_arg("evidence");
+    let acc: AccountHash = read(&format!("{}{}", prefixes::RECOVERY_ACCOUNT, id)).unwrap_or_revert_with(ApiError::User(Err::NotFound as u16));
+
+    if !holds(acc, roles::CONFIG_ADMIN) { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if !read::<bool>(&format!("{}{}", prefixes::CANCELLED, id)).unwrap_or(false) { runtime::revert(ApiError::User(Err::NotVetoed as u16)); }
+    if bps == 0 || bps > 10_000 || evidence.len() > 256 { runtime::revert(ApiError::User(Err::BadSlash as u16)); }
+    if read::<(u64, u32)>(&format!("{}{}", prefixes::SLASH_PROPOSAL, id)).is_some() { runtime::revert(ApiError::User(Err::BadSlash as u16)); }
+    if open_slash(acc).is_some() { runtime::revert(ApiError::User(Err::SlashPending as u16)); }
+
+    write(&format!("{}{}", prefixes::SLASH_PROPOSAL, id), (now().0, bps));
+    write(&format!("{}{}", prefixes::SLASH_EVIDENCE, id), evidence);
+    write(&format!("{}{:?}", prefixes::OPEN_SLASH, acc), Some(id));
+}
+
+/// An approver of the vetoed recovery records counter-evidence during the dispute window
+#[cfg(feature = "bonding")]
+#[no_mangle]
+pub extern "C" fn dispute_slash() {
+    check_args("dispute_slash");
+    let id: U256 = runtime::get_named_arg("id");
+    let evidence: String = runtime::get_named_arg("evidence");
+    let caller = runtime::get_caller();
+
+    let (at, _): (u64, u32) = read(&format!("{}{}", prefixes::SLASH_PROPOSAL, id)).unwrap_or_revert_with(ApiError::User(Err::NoSlash as u16));
+    let approvers: Vec<AccountHash> = read(&format!("{}{}", prefixes::VETOED_APPROVERS, id)).unwrap_or(vec![]);
+    if !approvers.contains(&caller) { runtime::revert(ApiError::User(Err::NotGuardian as u16)); }
+    if Instant(at).has_elapsed(SLASH_DISPUTE_WINDOW, now()) { runtime::revert(ApiError::User(Err::DisputeWindow as u16)); }
+    if evidence.len() > 256 { runtime::revert(ApiError::User(Err::BadSlash as u16)); }
+
+    write(&format!("{}{}_{:?}", prefixes::SLASH_DISPUTE, id, caller), evidence);
+}
+
+/// Owner withdraws an unexecuted slash proposal
+#[cfg(feature = "bonding")]
+#[no_mangle]
+pub extern "C" fn cancel_slash() {
+    check_args("cancel_slash");
+    let id: U256 = runtime::get_named_arg("id");
+    let acc: AccountHash = read(&format!("{}{}", prefixes::RECOVERY_ACCOUNT, id)).unwrap_or_revert_with(ApiError::User(Err::NotFound as u16));
+
+    if !holds(acc, roles::CONFIG_ADMIN) { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if open_slash(acc) != Some(id) { runtime::revert(ApiError::User(Err::NoSlash as u16)); }
+
+    write::<Option<U256>>(&format!("{}{:?}", prefixes::OPEN_SLASH, acc), None);
+}
+
+/// After the dispute window, take the proposed share of each approver's bond and pay it to the owner
+#[cfg(feature = "bonding")]
+#[no_mangle]
+pub extern "C" fn execute_slash() {
+    check_args("execute_slash");
+    let id: U256 = runtime::get_named_arg("id");
+    let acc: AccountHash = read(&format!("{}{}", prefixes::RECOVERY_ACCOUNT, id)).unwrap_or_revert_with(ApiError::User(Err::NotFound as u16));
+
+    if open_slash(acc) != Some(id) { runtime::revert(ApiError::User(Err::NoSlash as u16)); }
+    let (at, bps): (u64, u32) = read(&format!("{}{}", prefixes::SLASH_PROPOSAL, id)).unwrap_or_revert_with(ApiError::User(Err::NoSlash as u16));
+    if !Instant(at).has_elapsed(SLASH_DISPUTE_WINDOW, now()) { runtime::revert(ApiError::User(Err::DisputeWindow as u16)); }
+
+    let mut total = U512::zero();
+    let approvers: Vec<AccountHash> = read(&format!("{}{}", prefixes::VETOED_APPROVERS, id)).unwrap_or(vec![]);
+    for guard in &approvers {
+        let key = format!("{}{:?}_{:?}", prefixes::BOND, acc, guard);
+        let posted: U512 = read(&key).unwrap_or_default();
+        let cut = posted * U512::from(bps) / U512::from(10_000u32);
+        write(&key, posted - cut);
+        total += cut;
+        if !cut.is_zero() { record_stat(*guard, reputation::SLASHED); }
+    }
+
+    write::<Option<U256>>(&format!("{}{:?}", prefixes::OPEN_SLASH, acc), None);
+    write(&format!("{}{}", prefixes::SLASHED, id), total);
+    if !total.is_zero() {
+        system::transfer_from_purse_to_account(purse(&tenant_key(BOND_PURSE)), acc, total, None).unwrap_or_revert();
+    }
+}
+
+/// Returns (proposed at, bps, executed amount; zero until executed) for the slash on recovery `id`
+#[cfg(feature = "bonding")]
+#[no_mangle]
+pub extern "C" fn get_slash() {
+    check_args("get_slash");
+    let id: U256 = runtime::get_named_arg("id");
+    let (at, bps): (u64, u32) = read(&format!("{}{}", prefixes::SLASH_PROPOSAL, id)).unwrap_or_revert_with(ApiError::User(Err::NoSlash as u16));
+    let taken: U512 = read(&format!("{}{}", prefixes::SLASHED, id)).unwrap_or_default();
+    runtime::ret(CLValue::from_t((at, bps, taken)).unwrap_or_revert());
+}
+
+/// Record where `guardian`'s encrypted Shamir share lives: its content `hash` and a storage `uri`
+#[no_mangle]
+pub extern "C" fn set_share() {
+    check_args("set_share");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let guard: AccountHash = runtime::get_named_arg("guardian");
+    let hash: [u8; 32] = runtime::get_named_arg("hash");
+    let uri: String = runtime::get_named_arg("uri");
+
+    if !holds(acc, roles::CONFIG_ADMIN) { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::Frozen as u16)); }
+    let guards: Vec<AccountHash> = read(&format!("{}{:?}", prefixes::GUARDIANS, acc)).unwrap_or_revert_with(ApiError::User(Err::NotInit as u16));
+    if !guards.contains(&guard) { runtime::revert(ApiError::User(Err::NotGuardian as u16)); }
+    if uri.len() > 256 { runtime::revert(ApiError::User(Err::BadShare as u16)); }
+
+    // A new share hasn't been attested yet
+    write(&format!("{}{:?}_{:?}", prefixes::SHARE, acc, guard), Some((hash, uri, 0u64)));
+}
+
+#[no_mangle]
+pub extern "C" fn clear_share() {
+    check_args("clear_share");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let guard: AccountHash = runtime::get_named_arg("guardian");
+
+    if !holds(acc, roles::CONFIG_ADMIN) { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::Frozen as u16)); }
+    write::<Option<([u8; 32], String, u64)>>(&format!("{}{:?}_{:?}", prefixes::SHARE, acc, guard), None);
+}
+
+/// Guardian confirms they still hold the share with content `hash`
+#[no_mangle]
+pub extern "C" fn attest_share() {
+    check_args("attest_share");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let hash: [u8; 32] = runtime::get_named_arg("hash");
+    let caller = runtime::get_caller();
+
+    let key = format!("{}{:?}_{:?}", prefixes::SHARE, acc, caller);
+    let (stored, uri, _): ([u8; 32], String, u64) = read::<Option<([u8; 32], String, u64)>>(&key).flatten().unwrap_or_revert_with(ApiError::User(Err::NoShare as u16));
+    if stored != hash { runtime::revert(ApiError::User(Err::BadShare as u16)); }
+
+    write(&key, Some((stored, uri, now().0)));
+}
+
+/// Returns (guardian, (share hash, uri), last attested; 0 if never) for each guardian with a share on record
+#[no_mangle]
+pub extern "C" fn get_shares() {
+    check_args("get_shares");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let guards: Vec<AccountHash> = read(&format!("{}{:?}", prefixes::GUARDIANS, acc)).unwrap_or_default();
+    let shares: Vec<(AccountHash, ([u8; 32], String), u64)> = guards
+        .into_iter()
+        .filter_map(|g| read::<Option<([u8; 32], String, u64)>>(&format!("{}{:?}_{:?}", prefixes::SHARE, acc, g)).flatten().map(|(h, u, t)| (g, (h, u), t)))
+        .collect();
+    runtime::ret(CLValue::from_t(shares).unwrap_or_revert());
+}
+
+/// Add a watch-only party to be notified of `account`'s recovery events, without a vote. `watcher` is
+/// an opaque 32-byte handle: an account hash, or a digest an off-chain notifier maps to a contact.
+#[no_mangle]
+pub extern "C" fn add_watcher() {
+    check_args("add_watcher");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let watcher: [u8; 32] = runtime::get_named_arg("watcher");
+
+    if !holds(acc, roles::CONFIG_ADMIN) { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::Frozen as u16)); }
+    let mut list = watchers(acc);
+    if list.contains(&watcher) || list.len() >= MAX_WATCHERS { runtime::revert(ApiError::User(Err::BadWatcher as u16)); }
+    list.push(watcher);
+    write(&format!("{}{:?}", prefixes::WATCHERS, acc), list);
+}
+
+#[no_mangle]
+pub extern "C" fn remove_watcher() {
+    check_args("remove_watcher");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let watcher: [u8; 32] = runtime::get_named_arg("watcher");
+
+    if !holds(acc, roles::CONFIG_ADMIN) { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::Frozen as u16)); }
+    let mut list = watchers(acc);
+    if !list.contains(&watcher) { runtime::revert(ApiError::User(Err::BadWatcher as u16)); }
+    list.retain(|w| *w != watcher);
+    write(&format!("{}{:?}", prefixes::WATCHERS, acc), list);
+}
+
+#[no_mangle]
+pub extern "C" fn get_watchers() {
+    check_args("get_watchers");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    runtime::ret(CLValue::from_t(watchers(acc)).unwrap_or_revert());
+}
+
+/// Replace `member`'s role bits on `acc` with `bits`, dropping the member at 0
+fn set_roles(acc: AccountHash, member: Key, bits: u8) {
+    let mut list = members(acc);
+    match list.iter().position(|(m, _)| *m == member) {
+        Some(i) if bits == 0 => { list.remove(i); }
+        Some(i) => list[i].1 = bits,
+        None if bits == 0 => return,
+        None => {
+            if list.len() >= MAX_ROLE_MEMBERS { runtime::revert(ApiError::User(Err::BadRole as u16)); }
+            list.push((member, bits));
+        }
+    }
+    write(&format!("{}{:?}", prefixes::ROLES, acc), list);
+    emit(events::RolesChangedV1 { tenant: tenant(), account: acc, member, roles: bits });
+}
+
+/// Owner checks shared by granting and revoking roles; returns (`member` as stored, its current bits)
+fn role_change(acc: AccountHash, member: Key, bits: u8) -> (Key, u8) {
+    if runtime::get_caller() != acc { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::Frozen as u16)); }
+    let member = host::stored_key(member);
+    let valid = matches!(member, Key::Account(a) if a != acc) || matches!(member, Key::Hash(_));
+    if !valid || bits == 0 || bits & !roles::ALL != 0 { runtime::revert(ApiError::User(Err::BadRole as u16)); }
+    let held = members(acc).into_iter().find(|(m, _)| *m == member).map_or(0, |(_, r)| r);
+    (member, held)
+}
+
+/// Owner grants `roles` (`guardian_types::roles` bits) on `account` to `member`, an account or a
+/// contract package, on top of any it holds
+#[no_mangle]
+pub extern "C" fn grant_roles() {
+    check_args("grant_roles");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let member: Key = runtime::get_named_arg("member");
+    let bits: u8 = runtime::get_named_arg("roles");
+
+    let (member, held) = role_change(acc, member, bits);
+    set_roles(acc, member, held | bits);
+}
+
+/// Owner takes `roles` on `account` away from `member`
+#[no_mangle]
+pub extern "C" fn revoke_roles() {
+    check_args("revoke_roles");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let member: Key = runtime::get_named_arg("member");
+    let bits: u8 = runtime::get_named_arg("roles");
+
+    let (member, held) = role_change(acc, member, bits);
+    if held & bits == 0 { runtime::revert(ApiError::User(Err::BadRole as u16)); }
+    set_roles(acc, member, held & !bits);
+}
+
+/// A member gives up `roles` it holds on `account`, as the account or the contract package calling
+#[no_mangle]
+pub extern "C" fn renounce_roles() {
+    check_args("renounce_roles");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let bits: u8 = runtime::get_named_arg("roles");
+
+    let (caller, package) = (Key::Account(runtime::get_caller()), caller_package());
+    let (member, held) = members(acc).into_iter()
+        .find(|(m, r)| r & bits != 0 && (*m == caller || Some(*m) == package))
+        .unwrap_or_revert_with(ApiError::User(Err::BadRole as u16));
+    set_roles(acc, member, held & !bits);
+}
+
+/// Returns `account`'s role members as (member, `guardian_types::roles` bits)
+#[no_mangle]
+pub extern "C" fn get_roles() {
+    check_args("get_roles");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    runtime::ret(CLValue::from_t(members(acc)).unwrap_or_revert());
+}
+
+/// Returns the `guardian_types::roles` bits `member` holds on `account`; 0 if none
+#[no_mangle]
+pub extern "C" fn get_member_roles() {
+    check_args("get_member_roles");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let member: Key = host::stored_key(runtime::get_named_arg("member"));
+    let held = members(acc).into_iter().find(|(m, _)| *m == member).map_or(0, |(_, r)| r);
+    runtime::ret(CLValue::from_t(held).unwrap_or_revert());
+}
+
+/// Designate an heir who may inherit the account once the dead-man switch has lapsed
+#[no_mangle]
+pub extern "C" fn designate_heir() {
+    check_args("designate_heir");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let heir: AccountHash = runtime::get_named_arg("heir");
+
+    if runtime::get_caller() != acc { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::Frozen as u16)); }
+    if !read::<bool>(&format!("{}{:?}", prefixes::INITIALIZED, acc)).unwrap_or(false) { runtime::revert(ApiError::User(Err::NotInit as u16)); }
+
+    let guards: Vec<AccountHash> = read(&format!("{}{:?}", prefixes::GUARDIANS, acc)).unwrap_or(vec![]);
+    let key = format!("{}{:?}", prefixes::HEIRS, acc);
+    let mut heirs: Vec<AccountHash> = read(&key).unwrap_or(vec![]);
+    if heir == acc || guards.contains(&heir) || heirs.contains(&heir) { runtime::revert(ApiError::User(Err::BadHeir as u16)); }
+
+    heirs.push(heir);
+    write(&key, heirs);
+}
+
+#[no_mangle]
+pub extern "C" fn revoke_heir() {
+    check_args("revoke_heir");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let heir: AccountHash = runtime::get_named_arg("heir");
+
+    if runtime::get_caller() != acc { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::Frozen as u16)); }
+
+    let key = format!("{}{:?}", prefixes::HEIRS, acc);
+    let mut heirs: Vec<AccountHash> = read(&key).unwrap_or(vec![]);
+    if !heirs.contains(&heir) { runtime::revert(ApiError::User(Err::NotHeir as u16)); }
+
+    heirs.retain(|h| *h != heir);
+    write(&key, heirs);
+    // A revoked heir's pre-registered key must not survive a later re-designation
+    write::<Option<PublicKey>>(&format!("{}{:?}_{:?}", prefixes::HEIR_KEY, acc, heir), None);
+}
+
+/// Called by a designated heir to pre-register the key the account may be recovered to
+#[no_mangle]
+pub extern "C" fn register_heir_key() {
+    check_args("register_heir_key");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let nk: PublicKey = runtime::get_named_arg("new_key");
+    let caller = runtime::get_caller();
+
+    let heirs: Vec<AccountHash> = read(&format!("{}{:?}", prefixes::HEIRS, acc)).unwrap_or(vec![]);
+    if !heirs.contains(&caller) { runtime::revert(ApiError::User(Err::NotHeir as u16)); }
+
+    write(&format!("{}{:?}_{:?}", prefixes::HEIR_KEY, acc, caller), Some(nk));
+}
+
+#[no_mangle]
+pub extern "C" fn get_heirs() {
+    check_args("get_heirs");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let heirs: Vec<AccountHash> = read(&format!("{}{:?}", prefixes::HEIRS, acc)).unwrap_or(vec![]);
+    runtime::ret(CLValue::from_t(heirs).unwrap_or_revert());
+}
+
+/// Returns (inheritance active, block time at which the owner became or becomes inactive; 0 if not opted in)
+#[no_mangle]
+pub extern "C" fn get_inheritance_status() {
+    check_args("get_inheritance_status");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let period: u64 = read(&format!("{}{:?}", prefixes::HEARTBEAT_PERIOD, acc)).unwrap_or(0);
+    let at = if period == 0 { 0 } else { Instant(read(&format!("{}{:?}", prefixes::HEARTBEAT, acc)).unwrap_or(0)).plus(Span(period)).0 };
+    runtime::ret(CLValue::from_t((inheritable(acc), at)).unwrap_or_revert());
+}
+
+/// True if this registry implements the optional module `interface_id` (see `guardian_types::interfaces`)
+#[no_mangle]
+pub extern "C" fn supports_interface() {
+    check_args("supports_interface");
+    let id: u32 = runtime::get_named_arg("interface_id");
+    runtime::ret(CLValue::from_t(id < 64 && INTERFACES & (1 << id) != 0).unwrap_or_revert());
+}
+
+/// True if `guardian` is a guardian of `account`: a listed or privacy-mode account
+/// (`Key::Account`) or a contract guardian's package (`Key::Hash`). Merkle members need a proof, so
+/// they're not reported. Part of the stable guardianship queries in `guardian-interface`.
+#[no_mangle]
+pub extern "C" fn is_guardian_of_v1() {
+    check_args("is_guardian_of_v1");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let who: Key = runtime::get_named_arg("guardian");
+    let ok = match host::stored_key(who) {
+        Key::Account(a) => read::<Vec<AccountHash>>(&format!("{}{:?}", prefixes::GUARDIANS, acc)).is_some_and(|g| g.contains(&a)) || verify::hidden(acc, a),
+        Key::Hash(pkg) => contract_guardians(acc).contains(&Key::Hash(pkg)),
+        _ => false,
+    };
+    runtime::ret(CLValue::from_t(ok).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn guardian_count_v1() {
+    check_args("guardian_count_v1");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    runtime::ret(CLValue::from_t(guardian_count(acc) as u32).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn has_min_guardians_v1() {
+    check_args("has_min_guardians_v1");
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let min: u32 = runtime::get_named_arg("min");
+    runtime::ret(CLValue::from_t(guardian_count(acc) as u32 >= min).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn guardianship_version() {
+    check_args("guardianship_version");
+    runtime::ret(CLValue::from_t(interfaces::GUARDIANSHIP_VERSION).unwrap_or_revert());
+}
+
+/// Bitmask of every optional module this registry implements
+#[no_mangle]
+pub extern "C" fn supported_interfaces() {
+    check_args("supported_interfaces");
+    runtime::ret(CLValue::from_t(INTERFACES).unwrap_or_revert());
+}
+
+/// Admin winds the registry down: from now on it takes no new accounts or recoveries, and once
+/// `sunset` (ms) has passed it takes no changes at all, so accounts have that long to settle pending
+/// recoveries, withdraw what they hold and move to another registry. Can't be undone.
+#[no_mangle]
+pub extern "C" fn decommission() {
+    check_args("decommission");
+    let sunset: u64 = runtime::get_named_arg("sunset");
+
+    if !caller_is_registry_admin() || decommissioning().is_some() {
+        runtime::revert(ApiError::User(Err::NotAdmin as u16));
+    }
+    let at = now();
+    runtime::put_key(DECOMMISSION, storage::new_uref((at.0, at.plus(Span(sunset)).0)).into());
+}
+
+/// Admin sets the protocol fee (motes) of each operation, indexed by `guardian_types::fees`; zero
+/// leaves an operation free. Fees are collected from the `purse` the charged call passes, usually
+/// through the `funded_call` session.
+#[no_mangle]
+pub extern "C" fn set_fee_schedule() {
+    check_args("set_fee_schedule");
+    let schedule: Vec<U512> = runtime::get_named_arg("fees");
+
+    if !caller_is_admin() { runtime::revert(ApiError::User(Err::NotAdmin as u16)); }
+    if schedule.len() != fees::COUNT { runtime::revert(ApiError::User(Err::BadFeeSchedule as u16)); }
+    write(prefixes::FEE_SCHEDULE, schedule.clone());
+    emit(events::FeeScheduleSetV2 { tenant: tenant(), fees: schedule });
+}
+
+/// Returns the protocol fee of each operation, indexed by `guardian_types::fees`
+#[no_mangle]
+pub extern "C" fn get_fee_schedule() {
+    check_args("get_fee_schedule");
+    runtime::ret(CLValue::from_t(fee_schedule()).unwrap_or_revert());
+}
+
+/// Admin pays `amount` of the collected protocol fees out of the fee purse to `destination`, which
+/// has to be an account. Works through and after a decommissioning, so fees are never stranded.
+#[no_mangle]
+pub extern "C" fn withdraw_fees() {
+    check_args("withdraw_fees");
+    let amount: U512 = runtime::get_named_arg("amount");
+    let destination: Key = runtime::get_named_arg("destination");
+
+    if !caller_is_admin() { runtime::revert(ApiError::User(Err::NotAdmin as u16)); }
+    let to = match host::stored_key(destination) {
+        Key::Account(a) if a != AccountHash::default() => a,
+        _ => runtime::revert(ApiError::User(Err::BadDestination as u16)),
+    };
+    if amount.is_zero() || amount > fee_balance() { runtime::revert(ApiError::User(Err::BadWithdrawal as u16)); }
+    system::transfer_from_purse_to_account(purse(&tenant_key(FEE_PURSE)), to, amount, None)
+        .unwrap_or_revert_with(ApiError::User(Err::BadWithdrawal as u16));
+    emit(events::FeesWithdrawnV2 { tenant: tenant(), destination: to, amount });
+}
+
+/// Returns the motes collected in the fee purse and not yet withdrawn
+#[no_mangle]
+pub extern "C" fn get_fee_balance() {
+    check_args("get_fee_balance");
+    runtime::ret(CLValue::from_t(fee_balance()).unwrap_or_revert());
+}
+
+/// Registry admin creates a tenant administered by `admin`: a registry of its own inside this one,
+/// with its own accounts, recoveries, fee schedule and fee purse, reached by passing its id as the
+/// `tenant` argument of any call. Returns the new tenant's id; ids count up from 1.
+#[no_mangle]
+pub extern "C" fn create_tenant() {
+    check_args("create_tenant");
+    let admin: AccountHash = runtime::get_named_arg("admin");
+
+    if !caller_is_registry_admin() { runtime::revert(ApiError::User(Err::NotAdmin as u16)); }
+    taking_new();
+    let id = read_unscoped::<u32>(prefixes::TENANT_COUNT).unwrap_or(0) + 1;
+    write_unscoped(prefixes::TENANT_COUNT, id);
+    write_unscoped(&format!("{}{}", prefixes::TENANT_ADMIN, id), admin);
+    emit(events::TenantCreatedV1 { tenant: id, admin });
+    runtime::ret(CLValue::from_t(id).unwrap_or_revert());
+}
+
+/// A tenant's admin hands the tenant over to `admin`
+#[no_mangle]
+pub extern "C" fn set_tenant_admin() {
+    check_args("set_tenant_admin");
+    let admin: AccountHash = runtime::get_named_arg("admin");
+
+    let t = tenant();
+    if t == 0 { runtime::revert(ApiError::User(Err::NoTenant as u16)); }
+    if !caller_is_admin() { runtime::revert(ApiError::User(Err::NotAdmin as u16)); }
+    write_unscoped(&format!("{}{}", prefixes::TENANT_ADMIN, t), admin);
+    emit(events::TenantAdminChangedV1 { tenant: t, admin });
+}
+
+/// Returns `tenant`'s admin, the account that installed the registry for tenant 0; `None` for a
+/// tenant that doesn't exist
+#[no_mangle]
+pub extern "C" fn get_tenant_admin() {
+    check_args("get_tenant_admin");
+    let t: u32 = runtime::get_named_arg(entry_points::ARG_TENANT);
+
+    let admin = match t {
+        0 => runtime::get_key("admin").and_then(|k| k.into_account()),
+        t => read_unscoped::<AccountHash>(&format!("{}{}", prefixes::TENANT_ADMIN, t)),
+    };
+    runtime::ret(CLValue::from_t(admin).unwrap_or_revert());
+}
+
+/// Returns the number of tenants created, which is also the highest tenant id
+#[no_mangle]
+pub extern "C" fn get_tenant_count() {
+    check_args("get_tenant_count");
+    runtime::ret(CLValue::from_t(read_unscoped::<u32>(prefixes::TENANT_COUNT).unwrap_or(0)).unwrap_or_revert());
+}
+
+/// Returns (`guardian_types::lifecycle` state, decommissioned at, sunset end); both times are 0
+/// while the registry is active
+#[no_mangle]
+pub extern "C" fn get_lifecycle() {
+    check_args("get_lifecycle");
+    let (at, end) = decommissioning().unwrap_or((0, 0));
+    runtime::ret(CLValue::from_t((lifecycle(), at, end)).unwrap_or_revert());
+}
+
+/// Bring the records up to the schema this build writes, see `guardian_types::migrations`. The
+/// upgrade in `call()` runs it on the contract version it adds; the registry admin may run it again,
+/// which does nothing once the records are current.
+#[no_mangle]
+pub extern "C" fn migrate() {
+    check_args("migrate");
+    if !caller_is_registry_admin() { runtime::revert(ApiError::User(Err::NotAdmin as u16)); }
+    let from: u32 = read_unscoped(prefixes::SCHEMA_VERSION).unwrap_or(migrations::FIRST);
+    if from > migrations::CURRENT { runtime::revert(ApiError::User(Err::BadMigration as u16)); }
+    // A schema change adds its step here, run when `from` is below the version it introduced
+    write_unscoped(prefixes::SCHEMA_VERSION, migrations::CURRENT);
+}
+
+/// Schema version the registry's records are in
+#[no_mangle]
+pub extern "C" fn get_schema_version() {
+    check_args("get_schema_version");
+    let version: u32 = read_unscoped(prefixes::SCHEMA_VERSION).unwrap_or(migrations::FIRST);
+    runtime::ret(CLValue::from_t(version).unwrap_or_revert());
+}
+
+/// A guardian's directory listing: (fee in motes, contact hash, `interfaces` mask of what it supports)
+type Listing = (U512, [u8; 32], u64);
+
+/// `guardian`'s directory listing; an unlisted guardian's record stays behind as `None`
+fn listing(guardian: AccountHash) -> Option<Listing> {
+    read::<Option<Listing>>(&format!("{}{:?}", prefixes::DIRECTORY_LISTING, guardian)).flatten()
+}
+
+/// `guardian`'s reputation statistics, indexed by `reputation::*`
+fn reputation(guardian: AccountHash) -> Vec<u32> {
+    let mut stats: Vec<u32> = read(&format!("{}{:?}", prefixes::REPUTATION, guardian)).unwrap_or_default();
+    stats.resize(reputation::COUNT, 0);
+    stats
+}
+
+/// Count one more under `stat` for `guardian` if it is listed in the directory; other guardians'
+/// activity isn't tracked. Returns whether it counted.
+fn record_stat(guardian: AccountHash, stat: u8) -> bool {
+    if listing(guardian).is_none() { return false; }
+    let mut stats = reputation(guardian);
+    stats[stat as usize] = stats[stat as usize].saturating_add(1);
+    write(&format!("{}{:?}", prefixes::REPUTATION, guardian), stats);
+    true
+}
+
+/// Take back one `record_stat(guardian, stat)` that counted, listed or not by now
+fn retract_stat(guardian: AccountHash, stat: u8) {
+    let mut stats = reputation(guardian);
+    stats[stat as usize] = stats[stat as usize].saturating_sub(1);
+    write(&format!("{}{:?}", prefixes::REPUTATION, guardian), stats);
+}
+
+/// The caller lists itself in the guardian directory as available to guard accounts, or replaces
+/// its listing: the `fee` (motes) it expects per account, a hash of its off-chain `contact` details,
+/// and the `interfaces` it supports as the `policies` mask. A listing is an advertisement only; it
+/// gives no guardianship, which still takes the owner adding or inviting the guardian.
+#[no_mangle]
+pub extern "C" fn list_guardian() {
+    check_args("list_guardian");
+    let fee: U512 = runtime::get_named_arg("fee");
+    let contact: [u8; 32] = runtime::get_named_arg("contact");
+    let policies: u64 = runtime::get_named_arg("policies");
+    let guardian = runtime::get_caller();
+
+    if listing(guardian).is_none() {
+        taking_new();
+        let mut listed: Vec<AccountHash> = read(prefixes::DIRECTORY).unwrap_or_default();
+        listed.push(guardian);
+        write(prefixes::DIRECTORY, listed);
+    }
+    write(&format!("{}{:?}", prefixes::DIRECTORY_LISTING, guardian), Some::<Listing>((fee, contact, policies)));
+    emit(events::GuardianListedV2 { tenant: tenant(), guardian, fee, contact, policies });
+}
+
+/// The caller takes itself out of the guardian directory. Accounts it already guards keep it.
+#[no_mangle]
+pub extern "C" fn unlist_guardian() {
+    check_args("unlist_guardian");
+    let guardian = runtime::get_caller();
+    if listing(guardian).is_none() { runtime::revert(ApiError::User(Err::NotListed as u16)); }
+
+    let mut listed: Vec<AccountHash> = read(prefixes::DIRECTORY).unwrap_or_default();
+    listed.retain(|g| *g != guardian);
+    write(prefixes::DIRECTORY, listed);
+    write(&format!("{}{:?}", prefixes::DIRECTORY_LISTING, guardian), None::<Listing>);
+    emit(events::GuardianUnlistedV2 { tenant: tenant(), guardian });
+}
+
+/// Returns `guardian`'s directory listing, if it is listed
+#[no_mangle]
+pub extern "C" fn get_directory_listing() {
+    check_args("get_directory_listing");
+    let guardian: AccountHash = runtime::get_named_arg("guardian");
+    runtime::ret(CLValue::from_t(listing(guardian)).unwrap_or_revert());
+}
+
+/// Returns (how many accounts `guardian` guards now, its reputation statistics indexed by
+/// `guardian_types::reputation`, its `reputation::score`). Statistics only cover time spent listed in
+/// the directory and stay after unlisting.
+#[no_mangle]
+pub extern "C" fn get_reputation() {
+    check_args("get_reputation");
+    let guardian: AccountHash = runtime::get_named_arg("guardian");
+    let accounts: Vec<AccountHash> = read(&format!("{}{:?}", prefixes::PROTECTED_ACCOUNTS, guardian)).unwrap_or_default();
+    let stats = reputation(guardian);
+    let score = reputation::score(&stats);
+    runtime::ret(CLValue::from_t((accounts.len() as u32, stats, score)).unwrap_or_revert());
+}
+
+/// Returns (guardian, listing) for listed guardians asking at most `max_fee` (any fee if none) and
+/// supporting every interface in the `policies` mask, in listing order: `limit` of them, at most
+/// `MAX_DIRECTORY_PAGE`, after skipping the first `offset` that match.
+#[no_mangle]
+pub extern "C" fn find_guardians() {
+    check_args("find_guardians");
+    let max_fee: Option<U512> = runtime::get_named_arg("max_fee");
+    let policies: u64 = runtime::get_named_arg("policies");
+    let offset: u32 = runtime::get_named_arg("offset");
+    let limit: u32 = runtime::get_named_arg("limit");
+
+    let listed: Vec<AccountHash> = read(prefixes::DIRECTORY).unwrap_or_default();
+    let found: Vec<(AccountHash, Listing)> = listed.into_iter()
+        .filter_map(|g| listing(g).map(|l| (g, l)))
+        .filter(|(_, (fee, _, supported))| max_fee.is_none_or(|max| *fee <= max) && supported & policies == policies)
+        .skip(offset as usize)
+        .take(limit.min(MAX_DIRECTORY_PAGE) as usize)
+        .collect();
+    runtime::ret(CLValue::from_t(found).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn init_storage() {
+    check_args("init_storage");
+    storage::new_dictionary(DICT).unwrap_or_revert();
+
+    // CES: event log, its length, the standard version and every event's schema for indexers
+    #[cfg(feature = "events")]
+    {
+        storage::new_dictionary("__events").unwrap_or_revert();
+        runtime::put_key("__events_length", storage::new_uref(0u32).into());
+        runtime::put_key("__events_ces_version", storage::new_uref(events::CES_VERSION.to_string()).into());
+        runtime::put_key("__events_schema", storage::new_uref(events::schemas()).into());
+    }
+    write_unscoped(prefixes::SCHEMA_VERSION, migrations::CURRENT);
+}
+
+/// Get all active recovery IDs for a guardian
+#[no_mangle]
+pub extern "C" fn get_recoveries_for_guardian() {
+    check_args("get_recoveries_for_guardian");
+    let guardian: AccountHash = runtime::get_named_arg("guardian");
+    let recoveries: Vec<U256> = read(&format!("{}{:?}", prefixes::GUARDIAN_RECOVERIES, guardian)).unwrap_or(vec![]);
+    runtime::ret(CLValue::from_t(recoveries).unwrap_or_revert());
+}
+
+/// Returns (account, recovery id, expiry) of every open recovery `public_key` guards and hasn't
+/// voted on, for a wallet's "action required" inbox. Expiry is a block time in ms, or a block
+/// height for height-timed recoveries.
+#[no_mangle]
+pub extern "C" fn get_pending_approvals_for_guardian() {
+    check_args("get_pending_approvals_for_guardian");
+    let key: PublicKey = runtime::get_named_arg("public_key");
+    let guardian = key.to_account_hash();
+    let ids: Vec<U256> = read(&format!("{}{:?}", prefixes::GUARDIAN_RECOVERIES, guardian)).unwrap_or(vec![]);
+    let inbox: Vec<(AccountHash, U256, u64)> = ids
+        .into_iter()
+        .filter(|&id| !closed(id) && !voted(id, Key::Account(guardian)))
+        .filter_map(|id| {
+            let acc: AccountHash = read(&format!("{}{}", prefixes::RECOVERY_ACCOUNT, id))?;
+            let guards: Vec<AccountHash> = read(&format!("{}{:?}", prefixes::GUARDIANS, acc)).unwrap_or(vec![]);
+            guards.contains(&guardian).then(|| (acc, id, read(&format!("{}{}", prefixes::EXPIRES_AT, id)).unwrap_or(0)))
+        })
+        .collect();
+    runtime::ret(CLValue::from_t(inbox).unwrap_or_revert());
+}
+
+/// Get all accounts that a guardian protects
+#[no_mangle]
+pub extern "C" fn get_protected_accounts() {
+    check_args("get_protected_accounts");
+    let guardian: AccountHash = runtime::get_named_arg("guardian");
+    let accounts: Vec<AccountHash> = read(&format!("{}{:?}", prefixes::PROTECTED_ACCOUNTS, guardian)).unwrap_or(vec![]);
+    runtime::ret(CLValue::from_t(accounts).unwrap_or_revert());
+}
+
+/// Installer's named key holding the registry's package hash
+const PACKAGE_KEY: &str = "recovery_registry_package_hash";
+/// Installer's named key holding the access key that adds versions to the package
+const ACCESS_KEY: &str = "recovery_registry_access";
+/// Installer's named key holding the current contract version's hash
+const CONTRACT_KEY: &str = "recovery_registry_contract_hash";
+
+/// Installs the registry as an upgradable package, or with `upgrade` adds this build to the package
+/// the caller installed before: the new version keeps the previous one's named keys, the previous
+/// one is disabled, and `migrate` runs on the new one.
+#[no_mangle]
+pub extern "C" fn call() {
+    let eps = entry_points::registry_entry_points();
+    let mut keys = NamedKeys::new();
+    // Optional: reject arguments an entry point doesn't take, see `check_args`
+    if runtime::try_get_named_arg::<bool>("strict_args").unwrap_or(false) {
+        keys.insert(STRICT_ARGS.to_string(), storage::new_uref(true).into());
+    }
+
+    if runtime::try_get_named_arg::<bool>("upgrade").unwrap_or(false) {
+        let package = match runtime::get_key(PACKAGE_KEY) {
+            Some(Key::Hash(package)) => ContractPackageHash::new(package),
+            _ => runtime::revert(ApiError::MissingKey),
+        };
+        let previous = match runtime::get_key(CONTRACT_KEY) {
+            Some(Key::Hash(hash)) => ContractHash::new(hash),
+            _ => runtime::revert(ApiError::MissingKey),
+        };
+        #[cfg(feature = "casper-2")]
+        let (hash, _) = storage::add_contract_version(package, eps.into(), keys, alloc::collections::BTreeMap::new());
+        #[cfg(not(feature = "casper-2"))]
+        let (hash, _) = storage::add_contract_version(package, eps, keys);
+        storage::disable_contract_version(package, previous).unwrap_or_revert();
+        runtime::put_key(CONTRACT_KEY, Key::Hash(hash.value()));
+        runtime::call_contract::<()>(hash, "migrate", RuntimeArgs::new());
+        return;
+    }
+
+    // The installer administers the registry: it may decommission it, set and withdraw tenant 0's
+    // fees, create tenants and name their admins, and upgrade the package through the access key
+    keys.insert("admin".to_string(), Key::Account(runtime::get_caller()));
+    #[cfg(feature = "casper-2")]
+    let (hash, _) = storage::new_contract(
+        eps.into(),
+        Some(keys),
+        Some(PACKAGE_KEY.to_string()),
+        Some(ACCESS_KEY.to_string()),
+        None,
+    );
+    #[cfg(not(feature = "casper-2"))]
+    let (hash, _) = storage::new_contract(
+        eps,
+        Some(keys),
+        Some(PACKAGE_KEY.to_string()),
+        Some(ACCESS_KEY.to_string()),
     );
-    runtime::put_key("recovery_registry_contract_hash", Key::Hash(hash.value()));
+    runtime::put_key(CONTRACT_KEY, Key::Hash(hash.value()));
 }
@@ -0,0 +1,127 @@
+//! Signed approvals: a guardian signs `approval_payload` (from guardian_types) off-chain and anyone relays it to
+//! `approve_signed`. Casper keys are Ed25519 or secp256k1, so verification dispatches on the
+//! guardian key's variant. Secp256k1 signatures are 64-byte compact ECDSA over SHA-256 of the
+//! payload, the same scheme Casper uses for deploy approvals.
+
+use alloc::vec::Vec;
+use casper_types::PublicKey;
+use ed25519_dalek::{Signature as EdSignature, VerifyingKey as EdKey};
+use k256::ecdsa::{signature::Verifier, Signature as EcSignature, VerifyingKey as EcKey};
+
+pub use guardian_types::payloads::{approval_payload, meta_approval_payload, rotation_payload};
+
+/// True if `sig` is `key`'s signature over `msg`
+pub fn verify(key: &PublicKey, msg: &[u8], sig: &[u8]) -> bool {
+    let raw: Vec<u8> = key.into();
+    match key {
+        PublicKey::Ed25519(_) => {
+            let Ok(raw) = <[u8; 32]>::try_from(raw.as_slice()) else { return false };
+            let (Ok(pk), Ok(sig)) = (EdKey::from_bytes(&raw), EdSignature::from_slice(sig)) else { return false };
+            pk.verify_strict(msg, &sig).is_ok()
+        }
+        PublicKey::Secp256k1(_) => {
+            let (Ok(pk), Ok(sig)) = (EcKey::from_sec1_bytes(&raw), EcSignature::from_slice(sig)) else { return false };
+            pk.verify(msg, &sig).is_ok()
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Fixed vectors; run from the host through `registry-tests`, which builds this module.
+
+    use alloc::vec::Vec;
+    use casper_types::PublicKey;
+    use ed25519_dalek::{Signature as EdSignature, Verifier as _, VerifyingKey as EdKey};
+
+    use super::verify;
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).expect("hex")).collect()
+    }
+
+    fn ed25519(key: &str) -> PublicKey {
+        PublicKey::ed25519_from_bytes(hex(key)).expect("Ed25519 key")
+    }
+
+    fn secp256k1(key: &str) -> PublicKey {
+        PublicKey::secp256k1_from_bytes(hex(key)).expect("secp256k1 key")
+    }
+
+    // RFC 8032 section 7.1, tests 1 and 2
+    const ED_KEY_1: &str = "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a";
+    const ED_SIG_1: &str = "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b";
+    const ED_KEY_2: &str = "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c";
+    const ED_SIG_2: &str = "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00";
+
+    // Compact low-S ECDSA over SHA-256 of `SECP_MSG`
+    const SECP_KEY: &str = "02961a58f7ce58ec61de395e8d2dd056acb29a21992c84814e381a2580891de975";
+    const SECP_SIG: &str = "887044b5484c6bb2b96b413f3acad59c5d6ae686379a54892e6b4580ff1b3c74121d649cc3e65a927458370269b32d3be9cfb325732c6d6c3781a8c4959a85c3";
+    const SECP_MSG: &[u8] = b"guardian approval";
+    const SECP_OTHER_KEY: &str = "0200e3ae1974566ca06cc516d47e0fb165a674a3dabcfca15e722f0e3450f45889";
+
+    #[test]
+    fn ed25519_known_good() {
+        assert!(verify(&ed25519(ED_KEY_1), b"", &hex(ED_SIG_1)));
+        assert!(verify(&ed25519(ED_KEY_2), &[0x72], &hex(ED_SIG_2)));
+    }
+
+    #[test]
+    fn ed25519_tampered_message() {
+        assert!(!verify(&ed25519(ED_KEY_1), b"\x00", &hex(ED_SIG_1)));
+        assert!(!verify(&ed25519(ED_KEY_2), &[0x73], &hex(ED_SIG_2)));
+        let mut flipped = hex(ED_SIG_2);
+        flipped[0] ^= 1;
+        assert!(!verify(&ed25519(ED_KEY_2), &[0x72], &flipped));
+    }
+
+    #[test]
+    fn ed25519_wrong_key() {
+        assert!(!verify(&ed25519(ED_KEY_2), b"", &hex(ED_SIG_1)));
+        assert!(!verify(&ed25519(ED_KEY_1), &[0x72], &hex(ED_SIG_2)));
+    }
+
+    #[test]
+    fn ed25519_non_canonical_rejected() {
+        // Test 1's signature with L added to S: the same signature modulo L, not in canonical form
+        let s_plus_l = "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901554c8c7872aa064e049dbb3013fbf29380d25bf5f0595bbe24655141438e7a101b";
+        assert!(!verify(&ed25519(ED_KEY_1), b"", &hex(s_plus_l)));
+
+        // The identity point as key and as R with S = 0 satisfies the cofactorless equation for any
+        // message, so plain `verify` takes it; `verify_strict` refuses the small-order key
+        let identity = "0100000000000000000000000000000000000000000000000000000000000000";
+        let forged = hex(&[identity, "0000000000000000000000000000000000000000000000000000000000000000"].concat());
+        let lax = EdKey::from_bytes(&hex(identity).try_into().unwrap()).unwrap();
+        assert!(lax.verify(b"anything", &EdSignature::from_slice(&forged).unwrap()).is_ok());
+        assert!(!verify(&ed25519(identity), b"anything", &forged));
+    }
+
+    #[test]
+    fn secp256k1_known_good() {
+        assert!(verify(&secp256k1(SECP_KEY), SECP_MSG, &hex(SECP_SIG)));
+    }
+
+    #[test]
+    fn secp256k1_tampered_message() {
+        assert!(!verify(&secp256k1(SECP_KEY), b"guardian approvaL", &hex(SECP_SIG)));
+        assert!(!verify(&secp256k1(SECP_KEY), b"", &hex(SECP_SIG)));
+    }
+
+    #[test]
+    fn secp256k1_wrong_key() {
+        assert!(!verify(&secp256k1(SECP_OTHER_KEY), SECP_MSG, &hex(SECP_SIG)));
+    }
+
+    #[test]
+    fn secp256k1_high_s_and_bad_lengths_rejected() {
+        // SECP_SIG with S replaced by n - S: valid ECDSA, but not the low-S form Casper signs
+        let high_s = "887044b5484c6bb2b96b413f3acad59c5d6ae686379a54892e6b4580ff1b3c74ede29b633c19a56d8ba7c8fd964cd2c2d0df29c13c1c32cf8850b5c83a9bbb7e";
+        assert!(!verify(&secp256k1(SECP_KEY), SECP_MSG, &hex(high_s)));
+        assert!(!verify(&secp256k1(SECP_KEY), SECP_MSG, &hex(SECP_SIG)[..63]));
+        assert!(!verify(&ed25519(ED_KEY_1), b"", &hex(ED_SIG_1)[..63]));
+        // An Ed25519 signature offered for a secp256k1 key and the other way round
+        assert!(!verify(&secp256k1(SECP_KEY), b"", &hex(ED_SIG_1)));
+        assert!(!verify(&ed25519(ED_KEY_1), SECP_MSG, &hex(SECP_SIG)));
+    }
+}
@@ -0,0 +1,103 @@
+//! Proof-of-guardianship verifiers.
+//!
+//! `approve` asks the account's selected verifier which guardian the caller is voting as. A new
+//! proof system is a new `Verifier` impl plus an ID here; the lifecycle entry points don't change.
+//! Every verifier falls back to `CallerMatch`, so listed guardians and their delegates keep
+//! working whichever mode is selected.
+
+use alloc::{format, vec::Vec};
+use casper_contract::contract_api::runtime;
+use casper_types::{account::AccountHash, ApiError};
+use guardian_types::prefixes;
+
+use crate::{delegator, read, Err};
+
+/// Caller's account hash is in the guardian list (or is a guardian's active delegate)
+pub const CALLER: u8 = 0;
+/// Caller proves membership of a committed Merkle root with the `proof` argument
+pub const MERKLE: u8 = 1;
+/// Caller's `guardian_digest` is in the privacy-mode digest set
+pub const HASHED: u8 = 2;
+
+pub trait Verifier {
+    /// Guardian identity `caller` votes as for `acc`, or `None` if membership isn't proven
+    fn voter(&self, acc: AccountHash, caller: AccountHash) -> Option<AccountHash>;
+}
+
+struct CallerMatch;
+struct MerkleProof;
+struct HashedSet;
+
+impl Verifier for CallerMatch {
+    fn voter(&self, acc: AccountHash, caller: AccountHash) -> Option<AccountHash> {
+        let guards: Vec<AccountHash> = read(&format!("{}{:?}", prefixes::GUARDIANS, acc)).unwrap_or_default();
+        if guards.contains(&caller) { return Some(caller); }
+        // A delegate's approval counts as the delegating guardian's
+        delegator(acc, caller).filter(|g| guards.contains(g))
+    }
+}
+
+impl Verifier for MerkleProof {
+    fn voter(&self, acc: AccountHash, caller: AccountHash) -> Option<AccountHash> {
+        let committed = read::<Option<([u8; 32], u32)>>(&format!("{}{:?}", prefixes::MERKLE_ROOT, acc)).flatten();
+        let proof = runtime::try_get_named_arg::<Vec<[u8; 32]>>("proof");
+        match (committed, proof) {
+            (Some((root, _)), Some(proof)) => {
+                if !merkle_member(root, caller, &proof) { runtime::revert(ApiError::User(Err::BadProof as u16)); }
+                Some(caller)
+            }
+            _ => CallerMatch.voter(acc, caller),
+        }
+    }
+}
+
+impl Verifier for HashedSet {
+    fn voter(&self, acc: AccountHash, caller: AccountHash) -> Option<AccountHash> {
+        if hidden(acc, caller) { return Some(caller); }
+        CallerMatch.voter(acc, caller)
+    }
+}
+
+pub fn kind(acc: AccountHash) -> u8 {
+    read(&format!("{}{:?}", prefixes::VERIFIER, acc)).unwrap_or(CALLER)
+}
+
+pub fn for_account(acc: AccountHash) -> &'static dyn Verifier {
+    match kind(acc) {
+        MERKLE => &MerkleProof,
+        HASHED => &HashedSet,
+        _ => &CallerMatch,
+    }
+}
+
+/// True if `kind` is known and `acc` has the guardian set it verifies against
+pub fn available(acc: AccountHash, kind: u8) -> bool {
+    match kind {
+        CALLER => true,
+        MERKLE => read::<Option<([u8; 32], u32)>>(&format!("{}{:?}", prefixes::MERKLE_ROOT, acc)).flatten().is_some(),
+        HASHED => read::<Vec<[u8; 32]>>(&format!("{}{:?}", prefixes::HASHED_GUARDIANS, acc)).is_some(),
+        _ => false,
+    }
+}
+
+/// Merkle membership of `who` under `root`. Leaves are blake2b(0x00 ‖ account hash) and inner nodes
+/// blake2b(0x01 ‖ lesser child ‖ greater child), so proofs are plain sibling lists without position bits.
+fn merkle_member(root: [u8; 32], who: AccountHash, proof: &[[u8; 32]]) -> bool {
+    if proof.len() > 32 { return false; }
+    let mut node = runtime::blake2b([&[0u8][..], who.as_bytes()].concat());
+    for sib in proof {
+        let (a, b) = if node <= *sib { (node, *sib) } else { (*sib, node) };
+        node = runtime::blake2b([&[1u8][..], &a[..], &b[..]].concat());
+    }
+    node == root
+}
+
+/// Privacy-mode guardian entry: blake2b(account ‖ guardian account hash), salted per account so
+/// entries can't be matched across accounts
+fn guardian_digest(acc: AccountHash, who: AccountHash) -> [u8; 32] {
+    runtime::blake2b([acc.as_bytes(), who.as_bytes()].concat())
+}
+
+pub fn hidden(acc: AccountHash, who: AccountHash) -> bool {
+    read::<Vec<[u8; 32]>>(&format!("{}{:?}", prefixes::HASHED_GUARDIANS, acc)).is_some_and(|h| h.contains(&guardian_digest(acc, who)))
+}
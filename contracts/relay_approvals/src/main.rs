@@ -0,0 +1,91 @@
+//! Relayed Meta-Approvals
+//!
+//! Lets a relayer pay for guardians' approvals, so guardians needn't hold CSPR. Each guardian signs
+//! `meta_approval_payload` (guardian_types) with their current meta nonce off-chain; the relayer
+//! sends this session from its own account with any number of those signatures:
+//! 1. For each (recovery id, guardian key, (nonce, signature)) in `approvals`, reads the guardian's
+//!    meta nonce from the registry and skips the item if it has moved on, e.g. because another
+//!    relayer submitted the signature first, so a front-run doesn't fail the whole deploy
+//! 2. Submits every other item to the registry's `approve_meta`, which checks the signature, the
+//!    account's relayer allowlist and the guardian's vote
+//! 3. Stores how many it submitted under `last_relayed`
+//!
+//! Items for the same guardian have to be listed in nonce order.
+
+#![no_std]
+#![no_main]
+
+#[cfg(not(target_arch = "wasm32"))]
+compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
+
+#[cfg(not(feature = "casper-2"))]
+extern crate casper_contract_1 as casper_contract;
+#[cfg(not(feature = "casper-2"))]
+extern crate casper_types_1 as casper_types;
+
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+use casper_contract::contract_api::{runtime, storage};
+use casper_types::contracts::ContractHash;
+use casper_types::{runtime_args, ApiError, Key, PublicKey, U256};
+
+// Runtime argument names
+const ARG_REGISTRY: &str = "registry";
+const ARG_APPROVALS: &str = "approvals";
+const ARG_MEMO: &str = "memo";
+
+const LAST_RELAYED: &str = "last_relayed";
+
+// Custom errors
+#[repr(u16)]
+enum RelayError {
+    InvalidRegistry = 1,
+    NoApprovals = 2,
+}
+
+impl From<RelayError> for ApiError {
+    fn from(error: RelayError) -> Self {
+        ApiError::User(error as u16)
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let registry: Key = runtime::get_named_arg(ARG_REGISTRY);
+    let approvals: Vec<(U256, PublicKey, (u64, Vec<u8>))> = runtime::get_named_arg(ARG_APPROVALS);
+    let memo: String = runtime::try_get_named_arg(ARG_MEMO).unwrap_or_default();
+
+    let registry_hash = match registry {
+        Key::Hash(hash) => ContractHash::new(hash),
+        _ => runtime::revert(RelayError::InvalidRegistry),
+    };
+    if approvals.is_empty() {
+        runtime::revert(RelayError::NoApprovals);
+    }
+
+    let mut relayed: u32 = 0;
+    for (id, guardian, (nonce, signature)) in approvals {
+        let current: u64 = runtime::call_contract(
+            registry_hash,
+            "get_meta_nonce",
+            runtime_args! { "guardian" => guardian.to_account_hash() },
+        );
+        if current != nonce {
+            continue;
+        }
+        runtime::call_contract::<()>(
+            registry_hash,
+            "approve_meta",
+            runtime_args! {
+                "id" => id,
+                "guardian" => guardian,
+                "nonce" => nonce,
+                "signature" => signature,
+                "memo" => memo.clone(),
+            },
+        );
+        relayed += 1;
+    }
+    runtime::put_key(LAST_RELAYED, storage::new_uref(relayed).into());
+}
@@ -1,4 +1,7 @@
 #![no_std]
 
+#[cfg(not(feature = "casper-2"))]
+extern crate casper_types_1 as casper_types;
+
 pub mod constants;
 pub mod errors;
@@ -0,0 +1,194 @@
+//! Storage Layout Benchmark
+//!
+//! Keeps the same per-account record, an account's guardians and threshold, under either of two
+//! layouts so `guardian-cli bench` can compare what each costs as the number of accounts grows:
+//! - `named`: a named key per account on the contract, pointing at a URef that holds the record
+//! - `dictionary`: an item per account in the `d` dictionary, the way the registry keeps its records
+//!
+//! Records are made up from the account's index, so populating takes nothing but a range.
+
+#![no_std]
+#![no_main]
+
+#[cfg(not(target_arch = "wasm32"))]
+compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
+
+#[cfg(not(feature = "casper-2"))]
+extern crate casper_contract_1 as casper_contract;
+#[cfg(not(feature = "casper-2"))]
+extern crate casper_types_1 as casper_types;
+
+extern crate alloc;
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    account::AccountHash,
+    contracts::{EntryPoint, EntryPoints, NamedKeys},
+    ApiError, CLType, EntryPointAccess, Key, Parameter, URef,
+};
+use guardian_types::host::CALLED;
+
+const DICT: &str = "d";
+const ARG_LAYOUT: &str = "layout";
+const ARG_ACCOUNT: &str = "account";
+/// Guardians in each made-up record
+const GUARDIANS: u8 = 3;
+
+// Custom errors
+#[repr(u16)]
+enum BenchError {
+    MissingDict = 1,
+    BadLayout = 2,
+    NotFound = 3,
+}
+
+impl From<BenchError> for ApiError {
+    fn from(error: BenchError) -> Self {
+        ApiError::User(error as u16)
+    }
+}
+
+/// An account's guardians and threshold
+type Record = (Vec<AccountHash>, u8);
+
+enum Layout {
+    Named,
+    Dictionary,
+}
+
+fn layout() -> Layout {
+    match runtime::get_named_arg::<String>(ARG_LAYOUT).as_str() {
+        "named" => Layout::Named,
+        "dictionary" => Layout::Dictionary,
+        _ => runtime::revert(BenchError::BadLayout),
+    }
+}
+
+/// Record of account `account`; `version` tells a rewritten record from the one first stored
+fn record(account: u32, version: u8) -> Record {
+    let guardians = (0..GUARDIANS)
+        .map(|g| {
+            let mut bytes = [version; 32];
+            bytes[..4].copy_from_slice(&account.to_le_bytes());
+            bytes[4] = g;
+            AccountHash::new(bytes)
+        })
+        .collect();
+    (guardians, 2)
+}
+
+/// Named key or dictionary item key of account `account`
+fn key(account: u32) -> String {
+    format!("a{}", account)
+}
+
+fn get_dict() -> URef {
+    runtime::get_key(DICT)
+        .unwrap_or_revert_with(BenchError::MissingDict)
+        .into_uref()
+        .unwrap_or_revert_with(BenchError::MissingDict)
+}
+
+fn put(layout: &Layout, account: u32, r: Record) {
+    match layout {
+        Layout::Named => match runtime::get_key(&key(account)).and_then(|k| k.into_uref()) {
+            Some(uref) => storage::write(uref, r),
+            None => runtime::put_key(&key(account), storage::new_uref(r).into()),
+        },
+        Layout::Dictionary => storage::dictionary_put(get_dict(), &key(account), r),
+    }
+}
+
+fn get(layout: &Layout, account: u32) -> Record {
+    let found = match layout {
+        Layout::Named => runtime::get_key(&key(account))
+            .and_then(|k| k.into_uref())
+            .and_then(|uref| storage::read(uref).unwrap_or_revert()),
+        Layout::Dictionary => storage::dictionary_get(get_dict(), &key(account)).unwrap_or_revert(),
+    };
+    found.unwrap_or_revert_with(BenchError::NotFound)
+}
+
+#[no_mangle]
+pub extern "C" fn init_storage() {
+    storage::new_dictionary(DICT).unwrap_or_revert();
+}
+
+/// Store the records of accounts `from` to `from + count - 1` under `layout`
+#[no_mangle]
+pub extern "C" fn populate() {
+    let layout = layout();
+    let from: u32 = runtime::get_named_arg("from");
+    let count: u32 = runtime::get_named_arg("count");
+    for account in from..from.saturating_add(count) {
+        put(&layout, account, record(account, 0));
+    }
+}
+
+/// Read account `account`'s record under `layout`
+#[no_mangle]
+pub extern "C" fn read() {
+    let layout = layout();
+    let account: u32 = runtime::get_named_arg(ARG_ACCOUNT);
+    let (guardians, _) = get(&layout, account);
+    if guardians.len() != GUARDIANS as usize {
+        runtime::revert(BenchError::NotFound);
+    }
+}
+
+/// Replace account `account`'s record under `layout`
+#[no_mangle]
+pub extern "C" fn write() {
+    let layout = layout();
+    let account: u32 = runtime::get_named_arg(ARG_ACCOUNT);
+    put(&layout, account, record(account, 1));
+}
+
+fn entry_points() -> EntryPoints {
+    let mut eps = EntryPoints::new();
+    let layout = || Parameter::new(ARG_LAYOUT, CLType::String);
+
+    eps.add_entry_point(EntryPoint::new("init_storage", vec![], CLType::Unit, EntryPointAccess::Public, CALLED));
+    eps.add_entry_point(EntryPoint::new(
+        "populate",
+        vec![layout(), Parameter::new("from", CLType::U32), Parameter::new("count", CLType::U32)],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+    for name in ["read", "write"] {
+        eps.add_entry_point(EntryPoint::new(
+            name,
+            vec![layout(), Parameter::new(ARG_ACCOUNT, CLType::U32)],
+            CLType::Unit, EntryPointAccess::Public, CALLED,
+        ));
+    }
+    eps
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    #[cfg(feature = "casper-2")]
+    let (hash, _) = storage::new_locked_contract(
+        entry_points().into(),
+        Some(NamedKeys::new()),
+        Some("storage_bench_contract".to_string()),
+        Some("storage_bench_package".to_string()),
+        None,
+    );
+    #[cfg(not(feature = "casper-2"))]
+    let (hash, _) = storage::new_locked_contract(
+        entry_points(),
+        Some(NamedKeys::new()),
+        Some("storage_bench_contract".to_string()),
+        Some("storage_bench_package".to_string()),
+    );
+    runtime::put_key("storage_bench_contract_hash", Key::Hash(hash.value()));
+}
@@ -0,0 +1,191 @@
+//! Misbehaving guardians, relayers and strangers: the registry's invariants under each, asserting
+//! the error every call that must fail reverts with and the state it leaves behind. The engine runs
+//! calls one at a time, so each race is played out in both orders.
+
+use casper_types::{runtime_args, U256, U512};
+use guardian_types::{errors::RegistryError, flags, prefixes, reputation, settings::AccountSettings, time::Span};
+
+use crate::harness::{
+    id_args, meta_approval, quick, replayed_approval, replayed_meta_approval, settings_args, signed_approval, Registry,
+};
+
+/// Two of five guardians, against a threshold of three, vote every way they can
+#[test]
+fn sub_threshold_collusion() {
+    let mut registry = Registry::install();
+    let (owner, guardians) = registry.account(5, 3, quick());
+    let stranger = registry.user();
+    let [g1, g2, ..] = &guardians[..] else { unreachable!() };
+    let r = registry.start(&owner, g1.hash);
+
+    registry.ok(g1.hash, "approve", id_args(r.id));
+    registry.ok(g2.hash, "approve", id_args(r.id));
+    registry.fails(g2.hash, "approve", id_args(r.id), RegistryError::AlreadyApproved);
+    registry.fails(stranger.hash, "approve_signed", signed_approval(&owner, g1, &r), RegistryError::AlreadyApproved);
+    registry.fails(stranger.hash, "approve", id_args(r.id), RegistryError::NotGuardian);
+    registry.fails(g1.hash, "finalize", id_args(r.id), RegistryError::NotApproved);
+
+    assert_eq!(registry.item::<u8>(&format!("{}{}", prefixes::APPROVAL_COUNT, r.id)), Some(2));
+    assert_eq!(registry.item::<bool>(&format!("{}{}", prefixes::THRESHOLD_REACHED, r.id)), Some(false));
+    assert_eq!(registry.item::<bool>(&format!("{}{}", prefixes::FINALIZED, r.id)), None);
+}
+
+/// Guardians approve then reject, reject then approve, and approve again
+#[test]
+fn vote_flapping() {
+    let mut registry = Registry::install();
+    let (owner, guardians) = registry.account(3, 2, quick());
+    let [g1, g2, _] = &guardians[..] else { unreachable!() };
+    let r = registry.start(&owner, g1.hash);
+
+    registry.ok(g1.hash, "approve", id_args(r.id));
+    registry.fails(g1.hash, "reject", id_args(r.id), RegistryError::AlreadyApproved);
+    registry.fails(g1.hash, "approve", id_args(r.id), RegistryError::AlreadyApproved);
+    registry.ok(g2.hash, "reject", id_args(r.id));
+    registry.fails(g2.hash, "approve", id_args(r.id), RegistryError::AlreadyApproved);
+    registry.fails(g2.hash, "approve_signed", signed_approval(&owner, g2, &r), RegistryError::AlreadyApproved);
+    registry.fails(g2.hash, "reject", id_args(r.id), RegistryError::AlreadyApproved);
+
+    assert_eq!(registry.item::<u8>(&format!("{}{}", prefixes::APPROVAL_COUNT, r.id)), Some(1));
+    assert_eq!(registry.item::<bool>(&format!("{}{}", prefixes::THRESHOLD_REACHED, r.id)), Some(false));
+    registry.fails(g1.hash, "finalize", id_args(r.id), RegistryError::NotApproved);
+}
+
+/// A relayer resubmits guardians' signed approvals: on the same recovery, with a spent meta nonce,
+/// and on the account's next recovery
+#[test]
+fn approval_replay() {
+    let mut registry = Registry::install();
+    let (owner, guardians) = registry.account(3, 2, quick());
+    let relayer = registry.user();
+    let [g1, g2, _] = &guardians[..] else { unreachable!() };
+    let first = registry.start(&owner, relayer.hash);
+
+    registry.ok(relayer.hash, "approve_signed", signed_approval(&owner, g1, &first));
+    registry.fails(relayer.hash, "approve_signed", signed_approval(&owner, g1, &first), RegistryError::AlreadyApproved);
+    registry.ok(relayer.hash, "approve_meta", meta_approval(&owner, g2, &first, 0));
+    registry.fails(relayer.hash, "approve_meta", meta_approval(&owner, g2, &first, 0), RegistryError::BadNonce);
+    assert_eq!(registry.item::<u64>(&format!("{}{:?}", prefixes::META_NONCE, g2.hash)), Some(1));
+    registry.ok(owner.hash, "veto", id_args(first.id));
+
+    let second = registry.start(&owner, relayer.hash);
+    // The first recovery's signatures, pointed at the second
+    let signed = replayed_approval(&owner, g1, &first, second.id);
+    registry.fails(relayer.hash, "approve_signed", signed, RegistryError::BadSignature);
+    let meta = replayed_meta_approval(&owner, g2, &first, 0, second.id);
+    registry.fails(relayer.hash, "approve_meta", meta, RegistryError::BadNonce);
+    let meta = replayed_meta_approval(&owner, g2, &first, 1, second.id);
+    registry.fails(relayer.hash, "approve_meta", meta, RegistryError::BadSignature);
+    registry.fails(relayer.hash, "approve_meta", meta_approval(&owner, g2, &second, 2), RegistryError::BadNonce);
+
+    assert_eq!(registry.item::<u8>(&format!("{}{}", prefixes::APPROVAL_COUNT, second.id)), Some(0));
+    registry.ok(relayer.hash, "approve_meta", meta_approval(&owner, g2, &second, 1));
+    assert_eq!(registry.item::<u8>(&format!("{}{}", prefixes::APPROVAL_COUNT, second.id)), Some(1));
+}
+
+/// A stranger starts recovery after recovery of an account with an attempt limit of two
+#[test]
+fn initiation_spam() {
+    let mut registry = Registry::install();
+    let settings = AccountSettings { max_attempts: 2, ..quick() };
+    let (owner, guardians) = registry.account(3, 2, settings);
+    let spammer = registry.user();
+
+    let first = registry.start(&owner, spammer.hash);
+    let (_, stacked) = registry.proposal(&owner);
+    registry.fails(spammer.hash, "start_recovery", stacked, RegistryError::RecoveryExists);
+    registry.fails(spammer.hash, "veto", id_args(first.id), RegistryError::NotOwner);
+    registry.ok(owner.hash, "veto", id_args(first.id));
+
+    let second = registry.start(&owner, spammer.hash);
+    registry.ok(owner.hash, "veto", id_args(second.id));
+    let (_, third) = registry.proposal(&owner);
+    registry.fails(spammer.hash, "start_recovery", third.clone(), RegistryError::TooManyAttempts);
+    registry.fails(guardians[0].hash, "start_recovery", third, RegistryError::TooManyAttempts);
+    assert_eq!(registry.item::<u8>(&format!("{}{:?}", prefixes::ATTEMPTS, owner.hash)), Some(2));
+    assert_eq!(registry.item::<u64>(&format!("{}{:?}", prefixes::RECOVERY_NONCE, owner.hash)), Some(2));
+
+    // Saving settings again is the owner's reset of the count
+    registry.ok(owner.hash, "update_settings", settings_args(&owner, &settings));
+    registry.start(&owner, guardians[0].hash);
+}
+
+/// The owner vetoes a recovery in its veto window; then a veto and a finalize race once the
+/// window is over, in either order
+#[test]
+fn veto_race() {
+    let window = Span::seconds(45);
+    for veto_first in [true, false] {
+        let mut registry = Registry::install();
+        let (owner, guardians) = registry.account(3, 2, AccountSettings { veto_window: window.0, ..quick() });
+        let [g1, g2, g3] = &guardians[..] else { unreachable!() };
+
+        let vetoed = registry.start(&owner, g1.hash);
+        registry.ok(g1.hash, "approve", id_args(vetoed.id));
+        registry.ok(g2.hash, "approve", id_args(vetoed.id));
+        registry.fails(g1.hash, "finalize", id_args(vetoed.id), RegistryError::TooEarly);
+        registry.ok(owner.hash, "veto", id_args(vetoed.id));
+        registry.fails(g1.hash, "finalize", id_args(vetoed.id), RegistryError::Vetoed);
+        registry.fails(g3.hash, "approve", id_args(vetoed.id), RegistryError::Vetoed);
+        registry.fails(owner.hash, "veto", id_args(vetoed.id), RegistryError::NotFound);
+
+        let raced = registry.start(&owner, g1.hash);
+        registry.ok(g1.hash, "approve", id_args(raced.id));
+        registry.ok(g2.hash, "approve", id_args(raced.id));
+        registry.advance(window);
+        if veto_first {
+            registry.ok(owner.hash, "veto", id_args(raced.id));
+            registry.fails(g3.hash, "finalize", id_args(raced.id), RegistryError::Vetoed);
+            assert_eq!(registry.item::<bool>(&format!("{}{}", prefixes::FINALIZED, raced.id)), None);
+        } else {
+            registry.ok(g3.hash, "finalize", id_args(raced.id));
+            registry.fails(owner.hash, "veto", id_args(raced.id), RegistryError::NotFound);
+            assert_eq!(registry.item::<bool>(&format!("{}{}", prefixes::CANCELLED, raced.id)), None);
+            registry.fails(g1.hash, "finalize", id_args(raced.id), RegistryError::Finalized);
+        }
+    }
+}
+
+/// A guardian of one account approves nothing of another's, and an id nobody started is unknown
+#[test]
+fn strangers_to_the_recovery() {
+    let mut registry = Registry::install();
+    let (owner, guardians) = registry.account(3, 2, quick());
+    let (_, others) = registry.account(3, 2, quick());
+    let r = registry.start(&owner, guardians[0].hash);
+
+    registry.fails(others[0].hash, "approve", id_args(r.id), RegistryError::NotGuardian);
+    registry.fails(others[0].hash, "approve_signed", signed_approval(&owner, &others[0], &r), RegistryError::NotGuardian);
+    registry.fails(guardians[0].hash, "approve", id_args(r.id + U256::one()), RegistryError::NotFound);
+    let nobody = registry.key();
+    let (_, unknown) = registry.proposal(&nobody);
+    registry.fails(guardians[0].hash, "start_recovery", unknown, RegistryError::NotInit);
+}
+
+/// A guardian flags and withdraws over and over to run up a listed co-guardian's flagged approvals
+#[test]
+fn flag_withdraw_loop() {
+    let mut registry = Registry::install();
+    let (owner, guardians) = registry.account(3, 2, quick());
+    let [g1, g2, g3] = &guardians[..] else { unreachable!() };
+    let listing = runtime_args! { "fee" => U512::zero(), "contact" => [0u8; 32], "policies" => 0u64 };
+    registry.ok(g1.hash, "list_guardian", listing);
+    let r = registry.start(&owner, g1.hash);
+    registry.ok(g1.hash, "approve", id_args(r.id));
+    let flagged = |registry: &Registry| {
+        let stats = registry.item::<Vec<u32>>(&format!("{}{:?}", prefixes::REPUTATION, g1.hash)).unwrap_or_default();
+        stats.get(reputation::FLAGGED as usize).copied().unwrap_or(0)
+    };
+    let flag = runtime_args! { "id" => r.id, "reason" => flags::OWNER_DENIES };
+
+    for _ in 0..3 {
+        registry.ok(g2.hash, "flag_recovery", flag.clone());
+        assert_eq!(flagged(&registry), 1);
+        registry.ok(g2.hash, "withdraw_flag", id_args(r.id));
+        assert_eq!(flagged(&registry), 0);
+    }
+    registry.ok(g2.hash, "flag_recovery", flag.clone());
+    registry.fails(g2.hash, "flag_recovery", flag.clone(), RegistryError::BadFlag);
+    registry.ok(g3.hash, "flag_recovery", flag);
+    assert_eq!(flagged(&registry), 2);
+}
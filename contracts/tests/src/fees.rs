@@ -0,0 +1,115 @@
+//! Protocol fees: setup and finalize pay exactly the scheduled fee from the purse the call brings,
+//! and only the admin withdraws what the fee purse collected.
+
+use casper_types::{account::AccountHash, runtime_args, Key, RuntimeArgs, U512};
+use guardian_types::{errors::RegistryError, prefixes};
+
+use crate::harness::{expect_revert, fee_schedule, id_args, quick, settings_args, Registry, User};
+
+const SETUP_FEE: u64 = 3_000_000_000;
+const FINALIZE_FEE: u64 = 2_000_000_000;
+
+/// `init_guardians` args for `owner` with `guardians` at threshold 2
+fn init_args(owner: &User, guardians: &[User]) -> RuntimeArgs {
+    let list: Vec<AccountHash> = guardians.iter().map(|g| g.hash).collect();
+    runtime_args! { "account" => owner.hash, "guardians" => list, "threshold" => 2u8 }
+}
+
+/// `withdraw_fees` args paying `amount` to `destination`
+fn withdrawal(amount: U512, destination: Key) -> RuntimeArgs {
+    runtime_args! { "amount" => amount, "destination" => destination }
+}
+
+/// A registry charging `SETUP_FEE` and `FINALIZE_FEE`
+fn charging() -> Registry {
+    let mut registry = Registry::install();
+    let admin = registry.admin();
+    registry.ok(admin, "set_fee_schedule", fee_schedule(SETUP_FEE, FINALIZE_FEE));
+    registry
+}
+
+#[test]
+fn only_the_admin_schedules_whole_fee_lists() {
+    let mut registry = Registry::install();
+    let stranger = registry.user();
+    let admin = registry.admin();
+    registry.fails(stranger.hash, "set_fee_schedule", fee_schedule(0, 0), RegistryError::NotAdmin);
+    registry.fails(admin, "set_fee_schedule", runtime_args! { "fees" => vec![U512::one()] }, RegistryError::BadFeeSchedule);
+    registry.ok(admin, "set_fee_schedule", fee_schedule(SETUP_FEE, FINALIZE_FEE));
+}
+
+#[test]
+fn underpaid_setup() {
+    let mut registry = charging();
+    let owner = registry.user();
+    let guardians = [registry.user(), registry.user(), registry.user()];
+
+    registry.fails(owner.hash, "init_guardians", init_args(&owner, &guardians), RegistryError::FeeRequired);
+    let short = registry.funded(owner.hash, "init_guardians", U512::from(SETUP_FEE - 1), init_args(&owner, &guardians));
+    expect_revert("init_guardians", short, RegistryError::FeeRequired);
+    assert_eq!(registry.item::<bool>(&format!("{}{:?}", prefixes::INITIALIZED, owner.hash)), None);
+    assert_eq!(registry.purse_balance("fee_purse"), U512::zero());
+}
+
+#[test]
+fn overpaid_setup_takes_the_fee_only() {
+    let mut registry = charging();
+    let owner = registry.user();
+    let guardians = [registry.user(), registry.user(), registry.user()];
+
+    let paid = registry.funded(owner.hash, "init_guardians", U512::from(SETUP_FEE * 2), init_args(&owner, &guardians));
+    assert!(paid.is_none(), "an overpaid setup should succeed");
+    assert_eq!(registry.item::<bool>(&format!("{}{:?}", prefixes::INITIALIZED, owner.hash)), Some(true));
+    assert_eq!(registry.purse_balance("fee_purse"), U512::from(SETUP_FEE));
+}
+
+#[test]
+fn finalize_pays_its_fee() {
+    let mut registry = charging();
+    let owner = registry.user();
+    let guardians = [registry.user(), registry.user(), registry.user()];
+    assert!(registry.funded(owner.hash, "init_guardians", U512::from(SETUP_FEE), init_args(&owner, &guardians)).is_none());
+    registry.ok(owner.hash, "update_settings", settings_args(&owner, &quick()));
+    let [g1, g2, _] = &guardians;
+
+    let r = registry.start(&owner, g1.hash);
+    registry.ok(g1.hash, "approve", id_args(r.id));
+    registry.ok(g2.hash, "approve", id_args(r.id));
+    registry.fails(g1.hash, "finalize", id_args(r.id), RegistryError::FeeRequired);
+    let short = registry.funded(g1.hash, "finalize", U512::from(FINALIZE_FEE - 1), id_args(r.id));
+    expect_revert("finalize", short, RegistryError::FeeRequired);
+    assert!(registry.funded(g1.hash, "finalize", U512::from(FINALIZE_FEE), id_args(r.id)).is_none(), "a paid finalize should succeed");
+    assert_eq!(registry.item::<bool>(&format!("{}{}", prefixes::FINALIZED, r.id)), Some(true));
+    assert_eq!(registry.purse_balance("fee_purse"), U512::from(SETUP_FEE + FINALIZE_FEE));
+}
+
+#[test]
+fn withdrawals() {
+    let mut registry = charging();
+    let admin = registry.admin();
+    let owner = registry.user();
+    let guardians = [registry.user(), registry.user(), registry.user()];
+    assert!(registry.funded(owner.hash, "init_guardians", U512::from(SETUP_FEE), init_args(&owner, &guardians)).is_none());
+    let collected = U512::from(SETUP_FEE);
+    let treasury = registry.user();
+    let to = Key::Account(treasury.hash);
+
+    registry.fails(owner.hash, "withdraw_fees", withdrawal(collected, to), RegistryError::NotAdmin);
+    registry.fails(treasury.hash, "withdraw_fees", withdrawal(collected, to), RegistryError::NotAdmin);
+    registry.fails(admin, "withdraw_fees", withdrawal(collected, Key::Hash(registry.hash)), RegistryError::BadDestination);
+    registry.fails(admin, "withdraw_fees", withdrawal(collected, Key::Account(AccountHash::default())), RegistryError::BadDestination);
+    registry.fails(admin, "withdraw_fees", withdrawal(collected + U512::one(), to), RegistryError::BadWithdrawal);
+    registry.fails(admin, "withdraw_fees", withdrawal(U512::zero(), to), RegistryError::BadWithdrawal);
+    assert_eq!(registry.purse_balance("fee_purse"), collected);
+
+    // Part of it, then the rest: the purse and the destination move by exactly what was withdrawn
+    let before = registry.balance(treasury.hash);
+    let part = U512::from(SETUP_FEE / 3);
+    registry.ok(admin, "withdraw_fees", withdrawal(part, to));
+    assert_eq!(registry.purse_balance("fee_purse"), collected - part);
+    assert_eq!(registry.balance(treasury.hash), before + part);
+    registry.fails(admin, "withdraw_fees", withdrawal(collected, to), RegistryError::BadWithdrawal);
+    registry.ok(admin, "withdraw_fees", withdrawal(collected - part, to));
+    assert_eq!(registry.purse_balance("fee_purse"), U512::zero());
+    assert_eq!(registry.balance(treasury.hash), before + collected);
+}
@@ -0,0 +1,325 @@
+//! A registry installed on a fresh in-memory chain, the accounts tests call it as, and assertions
+//! on what each call leaves behind.
+
+use casper_engine_test_support::{
+    ExecuteRequest, ExecuteRequestBuilder, LmdbWasmTestBuilder, TransferRequestBuilder, DEFAULT_ACCOUNT_ADDR,
+    LOCAL_GENESIS_REQUEST,
+};
+use casper_execution_engine::{engine_state::Error as EngineError, execution::ExecError};
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{Bytes, FromBytes, ToBytes},
+    runtime_args, AddressableEntityHash, ApiError, CLTyped, Key, PublicKey, RuntimeArgs, SecretKey, StoredValue, U256,
+    U512,
+};
+use guardian_signer::{PemSigner, Signer};
+use guardian_types::{errors::RegistryError, fees, ids, payloads, prefixes, settings::AccountSettings, time::Span};
+
+const REGISTRY_WASM: &str = "recovery_registry.wasm";
+const FUNDED_CALL_WASM: &str = "funded_call.wasm";
+/// Named key the installer leaves the registry's contract hash under
+const REGISTRY_KEY: &str = "recovery_registry_contract_hash";
+/// Motes every new account starts with
+const FUNDING: u64 = 100_000_000_000_000;
+/// Block time (ms) of the first call after genesis
+const START: u64 = 1_700_000_000_000;
+
+/// A funded account, or an unfunded key proposed for a recovery. Keys are Ed25519.
+#[derive(Clone)]
+pub struct User {
+    pub hash: AccountHash,
+    pub public: PublicKey,
+    seed: [u8; 32],
+}
+
+impl User {
+    fn new(n: u32) -> User {
+        let mut seed = [7u8; 32];
+        seed[..4].copy_from_slice(&n.to_be_bytes());
+        let public = PublicKey::from(&secret(seed));
+        User { hash: public.to_account_hash(), public, seed }
+    }
+
+    /// Signer for this user's raw payload signatures
+    pub fn signer(&self) -> PemSigner {
+        PemSigner::from(secret(self.seed))
+    }
+}
+
+fn secret(seed: [u8; 32]) -> SecretKey {
+    SecretKey::ed25519_from_bytes(seed).expect("32-byte Ed25519 seed")
+}
+
+/// A recovery a test started
+pub struct Recovery {
+    pub id: U256,
+    pub new_key: PublicKey,
+}
+
+/// One registry on its own chain
+pub struct Registry {
+    pub builder: LmdbWasmTestBuilder,
+    /// Contract hash
+    pub hash: [u8; 32],
+    /// Block time (ms) the next call runs at
+    pub time: u64,
+    /// Keys made so far, to keep each one's seed apart
+    keys: u32,
+}
+
+impl Registry {
+    /// Run genesis, install the registry as the default account with `args` (e.g. `strict_args`)
+    /// and create its storage
+    pub fn install_with(args: RuntimeArgs) -> Registry {
+        let mut builder = LmdbWasmTestBuilder::default();
+        builder.run_genesis(LOCAL_GENESIS_REQUEST.clone());
+        let install = ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, REGISTRY_WASM, args).build();
+        builder.exec(install).expect_success().commit();
+        let hash = match account_named_key(&builder, DEFAULT_ACCOUNT_ADDR, REGISTRY_KEY) {
+            Some(Key::Hash(hash)) => hash,
+            other => panic!("the installer left {other:?} under {REGISTRY_KEY}"),
+        };
+        let mut registry = Registry { builder, hash, time: START, keys: 0 };
+        registry.ok(DEFAULT_ACCOUNT_ADDR, "init_storage", RuntimeArgs::new());
+        registry
+    }
+
+    pub fn install() -> Registry {
+        Registry::install_with(RuntimeArgs::new())
+    }
+
+    /// Run the install WASM again as the installer with `upgrade`, adding this build to the package
+    /// as its next version; later calls go to the new version. Returns the previous version's hash.
+    pub fn upgrade(&mut self) -> [u8; 32] {
+        let upgrade = ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, REGISTRY_WASM, runtime_args! { "upgrade" => true })
+            .with_block_time(self.time)
+            .build();
+        self.builder.exec(upgrade).expect_success().commit();
+        let previous = self.hash;
+        self.hash = match account_named_key(&self.builder, DEFAULT_ACCOUNT_ADDR, REGISTRY_KEY) {
+            Some(Key::Hash(hash)) => hash,
+            other => panic!("the upgrade left {other:?} under {REGISTRY_KEY}"),
+        };
+        previous
+    }
+
+    /// The account that installed the registry, and so administers it
+    pub fn admin(&self) -> AccountHash {
+        DEFAULT_ACCOUNT_ADDR
+    }
+
+    /// A new funded account
+    pub fn user(&mut self) -> User {
+        let user = self.key();
+        let transfer = TransferRequestBuilder::new(FUNDING, user.hash).build();
+        self.builder.transfer_and_commit(transfer).expect_success();
+        user
+    }
+
+    /// A new key with no account behind it
+    pub fn key(&mut self) -> User {
+        self.keys += 1;
+        User::new(self.keys)
+    }
+
+    /// Let `span` of block time pass before the next call
+    pub fn advance(&mut self, span: Span) {
+        self.time += span.0;
+    }
+
+    /// Call `entry_point` as `who`: `None` if it succeeded, else the registry error it reverted with
+    pub fn call(&mut self, who: AccountHash, entry_point: &str, args: RuntimeArgs) -> Option<RegistryError> {
+        let request = ExecuteRequestBuilder::contract_call_by_hash(who, AddressableEntityHash::new(self.hash), entry_point, args)
+            .with_block_time(self.time)
+            .build();
+        self.outcome(request)
+    }
+
+    /// Call `entry_point` as `who` through the `funded_call` session, which adds a purse holding
+    /// `amount` motes from `who`'s main purse to `args` as `purse`
+    pub fn funded(&mut self, who: AccountHash, entry_point: &str, amount: U512, args: RuntimeArgs) -> Option<RegistryError> {
+        let call_args = Bytes::from(args.to_bytes().expect("encodable args"));
+        let session = runtime_args! {
+            "registry" => Key::Hash(self.hash),
+            "entry_point" => entry_point.to_string(),
+            "amount" => amount,
+            "call_args" => call_args,
+        };
+        let request = ExecuteRequestBuilder::standard(who, FUNDED_CALL_WASM, session).with_block_time(self.time).build();
+        self.outcome(request)
+    }
+
+    fn outcome(&mut self, request: ExecuteRequest) -> Option<RegistryError> {
+        self.builder.exec(request).commit();
+        match self.builder.get_error()? {
+            EngineError::Exec(ExecError::Revert(ApiError::User(code))) => {
+                Some(RegistryError::from_code(code).unwrap_or_else(|| panic!("reverted with {code}, not a registry error")))
+            }
+            other => panic!("the call failed outside the registry: {other:?}"),
+        }
+    }
+
+    /// Call `entry_point` as `who`, failing the test unless it succeeds
+    #[track_caller]
+    pub fn ok(&mut self, who: AccountHash, entry_point: &str, args: RuntimeArgs) {
+        if let Some(e) = self.call(who, entry_point, args) {
+            panic!("{entry_point} should succeed but reverted with {}", e.name());
+        }
+    }
+
+    /// Call `entry_point` as `who`, failing the test unless it reverts with `want`
+    #[track_caller]
+    pub fn fails(&mut self, who: AccountHash, entry_point: &str, args: RuntimeArgs, want: RegistryError) {
+        let outcome = self.call(who, entry_point, args);
+        expect_revert(entry_point, outcome, want);
+    }
+
+    /// The registry's record `key`, `None` if it was never written
+    pub fn item<T: CLTyped + FromBytes>(&self, key: &str) -> Option<T> {
+        let Some(Key::URef(dict)) = self.named_key("d") else { panic!("the registry has no storage") };
+        match self.builder.query_dictionary_item(None, dict, key) {
+            Ok(StoredValue::CLValue(value)) => Some(value.into_t().unwrap_or_else(|e| panic!("record {key}: {e:?}"))),
+            _ => None,
+        }
+    }
+
+    /// The registry contract's named key `name`
+    pub fn named_key(&self, name: &str) -> Option<Key> {
+        match self.builder.query(None, Key::Hash(self.hash), &[]) {
+            Ok(StoredValue::Contract(contract)) => contract.named_keys().get(name).copied(),
+            other => panic!("the registry isn't a stored contract: {other:?}"),
+        }
+    }
+
+    /// Motes in the registry's purse `name`; zero before the registry creates it
+    pub fn purse_balance(&self, name: &str) -> U512 {
+        match self.named_key(name) {
+            Some(Key::URef(purse)) => self.builder.get_purse_balance(purse),
+            _ => U512::zero(),
+        }
+    }
+
+    /// Motes in `who`'s main purse
+    pub fn balance(&self, who: AccountHash) -> U512 {
+        match self.builder.query(None, Key::Account(who), &[]) {
+            Ok(StoredValue::Account(account)) => self.builder.get_purse_balance(account.main_purse()),
+            other => panic!("{who:?} isn't an account: {other:?}"),
+        }
+    }
+
+    /// Register `guardians` for `owner` at `threshold` and apply `settings`
+    pub fn set_up(&mut self, owner: &User, guardians: &[User], threshold: u8, settings: AccountSettings) {
+        let list: Vec<AccountHash> = guardians.iter().map(|g| g.hash).collect();
+        self.ok(owner.hash, "init_guardians", runtime_args! { "account" => owner.hash, "guardians" => list, "threshold" => threshold });
+        self.ok(owner.hash, "update_settings", settings_args(owner, &settings));
+    }
+
+    /// A funded owner and `count` funded guardians at `threshold`, set up under `settings`
+    pub fn account(&mut self, count: usize, threshold: u8, settings: AccountSettings) -> (User, Vec<User>) {
+        let owner = self.user();
+        let guardians: Vec<User> = (0..count).map(|_| self.user()).collect();
+        self.set_up(&owner, &guardians, threshold, settings);
+        (owner, guardians)
+    }
+
+    /// A fresh new key, the recovery of `owner`'s account it would start next, and the
+    /// `start_recovery` args proposing it
+    pub fn proposal(&mut self, owner: &User) -> (Recovery, RuntimeArgs) {
+        let new_key = self.key().public;
+        let nonce: u64 = self.item(&format!("{}{:?}", prefixes::RECOVERY_NONCE, owner.hash)).unwrap_or(0);
+        let id = ids::recovery_id(owner.hash, nonce, ids::key_commitment(&new_key));
+        let args = runtime_args! { "account" => owner.hash, "new_key" => new_key.clone() };
+        (Recovery { id, new_key }, args)
+    }
+
+    /// Start a recovery of `owner`'s account as `by`
+    #[track_caller]
+    pub fn start(&mut self, owner: &User, by: AccountHash) -> Recovery {
+        let (recovery, args) = self.proposal(owner);
+        self.ok(by, "start_recovery", args);
+        recovery
+    }
+}
+
+/// Fail the test unless `outcome` of `entry_point` is a revert with `want`
+#[track_caller]
+pub fn expect_revert(entry_point: &str, outcome: Option<RegistryError>, want: RegistryError) {
+    match outcome {
+        Some(e) => assert!(e == want, "{entry_point} should revert with {} but reverted with {}", want.name(), e.name()),
+        None => panic!("{entry_point} should revert with {} but succeeded", want.name()),
+    }
+}
+
+fn account_named_key(builder: &LmdbWasmTestBuilder, account: AccountHash, name: &str) -> Option<Key> {
+    match builder.query(None, Key::Account(account), &[]) {
+        Ok(StoredValue::Account(account)) => account.named_keys().get(name).copied(),
+        other => panic!("{account:?} isn't an account: {other:?}"),
+    }
+}
+
+/// `update_settings` args applying `settings` to `owner`'s account
+pub fn settings_args(owner: &User, s: &AccountSettings) -> RuntimeArgs {
+    runtime_args! {
+        "account" => owner.hash,
+        "expiry" => s.expiry,
+        "delay" => s.delay,
+        "max_attempts" => s.max_attempts,
+        "veto_window" => s.veto_window,
+        "approval_window" => s.approval_window,
+        "by_height" => s.by_height,
+        "rotation_window" => s.rotation_window,
+        "freeze_period" => s.freeze_period,
+        "grace_period" => s.grace_period,
+        "grace_weight" => s.grace_weight,
+        "cooldown" => s.cooldown,
+    }
+}
+
+/// `set_fee_schedule` args charging `setup` and `finalize` motes, every other operation free
+pub fn fee_schedule(setup: u64, finalize: u64) -> RuntimeArgs {
+    let mut schedule = vec![U512::zero(); fees::COUNT];
+    schedule[fees::SETUP as usize] = U512::from(setup);
+    schedule[fees::FINALIZE as usize] = U512::from(finalize);
+    runtime_args! { "fees" => schedule }
+}
+
+/// `id` as the only arg
+pub fn id_args(id: U256) -> RuntimeArgs {
+    runtime_args! { "id" => id }
+}
+
+/// Settings that keep a test short: recoveries live ten minutes and nothing waits
+pub fn quick() -> AccountSettings {
+    AccountSettings { expiry: Span::minutes(10).0, ..AccountSettings::default() }
+}
+
+/// `approve_signed` args for `guardian`'s signature over recovery `r` of `owner`'s account
+pub fn signed_approval(owner: &User, guardian: &User, r: &Recovery) -> RuntimeArgs {
+    replayed_approval(owner, guardian, r, r.id)
+}
+
+/// `signed_approval` of recovery `r`, submitted for recovery `on` instead
+pub fn replayed_approval(owner: &User, guardian: &User, r: &Recovery, on: U256) -> RuntimeArgs {
+    let (key, signature) = batch_item(owner, guardian, r);
+    runtime_args! { "id" => on, "guardian" => key, "signature" => signature }
+}
+
+/// `guardian`'s key and signature over recovery `r` of `owner`'s account, as one item of
+/// `submit_signed_approvals`
+pub fn batch_item(owner: &User, guardian: &User, r: &Recovery) -> (PublicKey, Vec<u8>) {
+    let signature = guardian.signer().sign_raw(&payloads::approval_payload(r.id, owner.hash, &r.new_key, &String::new())).expect("signs");
+    (guardian.public.clone(), signature)
+}
+
+/// `approve_meta` args for `guardian`'s signature over recovery `r` of `owner`'s account with meta
+/// nonce `nonce`
+pub fn meta_approval(owner: &User, guardian: &User, r: &Recovery, nonce: u64) -> RuntimeArgs {
+    replayed_meta_approval(owner, guardian, r, nonce, r.id)
+}
+
+/// `meta_approval` of recovery `r`, submitted for recovery `on` instead
+pub fn replayed_meta_approval(owner: &User, guardian: &User, r: &Recovery, nonce: u64, on: U256) -> RuntimeArgs {
+    let payload = payloads::meta_approval_payload(r.id, owner.hash, &r.new_key, &String::new(), nonce);
+    let signature = guardian.signer().sign_raw(&payload).expect("signs");
+    runtime_args! { "id" => on, "guardian" => guardian.public.clone(), "nonce" => nonce, "signature" => signature }
+}
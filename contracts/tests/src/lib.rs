@@ -0,0 +1,33 @@
+//! Engine tests of the recovery registry.
+//!
+//! Each test installs the registry WASM the workspace builds into a fresh in-memory chain and
+//! drives it through `casper-engine-test-support`, asserting the error every call that must fail
+//! reverts with and the records it leaves behind. Build the WASMs first: `make test` does both.
+//!
+//! The registry is a `no_main` WASM binary with no host test target, so its pure modules' unit
+//! tests are built here from their source files.
+
+extern crate alloc;
+
+#[cfg(test)]
+#[allow(dead_code, unused_imports)]
+#[path = "../../recovery_registry/src/sig.rs"]
+mod sig;
+
+#[cfg(test)]
+mod harness;
+
+#[cfg(test)]
+mod lifecycle;
+
+#[cfg(test)]
+mod upgrade;
+
+#[cfg(test)]
+mod fees;
+
+#[cfg(test)]
+mod adversarial;
+
+#[cfg(test)]
+mod precedence;
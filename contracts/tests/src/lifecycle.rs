@@ -0,0 +1,166 @@
+//! A recovery from setup to finalize, and the setups, waits and deadlines that turn it away.
+
+use casper_types::{
+    account::AccountHash, bytesrepr::{Bytes, ToBytes}, runtime_args, Key, PublicKey, U256,
+};
+use guardian_types::{
+    errors::RegistryError, policy::ApprovalPolicy, prefixes, settings::AccountSettings, time::Span,
+};
+
+use crate::harness::{batch_item, id_args, quick, signed_approval, Registry};
+
+#[test]
+fn setup_start_approve_finalize() {
+    let mut registry = Registry::install();
+    let (owner, guardians) = registry.account(3, 2, quick());
+    let [g1, g2, g3] = &guardians[..] else { unreachable!() };
+    let want: Vec<AccountHash> = guardians.iter().map(|g| g.hash).collect();
+    assert_eq!(registry.item::<Vec<AccountHash>>(&format!("{}{:?}", prefixes::GUARDIANS, owner.hash)), Some(want));
+    assert_eq!(registry.item::<u8>(&format!("{}{:?}", prefixes::THRESHOLD, owner.hash)), Some(2));
+    let again = runtime_args! { "account" => owner.hash, "guardians" => vec![g1.hash, g2.hash], "threshold" => 2u8 };
+    registry.fails(owner.hash, "init_guardians", again, RegistryError::AlreadyInit);
+
+    let r = registry.start(&owner, g1.hash);
+    assert_eq!(registry.item::<U256>(&format!("{}{:?}", prefixes::ACTIVE_RECOVERY, owner.hash)), Some(r.id));
+    assert_eq!(registry.item::<PublicKey>(&format!("{}{}", prefixes::NEW_KEY, r.id)), Some(r.new_key.clone()));
+    assert_eq!(registry.item::<u64>(&format!("{}{:?}", prefixes::RECOVERY_NONCE, owner.hash)), Some(1));
+
+    registry.ok(g1.hash, "approve", id_args(r.id));
+    registry.fails(g3.hash, "finalize", id_args(r.id), RegistryError::NotApproved);
+    registry.ok(g2.hash, "approve", id_args(r.id));
+    assert_eq!(registry.item::<u8>(&format!("{}{}", prefixes::APPROVAL_COUNT, r.id)), Some(2));
+    assert_eq!(registry.item::<bool>(&format!("{}{}", prefixes::THRESHOLD_REACHED, r.id)), Some(true));
+
+    registry.ok(g3.hash, "finalize", id_args(r.id));
+    assert_eq!(registry.item::<bool>(&format!("{}{}", prefixes::FINALIZED, r.id)), Some(true));
+    registry.fails(g1.hash, "finalize", id_args(r.id), RegistryError::Finalized);
+    // Closed, so the account can start its next one
+    registry.start(&owner, g1.hash);
+}
+
+/// Guardian sets the registry refuses, none of which leaves a record behind
+#[test]
+fn bad_setups() {
+    let mut registry = Registry::install();
+    let owner = registry.user();
+    let stranger = registry.user();
+    let [g1, g2, g3] = [registry.user(), registry.user(), registry.user()];
+    let setup = |guardians: Vec<AccountHash>, threshold: u8| {
+        runtime_args! { "account" => owner.hash, "guardians" => guardians, "threshold" => threshold }
+    };
+
+    registry.fails(stranger.hash, "init_guardians", setup(vec![g1.hash, g2.hash], 2), RegistryError::NotOwner);
+    registry.fails(owner.hash, "init_guardians", setup(vec![g1.hash], 1), RegistryError::BadGuardians);
+    registry.fails(owner.hash, "init_guardians", setup(vec![g1.hash, owner.hash], 1), RegistryError::BadGuardians);
+    registry.fails(owner.hash, "init_guardians", setup(vec![g1.hash, g2.hash, g1.hash], 2), RegistryError::BadGuardians);
+    registry.fails(owner.hash, "init_guardians", setup(vec![g1.hash, g2.hash, g3.hash], 0), RegistryError::BadThreshold);
+    registry.fails(owner.hash, "init_guardians", setup(vec![g1.hash, g2.hash, g3.hash], 4), RegistryError::BadThreshold);
+    assert_eq!(registry.item::<Vec<AccountHash>>(&format!("{}{:?}", prefixes::GUARDIANS, owner.hash)), None);
+    assert_eq!(registry.item::<bool>(&format!("{}{:?}", prefixes::INITIALIZED, owner.hash)), None);
+    let (_, start) = registry.proposal(&owner);
+    registry.fails(g1.hash, "start_recovery", start, RegistryError::NotInit);
+
+    registry.ok(owner.hash, "init_guardians", setup(vec![g1.hash, g2.hash, g3.hash], 3));
+    assert_eq!(registry.item::<u8>(&format!("{}{:?}", prefixes::THRESHOLD, owner.hash)), Some(3));
+}
+
+/// An approved recovery waits out the account's delay before it finalizes
+#[test]
+fn finalize_waits_for_the_delay() {
+    let delay = Span::minutes(2);
+    let mut registry = Registry::install();
+    let (owner, guardians) = registry.account(3, 2, AccountSettings { delay: delay.0, ..quick() });
+    let [g1, g2, _] = &guardians[..] else { unreachable!() };
+    let r = registry.start(&owner, g1.hash);
+    registry.ok(g1.hash, "approve", id_args(r.id));
+    registry.ok(g2.hash, "approve", id_args(r.id));
+
+    registry.fails(g1.hash, "finalize", id_args(r.id), RegistryError::TooEarly);
+    registry.advance(Span(delay.0 - 1));
+    registry.fails(g1.hash, "finalize", id_args(r.id), RegistryError::TooEarly);
+    registry.advance(Span(1));
+    registry.ok(g1.hash, "finalize", id_args(r.id));
+}
+
+/// Past its expiry a recovery takes no more approvals and never finalizes, and no longer holds the
+/// account's slot
+#[test]
+fn expired_recoveries_are_closed() {
+    let mut registry = Registry::install();
+    let (owner, guardians) = registry.account(3, 2, quick());
+    let [g1, g2, _] = &guardians[..] else { unreachable!() };
+    let r = registry.start(&owner, g1.hash);
+    registry.ok(g1.hash, "approve", id_args(r.id));
+
+    registry.advance(Span(quick().expiry));
+    registry.fails(g2.hash, "approve", id_args(r.id), RegistryError::Expired);
+    registry.fails(g1.hash, "finalize", id_args(r.id), RegistryError::Expired);
+    assert_eq!(registry.item::<u8>(&format!("{}{}", prefixes::APPROVAL_COUNT, r.id)), Some(1));
+    assert_eq!(registry.item::<bool>(&format!("{}{}", prefixes::FINALIZED, r.id)), None);
+
+    let next = registry.start(&owner, g2.hash);
+    assert_eq!(registry.item::<U256>(&format!("{}{:?}", prefixes::ACTIVE_RECOVERY, owner.hash)), Some(next.id));
+}
+
+/// A finalized recovery takes no more approvals, directly or signed, and counts none of them
+#[test]
+fn no_approvals_after_finalize() {
+    let mut registry = Registry::install();
+    let (owner, guardians) = registry.account(4, 2, quick());
+    let [g1, g2, g3, g4] = &guardians[..] else { unreachable!() };
+    let r = registry.start(&owner, g1.hash);
+    registry.ok(g1.hash, "approve", id_args(r.id));
+    registry.ok(g2.hash, "approve", id_args(r.id));
+    registry.ok(g1.hash, "finalize", id_args(r.id));
+
+    registry.fails(g3.hash, "approve", id_args(r.id), RegistryError::Finalized);
+    registry.fails(g1.hash, "approve_signed", signed_approval(&owner, g4, &r), RegistryError::Finalized);
+    registry.fails(g2.hash, "approve", id_args(r.id), RegistryError::AlreadyApproved);
+    assert_eq!(registry.item::<u8>(&format!("{}{}", prefixes::APPROVAL_COUNT, r.id)), Some(2));
+    assert_eq!(registry.item::<bool>(&format!("{}{}_{:?}", prefixes::APPROVED, r.id, g3.hash)), None);
+}
+
+/// A batch of signed approvals relayed after finalize counts none of them
+#[test]
+fn signed_batch_after_finalize() {
+    let mut registry = Registry::install();
+    let (owner, guardians) = registry.account(4, 2, quick());
+    let [g1, g2, g3, g4] = &guardians[..] else { unreachable!() };
+    let r = registry.start(&owner, g1.hash);
+    registry.ok(g1.hash, "approve", id_args(r.id));
+    registry.ok(g2.hash, "approve", id_args(r.id));
+    registry.ok(g1.hash, "finalize", id_args(r.id));
+
+    // Each item is turned away on its own, so the batch itself goes through
+    let items = vec![batch_item(&owner, g3, &r), batch_item(&owner, g4, &r), batch_item(&owner, g1, &r)];
+    registry.ok(g3.hash, "submit_signed_approvals", runtime_args! { "id" => r.id, "approvals" => items });
+    assert_eq!(registry.item::<u8>(&format!("{}{}", prefixes::APPROVAL_COUNT, r.id)), Some(2));
+    for g in [g3, g4] {
+        assert_eq!(registry.item::<bool>(&format!("{}{}_{:?}", prefixes::APPROVED, r.id, g.hash)), None);
+    }
+    let proofs = registry.item::<Vec<(PublicKey, Vec<u8>, String)>>(&format!("{}{}", prefixes::SIGNED_PROOFS, r.id));
+    assert_eq!(proofs, Some(vec![]));
+}
+
+/// The quorum certificate weighs each approval as the account's policy counted it
+#[test]
+fn certificate_records_policy_weights() {
+    let mut registry = Registry::install();
+    let (owner, guardians) = registry.account(3, 2, quick());
+    let [g1, g2, _] = &guardians[..] else { unreachable!() };
+    // g3 isn't named, so weighs 1
+    let weights = vec![(Key::Account(g1.hash), 3u8), (Key::Account(g2.hash), 2u8)];
+    let policy = ApprovalPolicy::Weighted { weights, threshold: 5 }.to_bytes().expect("encodable policy");
+    registry.ok(owner.hash, "set_approval_policy", runtime_args! { "account" => owner.hash, "policy" => Bytes::from(policy) });
+
+    let r = registry.start(&owner, g1.hash);
+    registry.ok(g1.hash, "approve", id_args(r.id));
+    registry.fails(g1.hash, "finalize", id_args(r.id), RegistryError::NotApproved);
+    registry.ok(g2.hash, "approve", id_args(r.id));
+    registry.ok(g1.hash, "finalize", id_args(r.id));
+
+    type Certificate = ((U256, AccountHash, PublicKey), Vec<(Key, u8, u64)>, (u64, bool));
+    let (_, quorum, _) = registry.item::<Certificate>(&format!("{}{}", prefixes::QUORUM_CERTIFICATE, r.id)).expect("certified");
+    let weighed: Vec<(Key, u8)> = quorum.into_iter().map(|(who, weight, _)| (who, weight)).collect();
+    assert_eq!(weighed, vec![(Key::Account(g1.hash), 3), (Key::Account(g2.hash), 2)]);
+}
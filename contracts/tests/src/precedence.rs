@@ -0,0 +1,122 @@
+//! Which of two conflicting operations wins. Each test's doc comment states the rule, and the
+//! engine runs the pair in the order it names, so the winner and the loser's revert are exact.
+
+use casper_types::{account::AccountHash, bytesrepr::{Bytes, ToBytes}, runtime_args, Key, RuntimeArgs, U256};
+use guardian_types::{errors::RegistryError, policy::ApprovalPolicy, prefixes, settings::AccountSettings, time::Span};
+
+use crate::harness::{expect_revert, id_args, quick, settings_args, Registry, User};
+
+/// `set_approval_policy` args making `threshold` approvals the policy of `owner`
+fn threshold_policy(owner: &User, threshold: u8) -> RuntimeArgs {
+    let policy = ApprovalPolicy::Threshold { threshold }.to_bytes().expect("encodable policy");
+    runtime_args! { "account" => owner.hash, "policy" => Bytes::from(policy) }
+}
+
+/// `add_guardian` and `remove_guardian` args naming `guardian` of `owner`
+fn guardian_args(owner: &User, guardian: &User) -> RuntimeArgs {
+    runtime_args! { "account" => owner.hash, "guardian" => Key::Account(guardian.hash) }
+}
+
+/// Rule: while a recovery is pending the owner can neither remove nor add a guardian, so the set
+/// that votes on it is the set it started with. A guardian's approval counts through to finalize,
+/// and removal works again once the recovery closes.
+#[test]
+fn removal_while_pending() {
+    let mut registry = Registry::install();
+    let (owner, guardians) = registry.account(4, 2, quick());
+    let stranger = registry.user();
+    let [g1, g2, _, g4] = &guardians[..] else { unreachable!() };
+    let r = registry.start(&owner, g1.hash);
+
+    registry.ok(g1.hash, "approve", id_args(r.id));
+    registry.fails(owner.hash, "remove_guardian", guardian_args(&owner, g1), RegistryError::RecoveryExists);
+    registry.fails(owner.hash, "remove_guardian", guardian_args(&owner, g4), RegistryError::RecoveryExists);
+    registry.fails(owner.hash, "add_guardian", guardian_args(&owner, &stranger), RegistryError::RecoveryExists);
+    registry.ok(g2.hash, "approve", id_args(r.id));
+    assert_eq!(registry.item::<bool>(&format!("{}{}", prefixes::THRESHOLD_REACHED, r.id)), Some(true));
+
+    registry.ok(owner.hash, "veto", id_args(r.id));
+    registry.ok(owner.hash, "remove_guardian", guardian_args(&owner, g4));
+    let left: Vec<AccountHash> = guardians[..3].iter().map(|g| g.hash).collect();
+    assert_eq!(registry.item::<Vec<AccountHash>>(&format!("{}{:?}", prefixes::GUARDIANS, owner.hash)), Some(left));
+}
+
+/// Rule: the owner can't change the approval policy (and with it the threshold) while a recovery
+/// is pending; the recovery finishes under the threshold it was approved under and the change goes
+/// through once it has closed.
+#[test]
+fn threshold_mid_approval() {
+    let mut registry = Registry::install();
+    let (owner, guardians) = registry.account(3, 2, quick());
+    let [g1, g2, g3] = &guardians[..] else { unreachable!() };
+    let r = registry.start(&owner, g1.hash);
+
+    registry.ok(g1.hash, "approve", id_args(r.id));
+    registry.fails(owner.hash, "set_approval_policy", threshold_policy(&owner, 3), RegistryError::RecoveryExists);
+    registry.fails(owner.hash, "set_approval_policy", threshold_policy(&owner, 1), RegistryError::RecoveryExists);
+    assert_eq!(registry.item::<u8>(&format!("{}{:?}", prefixes::THRESHOLD, owner.hash)), Some(2));
+    registry.ok(g2.hash, "approve", id_args(r.id));
+    registry.ok(g3.hash, "finalize", id_args(r.id));
+
+    registry.ok(owner.hash, "set_approval_policy", threshold_policy(&owner, 3));
+    assert_eq!(registry.item::<u8>(&format!("{}{:?}", prefixes::THRESHOLD, owner.hash)), Some(3));
+    let next = registry.start(&owner, g1.hash);
+    registry.ok(g1.hash, "approve", id_args(next.id));
+    registry.ok(g2.hash, "approve", id_args(next.id));
+    registry.fails(g1.hash, "finalize", id_args(next.id), RegistryError::NotApproved);
+}
+
+/// Rule: an account has one pending recovery, so of two started in one block whichever executes
+/// first stands and the other reverts with `RecoveryExists`, for the same new key as for different
+/// ones. The nonce moves on once.
+#[test]
+fn two_initiations() {
+    let mut registry = Registry::install();
+    let (owner, guardians) = registry.account(3, 2, quick());
+    let stranger = registry.user();
+    let [g1, g2, _] = &guardians[..] else { unreachable!() };
+
+    for (round, same_key) in [(1u64, false), (2, true)] {
+        let (winner, first) = registry.proposal(&owner);
+        let (_, different) = registry.proposal(&owner);
+        let second = if same_key { first.clone() } else { different };
+        registry.ok(g1.hash, "start_recovery", first);
+        let lost = registry.call(stranger.hash, "start_recovery", second);
+        expect_revert("start_recovery", lost, RegistryError::RecoveryExists);
+
+        assert_eq!(registry.item::<U256>(&format!("{}{:?}", prefixes::ACTIVE_RECOVERY, owner.hash)), Some(winner.id));
+        assert_eq!(registry.item::<u64>(&format!("{}{:?}", prefixes::RECOVERY_NONCE, owner.hash)), Some(round));
+        assert_eq!(registry.item::<AccountHash>(&format!("{}{}", prefixes::INITIATOR, winner.id)), Some(g1.hash));
+        registry.ok(g2.hash, "approve", id_args(winner.id));
+        registry.ok(owner.hash, "veto", id_args(winner.id));
+    }
+}
+
+/// Rule: settings saved while a recovery is pending apply to recoveries started afterwards. The
+/// pending one keeps the veto window it started with, so shortening the window can't cut a veto
+/// short, and the owner can still veto inside it; only switching to block-height timing is
+/// refused while it is pending.
+#[test]
+fn settings_in_veto_window() {
+    let window = Span::seconds(45);
+    let mut registry = Registry::install();
+    let (owner, guardians) = registry.account(3, 2, AccountSettings { veto_window: window.0, ..quick() });
+    let [g1, g2, _] = &guardians[..] else { unreachable!() };
+
+    let r = registry.start(&owner, g1.hash);
+    registry.ok(g1.hash, "approve", id_args(r.id));
+    registry.ok(g2.hash, "approve", id_args(r.id));
+    registry.ok(owner.hash, "update_settings", settings_args(&owner, &quick()));
+    assert_eq!(registry.item::<u64>(&format!("{}{}", prefixes::VETO_WINDOW, r.id)), Some(window.0));
+    registry.fails(g1.hash, "finalize", id_args(r.id), RegistryError::TooEarly);
+    let by_height = AccountSettings { by_height: true, ..quick() };
+    registry.fails(owner.hash, "update_settings", settings_args(&owner, &by_height), RegistryError::RecoveryExists);
+    registry.ok(owner.hash, "veto", id_args(r.id));
+
+    // The next recovery runs under the new settings: no veto window to wait out
+    let next = registry.start(&owner, g1.hash);
+    assert_eq!(registry.item::<u64>(&format!("{}{}", prefixes::VETO_WINDOW, next.id)), None);
+    registry.ok(g1.hash, "approve", id_args(next.id));
+    registry.ok(g2.hash, "approve", id_args(next.id));
+    registry.ok(g1.hash, "finalize", id_args(next.id));
+}
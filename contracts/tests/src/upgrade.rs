@@ -0,0 +1,116 @@
+//! Upgrading the registry package: state written through one contract version reads back through
+//! the next once `migrate` has run, and the previous version takes no more calls.
+//!
+//! The schema is still at its first version (`migrations::CURRENT == migrations::FIRST`), so no
+//! migration step exists yet: these tests check that an upgrade keeps every record as it was and
+//! that `migrate` only records the version. The first schema change adds its step and a test here
+//! that writes the old form, upgrades, and reads the new one.
+
+use casper_engine_test_support::ExecuteRequestBuilder;
+use casper_types::{account::AccountHash, runtime_args, AddressableEntityHash, Key, RuntimeArgs, U512};
+use guardian_types::{
+    errors::RegistryError, migrations, prefixes, roles, settings::AccountSettings, time::Span,
+};
+
+use crate::harness::{fee_schedule, id_args, quick, Registry};
+
+const SETUP_FEE: u64 = 3_000_000_000;
+
+#[test]
+fn upgrade_keeps_every_account() {
+    assert_eq!(migrations::CURRENT, migrations::FIRST, "a schema change needs its own migration test");
+    let window = Span::minutes(5);
+    let mut registry = Registry::install();
+    let admin = registry.admin();
+
+    // A settled account: one recovery finalized
+    let (settled, settled_guardians) = registry.account(3, 2, quick());
+    let [s1, s2, _] = &settled_guardians[..] else { unreachable!() };
+    let done = registry.start(&settled, s1.hash);
+    registry.ok(s1.hash, "approve", id_args(done.id));
+    registry.ok(s2.hash, "approve", id_args(done.id));
+    registry.ok(s1.hash, "finalize", id_args(done.id));
+
+    // An account mid-recovery, with an auditor and its own veto window
+    let settings = AccountSettings { veto_window: window.0, ..quick() };
+    let (owner, guardians) = registry.account(3, 2, settings);
+    let [g1, g2, g3] = &guardians[..] else { unreachable!() };
+    let auditor = registry.user();
+    let grant = runtime_args! { "account" => owner.hash, "member" => Key::Account(auditor.hash), "roles" => roles::AUDITOR };
+    registry.ok(owner.hash, "grant_roles", grant);
+    let pending = registry.start(&owner, g1.hash);
+    registry.ok(g1.hash, "approve", id_args(pending.id));
+
+    // A fee collected from a paid setup
+    registry.ok(admin, "set_fee_schedule", fee_schedule(SETUP_FEE, 0));
+    let payer = registry.user();
+    let payer_guardians = [registry.user(), registry.user()];
+    let init = runtime_args! {
+        "account" => payer.hash,
+        "guardians" => payer_guardians.iter().map(|g| g.hash).collect::<Vec<AccountHash>>(),
+        "threshold" => 2u8,
+    };
+    assert!(registry.funded(payer.hash, "init_guardians", U512::from(SETUP_FEE), init).is_none(), "the paid setup should succeed");
+
+    let previous = registry.upgrade();
+    assert_ne!(previous, registry.hash, "the upgrade should add a contract version");
+    assert_eq!(registry.item::<u32>(prefixes::SCHEMA_VERSION), Some(migrations::CURRENT));
+
+    // Every record reads back through the new version
+    let want: Vec<AccountHash> = settled_guardians.iter().map(|g| g.hash).collect();
+    assert_eq!(registry.item::<Vec<AccountHash>>(&format!("{}{:?}", prefixes::GUARDIANS, settled.hash)), Some(want));
+    assert_eq!(registry.item::<bool>(&format!("{}{}", prefixes::FINALIZED, done.id)), Some(true));
+    assert_eq!(registry.item::<AccountSettings>(&format!("{}{:?}", prefixes::SETTINGS, owner.hash)), Some(settings));
+    assert_eq!(registry.item::<u8>(&format!("{}{}", prefixes::APPROVAL_COUNT, pending.id)), Some(1));
+    let members = registry.item::<Vec<(Key, u8)>>(&format!("{}{:?}", prefixes::ROLES, owner.hash));
+    assert_eq!(members, Some(vec![(Key::Account(auditor.hash), roles::AUDITOR)]));
+    assert_eq!(registry.item::<bool>(&format!("{}{:?}", prefixes::INITIALIZED, payer.hash)), Some(true));
+    assert_eq!(registry.purse_balance("fee_purse"), U512::from(SETUP_FEE));
+    assert_eq!(registry.named_key("admin"), Some(Key::Account(admin)));
+
+    // The pending recovery carries on through it, under the veto window it started with
+    registry.ok(g2.hash, "approve", id_args(pending.id));
+    registry.fails(g3.hash, "finalize", id_args(pending.id), RegistryError::TooEarly);
+    registry.advance(window);
+    registry.ok(g3.hash, "finalize", id_args(pending.id));
+    registry.fails(settled.hash, "init_guardians", runtime_args! {
+        "account" => settled.hash, "guardians" => vec![s1.hash, s2.hash], "threshold" => 2u8,
+    }, RegistryError::AlreadyInit);
+
+    // The previous version is disabled
+    let stale = ExecuteRequestBuilder::contract_call_by_hash(g1.hash, AddressableEntityHash::new(previous), "get_schema_version", RuntimeArgs::new())
+        .with_block_time(registry.time)
+        .build();
+    registry.builder.exec(stale).commit();
+    assert!(registry.builder.get_error().is_some(), "the previous version should take no calls");
+}
+
+#[test]
+fn migrate_is_the_admins_and_runs_once() {
+    let mut registry = Registry::install();
+    let stranger = registry.user();
+    assert_eq!(registry.item::<u32>(prefixes::SCHEMA_VERSION), Some(migrations::CURRENT));
+
+    registry.fails(stranger.hash, "migrate", RuntimeArgs::new(), RegistryError::NotAdmin);
+    let admin = registry.admin();
+    registry.ok(admin, "migrate", RuntimeArgs::new());
+    registry.ok(admin, "migrate", RuntimeArgs::new());
+    assert_eq!(registry.item::<u32>(prefixes::SCHEMA_VERSION), Some(migrations::CURRENT));
+
+    // Twice over: each upgrade adds a version and leaves the records current
+    let first = registry.upgrade();
+    let second = registry.upgrade();
+    assert_ne!(first, second);
+    assert_eq!(registry.item::<u32>(prefixes::SCHEMA_VERSION), Some(migrations::CURRENT));
+}
+
+#[test]
+fn only_the_installer_upgrades() {
+    let mut registry = Registry::install();
+    let stranger = registry.user();
+    let upgrade = ExecuteRequestBuilder::standard(stranger.hash, "recovery_registry.wasm", runtime_args! { "upgrade" => true })
+        .with_block_time(registry.time)
+        .build();
+    registry.builder.exec(upgrade).commit();
+    assert!(registry.builder.get_error().is_some(), "an account with no package to upgrade should fail");
+}
@@ -0,0 +1,24 @@
+//! Lifetime recovery counters the registry keeps per account, as indexes into the lists
+//! `get_recovery_counts` returns and `set_anomaly_limits` takes.
+
+/// Recoveries started
+pub const STARTED: u8 = 0;
+/// Recoveries finalized
+pub const FINALIZED: u8 = 1;
+/// Recoveries the owner vetoed
+pub const VETOED: u8 = 2;
+/// Recoveries that expired unfinalized; counted once the next one starts, or by the query
+pub const EXPIRED: u8 = 3;
+/// Number of counters
+pub const COUNT: usize = 4;
+
+/// Short name of a counter, for display
+pub fn name(counter: u8) -> &'static str {
+    match counter {
+        STARTED => "started",
+        FINALIZED => "finalized",
+        VETOED => "vetoed",
+        EXPIRED => "expired",
+        _ => "unknown",
+    }
+}
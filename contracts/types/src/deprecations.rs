@@ -0,0 +1,16 @@
+//! Entry points a newer version has replaced, as `get_deprecations` reports them.
+//!
+//! An entry point whose arguments or return type change is installed again under its name with
+//! the next `_vN` suffix (`get_recovery_status_v2`). The old name stays installed for deployed
+//! integrations: it runs the same logic, returns its old shape, emits `EntryPointDeprecatedV1` when
+//! called and is listed here with its replacement.
+
+/// (deprecated entry point, its replacement)
+pub const DEPRECATED: &[(&str, &str)] = &[
+    ("get_recovery_status", "get_recovery_status_v2"),
+];
+
+/// Replacement of `entry_point`, if it is deprecated
+pub fn replacement(entry_point: &str) -> Option<&'static str> {
+    DEPRECATED.iter().find(|(old, _)| *old == entry_point).map(|(_, new)| *new)
+}
@@ -0,0 +1,1307 @@
+//! Entry points of the recovery registry and the recovery executor: names, argument CLTypes and
+//! return types. Each contract's `call()` installs exactly these and `guardian-schema` describes the
+//! registry's to integrators, so the two can't drift.
+
+use alloc::{boxed::Box, vec};
+use casper_types::{
+    contracts::{EntryPoint, EntryPoints},
+    CLType, EntryPointAccess, Parameter,
+};
+
+use crate::{host::CALLED, modules};
+
+/// Optional argument of every registry entry point naming the tenant whose records the call works
+/// on; tenant 0, the registry's own, without it. Only the tenant entry points list it.
+pub const ARG_TENANT: &str = "tenant";
+
+/// Every entry point the registry installs
+pub fn registry_entry_points() -> EntryPoints {
+    let mut eps = EntryPoints::new();
+
+    eps.add_entry_point(EntryPoint::new(
+        "init_storage",
+        vec![],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "init_guardians",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("guardians", CLType::List(Box::new(CLType::ByteArray(32)))),
+            Parameter::new("threshold", CLType::U8),
+            Parameter::new("guardian_keys", CLType::List(Box::new(CLType::PublicKey))),
+            Parameter::new("purse", CLType::URef),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "init_from_template",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("template", CLType::String),
+            Parameter::new("guardians", CLType::List(Box::new(CLType::ByteArray(32)))),
+            Parameter::new("guardian_keys", CLType::List(Box::new(CLType::PublicKey))),
+            Parameter::new("purse", CLType::URef),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "init_guardians_batch",
+        vec![
+            Parameter::new("entries", CLType::List(Box::new(CLType::Tuple3([
+                Box::new(CLType::ByteArray(32)),
+                Box::new(CLType::List(Box::new(CLType::ByteArray(32)))),
+                Box::new(CLType::U8),
+            ])))),
+            Parameter::new("purse", CLType::URef),
+        ],
+        CLType::List(Box::new(CLType::Tuple2([Box::new(CLType::ByteArray(32)), Box::new(CLType::U32)]))),
+        EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "start_recovery",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("new_key", CLType::PublicKey),
+            Parameter::new("recovery_type", CLType::String),
+            Parameter::new("purse", CLType::URef),
+        ],
+        CLType::U256, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "init_private_guardians",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("guardian_hashes", CLType::List(Box::new(CLType::ByteArray(32)))),
+            Parameter::new("threshold", CLType::U8),
+            Parameter::new("purse", CLType::URef),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "authorize_setup",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("provider", CLType::Option(Box::new(CLType::Key))),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_setup_consent", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::Option(Box::new(CLType::Tuple3([Box::new(CLType::Key), Box::new(CLType::U64), Box::new(CLType::Bool)]))),
+        EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "is_guardian",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("guardian", CLType::ByteArray(32)),
+        ],
+        CLType::Bool, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "init_merkle_guardians",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("root", CLType::ByteArray(32)),
+            Parameter::new("size", CLType::U32),
+            Parameter::new("threshold", CLType::U8),
+            Parameter::new("purse", CLType::URef),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "set_guardian_root",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("root", CLType::ByteArray(32)),
+            Parameter::new("size", CLType::U32),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_guardian_root", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::Option(Box::new(CLType::Tuple2([Box::new(CLType::ByteArray(32)), Box::new(CLType::U32)]))), EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "approve",
+        vec![
+            Parameter::new("id", CLType::U256),
+            Parameter::new("proof", CLType::List(Box::new(CLType::ByteArray(32)))),
+            Parameter::new("memo", CLType::String),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "approve_signed",
+        vec![
+            Parameter::new("id", CLType::U256),
+            Parameter::new("guardian", CLType::PublicKey),
+            Parameter::new("signature", CLType::List(Box::new(CLType::U8))),
+            Parameter::new("memo", CLType::String),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "approve_meta",
+        vec![
+            Parameter::new("id", CLType::U256),
+            Parameter::new("guardian", CLType::PublicKey),
+            Parameter::new("nonce", CLType::U64),
+            Parameter::new("signature", CLType::List(Box::new(CLType::U8))),
+            Parameter::new("memo", CLType::String),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_meta_nonce", vec![Parameter::new("guardian", CLType::ByteArray(32))],
+        CLType::U64, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "void_meta_approvals", vec![],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "set_relayers",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("relayers", CLType::List(Box::new(CLType::ByteArray(32)))),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_relayers", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::List(Box::new(CLType::ByteArray(32))), EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_approval_proofs", vec![Parameter::new("id", CLType::U256)],
+        CLType::List(Box::new(CLType::Tuple3([
+            Box::new(CLType::PublicKey),
+            Box::new(CLType::List(Box::new(CLType::U8))),
+            Box::new(CLType::String),
+        ]))),
+        EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "submit_signed_approvals",
+        vec![
+            Parameter::new("id", CLType::U256),
+            Parameter::new("approvals", CLType::List(Box::new(CLType::Tuple2([
+                Box::new(CLType::PublicKey),
+                Box::new(CLType::List(Box::new(CLType::U8))),
+            ])))),
+            Parameter::new("memo", CLType::String),
+        ],
+        CLType::List(Box::new(CLType::Tuple2([Box::new(CLType::PublicKey), Box::new(CLType::U32)]))),
+        EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "set_frost_key",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("group_key", CLType::Option(Box::new(CLType::PublicKey))),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_frost_key", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::Option(Box::new(CLType::PublicKey)), EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "approve_frost",
+        vec![
+            Parameter::new("id", CLType::U256),
+            Parameter::new("signature", CLType::List(Box::new(CLType::U8))),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "reject",
+        vec![
+            Parameter::new("id", CLType::U256),
+            Parameter::new("proof", CLType::List(Box::new(CLType::ByteArray(32)))),
+            Parameter::new("memo", CLType::String),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_recovery_status", vec![Parameter::new("id", CLType::U256)],
+        CLType::Tuple3([
+            Box::new(CLType::U8),
+            Box::new(CLType::Tuple2([Box::new(CLType::U8), Box::new(CLType::U8)])),
+            Box::new(CLType::List(Box::new(CLType::Tuple3([Box::new(CLType::Key), Box::new(CLType::Bool), Box::new(CLType::String)])))),
+        ]),
+        EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_recovery_status_v2", vec![Parameter::new("id", CLType::U256)],
+        CLType::Tuple3([
+            Box::new(CLType::U8),
+            Box::new(CLType::Tuple3([Box::new(CLType::U32), Box::new(CLType::U8), Box::new(CLType::U64)])),
+            Box::new(CLType::List(Box::new(CLType::Tuple3([Box::new(CLType::Key), Box::new(CLType::Bool), Box::new(CLType::String)])))),
+        ]),
+        EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_deprecations", vec![],
+        CLType::List(Box::new(CLType::Tuple2([Box::new(CLType::String), Box::new(CLType::String)]))),
+        EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "simulate_recovery",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("approvers", CLType::List(Box::new(CLType::Key))),
+            Parameter::new("recovery_type", CLType::String),
+        ],
+        CLType::Tuple3([Box::new(CLType::Bool), Box::new(CLType::U8), Box::new(CLType::U64)]),
+        EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_recovery_nonce", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::U64, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "has_voted",
+        vec![
+            Parameter::new("id", CLType::U256),
+            Parameter::new("guardian", CLType::Key),
+        ],
+        CLType::Bool, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_approval_timeline", vec![Parameter::new("id", CLType::U256)],
+        CLType::List(Box::new(CLType::Tuple2([Box::new(CLType::Key), Box::new(CLType::U64)]))),
+        EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "set_approval_window",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("window", CLType::U64),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "is_approved", vec![Parameter::new("id", CLType::U256)],
+        CLType::Bool, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "finalize", vec![Parameter::new("id", CLType::U256), Parameter::new("purse", CLType::URef)],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_quorum_certificate", vec![Parameter::new("id", CLType::U256)],
+        CLType::Option(Box::new(CLType::Tuple3([
+            Box::new(CLType::Tuple3([Box::new(CLType::U256), Box::new(CLType::ByteArray(32)), Box::new(CLType::PublicKey)])),
+            Box::new(CLType::List(Box::new(CLType::Tuple3([Box::new(CLType::Key), Box::new(CLType::U8), Box::new(CLType::U64)])))),
+            Box::new(CLType::Tuple2([Box::new(CLType::U64), Box::new(CLType::Bool)])),
+        ]))),
+        EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "finalize_removal", vec![Parameter::new("id", CLType::U256)],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_grace", vec![Parameter::new("id", CLType::U256)],
+        CLType::Option(Box::new(CLType::Tuple3([Box::new(CLType::U64), Box::new(CLType::U8), Box::new(CLType::Bool)]))),
+        EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_guardians", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::List(Box::new(CLType::ByteArray(32))), EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_guardian_config", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::Tuple3([
+            Box::new(CLType::List(Box::new(CLType::ByteArray(32)))),
+            Box::new(CLType::U8),
+            Box::new(CLType::Option(Box::new(CLType::Tuple2([Box::new(CLType::ByteArray(32)), Box::new(CLType::String)])))),
+        ]),
+        EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "set_instructions",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("hash", CLType::ByteArray(32)),
+            Parameter::new("uri", CLType::String),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "clear_instructions", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_contract_guardians", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::List(Box::new(CLType::Key)), EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "has_guardians", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::Bool, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_recoveries_for_guardian", vec![Parameter::new("guardian", CLType::ByteArray(32))],
+        CLType::List(Box::new(CLType::U256)), EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_pending_approvals_for_guardian", vec![Parameter::new("public_key", CLType::PublicKey)],
+        CLType::List(Box::new(CLType::Tuple3([
+            Box::new(CLType::ByteArray(32)),
+            Box::new(CLType::U256),
+            Box::new(CLType::U64),
+        ]))),
+        EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_protected_accounts", vec![Parameter::new("guardian", CLType::ByteArray(32))],
+        CLType::List(Box::new(CLType::ByteArray(32))), EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "configure_heartbeat",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("period", CLType::U64),
+            Parameter::new("threshold", CLType::U8),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "heartbeat", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "is_inactive", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::Bool, EntryPointAccess::Public, CALLED,
+    ));
+
+    #[cfg(feature = "policies")]
+    eps.add_entry_point(EntryPoint::new(
+        "set_threshold_decay",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("schedule", CLType::List(Box::new(CLType::Tuple2([Box::new(CLType::U64), Box::new(CLType::U8)])))),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    #[cfg(feature = "policies")]
+    eps.add_entry_point(EntryPoint::new(
+        "get_threshold_decay", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::List(Box::new(CLType::Tuple2([Box::new(CLType::U64), Box::new(CLType::U8)]))), EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_current_threshold", vec![Parameter::new("id", CLType::U256)],
+        CLType::U8, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "set_recovery_type",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("name", CLType::String),
+            Parameter::new("threshold", CLType::U8),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_recovery_types", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::List(Box::new(CLType::Tuple2([Box::new(CLType::String), Box::new(CLType::U8)]))), EntryPointAccess::Public, CALLED,
+    ));
+
+    #[cfg(feature = "bonding")]
+    eps.add_entry_point(EntryPoint::new(
+        "set_guardian_bond",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("amount", CLType::U512),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    #[cfg(feature = "bonding")]
+    eps.add_entry_point(EntryPoint::new(
+        "post_bond",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("purse", CLType::URef),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    #[cfg(feature = "bonding")]
+    eps.add_entry_point(EntryPoint::new(
+        "get_bond",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("guardian", CLType::ByteArray(32)),
+        ],
+        CLType::Tuple2([Box::new(CLType::U512), Box::new(CLType::U512)]), EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "set_key_policy",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("allowed", CLType::U8),
+            Parameter::new("guardian_keys", CLType::List(Box::new(CLType::PublicKey))),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_key_policy", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::U8, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "set_verifier",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("verifier", CLType::U8),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_verifier", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::U8, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "delegate_guardianship",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("delegate", CLType::PublicKey),
+            Parameter::new("until", CLType::U64),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "revoke_delegation", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_delegation",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("guardian", CLType::ByteArray(32)),
+        ],
+        CLType::Option(Box::new(CLType::Tuple2([Box::new(CLType::PublicKey), Box::new(CLType::U64)]))), EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "add_guardian",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("guardian", CLType::Key),
+            Parameter::new("guardian_keys", CLType::List(Box::new(CLType::PublicKey))),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "invite_guardian",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("guardian", CLType::ByteArray(32)),
+            Parameter::new("deadline", CLType::U64),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "accept_invitation",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("guardian_keys", CLType::List(Box::new(CLType::PublicKey))),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "sweep_invitations", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::U32, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_invitations", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::List(Box::new(CLType::Tuple2([Box::new(CLType::ByteArray(32)), Box::new(CLType::U64)]))),
+        EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "remove_guardian",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("guardian", CLType::Key),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "rotate_my_key",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("old_key", CLType::PublicKey),
+            Parameter::new("new_key", CLType::PublicKey),
+            Parameter::new("old_signature", CLType::List(Box::new(CLType::U8))),
+            Parameter::new("new_signature", CLType::List(Box::new(CLType::U8))),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    for name in ["complete_rotation", "veto_rotation"] {
+        eps.add_entry_point(EntryPoint::new(
+            name,
+            vec![
+                Parameter::new("account", CLType::ByteArray(32)),
+                Parameter::new("guardian", CLType::ByteArray(32)),
+            ],
+            CLType::Unit, EntryPointAccess::Public, CALLED,
+        ));
+    }
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_pending_rotation",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("guardian", CLType::ByteArray(32)),
+        ],
+        CLType::Option(Box::new(CLType::Tuple2([Box::new(CLType::PublicKey), Box::new(CLType::U64)]))),
+        EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "set_guardian_reward",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("amount", CLType::U512),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "fund_rewards",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("purse", CLType::URef),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "withdraw_rewards",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("amount", CLType::U512),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_rewards", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::Tuple2([Box::new(CLType::U512), Box::new(CLType::U512)]), EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "veto",
+        vec![
+            Parameter::new("id", CLType::U256),
+            Parameter::new("malicious", CLType::Bool),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "flag_recovery",
+        vec![
+            Parameter::new("id", CLType::U256),
+            Parameter::new("reason", CLType::U8),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "withdraw_flag", vec![Parameter::new("id", CLType::U256)],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_flags", vec![Parameter::new("id", CLType::U256)],
+        CLType::Tuple2([
+            Box::new(CLType::List(Box::new(CLType::Tuple2([Box::new(CLType::Key), Box::new(CLType::U8)])))),
+            Box::new(CLType::Bool),
+        ]),
+        EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "set_flag_policy",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("limit", CLType::Option(Box::new(CLType::U8))),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_flag_policy", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::Option(Box::new(CLType::U8)), EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "set_recovery_deposit",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("amount", CLType::U512),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_recovery_deposit", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::U512, EntryPointAccess::Public, CALLED,
+    ));
+
+    #[cfg(feature = "bonding")]
+    eps.add_entry_point(EntryPoint::new(
+        "propose_slash",
+        vec![
+            Parameter::new("id", CLType::U256),
+            Parameter::new("bps", CLType::U32),
+            Parameter::new("evidence", CLType::String),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    #[cfg(feature = "bonding")]
+    eps.add_entry_point(EntryPoint::new(
+        "dispute_slash",
+        vec![
+            Parameter::new("id", CLType::U256),
+            Parameter::new("evidence", CLType::String),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    #[cfg(feature = "bonding")]
+    eps.add_entry_point(EntryPoint::new(
+        "cancel_slash", vec![Parameter::new("id", CLType::U256)],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    #[cfg(feature = "bonding")]
+    eps.add_entry_point(EntryPoint::new(
+        "execute_slash", vec![Parameter::new("id", CLType::U256)],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    #[cfg(feature = "bonding")]
+    eps.add_entry_point(EntryPoint::new(
+        "get_slash", vec![Parameter::new("id", CLType::U256)],
+        CLType::Tuple3([Box::new(CLType::U64), Box::new(CLType::U32), Box::new(CLType::U512)]), EntryPointAccess::Public, CALLED,
+    ));
+
+    let policy = CLType::Option(Box::new(CLType::Tuple3([Box::new(CLType::U64), Box::new(CLType::U8), Box::new(CLType::U64)])));
+    eps.add_entry_point(EntryPoint::new(
+        "set_confirmation_policy",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("policy", policy.clone()),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_confirmation_policy", vec![Parameter::new("account", CLType::ByteArray(32))],
+        policy, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "confirm", vec![Parameter::new("id", CLType::U256)],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "set_recovery_lifetime",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("lifetime", CLType::U64),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "update_settings",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("expiry", CLType::U64),
+            Parameter::new("delay", CLType::U64),
+            Parameter::new("max_attempts", CLType::U8),
+            Parameter::new("veto_window", CLType::U64),
+            Parameter::new("approval_window", CLType::U64),
+            Parameter::new("by_height", CLType::Bool),
+            Parameter::new("rotation_window", CLType::U64),
+            Parameter::new("freeze_period", CLType::U64),
+            Parameter::new("grace_period", CLType::U64),
+            Parameter::new("grace_weight", CLType::U8),
+            Parameter::new("cooldown", CLType::U64),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "set_recovery_modules",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("modules", CLType::List(Box::new(CLType::Key))),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_recovery_modules", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::List(Box::new(CLType::Key)), EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_recovery_counts", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::Tuple2([Box::new(CLType::List(Box::new(CLType::U32))), Box::new(CLType::List(Box::new(CLType::U32)))]),
+        EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "set_anomaly_limits",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("limits", CLType::List(Box::new(CLType::U32))),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_cooldown", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::U64, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_settings", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::Tuple3([
+            Box::new(CLType::Tuple3([Box::new(CLType::U64), Box::new(CLType::U64), Box::new(CLType::U8)])),
+            Box::new(CLType::Tuple3([Box::new(CLType::U64), Box::new(CLType::U64), Box::new(CLType::U64)])),
+            Box::new(CLType::Tuple3([Box::new(CLType::Bool), Box::new(CLType::U8), Box::new(CLType::U64)])),
+        ]),
+        EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_freeze", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::U64, EntryPointAccess::Public, CALLED,
+    ));
+
+    let minimums = CLType::List(Box::new(CLType::Tuple2([Box::new(CLType::U8), Box::new(CLType::U8)])));
+    let categories = CLType::List(Box::new(CLType::Tuple2([Box::new(CLType::Key), Box::new(CLType::U8)])));
+    #[cfg(feature = "policies")]
+    eps.add_entry_point(EntryPoint::new(
+        "set_guardian_categories",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("categories", categories.clone()),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    #[cfg(feature = "policies")]
+    eps.add_entry_point(EntryPoint::new(
+        "set_category_policy",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("minimums", CLType::Option(Box::new(minimums.clone()))),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    #[cfg(feature = "policies")]
+    eps.add_entry_point(EntryPoint::new(
+        "get_category_policy", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::Tuple2([Box::new(categories), Box::new(CLType::Option(Box::new(minimums)))]),
+        EntryPointAccess::Public, CALLED,
+    ));
+
+    #[cfg(feature = "policies")]
+    eps.add_entry_point(EntryPoint::new(
+        "set_approval_policy",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("policy", CLType::List(Box::new(CLType::U8))),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    #[cfg(feature = "policies")]
+    eps.add_entry_point(EntryPoint::new(
+        "get_approval_policy", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::List(Box::new(CLType::U8)), EntryPointAccess::Public, CALLED,
+    ));
+
+    #[cfg(feature = "policies")]
+    eps.add_entry_point(EntryPoint::new(
+        "set_guardian_term",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("guardian", CLType::Key),
+            Parameter::new("until", CLType::Option(Box::new(CLType::U64))),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    #[cfg(feature = "policies")]
+    eps.add_entry_point(EntryPoint::new(
+        "suspend_guardian",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("guardian", CLType::Key),
+            Parameter::new("suspended", CLType::Bool),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    #[cfg(feature = "policies")]
+    eps.add_entry_point(EntryPoint::new(
+        "get_guardian_term",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("guardian", CLType::Key),
+        ],
+        CLType::Tuple2([Box::new(CLType::Option(Box::new(CLType::U64))), Box::new(CLType::Bool)]),
+        EntryPointAccess::Public, CALLED,
+    ));
+
+    #[cfg(feature = "policies")]
+    eps.add_entry_point(EntryPoint::new(
+        "get_category_shortfall", vec![Parameter::new("id", CLType::U256)],
+        CLType::List(Box::new(CLType::Tuple2([Box::new(CLType::U8), Box::new(CLType::U8)]))),
+        EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "set_arbiter",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("arbiter", CLType::Option(Box::new(CLType::ByteArray(32)))),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_arbiter", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::Option(Box::new(CLType::ByteArray(32))), EntryPointAccess::Public, CALLED,
+    ));
+
+    for name in ["arbiter_extend", "arbiter_cancel", "arbiter_approve", "arbiter_dismiss_flags"] {
+        eps.add_entry_point(EntryPoint::new(
+            name, vec![Parameter::new("id", CLType::U256)],
+            CLType::Unit, EntryPointAccess::Public, CALLED,
+        ));
+    }
+
+    eps.add_entry_point(EntryPoint::new(
+        "set_share",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("guardian", CLType::ByteArray(32)),
+            Parameter::new("hash", CLType::ByteArray(32)),
+            Parameter::new("uri", CLType::String),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "clear_share",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("guardian", CLType::ByteArray(32)),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "attest_share",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("hash", CLType::ByteArray(32)),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_shares", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::List(Box::new(CLType::Tuple3([
+            Box::new(CLType::ByteArray(32)),
+            Box::new(CLType::Tuple2([Box::new(CLType::ByteArray(32)), Box::new(CLType::String)])),
+            Box::new(CLType::U64),
+        ]))),
+        EntryPointAccess::Public, CALLED,
+    ));
+
+    for name in ["grant_roles", "revoke_roles"] {
+        eps.add_entry_point(EntryPoint::new(
+            name,
+            vec![
+                Parameter::new("account", CLType::ByteArray(32)),
+                Parameter::new("member", CLType::Key),
+                Parameter::new("roles", CLType::U8),
+            ],
+            CLType::Unit, EntryPointAccess::Public, CALLED,
+        ));
+    }
+
+    eps.add_entry_point(EntryPoint::new(
+        "renounce_roles",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("roles", CLType::U8),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_roles", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::List(Box::new(CLType::Tuple2([Box::new(CLType::Key), Box::new(CLType::U8)]))),
+        EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_member_roles",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("member", CLType::Key),
+        ],
+        CLType::U8, EntryPointAccess::Public, CALLED,
+    ));
+
+    for name in ["add_watcher", "remove_watcher"] {
+        eps.add_entry_point(EntryPoint::new(
+            name,
+            vec![
+                Parameter::new("account", CLType::ByteArray(32)),
+                Parameter::new("watcher", CLType::ByteArray(32)),
+            ],
+            CLType::Unit, EntryPointAccess::Public, CALLED,
+        ));
+    }
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_watchers", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::List(Box::new(CLType::ByteArray(32))), EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "designate_heir",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("heir", CLType::ByteArray(32)),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "revoke_heir",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("heir", CLType::ByteArray(32)),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "register_heir_key",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("new_key", CLType::PublicKey),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_heirs", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::List(Box::new(CLType::ByteArray(32))), EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_inheritance_status", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::Tuple2([Box::new(CLType::Bool), Box::new(CLType::U64)]), EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "supports_interface", vec![Parameter::new("interface_id", CLType::U32)],
+        CLType::Bool, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "supported_interfaces", vec![],
+        CLType::U64, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "decommission", vec![Parameter::new("sunset", CLType::U64)],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "set_fee_schedule", vec![Parameter::new("fees", CLType::List(Box::new(CLType::U512)))],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_fee_schedule", vec![],
+        CLType::List(Box::new(CLType::U512)), EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "withdraw_fees",
+        vec![
+            Parameter::new("amount", CLType::U512),
+            Parameter::new("destination", CLType::Key),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_fee_balance", vec![],
+        CLType::U512, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "create_tenant", vec![Parameter::new("admin", CLType::ByteArray(32))],
+        CLType::U32, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "set_tenant_admin",
+        vec![
+            Parameter::new(ARG_TENANT, CLType::U32),
+            Parameter::new("admin", CLType::ByteArray(32)),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_tenant_admin", vec![Parameter::new(ARG_TENANT, CLType::U32)],
+        CLType::Option(Box::new(CLType::ByteArray(32))), EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_tenant_count", vec![],
+        CLType::U32, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_lifecycle", vec![],
+        CLType::Tuple3([Box::new(CLType::U8), Box::new(CLType::U64), Box::new(CLType::U64)]),
+        EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "migrate", vec![],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_schema_version", vec![],
+        CLType::U32, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "is_guardian_of_v1",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("guardian", CLType::Key),
+        ],
+        CLType::Bool, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "guardian_count_v1", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::U32, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "has_min_guardians_v1",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("min", CLType::U32),
+        ],
+        CLType::Bool, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "guardianship_version", vec![],
+        CLType::U32, EntryPointAccess::Public, CALLED,
+    ));
+
+    let listing = || CLType::Tuple3([Box::new(CLType::U512), Box::new(CLType::ByteArray(32)), Box::new(CLType::U64)]);
+    eps.add_entry_point(EntryPoint::new(
+        "list_guardian",
+        vec![
+            Parameter::new("fee", CLType::U512),
+            Parameter::new("contact", CLType::ByteArray(32)),
+            Parameter::new("policies", CLType::U64),
+        ],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new("unlist_guardian", vec![], CLType::Unit, EntryPointAccess::Public, CALLED));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_directory_listing", vec![Parameter::new("guardian", CLType::ByteArray(32))],
+        CLType::Option(Box::new(listing())), EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "find_guardians",
+        vec![
+            Parameter::new("max_fee", CLType::Option(Box::new(CLType::U512))),
+            Parameter::new("policies", CLType::U64),
+            Parameter::new("offset", CLType::U32),
+            Parameter::new("limit", CLType::U32),
+        ],
+        CLType::List(Box::new(CLType::Tuple2([Box::new(CLType::ByteArray(32)), Box::new(listing())]))),
+        EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_reputation", vec![Parameter::new("guardian", CLType::ByteArray(32))],
+        CLType::Tuple3([Box::new(CLType::U32), Box::new(CLType::List(Box::new(CLType::U32))), Box::new(CLType::U32)]),
+        EntryPointAccess::Public, CALLED,
+    ));
+
+    eps
+}
+
+/// The hooks a recovery module installs (see `modules`), alongside its own entry points
+pub fn module_entry_points() -> EntryPoints {
+    let mut eps = EntryPoints::new();
+    let id = || Parameter::new("id", CLType::U256);
+    let account = || Parameter::new("account", CLType::ByteArray(32));
+
+    eps.add_entry_point(EntryPoint::new(
+        modules::ON_INITIATE,
+        vec![id(), account(), Parameter::new("new_key", CLType::PublicKey)],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        modules::ON_APPROVE,
+        vec![id(), account(), Parameter::new("guardian", CLType::Key)],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        modules::ON_EXECUTE,
+        vec![id(), account()],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps
+}
+
+/// Every entry point the recovery executor installs
+pub fn executor_entry_points() -> EntryPoints {
+    let mut eps = EntryPoints::new();
+
+    eps.add_entry_point(EntryPoint::new(
+        "init_storage", vec![],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "queue", vec![Parameter::new("id", CLType::U256)],
+        CLType::U64, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "execute", vec![Parameter::new("id", CLType::U256)],
+        CLType::PublicKey, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_execution", vec![Parameter::new("id", CLType::U256)],
+        CLType::Option(Box::new(CLType::Tuple3([Box::new(CLType::ByteArray(32)), Box::new(CLType::U64), Box::new(CLType::U64)]))),
+        EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "set_registry", vec![Parameter::new("registry", CLType::Key)],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "set_timelock", vec![Parameter::new("timelock", CLType::U64)],
+        CLType::Unit, EntryPointAccess::Public, CALLED,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_executor_config", vec![],
+        CLType::Tuple3([Box::new(CLType::Key), Box::new(CLType::U64), Box::new(CLType::Key)]),
+        EntryPointAccess::Public, CALLED,
+    ));
+
+    eps
+}
@@ -3,6 +3,7 @@
 use casper_types::ApiError;
 
 /// Errors for recovery_registry contract
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(u16)]
 pub enum GuardianError {
     /// Invalid guardian setup (less than 2 guardians or duplicates)
@@ -20,3 +21,207 @@ impl From<GuardianError> for ApiError {
         ApiError::User(error as u16)
     }
 }
+
+macro_rules! registry_errors {
+    ($($(#[doc = $doc:literal])+ $name:ident = $code:literal,)+) => {
+        /// Codes the recovery registry reverts with, as `ApiError::User(code)`
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        #[repr(u16)]
+        pub enum RegistryError {
+            $($(#[doc = $doc])+ $name = $code,)+
+        }
+
+        impl RegistryError {
+            /// Every code, in order
+            pub const ALL: &'static [RegistryError] = &[$(RegistryError::$name,)+];
+
+            /// Error of an `ApiError::User` code
+            pub fn from_code(code: u16) -> Option<Self> {
+                match code {
+                    $($code => Some(RegistryError::$name),)+
+                    _ => None,
+                }
+            }
+
+            /// Variant name, e.g. `NotOwner`
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(RegistryError::$name => stringify!($name),)+
+                }
+            }
+
+            /// The variant's doc comment
+            pub fn description(self) -> &'static str {
+                match self {
+                    $(RegistryError::$name => concat!($($doc),+).trim(),)+
+                }
+            }
+        }
+    };
+}
+
+registry_errors! {
+    /// Caller is not the account owner, nor a member holding the role the call needs
+    NotOwner = 1,
+    /// Guardians are already set up for this account
+    AlreadyInit = 2,
+    /// Fewer than two guardians, a duplicate, or the owner among them
+    BadGuardians = 3,
+    /// Threshold is zero or above the guardian count
+    BadThreshold = 4,
+    /// Caller is not a guardian of the account
+    NotGuardian = 5,
+    /// The account already has a recovery open
+    RecoveryExists = 6,
+    /// No such recovery, or it is closed
+    NotFound = 7,
+    /// Caller already voted on this recovery
+    AlreadyApproved = 8,
+    /// The recovery hasn't reached its threshold
+    NotApproved = 9,
+    /// The account has no guardians set up
+    NotInit = 10,
+    /// The registry's storage dictionary is missing; call `init_storage`
+    MissingDict = 11,
+    /// Caller is not an heir of the account
+    NotHeir = 12,
+    /// Heir is the owner, a guardian or already designated
+    BadHeir = 13,
+    /// The new key isn't one an heir registered
+    NotHeirKey = 14,
+    /// Unknown recovery type
+    BadType = 15,
+    /// Caller hasn't posted the account's guardian bond
+    BondRequired = 16,
+    /// Bond already posted in full, or the transfer failed
+    BadBond = 17,
+    /// The recovery was vetoed
+    Vetoed = 18,
+    /// The recovery wasn't vetoed
+    NotVetoed = 19,
+    /// Slash share out of range, evidence too long, or already proposed
+    BadSlash = 20,
+    /// No slash proposed for this recovery
+    NoSlash = 21,
+    /// Outside the slash dispute window
+    DisputeWindow = 22,
+    /// A slash against the account's guardians is still open
+    SlashPending = 23,
+    /// The reward pool can't cover the withdrawal
+    InsufficientRewards = 24,
+    /// The recovery is already finalized
+    Finalized = 25,
+    /// The account requires a recovery deposit; pass a funded `purse`
+    DepositRequired = 26,
+    /// Delegate is the owner, a guardian, already taken, or the delegation has already ended
+    BadDelegate = 27,
+    /// Merkle proof doesn't show the caller in the committed guardian set
+    BadProof = 28,
+    /// The recovery type's verifier isn't available for this account
+    BadVerifier = 29,
+    /// Caller stored no share for the account
+    NoShare = 30,
+    /// Share URI too long, or the attested hash doesn't match
+    BadShare = 31,
+    /// Instructions URI longer than 256 bytes
+    BadUri = 32,
+    /// Caller is not the recovery's arbiter
+    NotArbiter = 33,
+    /// Arbiter is the owner or a guardian, or a tie-break isn't possible
+    BadArbiter = 34,
+    /// The arbiter already used this power on the recovery
+    ArbiterUsed = 35,
+    /// The recovery has expired
+    Expired = 36,
+    /// The recovery lacks its confirmation quorum
+    NotConfirmed = 37,
+    /// Outside the confirmation window
+    ConfirmWindow = 38,
+    /// Memo longer than 140 bytes
+    BadMemo = 39,
+    /// A guardian key's algorithm isn't allowed by the account's key policy
+    BadKeyType = 40,
+    /// Signature doesn't verify against the payload
+    BadSignature = 41,
+    /// Settings under which no recovery could finalize, or more anomaly limits than counters
+    BadSettings = 42,
+    /// The wait before this step hasn't passed
+    TooEarly = 43,
+    /// Too many unfinalized recoveries; the owner has to reset the count
+    TooManyAttempts = 44,
+    /// Reward provider must be an account or a contract
+    BadProvider = 45,
+    /// Batch empty or larger than the limit
+    BadBatch = 46,
+    /// Watcher already added, list full, or not a watcher
+    BadWatcher = 47,
+    /// Configuration is frozen after a malicious veto
+    Frozen = 48,
+    /// No FROST group key registered, or the guardian set changed since it was
+    NoFrostKey = 49,
+    /// Enough guardians flagged the recovery to hold it until they withdraw or the arbiter rules
+    Flagged = 50,
+    /// Caller already flagged the recovery, has no flag on it to withdraw, or the flag limit is zero
+    BadFlag = 51,
+    /// Approval policy malformed, its weights or category minimums invalid for the guardians, or not
+    /// one this entry point can change or approve under
+    BadPolicy = 52,
+    /// A recovery of the account finalized too recently; its cool-down hasn't passed
+    CoolingDown = 53,
+    /// Too many recovery modules, one listed twice, or one that isn't a contract package
+    BadModule = 54,
+    /// Registry is decommissioning and takes no new accounts or recoveries, or its sunset has
+    /// passed and it takes no changes at all
+    Decommissioned = 55,
+    /// Caller is not the registry's admin, or the registry is already decommissioning
+    NotAdmin = 56,
+    /// Meta-approval signed for another nonce than the guardian's current one: already used, voided,
+    /// or ahead of one not yet submitted
+    BadNonce = 57,
+    /// Caller isn't on the account's relayer allowlist, or the allowlist is too long or names a
+    /// relayer twice
+    NotRelayer = 58,
+    /// Call passed an argument the entry point doesn't take, on a registry installed with
+    /// `strict_args`
+    UnexpectedArgument = 59,
+    /// The records are in a newer schema than this build migrates to
+    BadMigration = 60,
+    /// The caller has no pending invitation from the account
+    NoInvitation = 61,
+    /// The invitation's deadline has passed, or the deadline given is already past
+    InvitationExpired = 62,
+    /// The caller isn't listed in the guardian directory
+    NotListed = 63,
+    /// Fee schedule doesn't give exactly one fee per chargeable operation
+    BadFeeSchedule = 64,
+    /// The operation carries a protocol fee and the call passed no `purse`, or one that couldn't pay it
+    FeeRequired = 65,
+    /// Fee withdrawal destination isn't an account, or is the all-zero account hash
+    BadDestination = 66,
+    /// Fee withdrawal of nothing, or of more than the fee purse holds
+    BadWithdrawal = 67,
+    /// The call names a tenant that doesn't exist, or tenant 0 where a created tenant is needed
+    NoTenant = 68,
+    /// Role change naming no known role, a member that is the owner itself, or one member too many
+    BadRole = 69,
+    /// The account has recovery initiators and the caller isn't one of them
+    NotInitiator = 70,
+    /// No built-in template has that name, or the guardians don't fill its slots exactly
+    BadTemplate = 71,
+}
+
+impl RegistryError {
+    /// Error behind a node's execution error message: `User error: 12` (Casper 1.x) or
+    /// `ApiError::User(12) [65548]` (2.x)
+    pub fn from_revert(message: &str) -> Option<Self> {
+        let rest = message.split_once("User error: ").or_else(|| message.split_once("User("))?.1;
+        let digits = rest.find(|c: char| !c.is_ascii_digit()).map_or(rest, |end| &rest[..end]);
+        Self::from_code(digits.parse().ok()?)
+    }
+}
+
+impl From<RegistryError> for ApiError {
+    fn from(error: RegistryError) -> Self {
+        ApiError::User(error as u16)
+    }
+}
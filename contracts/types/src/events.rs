@@ -0,0 +1,155 @@
+//! Registry events in the Casper Event Standard (CES) layout.
+//!
+//! Each event is stored as its name prefixed with `event_`, followed by its fields in schema order.
+//! Names carry the version: a changed layout is a new `…V2` struct, and the old one keeps decoding.
+
+use alloc::{collections::BTreeMap, format, string::String, vec, vec::Vec};
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{self, ToBytes},
+    CLType, CLTyped, Key, PublicKey, U256, U512,
+};
+
+/// CES version the registry's event named keys follow
+pub const CES_VERSION: &str = "0.1.0";
+
+pub trait Event {
+    const NAME: &'static str;
+    /// (field name, type) of each field, in serialization order
+    fn schema() -> Vec<(String, CLType)>;
+    fn event_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error>;
+}
+
+macro_rules! events {
+    ($($(#[$doc:meta])* $name:ident { $($field:ident: $ty:ty),* $(,)? })*) => {
+        $(
+            $(#[$doc])*
+            pub struct $name { $(pub $field: $ty),* }
+
+            impl Event for $name {
+                const NAME: &'static str = stringify!($name);
+
+                fn schema() -> Vec<(String, CLType)> {
+                    vec![$((stringify!($field).into(), <$ty>::cl_type())),*]
+                }
+
+                fn event_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+                    let mut out = format!("event_{}", Self::NAME).to_bytes()?;
+                    $(out.extend(self.$field.to_bytes()?);)*
+                    Ok(out)
+                }
+            }
+        )*
+
+        /// Schemas of every event, registered under `__events_schema` at install
+        pub fn schemas() -> Schemas {
+            Schemas(BTreeMap::from([$(($name::NAME.into(), $name::schema())),*]))
+        }
+    };
+}
+
+events! {
+    /// An account registered its guardian set
+    GuardiansInitializedV1 { account: AccountHash, threshold: u8 }
+    GuardianAddedV1 { account: AccountHash, guardian: Key }
+    GuardianRemovedV1 { account: AccountHash, guardian: Key }
+    /// A guardian asked to move to `new_key`; it takes effect at `effective_at` unless the owner vetoes
+    GuardianRotationRequestedV1 { account: AccountHash, guardian: AccountHash, new_key: PublicKey, effective_at: u64 }
+    GuardianKeyRotatedV1 { account: AccountHash, old: AccountHash, new: AccountHash }
+    /// The owner invited `guardian`, who joins by accepting before `deadline` (block time, ms).
+    /// Accepting emits `GuardianAddedV1`.
+    GuardianInvitedV1 { account: AccountHash, guardian: AccountHash, deadline: u64 }
+    /// `guardian`'s invitation passed its deadline unaccepted and was dropped
+    InvitationExpiredV1 { account: AccountHash, guardian: AccountHash, deadline: u64 }
+    RecoveryStartedV1 { id: U256, account: AccountHash, new_key: PublicKey }
+    /// `approvals` is the count after this approval
+    RecoveryApprovedV1 { id: U256, guardian: Key, approvals: u8 }
+    RecoveryFinalizedV1 { id: U256, account: AccountHash }
+    /// `vetoed` distinguishes an owner veto from an arbiter cancellation
+    RecoveryCancelledV1 { id: U256, account: AccountHash, vetoed: bool }
+    /// V2 recovery events carry the account's watch-only `notify` list for off-chain notifiers
+    RecoveryStartedV2 { id: U256, account: AccountHash, new_key: PublicKey, notify: Vec<[u8; 32]> }
+    RecoveryApprovedV2 { id: U256, account: AccountHash, guardian: Key, approvals: u8, notify: Vec<[u8; 32]> }
+    RecoveryFinalizedV2 { id: U256, account: AccountHash, notify: Vec<[u8; 32]> }
+    RecoveryCancelledV2 { id: U256, account: AccountHash, vetoed: bool, notify: Vec<[u8; 32]> }
+    /// The guardians' FROST group approved with one aggregate signature, reaching the threshold
+    RecoveryFrostApprovedV1 { id: U256, account: AccountHash, group_key: PublicKey, notify: Vec<[u8; 32]> }
+    /// A relayed batch of signed approvals; `outcomes` has each guardian's `RegistryError` code, 0 if
+    /// counted (those also get their own `RecoveryApprovedV2`)
+    SignedApprovalsSubmittedV1 { id: U256, account: AccountHash, counted: u8, outcomes: Vec<(PublicKey, u32)>, notify: Vec<[u8; 32]> }
+    /// A guardian flagged a pending recovery as suspected fraud, with a `flags::` reason code. High
+    /// priority: notifiers should reach the owner at once. `flags` is the count after this one.
+    RecoveryFlaggedV1 { id: U256, account: AccountHash, guardian: Key, reason: u8, flags: u8, notify: Vec<[u8; 32]> }
+    RecoveryFlagWithdrawnV1 { id: U256, account: AccountHash, guardian: Key, flags: u8 }
+    /// A lifetime counter (`counters::*`) of the account passed its anomaly limit with recovery `id`
+    RecoveryAnomalyV1 { id: U256, account: AccountHash, counter: u8, count: u32, limit: u32, notify: Vec<[u8; 32]> }
+    /// `entry_point` turned away `guardian`'s part of a call with `RegistryError` `code` and went on
+    /// with the rest. `arg` is the offending argument's position in the entry point's parameters and
+    /// `item` its position within that argument when it is a list. A reverted deploy keeps no
+    /// events, so only entry points that skip rather than revert emit this.
+    RejectedV1 { entry_point: String, account: AccountHash, guardian: Key, code: u32, arg: u8, item: u32 }
+    /// `caller` called `entry_point`, which `deprecations` lists as replaced by `replacement`
+    EntryPointDeprecatedV1 { entry_point: String, replacement: String, caller: AccountHash }
+    /// `guardian` listed itself in the guardian directory, or changed its listing
+    GuardianListedV1 { guardian: AccountHash, fee: U512, contact: [u8; 32], policies: u64 }
+    GuardianUnlistedV1 { guardian: AccountHash }
+    /// The admin set the fee schedule, indexed by `fees::*`
+    FeeScheduleSetV1 { fees: Vec<U512> }
+    /// `payer` paid `amount` for `operation` (`fees::*`) on `account`
+    FeeChargedV1 { operation: u8, account: AccountHash, payer: AccountHash, amount: U512 }
+    /// The admin withdrew `amount` of collected fees to `destination`
+    FeesWithdrawnV1 { destination: AccountHash, amount: U512 }
+    /// The registry's admin created `tenant`, administered by `admin`
+    TenantCreatedV1 { tenant: u32, admin: AccountHash }
+    /// `tenant`'s admin handed over to `admin`
+    TenantAdminChangedV1 { tenant: u32, admin: AccountHash }
+    /// Events of tenant-scoped operations carry the `tenant` whose records they changed: account
+    /// hashes and recovery ids repeat across tenants, so they alone don't say which records moved
+    GuardiansInitializedV2 { tenant: u32, account: AccountHash, threshold: u8 }
+    GuardianAddedV2 { tenant: u32, account: AccountHash, guardian: Key }
+    GuardianRemovedV2 { tenant: u32, account: AccountHash, guardian: Key }
+    GuardianRotationRequestedV2 { tenant: u32, account: AccountHash, guardian: AccountHash, new_key: PublicKey, effective_at: u64 }
+    GuardianKeyRotatedV2 { tenant: u32, account: AccountHash, old: AccountHash, new: AccountHash }
+    /// Accepting emits `GuardianAddedV2`
+    GuardianInvitedV2 { tenant: u32, account: AccountHash, guardian: AccountHash, deadline: u64 }
+    InvitationExpiredV2 { tenant: u32, account: AccountHash, guardian: AccountHash, deadline: u64 }
+    RecoveryStartedV3 { tenant: u32, id: U256, account: AccountHash, new_key: PublicKey, notify: Vec<[u8; 32]> }
+    RecoveryApprovedV3 { tenant: u32, id: U256, account: AccountHash, guardian: Key, approvals: u8, notify: Vec<[u8; 32]> }
+    RecoveryFinalizedV3 { tenant: u32, id: U256, account: AccountHash, notify: Vec<[u8; 32]> }
+    RecoveryCancelledV3 { tenant: u32, id: U256, account: AccountHash, vetoed: bool, notify: Vec<[u8; 32]> }
+    RecoveryFrostApprovedV2 { tenant: u32, id: U256, account: AccountHash, group_key: PublicKey, notify: Vec<[u8; 32]> }
+    /// Counted guardians also get their own `RecoveryApprovedV3`
+    SignedApprovalsSubmittedV2 { tenant: u32, id: U256, account: AccountHash, counted: u8, outcomes: Vec<(PublicKey, u32)>, notify: Vec<[u8; 32]> }
+    RecoveryFlaggedV2 { tenant: u32, id: U256, account: AccountHash, guardian: Key, reason: u8, flags: u8, notify: Vec<[u8; 32]> }
+    RecoveryFlagWithdrawnV2 { tenant: u32, id: U256, account: AccountHash, guardian: Key, flags: u8 }
+    RecoveryAnomalyV2 { tenant: u32, id: U256, account: AccountHash, counter: u8, count: u32, limit: u32, notify: Vec<[u8; 32]> }
+    RejectedV2 { tenant: u32, entry_point: String, account: AccountHash, guardian: Key, code: u32, arg: u8, item: u32 }
+    GuardianListedV2 { tenant: u32, guardian: AccountHash, fee: U512, contact: [u8; 32], policies: u64 }
+    GuardianUnlistedV2 { tenant: u32, guardian: AccountHash }
+    FeeScheduleSetV2 { tenant: u32, fees: Vec<U512> }
+    FeeChargedV2 { tenant: u32, operation: u8, account: AccountHash, payer: AccountHash, amount: U512 }
+    FeesWithdrawnV2 { tenant: u32, destination: AccountHash, amount: U512 }
+    /// `member` now holds `roles` (`guardian_types::roles` bits) on `account`; 0 once all are revoked
+    RolesChangedV1 { tenant: u32, account: AccountHash, member: Key, roles: u8 }
+    /// `account` was set up from the built-in `guardian_types::templates` template `template`
+    TemplateAppliedV1 { tenant: u32, account: AccountHash, template: String }
+}
+
+/// Event name to field schema, stored as CES expects (`CLType::Any` over a bytesrepr map)
+pub struct Schemas(pub BTreeMap<String, Vec<(String, CLType)>>);
+
+impl CLTyped for Schemas {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for Schemas {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        self.0.to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.0.serialized_length()
+    }
+}
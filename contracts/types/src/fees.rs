@@ -0,0 +1,20 @@
+//! Registry operations the admin can charge a protocol fee for, as indexes into the fee schedule
+//! `set_fee_schedule` takes and `get_fee_schedule` returns. Every fee is zero until the admin sets
+//! one.
+
+/// Registering an account's guardians: `init_guardians`, each entry of `init_guardians_batch`,
+/// `init_private_guardians` and `init_merkle_guardians`
+pub const SETUP: u8 = 0;
+/// Finalizing a recovery
+pub const FINALIZE: u8 = 1;
+/// Number of chargeable operations
+pub const COUNT: usize = 2;
+
+/// Short name of an operation, for display
+pub fn name(operation: u8) -> &'static str {
+    match operation {
+        SETUP => "setup",
+        FINALIZE => "finalize",
+        _ => "unknown",
+    }
+}
@@ -0,0 +1,24 @@
+//! Reason codes guardians give `flag_recovery`. The registry stores any code; wallets and notifiers
+//! should show unknown ones as `OTHER`.
+
+/// No specific reason, or one the guardian explains off-chain
+pub const OTHER: u8 = 0;
+/// The guardian reached the owner, who didn't ask for this recovery
+pub const OWNER_DENIES: u8 = 1;
+/// The new key isn't one the owner told the guardian about
+pub const UNKNOWN_KEY: u8 = 2;
+/// Whoever started the recovery contacted the guardian in a suspicious way (pressure, impersonation)
+pub const SOCIAL_ENGINEERING: u8 = 3;
+/// Another guardian's key or account looks compromised
+pub const COMPROMISED_GUARDIAN: u8 = 4;
+
+/// Short name of a reason code, for display
+pub fn name(reason: u8) -> &'static str {
+    match reason {
+        OWNER_DENIES => "owner_denies",
+        UNKNOWN_KEY => "unknown_key",
+        SOCIAL_ENGINEERING => "social_engineering",
+        COMPROMISED_GUARDIAN => "compromised_guardian",
+        _ => "other",
+    }
+}
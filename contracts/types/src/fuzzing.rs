@@ -0,0 +1,152 @@
+//! `Arbitrary` values of the types the registry stores, and the bytesrepr round-trip check the
+//! fuzz targets in `fuzz/` run over them. Built with the `arbitrary` feature, for host-side property
+//! tests and fuzzing only.
+//!
+//! Keys and account hashes come from raw bytes, so generated policies name arbitrary guardians;
+//! nothing here checks a value is one the registry would accept, only that it encodes losslessly.
+
+use alloc::vec::Vec;
+use core::fmt::Debug;
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{FromBytes, ToBytes},
+    Key,
+};
+
+use crate::{meta::NonceLedger, policy::ApprovalPolicy, settings::AccountSettings};
+
+/// A guardian key in one of the forms the registry stores: an account or a contract package
+pub fn key(u: &mut Unstructured) -> Result<Key> {
+    let bytes: [u8; 32] = u.arbitrary()?;
+    Ok(if u.arbitrary()? { Key::Account(AccountHash::new(bytes)) } else { Key::Hash(bytes) })
+}
+
+/// Up to 16 (guardian, weight) pairs
+fn weights<W: for<'a> Arbitrary<'a>>(u: &mut Unstructured) -> Result<Vec<(Key, W)>> {
+    let len = u.int_in_range(0..=16usize)?;
+    (0..len).map(|_| Ok((key(u)?, u.arbitrary()?))).collect()
+}
+
+impl<'a> Arbitrary<'a> for ApprovalPolicy {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=4u8)? {
+            0 => ApprovalPolicy::Threshold { threshold: u.arbitrary()? },
+            1 => ApprovalPolicy::Weighted { weights: weights(u)?, threshold: u.arbitrary()? },
+            2 => ApprovalPolicy::CategoryQuorum { threshold: u.arbitrary()?, minimums: u.arbitrary()? },
+            3 => ApprovalPolicy::Decaying { threshold: u.arbitrary()?, steps: u.arbitrary()? },
+            _ => ApprovalPolicy::Fractional { weights: weights(u)?, threshold_bps: u.arbitrary()? },
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for AccountSettings {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(AccountSettings {
+            expiry: u.arbitrary()?,
+            delay: u.arbitrary()?,
+            max_attempts: u.arbitrary()?,
+            veto_window: u.arbitrary()?,
+            approval_window: u.arbitrary()?,
+            by_height: u.arbitrary()?,
+            rotation_window: u.arbitrary()?,
+            freeze_period: u.arbitrary()?,
+            grace_period: u.arbitrary()?,
+            grace_weight: u.arbitrary()?,
+            cooldown: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for NonceLedger {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        // Issued the way a wallet does: consecutive nonces from the one on chain
+        let chain = u.int_in_range(0..=u32::MAX as u64)?;
+        let mut ledger = NonceLedger::new();
+        for payload in u.arbitrary_iter::<Vec<u8>>()? {
+            let nonce = ledger.next(chain);
+            ledger.record(chain, nonce, payload?).map_err(|_| arbitrary::Error::IncorrectFormat)?;
+        }
+        Ok(ledger)
+    }
+}
+
+/// Panics unless `value` survives a bytesrepr round trip: `to_bytes` is `serialized_length` long,
+/// `from_bytes` gives `value` back and consumes exactly those bytes, trailing bytes are left
+/// untouched, and every truncation fails to decode instead of yielding some other value.
+pub fn roundtrip<T: ToBytes + FromBytes + PartialEq + Debug>(value: &T) {
+    let bytes = value.to_bytes().expect("serializes");
+    assert_eq!(bytes.len(), value.serialized_length(), "serialized_length of {:?}", value);
+
+    let (decoded, rest) = T::from_bytes(&bytes).expect("deserializes");
+    assert_eq!(&decoded, value);
+    assert!(rest.is_empty(), "{} bytes left over decoding {:?}", rest.len(), value);
+
+    let mut padded = bytes.clone();
+    padded.extend_from_slice(&[0xAB; 3]);
+    let (_, rest) = T::from_bytes(&padded).expect("deserializes with trailing bytes");
+    assert_eq!(rest, &[0xAB; 3]);
+
+    for len in 0..bytes.len() {
+        assert!(T::from_bytes(&bytes[..len]).is_err(), "{:?} decoded from {} of {} bytes", value, len, bytes.len());
+    }
+}
+
+/// Panics unless decoding arbitrary `bytes` as `T` either fails or re-encodes to exactly the bytes
+/// it consumed, so no two encodings decode to the same value. Not for types holding a `BTreeMap`,
+/// whose decoding accepts entries in any order.
+pub fn canonical<T: ToBytes + FromBytes + PartialEq + Debug>(bytes: &[u8]) {
+    if let Ok((value, rest)) = T::from_bytes(bytes) {
+        let consumed = &bytes[..bytes.len() - rest.len()];
+        assert_eq!(value.to_bytes().expect("serializes"), consumed, "{:?} has another encoding", value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! The fuzz targets' checks as property tests, so `cargo test --features arbitrary` runs them on
+    //! stable without cargo-fuzz: proptest feeds random bytes to the same `Arbitrary` impls.
+
+    use proptest::{collection::vec, prelude::*};
+
+    use super::*;
+
+    /// The value `bytes` make through `Arbitrary`, if they make one
+    fn make<T: for<'a> Arbitrary<'a>>(bytes: &[u8]) -> Option<T> {
+        T::arbitrary(&mut Unstructured::new(bytes)).ok()
+    }
+
+    proptest! {
+        #[test]
+        fn policies_roundtrip(bytes in vec(any::<u8>(), 0..1024)) {
+            if let Some(policy) = make::<ApprovalPolicy>(&bytes) { roundtrip(&policy); }
+        }
+
+        #[test]
+        fn settings_roundtrip(bytes in vec(any::<u8>(), 0..128)) {
+            if let Some(settings) = make::<AccountSettings>(&bytes) { roundtrip(&settings); }
+        }
+
+        #[test]
+        fn nonce_ledgers_roundtrip(bytes in vec(any::<u8>(), 0..1024)) {
+            if let Some(ledger) = make::<NonceLedger>(&bytes) { roundtrip(&ledger); }
+        }
+
+        #[test]
+        fn stored_types_decode_canonically(bytes in vec(any::<u8>(), 0..256)) {
+            canonical::<ApprovalPolicy>(&bytes);
+            canonical::<AccountSettings>(&bytes);
+        }
+
+        #[test]
+        fn policy_encodings_decode_canonically(bytes in vec(any::<u8>(), 0..1024), tail in vec(any::<u8>(), 0..16)) {
+            // Random bytes rarely start a valid policy; an encoded one with junk after it does
+            if let Some(policy) = make::<ApprovalPolicy>(&bytes) {
+                let mut encoded = policy.to_bytes().unwrap();
+                encoded.extend(tail);
+                canonical::<ApprovalPolicy>(&encoded);
+            }
+        }
+    }
+}
@@ -0,0 +1,43 @@
+//! What differs between the hosts the contracts build for: Casper 2.0 (Condor), the default, and
+//! Casper 1.x, built with `--no-default-features --features casper-1` against the 1.x crates. The
+//! rest of the contracts is written against the API both share.
+//!
+//! Condor can also name a package as `Key::SmartContract` and an account as an addressable entity.
+//! The registry stores them in the 1.x forms, `Key::Hash` and `Key::Account`, so its records read
+//! the same whichever host wrote them, and a registry moved from 1.x to Condor needs no rewrite.
+
+use alloc::format;
+#[cfg(feature = "casper-2")]
+use casper_types::EntityAddr;
+use casper_types::{account::AccountHash, EntryPointType, Key};
+
+/// Type of the entry points a contract installs to be called by others
+#[cfg(feature = "casper-2")]
+pub const CALLED: EntryPointType = EntryPointType::Called;
+#[cfg(not(feature = "casper-2"))]
+pub const CALLED: EntryPointType = EntryPointType::Contract;
+
+/// `k` as the registry stores it: a package as `Key::Hash` and an account as `Key::Account`. Other
+/// keys come back unchanged for the caller to accept or reject, including a Condor contract entity,
+/// which names one contract version rather than its package.
+#[cfg(feature = "casper-2")]
+pub fn stored_key(k: Key) -> Key {
+    match k {
+        Key::SmartContract(pkg) => Key::Hash(pkg),
+        Key::AddressableEntity(EntityAddr::Account(acc)) => Key::Account(AccountHash::new(acc)),
+        k => k,
+    }
+}
+
+/// `k` as the registry stores it; 1.x hosts have only the one form of each key
+#[cfg(not(feature = "casper-2"))]
+pub fn stored_key(k: Key) -> Key {
+    k
+}
+
+/// Account hash of an `account-hash-…` string, or of the `entity-account-…` form Condor shows for
+/// the same account once it has migrated to an addressable entity
+pub fn formatted_account(value: &str) -> Option<AccountHash> {
+    let hex = value.strip_prefix("account-hash-").or_else(|| value.strip_prefix("entity-account-"))?;
+    AccountHash::from_formatted_str(&format!("account-hash-{hex}")).ok()
+}
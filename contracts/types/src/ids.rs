@@ -0,0 +1,18 @@
+//! Recovery request IDs, derivable off-chain before `start_recovery` is submitted.
+
+use alloc::vec::Vec;
+use casper_types::{account::AccountHash, bytesrepr::ToBytes, crypto, PublicKey, U256};
+
+/// blake2b of the proposed key's bytesrepr encoding
+pub fn key_commitment(new_key: &PublicKey) -> [u8; 32] {
+    crypto::blake2b(new_key.to_bytes().unwrap_or_default())
+}
+
+/// ID of `account`'s recovery number `nonce` (counting from zero, see `get_recovery_nonce`) to the key
+/// behind `commitment`: blake2b(account hash ‖ nonce as u64 big-endian ‖ commitment), read big-endian
+pub fn recovery_id(account: AccountHash, nonce: u64, commitment: [u8; 32]) -> U256 {
+    let mut preimage: Vec<u8> = account.as_bytes().to_vec();
+    preimage.extend_from_slice(&nonce.to_be_bytes());
+    preimage.extend_from_slice(&commitment);
+    U256::from_big_endian(&crypto::blake2b(preimage))
+}
@@ -0,0 +1,65 @@
+//! Optional registry modules, as bits of the mask returned by `supported_interfaces`.
+//!
+//! IDs are bit positions and never reused; `supports_interface` takes the position.
+
+/// Guardians carry individual voting weights
+pub const WEIGHTED_GUARDIANS: u32 = 0;
+/// Relayed approvals signed off-chain by the guardian (`approve_signed`)
+pub const SIGNED_APPROVALS: u32 = 1;
+/// Heirs and the heartbeat dead-man switch
+pub const INHERITANCE: u32 = 2;
+/// Guardian bonds and slashing of approvers of vetoed recoveries
+pub const BONDING: u32 = 3;
+/// Time-limited delegation of a guardian's vote
+pub const DELEGATION: u32 = 4;
+/// Merkle-committed and privacy-mode guardian sets
+pub const PRIVATE_GUARDIANS: u32 = 5;
+/// Contract packages acting as guardians
+pub const CONTRACT_GUARDIANS: u32 = 6;
+/// Rewards paid to approving guardians
+pub const REWARDS: u32 = 7;
+/// Arbiter who can extend, cancel or tie-break recoveries
+pub const ARBITER: u32 = 8;
+/// Second confirmation round before finalizing
+pub const CONFIRMATION: u32 = 9;
+/// Versioned guardianship queries for contract-to-contract calls (see the `guardian-interface` crate)
+pub const GUARDIANSHIP_QUERIES: u32 = 10;
+/// Guardian groups approving with one FROST aggregate Ed25519 signature (`approve_frost`)
+pub const FROST_APPROVALS: u32 = 11;
+/// Guardians flagging suspect recoveries, optionally holding finalization (`flag_recovery`)
+pub const FLAGGING: u32 = 12;
+/// Per-category approval minimums on top of the threshold (`set_category_policy`)
+pub const CATEGORY_QUORUMS: u32 = 13;
+/// Per-account approval policies: threshold, weighted, category quorum or decaying (`set_approval_policy`)
+pub const APPROVAL_POLICIES: u32 = 14;
+/// Module contracts hooked into recovery start, approval and finalization (`set_recovery_modules`)
+pub const RECOVERY_MODULES: u32 = 15;
+/// CES events for every state change (absent from minimal builds)
+pub const EVENTS: u32 = 16;
+/// Nonce-bound approvals submitted by allowlisted relayers who pay the gas (`approve_meta`)
+pub const META_APPROVALS: u32 = 17;
+/// Opt-in directory of guardians offering their services (`list_guardian`)
+pub const GUARDIAN_DIRECTORY: u32 = 18;
+/// Admin-set protocol fees on setup and finalization (`set_fee_schedule`)
+pub const PROTOCOL_FEES: u32 = 19;
+/// Isolated tenants with their own admin and records, chosen per call by the `tenant` argument
+/// (`create_tenant`)
+pub const TENANTS: u32 = 20;
+/// Per-account roles held by other keys or contracts (`grant_roles`)
+pub const ACCOUNT_ROLES: u32 = 21;
+/// Setting accounts up from built-in policy templates (`init_from_template`)
+pub const POLICY_TEMPLATES: u32 = 22;
+
+/// Current version of the guardianship queries
+pub const GUARDIANSHIP_VERSION: u32 = 1;
+
+/// Mask with the bit of each interface in `ids` set
+pub const fn mask(ids: &[u32]) -> u64 {
+    let mut m = 0u64;
+    let mut i = 0;
+    while i < ids.len() {
+        m |= 1 << ids[i];
+        i += 1;
+    }
+    m
+}
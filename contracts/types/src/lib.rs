@@ -3,12 +3,38 @@
 //! Minimal shared types for the recovery_registry contract.
 //! Session WASMs don't need this library - they're self-contained.
 
-#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
 extern crate alloc;
+#[cfg(not(feature = "casper-2"))]
+extern crate casper_types_1 as casper_types;
 
 pub mod constants;
+pub mod counters;
+pub mod deprecations;
+pub mod entry_points;
 pub mod errors;
+pub mod events;
+pub mod fees;
+pub mod flags;
+#[cfg(feature = "arbitrary")]
+pub mod fuzzing;
+pub mod host;
+pub mod ids;
+pub mod interfaces;
+pub mod lifecycle;
+pub mod meta;
+pub mod migrations;
+pub mod modules;
+pub mod payloads;
+pub mod policy;
+pub mod prefixes;
+pub mod reputation;
+pub mod roles;
+pub mod settings;
+pub mod templates;
+pub mod time;
+pub mod validation;
 
 pub use constants::*;
 pub use errors::*;
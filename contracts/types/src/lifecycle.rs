@@ -0,0 +1,28 @@
+//! Lifecycle of a registry deployment, as `get_lifecycle` reports it. The admin's `decommission`
+//! starts a sunset period in which existing accounts can settle and move to another registry; after
+//! it the registry is read-only for good.
+
+/// Normal operation
+pub const ACTIVE: u8 = 0;
+/// Decommissioning: no new accounts or recoveries, everything else still works
+pub const SUNSET: u8 = 1;
+/// Sunset over: only queries succeed
+pub const DECOMMISSIONED: u8 = 2;
+
+/// State at block time `now` of a registry whose sunset ends at `sunset_end`, if decommissioning
+pub fn state(now: u64, sunset_end: Option<u64>) -> u8 {
+    match sunset_end {
+        None => ACTIVE,
+        Some(end) if now < end => SUNSET,
+        Some(_) => DECOMMISSIONED,
+    }
+}
+
+/// Short name of a state, for display
+pub fn name(state: u8) -> &'static str {
+    match state {
+        ACTIVE => "active",
+        SUNSET => "sunset",
+        _ => "decommissioned",
+    }
+}
@@ -0,0 +1,97 @@
+//! Nonce hygiene for meta-approvals: the nonces a guardian has already signed `approve_meta`
+//! payloads with, so a wallet never signs two approvals for one nonce, one the registry has moved
+//! past, or one it couldn't accept until a gap is filled.
+//!
+//! A ledger belongs to one guardian key. Wallets keep it across sessions in its bytesrepr encoding
+//! and pass the guardian's current nonce (`get_meta_nonce`) to every call.
+
+use alloc::{collections::BTreeMap, vec::Vec};
+use casper_types::bytesrepr::{self, FromBytes, ToBytes};
+
+/// Why a meta-approval must not be signed with a nonce
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NonceError {
+    /// Below the guardian's nonce on chain: already used or voided, so `approve_meta` would revert
+    Stale,
+    /// Already signed over a different payload; both signatures would be valid for the nonce
+    Reused,
+    /// Past the next unsigned nonce; the registry takes nonces in order, so the approval couldn't
+    /// be submitted until the gap is filled
+    Ahead,
+}
+
+impl NonceError {
+    pub fn description(self) -> &'static str {
+        match self {
+            NonceError::Stale => "Nonce already used or voided on chain",
+            NonceError::Reused => "Nonce already signed for another approval",
+            NonceError::Ahead => "Nonce skips ones not yet signed",
+        }
+    }
+}
+
+/// Payloads one guardian signed meta-approvals over, by nonce
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NonceLedger {
+    issued: BTreeMap<u64, Vec<u8>>,
+}
+
+impl NonceLedger {
+    pub fn new() -> Self {
+        NonceLedger::default()
+    }
+
+    /// Nonce to sign the next meta-approval with, past `chain` and every nonce signed since
+    pub fn next(&self, chain: u64) -> u64 {
+        self.issued.range(chain..).next_back().map_or(chain, |(nonce, _)| nonce + 1)
+    }
+
+    /// Whether `payload` may be signed with `nonce`. Signing the same payload again is fine: it
+    /// yields a signature for the same approval.
+    pub fn check(&self, chain: u64, nonce: u64, payload: &[u8]) -> Result<(), NonceError> {
+        if nonce < chain {
+            return Err(NonceError::Stale);
+        }
+        match self.issued.get(&nonce) {
+            Some(signed) if signed.as_slice() == payload => Ok(()),
+            Some(_) => Err(NonceError::Reused),
+            None if nonce > self.next(chain) => Err(NonceError::Ahead),
+            None => Ok(()),
+        }
+    }
+
+    /// Note `payload` as signed with `nonce` if `check` allows it, forgetting nonces below `chain`
+    pub fn record(&mut self, chain: u64, nonce: u64, payload: Vec<u8>) -> Result<(), NonceError> {
+        self.check(chain, nonce, &payload)?;
+        self.prune(chain);
+        self.issued.insert(nonce, payload);
+        Ok(())
+    }
+
+    /// Forget nonces below `chain`: the registry has used or voided them
+    pub fn prune(&mut self, chain: u64) {
+        self.issued = self.issued.split_off(&chain);
+    }
+
+    /// Nonces signed and not yet pruned, with their payloads, lowest first
+    pub fn issued(&self) -> impl Iterator<Item = (u64, &[u8])> {
+        self.issued.iter().map(|(nonce, payload)| (*nonce, payload.as_slice()))
+    }
+}
+
+impl ToBytes for NonceLedger {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        self.issued.to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.issued.serialized_length()
+    }
+}
+
+impl FromBytes for NonceLedger {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (issued, rest) = BTreeMap::from_bytes(bytes)?;
+        Ok((NonceLedger { issued }, rest))
+    }
+}
@@ -0,0 +1,16 @@
+//! Storage schema versions of the registry. The registry is installed as an upgradable package:
+//! the installer runs the install WASM again with `upgrade` set to add the new build as the
+//! package's next contract version, which keeps the previous version's named keys (the `d`
+//! dictionary, the purses, the admin), disables the previous version, and calls `migrate` on the
+//! new one. `migrate` brings the records from the version stored under `prefixes::SCHEMA_VERSION`
+//! up to `CURRENT`, one step per version in between, and records `CURRENT`.
+//!
+//! A release that changes how a record is stored bumps `CURRENT` and adds the step rewriting the
+//! old form to the registry's `migrate`; one that only adds record kinds or entry points needs no
+//! step, since a new prefix starts out empty.
+
+/// Schema of the records this build writes
+pub const CURRENT: u32 = 1;
+
+/// Schema of a registry that has no version recorded
+pub const FIRST: u32 = 1;
@@ -0,0 +1,17 @@
+//! Recovery modules: separate contracts an account plugs into its registry records with
+//! `set_recovery_modules`. The registry calls each module's hooks, in the order listed, as a recovery
+//! is started, approved and finalized; a module rejects the step by reverting, which reverts the
+//! registry call with the module's error. Modules implement `guardian_interface::RecoveryModule`.
+//!
+//! Hook names carry a version like the guardianship queries: a changed signature ships as new `_v2`
+//! hooks alongside these.
+
+/// `(id: U256, account: AccountHash, new_key: PublicKey)`, after a recovery is recorded as started
+pub const ON_INITIATE: &str = "module_on_initiate_v1";
+/// `(id: U256, account: AccountHash, guardian: Key)`, after a guardian's approval is counted
+pub const ON_APPROVE: &str = "module_on_approve_v1";
+/// `(id: U256, account: AccountHash)`, after a recovery is finalized
+pub const ON_EXECUTE: &str = "module_on_execute_v1";
+
+/// Most modules one account can plug in, to bound the gas every recovery step spends on them
+pub const MAX_MODULES: usize = 4;
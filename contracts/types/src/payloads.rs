@@ -0,0 +1,55 @@
+//! Canonical bytes guardians sign off-chain, checked by the registry's `approve_signed`,
+//! `approve_meta` and `rotate_my_key`. Each is a domain tag followed by the bytesrepr encoding of
+//! its fields.
+
+use alloc::{string::String, vec::Vec};
+use casper_types::{account::AccountHash, bytesrepr::ToBytes, PublicKey, U256};
+
+pub const APPROVE_DOMAIN: &[u8] = b"guardian-recovery:approve";
+pub const ROTATE_DOMAIN: &[u8] = b"guardian-recovery:rotate";
+pub const FROST_DOMAIN: &[u8] = b"guardian-recovery:frost";
+pub const META_DOMAIN: &[u8] = b"guardian-recovery:meta-approve";
+
+/// Bytes a guardian signs to approve recovery `id` of `acc` to `new_key` with `memo`:
+/// `APPROVE_DOMAIN` followed by the bytesrepr encoding of each field in that order
+pub fn approval_payload(id: U256, acc: AccountHash, new_key: &PublicKey, memo: &String) -> Vec<u8> {
+    let mut msg = APPROVE_DOMAIN.to_vec();
+    for part in [id.to_bytes(), acc.to_bytes(), new_key.to_bytes(), memo.to_bytes()] {
+        msg.extend(part.unwrap_or_default());
+    }
+    msg
+}
+
+/// Bytes both the old and the new key sign to move a guardian of `acc` from `old` to `new`:
+/// `ROTATE_DOMAIN` followed by the bytesrepr encoding of each field. `nonce` is the account's
+/// rotation count, so a signed rotation can't be replayed after the guardian rotates back.
+pub fn rotation_payload(acc: AccountHash, nonce: u64, old: &PublicKey, new: &PublicKey) -> Vec<u8> {
+    let mut msg = ROTATE_DOMAIN.to_vec();
+    for part in [acc.to_bytes(), nonce.to_bytes(), old.to_bytes(), new.to_bytes()] {
+        msg.extend(part.unwrap_or_default());
+    }
+    msg
+}
+
+/// Bytes a FROST guardian group signs together for `approve_frost`: `FROST_DOMAIN` followed by the
+/// bytesrepr encoding of the recovery id, account and new key. Its own domain keeps a group
+/// signature from ever passing for a single guardian's approval.
+pub fn frost_approval_payload(id: U256, acc: AccountHash, new_key: &PublicKey) -> Vec<u8> {
+    let mut msg = FROST_DOMAIN.to_vec();
+    for part in [id.to_bytes(), acc.to_bytes(), new_key.to_bytes()] {
+        msg.extend(part.unwrap_or_default());
+    }
+    msg
+}
+
+/// Bytes a guardian signs for a relayer to submit as their approval through `approve_meta`:
+/// `META_DOMAIN` followed by the bytesrepr encoding of each field. `nonce` is the guardian's meta
+/// nonce (`get_meta_nonce`), so each signature is accepted once and a guardian can void signatures
+/// they handed out but no relayer has submitted yet.
+pub fn meta_approval_payload(id: U256, acc: AccountHash, new_key: &PublicKey, memo: &String, nonce: u64) -> Vec<u8> {
+    let mut msg = META_DOMAIN.to_vec();
+    for part in [id.to_bytes(), acc.to_bytes(), new_key.to_bytes(), memo.to_bytes(), nonce.to_bytes()] {
+        msg.extend(part.unwrap_or_default());
+    }
+    msg
+}
@@ -0,0 +1,476 @@
+//! Approval policies: how the registry decides a recovery is approved. Every lifecycle entry point
+//! asks `ApprovalPolicy::approved`; wallets can run the same evaluation and checks off-chain.
+//!
+//! Category quorums add minimum approvals from each guardian category (family, friends,
+//! institutions, ...) on top of the overall threshold. Categories are plain `u8` labels assigned per
+//! guardian; guardians without one count toward the threshold but toward no category.
+//!
+//! Fractional policies weigh guardians in basis points (1/10 000) of the whole set and ask for a share
+//! of the *active* weight: guardians whose term has expired or who are suspended drop out of both the
+//! approvals and the total. All of it is integer arithmetic in `u64`, so nothing overflows:
+//!
+//! * a recovery is approved once `approved * 10 000 >= threshold_bps * active`, i.e. the weight
+//!   required is `active * threshold_bps / 10 000` rounded **up** (60% of 7 554 is 4 533, not 4 532),
+//!   and at least 1, so no active weight approves nothing;
+//! * shares shown to people ([`percent`]) round approvals down and requirements up, so a display never
+//!   claims a recovery is further along than the contract counts it.
+
+use alloc::vec::Vec;
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLTyped, Key,
+};
+
+use crate::RegistryError;
+
+/// One whole in basis points
+pub const BPS: u32 = 10_000;
+
+/// Weight (in units of `active`) that `threshold_bps` of `active` requires, rounded up, at least 1
+pub fn required_weight(active: u32, threshold_bps: u16) -> u32 {
+    let exact = active as u64 * threshold_bps as u64;
+    (exact.div_ceil(BPS as u64) as u32).max(1)
+}
+
+/// `weight` as a whole percentage of `active`, rounded up for requirements (`up`) and down otherwise;
+/// 0 with no active weight
+pub fn percent(weight: u32, active: u32, up: bool) -> u8 {
+    if active == 0 { return 0; }
+    let scaled = weight as u64 * 100;
+    let p = if up { scaled.div_ceil(active as u64) } else { scaled / active as u64 };
+    p.min(u8::MAX as u64) as u8
+}
+
+/// Category of `who` among `categories`, if assigned
+pub fn category_of<K: PartialEq>(categories: &[(K, u8)], who: &K) -> Option<u8> {
+    categories.iter().find(|(k, _)| k == who).map(|(_, c)| *c)
+}
+
+/// Accepts `minimums` as (category, minimum approvals) for `guardians` with `categories` assigned:
+/// non-empty, each category listed once with a minimum of at least 1, and each minimum reachable by
+/// the guardians in that category. Assignments to non-guardians are ignored. The threshold needs no
+/// check here: the registry already keeps it within the guardian count, and categories are disjoint,
+/// so any set meeting every minimum can be topped up to the threshold.
+pub fn check_policy<K: PartialEq>(guardians: &[K], categories: &[(K, u8)], minimums: &[(u8, u8)]) -> Result<(), RegistryError> {
+    if minimums.is_empty() {
+        return Err(RegistryError::BadPolicy);
+    }
+    for (i, (category, min)) in minimums.iter().enumerate() {
+        if *min == 0 || minimums[..i].iter().any(|(c, _)| c == category) {
+            return Err(RegistryError::BadPolicy);
+        }
+        let members = guardians.iter().filter(|g| category_of(categories, g) == Some(*category)).count();
+        if members < *min as usize {
+            return Err(RegistryError::BadPolicy);
+        }
+    }
+    Ok(())
+}
+
+/// Approvals still missing from each category of `minimums`, given the counted `approvers`; empty
+/// once every category minimum is met. Repeated approvers count once.
+pub fn shortfall<K: PartialEq>(approvers: &[K], categories: &[(K, u8)], minimums: &[(u8, u8)]) -> Vec<(u8, u8)> {
+    minimums.iter()
+        .filter_map(|(category, min)| {
+            let got = approvers.iter().enumerate()
+                .filter(|(i, a)| !approvers[..*i].contains(a) && category_of(categories, a) == Some(*category))
+                .count();
+            (got < *min as usize).then(|| (*category, min - got as u8))
+        })
+        .collect()
+}
+
+/// How an account decides that a recovery is approved, one per account (`set_approval_policy`).
+/// Serialized (as `CLType::Any`) as a tag byte, 0 to 4 in declaration order, then the variant's
+/// fields. Thresholds of the count-based variants are also the account's `t` threshold.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ApprovalPolicy {
+    /// At least `threshold` approvals
+    Threshold { threshold: u8 },
+    /// Approvals whose guardians' weights sum to at least `threshold`; guardians missing from
+    /// `weights` weigh 1
+    Weighted { weights: Vec<(Key, u8)>, threshold: u32 },
+    /// At least `threshold` approvals, among them each category's minimum (categories are assigned
+    /// per guardian with `set_guardian_categories`)
+    CategoryQuorum { threshold: u8, minimums: Vec<(u8, u8)> },
+    /// At least `threshold` approvals, lowered to each step's threshold once a recovery is `after`
+    /// ms old
+    Decaying { threshold: u8, steps: Vec<(u64, u8)> },
+    /// Approvals holding at least `threshold_bps` of the active guardians' weight, with `weights` in
+    /// basis points of the whole set (adding up to at most 10 000); guardians missing from `weights`
+    /// weigh 0. Recovery-type and dead-man thresholds, which count approvals, don't apply.
+    Fractional { weights: Vec<(Key, u16)>, threshold_bps: u16 },
+}
+
+/// A recovery's standing as the policy sees it
+pub struct Tally<'a> {
+    /// Guardians whose approvals count, inside any approval window
+    pub approvers: &'a [Key],
+    /// Approvals counted without a guardian behind them, i.e. an arbiter's tie-break
+    pub extra: u8,
+    /// Time since the recovery started, in ms
+    pub age: u64,
+    /// Replaces the policy's own threshold, e.g. a recovery type's, before any decay
+    pub base: Option<u32>,
+    /// Ceiling on what the policy requires, e.g. while the owner is inactive
+    pub cap: Option<u32>,
+    /// Category of each categorized guardian
+    pub categories: &'a [(Key, u8)],
+    /// Guardians whose term has expired or who are suspended
+    pub inactive: &'a [Key],
+}
+
+impl ApprovalPolicy {
+    /// Approvals (or weight, for `Weighted` and `Fractional`) required of a recovery `age` ms old whose
+    /// threshold is `base`, with `inactive` guardians out of the running
+    pub fn required(&self, base: Option<u32>, age: u64, inactive: &[Key]) -> u32 {
+        match self {
+            ApprovalPolicy::Threshold { threshold } | ApprovalPolicy::CategoryQuorum { threshold, .. } => {
+                base.unwrap_or(*threshold as u32)
+            }
+            ApprovalPolicy::Weighted { threshold, .. } => base.unwrap_or(*threshold),
+            ApprovalPolicy::Decaying { threshold, steps } => steps.iter()
+                .filter(|(after, _)| age >= *after)
+                .fold(base.unwrap_or(*threshold as u32), |t, (_, step)| t.min(*step as u32)),
+            ApprovalPolicy::Fractional { threshold_bps, .. } => required_weight(self.active_weight(inactive), *threshold_bps),
+        }
+    }
+
+    /// Weight of the guardians not `inactive`, for `Fractional`; 0 under any other policy
+    pub fn active_weight(&self, inactive: &[Key]) -> u32 {
+        match self {
+            ApprovalPolicy::Fractional { weights, .. } => weights.iter()
+                .filter(|(k, _)| !inactive.contains(k))
+                .map(|(_, w)| *w as u32)
+                .sum(),
+            _ => 0,
+        }
+    }
+
+    /// What `approvers` add up to: their number, or for `Weighted` and `Fractional` their total
+    /// weight, leaving out `inactive` guardians for `Fractional`. Repeated approvers count once.
+    pub fn score(&self, approvers: &[Key], inactive: &[Key]) -> u32 {
+        let unique = approvers.iter().enumerate().filter(|(i, a)| !approvers[..*i].contains(a)).map(|(_, a)| a);
+        match self {
+            ApprovalPolicy::Weighted { weights, .. } => unique
+                .map(|a| category_of(weights, a).unwrap_or(1) as u32)
+                .sum(),
+            ApprovalPolicy::Fractional { weights, .. } => unique
+                .filter(|a| !inactive.contains(a))
+                .map(|a| weights.iter().find(|(k, _)| k == a).map_or(0, |(_, w)| *w as u32))
+                .sum(),
+            _ => unique.count() as u32,
+        }
+    }
+
+    /// Approvals still missing per category, empty unless this is a `CategoryQuorum`
+    pub fn shortfall(&self, approvers: &[Key], categories: &[(Key, u8)]) -> Vec<(u8, u8)> {
+        match self {
+            ApprovalPolicy::CategoryQuorum { minimums, .. } => shortfall(approvers, categories, minimums),
+            _ => Vec::new(),
+        }
+    }
+
+    /// The answer to "is this recovery approved?"
+    pub fn approved(&self, tally: &Tally) -> bool {
+        if let ApprovalPolicy::Fractional { .. } = self {
+            // Shares of weight only: no count-based base, cap or weightless extra approvals
+            return self.score(tally.approvers, tally.inactive) >= self.required(None, tally.age, tally.inactive);
+        }
+        let required = self.required(tally.base, tally.age, tally.inactive);
+        let required = tally.cap.map_or(required, |c| c.min(required));
+        self.score(tally.approvers, tally.inactive) + tally.extra as u32 >= required
+            && self.shortfall(tally.approvers, tally.categories).is_empty()
+    }
+
+    /// The account threshold (`t`) this policy implies: its count threshold, or for weight-based
+    /// policies none
+    pub fn count_threshold(&self) -> Option<u8> {
+        match self {
+            ApprovalPolicy::Threshold { threshold }
+            | ApprovalPolicy::CategoryQuorum { threshold, .. }
+            | ApprovalPolicy::Decaying { threshold, .. } => Some(*threshold),
+            ApprovalPolicy::Weighted { .. } | ApprovalPolicy::Fractional { .. } => None,
+        }
+    }
+
+    /// Accepts the policy for `guardians` (listed by key; `hidden` more that can't be named) with
+    /// `categories` assigned: every threshold reachable, weights only of listed guardians and
+    /// non-zero (basis points adding up to at most 10 000, with a threshold of 1 to 10 000, for
+    /// `Fractional`), decay steps strictly later and strictly lower, and category minimums as
+    /// `check_policy` wants them
+    pub fn check(&self, guardians: &[Key], hidden: usize, categories: &[(Key, u8)]) -> Result<(), RegistryError> {
+        let count = guardians.len() + hidden;
+        let count_ok = |t: u8| t >= 1 && t as usize <= count;
+        match self {
+            ApprovalPolicy::Threshold { threshold } => {
+                if !count_ok(*threshold) { return Err(RegistryError::BadThreshold); }
+            }
+            ApprovalPolicy::Weighted { weights, threshold } => {
+                for (i, (who, w)) in weights.iter().enumerate() {
+                    if *w == 0 || !guardians.contains(who) || weights[..i].iter().any(|(k, _)| k == who) {
+                        return Err(RegistryError::BadPolicy);
+                    }
+                }
+                let total = self.score(guardians, &[]) + hidden as u32;
+                if *threshold == 0 || *threshold > total { return Err(RegistryError::BadThreshold); }
+            }
+            ApprovalPolicy::Fractional { weights, threshold_bps } => {
+                for (i, (who, w)) in weights.iter().enumerate() {
+                    if *w == 0 || !guardians.contains(who) || weights[..i].iter().any(|(k, _)| k == who) {
+                        return Err(RegistryError::BadPolicy);
+                    }
+                }
+                if self.active_weight(&[]) > BPS { return Err(RegistryError::BadPolicy); }
+                if *threshold_bps == 0 || *threshold_bps as u32 > BPS { return Err(RegistryError::BadThreshold); }
+            }
+            ApprovalPolicy::CategoryQuorum { threshold, minimums } => {
+                if !count_ok(*threshold) { return Err(RegistryError::BadThreshold); }
+                check_policy(guardians, categories, minimums)?;
+            }
+            ApprovalPolicy::Decaying { threshold, steps } => {
+                if !count_ok(*threshold) { return Err(RegistryError::BadThreshold); }
+                let mut prev = (0u64, *threshold);
+                for (i, &(after, t)) in steps.iter().enumerate() {
+                    if (i > 0 && after <= prev.0) || t == 0 || t >= prev.1 { return Err(RegistryError::BadThreshold); }
+                    prev = (after, t);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The policy with guardian `from` renamed to `to`, after a key rotation
+    pub fn rotated(mut self, from: &Key, to: &Key) -> Self {
+        match &mut self {
+            ApprovalPolicy::Weighted { weights, .. } => {
+                weights.iter_mut().filter(|(k, _)| k == from).for_each(|(k, _)| *k = *to);
+            }
+            ApprovalPolicy::Fractional { weights, .. } => {
+                weights.iter_mut().filter(|(k, _)| k == from).for_each(|(k, _)| *k = *to);
+            }
+            _ => {}
+        }
+        self
+    }
+
+    /// The policy without guardian `removed`'s weight, after it leaves the set
+    pub fn without(self, removed: &Key) -> Self {
+        match self {
+            ApprovalPolicy::Weighted { mut weights, threshold } => {
+                weights.retain(|(k, _)| k != removed);
+                ApprovalPolicy::Weighted { weights, threshold }
+            }
+            ApprovalPolicy::Fractional { mut weights, threshold_bps } => {
+                weights.retain(|(k, _)| k != removed);
+                ApprovalPolicy::Fractional { weights, threshold_bps }
+            }
+            p => p,
+        }
+    }
+}
+
+impl CLTyped for ApprovalPolicy {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for ApprovalPolicy {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut out = Vec::new();
+        match self {
+            ApprovalPolicy::Threshold { threshold } => {
+                out.push(0);
+                out.extend(threshold.to_bytes()?);
+            }
+            ApprovalPolicy::Weighted { weights, threshold } => {
+                out.push(1);
+                out.extend(weights.to_bytes()?);
+                out.extend(threshold.to_bytes()?);
+            }
+            ApprovalPolicy::CategoryQuorum { threshold, minimums } => {
+                out.push(2);
+                out.extend(threshold.to_bytes()?);
+                out.extend(minimums.to_bytes()?);
+            }
+            ApprovalPolicy::Decaying { threshold, steps } => {
+                out.push(3);
+                out.extend(threshold.to_bytes()?);
+                out.extend(steps.to_bytes()?);
+            }
+            ApprovalPolicy::Fractional { weights, threshold_bps } => {
+                out.push(4);
+                out.extend(weights.to_bytes()?);
+                out.extend(threshold_bps.to_bytes()?);
+            }
+        }
+        Ok(out)
+    }
+
+    fn serialized_length(&self) -> usize {
+        1 + match self {
+            ApprovalPolicy::Threshold { threshold } => threshold.serialized_length(),
+            ApprovalPolicy::Weighted { weights, threshold } => weights.serialized_length() + threshold.serialized_length(),
+            ApprovalPolicy::CategoryQuorum { threshold, minimums } => threshold.serialized_length() + minimums.serialized_length(),
+            ApprovalPolicy::Decaying { threshold, steps } => threshold.serialized_length() + steps.serialized_length(),
+            ApprovalPolicy::Fractional { weights, threshold_bps } => weights.serialized_length() + threshold_bps.serialized_length(),
+        }
+    }
+}
+
+impl FromBytes for ApprovalPolicy {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, rest) = u8::from_bytes(bytes)?;
+        match tag {
+            0 => {
+                let (threshold, rest) = u8::from_bytes(rest)?;
+                Ok((ApprovalPolicy::Threshold { threshold }, rest))
+            }
+            1 => {
+                let (weights, rest) = Vec::<(Key, u8)>::from_bytes(rest)?;
+                let (threshold, rest) = u32::from_bytes(rest)?;
+                Ok((ApprovalPolicy::Weighted { weights, threshold }, rest))
+            }
+            2 => {
+                let (threshold, rest) = u8::from_bytes(rest)?;
+                let (minimums, rest) = Vec::<(u8, u8)>::from_bytes(rest)?;
+                Ok((ApprovalPolicy::CategoryQuorum { threshold, minimums }, rest))
+            }
+            3 => {
+                let (threshold, rest) = u8::from_bytes(rest)?;
+                let (steps, rest) = Vec::<(u64, u8)>::from_bytes(rest)?;
+                Ok((ApprovalPolicy::Decaying { threshold, steps }, rest))
+            }
+            4 => {
+                let (weights, rest) = Vec::<(Key, u16)>::from_bytes(rest)?;
+                let (threshold_bps, rest) = u16::from_bytes(rest)?;
+                Ok((ApprovalPolicy::Fractional { weights, threshold_bps }, rest))
+            }
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+    use casper_types::{account::AccountHash, Key};
+
+    use super::*;
+
+    fn g(n: u8) -> Key {
+        Key::Account(AccountHash::new([n; 32]))
+    }
+
+    fn guardians(count: u8) -> Vec<Key> {
+        (1..=count).map(g).collect()
+    }
+
+    fn tally<'a>(approvers: &'a [Key], categories: &'a [(Key, u8)]) -> Tally<'a> {
+        Tally { approvers, extra: 0, age: 0, base: None, cap: None, categories, inactive: &[] }
+    }
+
+    #[test]
+    fn threshold_zero_is_rejected() {
+        let set = guardians(3);
+        assert_eq!(ApprovalPolicy::Threshold { threshold: 0 }.check(&set, 0, &[]), Err(RegistryError::BadThreshold));
+        let quorum = ApprovalPolicy::CategoryQuorum { threshold: 0, minimums: vec![(1, 1)] };
+        assert_eq!(quorum.check(&set, 0, &[(g(1), 1)]), Err(RegistryError::BadThreshold));
+        let decaying = ApprovalPolicy::Decaying { threshold: 0, steps: vec![] };
+        assert_eq!(decaying.check(&set, 0, &[]), Err(RegistryError::BadThreshold));
+        assert_eq!(ApprovalPolicy::Weighted { weights: vec![], threshold: 0 }.check(&set, 0, &[]), Err(RegistryError::BadThreshold));
+        let fractional = ApprovalPolicy::Fractional { weights: vec![(g(1), 5_000)], threshold_bps: 0 };
+        assert_eq!(fractional.check(&set, 0, &[]), Err(RegistryError::BadThreshold));
+        // A decay step can't drop to zero either
+        let to_zero = ApprovalPolicy::Decaying { threshold: 2, steps: vec![(1_000, 0)] };
+        assert_eq!(to_zero.check(&set, 0, &[]), Err(RegistryError::BadThreshold));
+    }
+
+    #[test]
+    fn threshold_above_guardian_count_is_rejected() {
+        let set = guardians(3);
+        assert_eq!(ApprovalPolicy::Threshold { threshold: 3 }.check(&set, 0, &[]), Ok(()));
+        assert_eq!(ApprovalPolicy::Threshold { threshold: 4 }.check(&set, 0, &[]), Err(RegistryError::BadThreshold));
+        // Hidden guardians count toward what is reachable
+        assert_eq!(ApprovalPolicy::Threshold { threshold: 4 }.check(&set, 1, &[]), Ok(()));
+        let categories = [(g(1), 1), (g(2), 1), (g(3), 2)];
+        let quorum = ApprovalPolicy::CategoryQuorum { threshold: 4, minimums: vec![(1, 1)] };
+        assert_eq!(quorum.check(&set, 0, &categories), Err(RegistryError::BadThreshold));
+        // Unit weights of three guardians add up to 3
+        assert_eq!(ApprovalPolicy::Weighted { weights: vec![], threshold: 4 }.check(&set, 0, &[]), Err(RegistryError::BadThreshold));
+        let heavy = ApprovalPolicy::Weighted { weights: vec![(g(1), 3)], threshold: 5 };
+        assert_eq!(heavy.check(&set, 0, &[]), Ok(()));
+        assert_eq!(ApprovalPolicy::Fractional { weights: vec![], threshold_bps: 10_001 }.check(&set, 0, &[]), Err(RegistryError::BadThreshold));
+    }
+
+    #[test]
+    fn a_category_minimum_above_its_members_is_unsatisfiable() {
+        let set = guardians(4);
+        let categories = [(g(1), 1), (g(2), 1), (g(3), 2)];
+        assert_eq!(check_policy(&set, &categories, &[(1, 2), (2, 1)]), Ok(()));
+        assert_eq!(check_policy(&set, &categories, &[(1, 3)]), Err(RegistryError::BadPolicy));
+        assert_eq!(check_policy(&set, &categories, &[(3, 1)]), Err(RegistryError::BadPolicy));
+        assert_eq!(check_policy(&set, &categories, &[(1, 0)]), Err(RegistryError::BadPolicy));
+        assert_eq!(check_policy(&set, &categories, &[]), Err(RegistryError::BadPolicy));
+        // Assignments to non-guardians don't make a minimum reachable
+        let outsiders = [(g(1), 1), (g(9), 1)];
+        assert_eq!(check_policy(&set, &outsiders, &[(1, 2)]), Err(RegistryError::BadPolicy));
+    }
+
+    #[test]
+    fn empty_guardian_set() {
+        let none: [Key; 0] = [];
+        assert_eq!(ApprovalPolicy::Threshold { threshold: 1 }.check(&none, 0, &[]), Err(RegistryError::BadThreshold));
+        let quorum = ApprovalPolicy::CategoryQuorum { threshold: 1, minimums: vec![(1, 1)] };
+        assert_eq!(quorum.check(&none, 0, &[]), Err(RegistryError::BadThreshold));
+        assert_eq!(check_policy(&none, &[], &[(1, 1)]), Err(RegistryError::BadPolicy));
+        assert_eq!(ApprovalPolicy::Weighted { weights: vec![(g(1), 1)], threshold: 1 }.check(&none, 0, &[]), Err(RegistryError::BadPolicy));
+
+        // Nothing approves a recovery with no approvers, nor a fractional one with no active weight
+        assert!(!ApprovalPolicy::Threshold { threshold: 1 }.approved(&tally(&[], &[])));
+        let fractional = ApprovalPolicy::Fractional { weights: vec![], threshold_bps: 5_000 };
+        assert_eq!(fractional.required(None, 0, &[]), 1);
+        assert!(!fractional.approved(&tally(&[], &[])));
+        assert_eq!(percent(0, 0, true), 0);
+    }
+
+    #[test]
+    fn overlapping_categories() {
+        let set = guardians(5);
+        // Guardian 1 is assigned twice: the first assignment is the one that counts
+        let categories = [(g(1), 1), (g(1), 2), (g(2), 1), (g(3), 2), (g(4), 2)];
+        assert_eq!(category_of(&categories, &g(1)), Some(1));
+        assert_eq!(check_policy(&set, &categories, &[(1, 2)]), Ok(()));
+        assert_eq!(check_policy(&set, &categories, &[(2, 3)]), Err(RegistryError::BadPolicy));
+
+        // One approval can't fill two categories' minimums
+        let quorum = ApprovalPolicy::CategoryQuorum { threshold: 2, minimums: vec![(1, 1), (2, 1)] };
+        assert_eq!(quorum.check(&set, 0, &categories), Ok(()));
+        assert_eq!(quorum.shortfall(&[g(1)], &categories), vec![(2, 1)]);
+        assert!(!quorum.approved(&tally(&[g(1), g(5)], &categories)));
+        assert!(quorum.approved(&tally(&[g(1), g(3)], &categories)));
+
+        // A category listed twice in the minimums is rejected rather than counted twice or merged
+        let twice = ApprovalPolicy::CategoryQuorum { threshold: 2, minimums: vec![(1, 1), (1, 2)] };
+        assert_eq!(twice.check(&set, 0, &categories), Err(RegistryError::BadPolicy));
+    }
+
+    #[test]
+    fn minimums_above_the_threshold_still_bind() {
+        let set = guardians(5);
+        let categories = [(g(1), 1), (g(2), 1), (g(3), 2), (g(4), 2)];
+        let quorum = ApprovalPolicy::CategoryQuorum { threshold: 2, minimums: vec![(1, 2), (2, 2)] };
+        assert_eq!(quorum.check(&set, 0, &categories), Ok(()));
+        // The threshold is met three times over, but category 2 is one short
+        let approvers = [g(1), g(2), g(3), g(5)];
+        assert_eq!(quorum.shortfall(&approvers, &categories), vec![(2, 1)]);
+        assert!(!quorum.approved(&tally(&approvers, &categories)));
+        // Repeated approvals don't make up the difference
+        assert!(!quorum.approved(&tally(&[g(1), g(2), g(3), g(3)], &categories)));
+        assert!(quorum.approved(&tally(&[g(1), g(2), g(3), g(4)], &categories)));
+        // Nor does an arbiter's extra approval, which counts toward the threshold only
+        let extra = Tally { extra: 1, ..tally(&[g(1), g(2), g(3)], &categories) };
+        assert!(!quorum.approved(&extra));
+    }
+}
@@ -0,0 +1,336 @@
+//! Key prefixes of the registry's `d` dictionary, one per record kind, checked for collisions at
+//! compile time.
+//!
+//! Every key is a prefix followed by what the record belongs to: an `AccountHash` or `Key` in its
+//! `Debug` form (`AccountHash(…)`, `Key::…`), a decimal recovery id, or several of those joined by
+//! `_`, or nothing for the few records of which there is one per tenant or per registry. None of
+//! these start with a lowercase letter, so two keys can only collide if their prefixes are equal.
+//! `ALL` lists every prefix ever used, retired ones included, and building this crate fails if two
+//! are equal or one breaks the shape its namespace version allows.
+//!
+//! Namespace versions: the prefixes below are version 1, one or two lowercase letters. The two-letter
+//! space is nearly spent, so a new record kind takes a version 2 prefix: `2` followed by one to three
+//! lowercase letters (`2ab`). A record whose stored type changes gets a new prefix rather than
+//! reusing its old one, and the old one stays in `ALL` so it is never handed out again.
+//!
+//! Tenants: the records of tenant 0 are the registry's own and keyed as above. Any other tenant's
+//! are keyed by `scoped`: `3`, the tenant's decimal id and `_` in front of the key tenant 0 would
+//! use. No prefix has a digit after its namespace digit, so tenant keys can't collide with each
+//! other or with tenant 0's.
+
+use alloc::{borrow::Cow, format};
+
+macro_rules! prefixes {
+    ($($(#[doc = $doc:literal])+ $name:ident = $prefix:literal,)+) => {
+        $($(#[doc = $doc])+ pub const $name: &str = $prefix;)+
+
+        /// Every prefix, in declaration order
+        pub const ALL: &[&str] = &[$($prefix,)+];
+    };
+}
+
+prefixes! {
+    /// An account's role members as (member, `roles` bits), in the order first granted
+    ROLES = "2ro",
+    /// A tenant's admin, per tenant id; kept in tenant 0's records
+    TENANT_ADMIN = "2ta",
+    /// Number of tenants created, registry-wide, the prefix alone; kept in tenant 0's records
+    TENANT_COUNT = "2tc",
+    /// The guardian directory's listed guardians, in listing order; one per tenant, the prefix alone
+    DIRECTORY = "2dl",
+    /// A guardian's directory listing
+    DIRECTORY_LISTING = "2ds",
+    /// The tenant's fee schedule, indexed by `fees`; one per tenant, the prefix alone
+    FEE_SCHEDULE = "2fs",
+    /// A directory guardian's reputation statistics, indexed by `reputation`
+    REPUTATION = "2rp",
+    /// Directory guardians a guardian's flag on a recovery counted a flagged approval against, per
+    /// (recovery, flagger)
+    FLAGGED_APPROVERS = "2fa",
+    /// Schema version the registry's records are in, see `migrations`; registry-wide, the prefix
+    /// alone, kept in tenant 0's records
+    SCHEMA_VERSION = "2sv",
+    /// Account's pending guardian invitations with their deadlines
+    INVITATIONS = "2iv",
+    /// Account's most recent recovery id
+    ACTIVE_RECOVERY = "a",
+    /// Recovery approved by its arbiter's tie-break
+    ARBITER_APPROVED = "ab",
+    /// Recovery whose expiry the arbiter extended
+    ARBITER_EXTENDED = "ae",
+    /// Account's anomaly limits, indexed by `counters`
+    ANOMALY_LIMITS = "an",
+    /// When a recovery reached its threshold, on the recovery's clock
+    APPROVED_AT = "ap",
+    /// Account's arbiter
+    ARBITER = "ar",
+    /// Bond a guardian posted, per (account, guardian)
+    BOND = "bd",
+    /// Bond an account requires of its guardians
+    BOND_REQUIRED = "bn",
+    /// Account's confirmation policy
+    CONFIRMATION_POLICY = "cf",
+    /// Account's guardian categories
+    CATEGORIES = "cg",
+    /// A guardian's delegate and until when, per (account, guardian)
+    DELEGATION = "dg",
+    /// The guardian a delegate stands in for, per (account, delegate)
+    DELEGATOR = "dl",
+    /// Deposit an account requires to start a recovery
+    DEPOSIT_REQUIRED = "dp",
+    /// Account's FROST group key and the guardians it was made for
+    FROST_KEY = "fk",
+    /// A recovery's FROST group approval
+    FROST_APPROVAL = "fq",
+    /// Until when an account's configuration is frozen
+    FROZEN_UNTIL = "fz",
+    /// Account's guardians
+    GUARDIANS = "g",
+    /// Accounts a guardian protects
+    PROTECTED_ACCOUNTS = "ga",
+    /// Account's contract guardians
+    CONTRACT_GUARDIANS = "gc",
+    /// Grace period (deadline, weight) of the key a finalized recovery replaced
+    GRACE = "gd",
+    /// The replaced key's grace weight was removed
+    GRACE_REMOVED = "gx",
+    /// Account's private guardians' digests
+    HASHED_GUARDIANS = "gh",
+    /// Open recoveries a guardian guards
+    GUARDIAN_RECOVERIES = "gr",
+    /// Account's guardian terms
+    GUARDIAN_TERMS = "gt",
+    /// Account's suspended guardians
+    SUSPENDED = "gu",
+    /// Account's last heartbeat
+    HEARTBEAT = "hb",
+    /// Account's heirs
+    HEIRS = "he",
+    /// An heir's registered key, per (account, heir)
+    HEIR_KEY = "hk",
+    /// Account's heartbeat period
+    HEARTBEAT_PERIOD = "hp",
+    /// Account's threshold once inactive
+    HEARTBEAT_THRESHOLD = "hr",
+    /// Account has guardians
+    INITIALIZED = "i",
+    /// Account's recovery instructions
+    INSTRUCTIONS = "in",
+    /// Account's guardian key rotation count
+    ROTATION_NONCE = "kn",
+    /// Account's allowed guardian key types
+    KEY_POLICY = "kp",
+    /// A guardian's pending key rotation, per (account, guardian)
+    PENDING_ROTATION = "kr",
+    /// When and at what height an account's last recovery finalized
+    LAST_COMPLETED = "lc",
+    /// A recovery's flags
+    FLAGS = "lf",
+    /// Account's flag limit
+    FLAG_LIMIT = "lp",
+    /// The arbiter dismissed a recovery's flags
+    FLAGS_DISMISSED = "lr",
+    /// Account's lifetime recovery counts, indexed by `counters`
+    LIFETIME_COUNTS = "lt",
+    /// Account's recovery modules
+    MODULES = "md",
+    /// Account's relayer allowlist
+    RELAYERS = "ml",
+    /// A guardian's meta-approval nonce
+    META_NONCE = "mn",
+    /// Account's Merkle guardian root and set size
+    MERKLE_ROOT = "mr",
+    /// Recoveries an account started without one finalizing
+    ATTEMPTS = "na",
+    /// Account's recovery count, for recovery ids
+    RECOVERY_NONCE = "nc",
+    /// Account's approval policy
+    APPROVAL_POLICY = "pl",
+    /// Account's setup consent
+    SETUP_CONSENT = "ps",
+    /// A recovery's quorum certificate
+    QUORUM_CERTIFICATE = "qc",
+    /// Account a recovery belongs to
+    RECOVERY_ACCOUNT = "ra",
+    /// A recovery's approval count
+    APPROVAL_COUNT = "rc",
+    /// Deposit paid to start a recovery
+    RECOVERY_DEPOSIT = "rd",
+    /// When a recovery expires
+    EXPIRES_AT = "re",
+    /// Recovery finalized
+    FINALIZED = "rf",
+    /// A recovery's approval window
+    APPROVAL_WINDOW = "rh",
+    /// Who started a recovery
+    INITIATOR = "ri",
+    /// A vote to reject, per (recovery, voter)
+    REJECTED = "rj",
+    /// A recovery's new key
+    NEW_KEY = "rk",
+    /// A recovery's votes with their memos
+    VOTE_LOG = "rl",
+    /// Recovery timed in block heights
+    BY_HEIGHT = "rm",
+    /// A recovery's confirmations, and each voter's, per (recovery, voter)
+    CONFIRMATIONS = "rn",
+    /// Recovery reached its threshold
+    THRESHOLD_REACHED = "ro",
+    /// A vote to approve, per (recovery, voter)
+    APPROVED = "rp",
+    /// Threshold of a recovery's type
+    REQUIRED = "rq",
+    /// A recovery's relayed approval signatures
+    SIGNED_PROOFS = "rs",
+    /// When a recovery started
+    STARTED_AT = "rt",
+    /// When a recovery's delay ends
+    UNLOCK_AT = "ru",
+    /// A vetoed recovery's approvers
+    VETOED_APPROVERS = "rv",
+    /// A recovery's confirmation round
+    CONFIRMATION_ROUND = "rw",
+    /// Recovery cancelled or vetoed
+    CANCELLED = "rx",
+    /// A recovery's type
+    RECOVERY_TYPE = "ry",
+    /// A recovery's approvals with their times
+    TIMELINE = "rz",
+    /// A guardian's dispute of a slash, per (recovery, guardian)
+    SLASH_DISPUTE = "sd",
+    /// A slash proposal's evidence
+    SLASH_EVIDENCE = "se",
+    /// A guardian's share, per (account, guardian)
+    SHARE = "sh",
+    /// Account's open slash proposal
+    OPEN_SLASH = "so",
+    /// A slash proposal (when, basis points)
+    SLASH_PROPOSAL = "sp",
+    /// Account's settings
+    SETTINGS = "st",
+    /// Amount a slash took
+    SLASHED = "sx",
+    /// Account's threshold
+    THRESHOLD = "t",
+    /// Account's threshold decay steps
+    THRESHOLD_DECAY = "td",
+    /// Account's recovery types
+    RECOVERY_TYPES = "ty",
+    /// Account's guardian verifier
+    VERIFIER = "vf",
+    /// A recovery's veto window
+    VETO_WINDOW = "vw",
+    /// What is left of the reward balance an account funded for paying its approving guardians
+    REWARD_BALANCE = "wb",
+    /// Account's watch-only notify list
+    WATCHERS = "wn",
+    /// Reward an account pays per approval
+    REWARD = "wr",
+}
+
+/// Dictionary key of record `key` in `tenant`'s records
+pub fn scoped(tenant: u32, key: &str) -> Cow<'_, str> {
+    match tenant {
+        0 => Cow::Borrowed(key),
+        t => Cow::Owned(format!("3{}_{}", t, key)),
+    }
+}
+
+/// Namespace version of `p`: 1 for one or two lowercase letters, or the leading digit followed by
+/// one to three lowercase letters; 0 if it fits neither
+pub const fn version(p: &str) -> u8 {
+    let b = p.as_bytes();
+    let (version, start, max) = match b {
+        [d @ b'2'..=b'9', ..] => (*d - b'0', 1, 3),
+        _ => (1, 0, 2),
+    };
+    let letters = b.len() - start;
+    if letters == 0 || letters > max {
+        return 0;
+    }
+    let mut i = start;
+    while i < b.len() {
+        if !b[i].is_ascii_lowercase() {
+            return 0;
+        }
+        i += 1;
+    }
+    version
+}
+
+const fn same(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Index of the first prefix in `all` that is malformed or equals an earlier one
+pub const fn first_clash(all: &[&str]) -> Option<usize> {
+    let mut i = 0;
+    while i < all.len() {
+        if version(all[i]) == 0 {
+            return Some(i);
+        }
+        let mut j = 0;
+        while j < i {
+            if same(all[i], all[j]) {
+                return Some(i);
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+    None
+}
+
+const _: () = assert!(first_clash(ALL).is_none(), "registry key prefixes must be unique and well-formed");
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+    use proptest::prelude::*;
+
+    use super::*;
+
+    /// A key the registry could write: a prefix followed by what the record belongs to, in one of
+    /// the forms the module doc lists
+    fn record_key() -> impl Strategy<Value = String> {
+        let owner = prop_oneof![
+            any::<[u8; 32]>().prop_map(|h| format!("{:?}", casper_types::account::AccountHash::new(h))),
+            any::<[u8; 32]>().prop_map(|h| format!("{:?}", casper_types::Key::Hash(h))),
+            any::<u64>().prop_map(|id| format!("{id}")),
+            (any::<u64>(), any::<[u8; 32]>()).prop_map(|(id, h)| format!("{id}_{:?}", casper_types::account::AccountHash::new(h))),
+            Just(String::new()),
+        ];
+        (prop::sample::select(ALL), owner).prop_map(|(prefix, owner)| format!("{prefix}{owner}"))
+    }
+
+    proptest! {
+        #[test]
+        // Small tenant ids, so ids sharing leading digits (1, 12, 123) meet often
+        fn scoped_is_injective(a in (0..200u32, record_key()), b in (0..200u32, record_key())) {
+            prop_assume!(a != b);
+            prop_assert_ne!(scoped(a.0, &a.1), scoped(b.0, &b.1));
+        }
+
+        #[test]
+        fn tenants_never_share_a_key(t in 1..u32::MAX, u in any::<u32>(), key in record_key()) {
+            prop_assume!(t != u);
+            prop_assert_ne!(scoped(t, &key), scoped(u, &key));
+        }
+
+        #[test]
+        fn tenant_zero_keys_are_unchanged(key in record_key()) {
+            prop_assert_eq!(scoped(0, &key), key.as_str());
+        }
+    }
+}
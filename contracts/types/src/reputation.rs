@@ -0,0 +1,52 @@
+//! Reputation of guardians listed in the registry's directory: statistics the registry keeps while a
+//! guardian is listed, as indexes into the list `get_reputation` returns, and the score it computes
+//! from them. Wallets can run `score` off-chain on the same list.
+//!
+//! The score runs from 0 to `MAX_SCORE`. Its base is the share of the guardian's approvals cast
+//! within `APPROVAL_SLA` of their recovery starting, or half marks before any approval; every
+//! approval of a recovery another guardian flagged and every slash take a fixed penalty off that.
+
+use crate::time::Span;
+
+/// Approvals counted
+pub const APPROVALS: u8 = 0;
+/// Approvals cast within `APPROVAL_SLA` of the recovery starting
+pub const APPROVALS_IN_SLA: u8 = 1;
+/// Flags other guardians raised on recoveries the guardian had approved by then, each counted once
+/// and taken back if the flag is withdrawn
+pub const FLAGGED: u8 = 2;
+/// Slashes executed on the guardian's bond
+pub const SLASHED: u8 = 3;
+/// Number of statistics
+pub const COUNT: usize = 4;
+
+/// How soon after a recovery starts an approval counts as responsive
+pub const APPROVAL_SLA: Span = Span::days(1);
+/// Best possible score
+pub const MAX_SCORE: u32 = 1_000;
+/// Taken off the score per approval of a flagged recovery
+pub const FLAG_PENALTY: u32 = 50;
+/// Taken off the score per slash
+pub const SLASH_PENALTY: u32 = 250;
+
+/// Short name of a statistic, for display
+pub fn name(stat: u8) -> &'static str {
+    match stat {
+        APPROVALS => "approvals",
+        APPROVALS_IN_SLA => "approvals within SLA",
+        FLAGGED => "flagged approvals",
+        SLASHED => "slashes",
+        _ => "unknown",
+    }
+}
+
+/// Score of a guardian with `stats`, indexed as above; missing statistics count as zero
+pub fn score(stats: &[u32]) -> u32 {
+    let stat = |i: u8| stats.get(i as usize).copied().unwrap_or(0) as u64;
+    let base = match stat(APPROVALS) {
+        0 => MAX_SCORE as u64 / 2,
+        approvals => stat(APPROVALS_IN_SLA).min(approvals) * MAX_SCORE as u64 / approvals,
+    };
+    let penalty = stat(FLAGGED) * FLAG_PENALTY as u64 + stat(SLASHED) * SLASH_PENALTY as u64;
+    base.saturating_sub(penalty) as u32
+}
@@ -0,0 +1,22 @@
+//! Roles an account's owner can grant to other keys or contracts, so an organization can run its
+//! account without sharing one owner key. Roles are bits, and a member may hold several; the owner
+//! holds them all and is the only one who grants or revokes them.
+
+/// Changes the account's configuration: guardians, threshold, policies, modules and watchers
+pub const CONFIG_ADMIN: u8 = 1;
+/// Starts recoveries of the account; once any member holds it, no one else can
+pub const RECOVERY_INITIATOR: u8 = 2;
+/// Read-only: notified of the account's recovery events alongside its watchers
+pub const AUDITOR: u8 = 4;
+/// Every role
+pub const ALL: u8 = CONFIG_ADMIN | RECOVERY_INITIATOR | AUDITOR;
+
+/// Short name of a single role bit, for display
+pub fn name(role: u8) -> &'static str {
+    match role {
+        CONFIG_ADMIN => "config_admin",
+        RECOVERY_INITIATOR => "recovery_initiator",
+        AUDITOR => "auditor",
+        _ => "unknown",
+    }
+}
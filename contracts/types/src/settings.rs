@@ -0,0 +1,94 @@
+//! Per-account recovery tunables.
+
+use alloc::vec::Vec;
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLTyped,
+};
+
+/// Time and limit settings of one account, each zero when disabled. Times are in ms, or in blocks
+/// with `by_height`.
+/// Serialized as its fields in declaration order (as `CLType::Any`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AccountSettings {
+    /// How long a recovery stays open before expiring
+    pub expiry: u64,
+    /// Minimum time between starting a recovery and finalizing it
+    pub delay: u64,
+    /// Recoveries that may be started without one finalizing before the owner has to reset the count
+    pub max_attempts: u8,
+    /// Time the owner has to veto once a recovery reaches its threshold
+    pub veto_window: u64,
+    /// Only approvals cast within this long count toward a recovery
+    pub approval_window: u64,
+    /// Measure the above in block heights instead of block time
+    pub by_height: bool,
+    /// Time the owner has to veto a guardian's key rotation before it takes effect
+    pub rotation_window: u64,
+    /// How long configuration stays frozen after a recovery is vetoed as malicious, in ms; the
+    /// registry enforces a floor
+    pub freeze_period: u64,
+    /// How long the replaced key keeps `grace_weight` after a recovery finalizes, so a mistaken
+    /// recovery can still be unwound; removed with `finalize_removal` afterwards
+    pub grace_period: u64,
+    /// Weight the replaced key keeps during the grace period
+    pub grace_weight: u8,
+    /// Quiet period after a recovery finalizes before another one can be started
+    pub cooldown: u64,
+}
+
+impl AccountSettings {
+    /// False if a recovery could never be finalized under these settings
+    pub fn is_valid(&self) -> bool {
+        (self.expiry == 0 || self.expiry > self.delay.max(self.veto_window))
+            && (self.grace_period == 0) == (self.grace_weight == 0)
+    }
+}
+
+impl CLTyped for AccountSettings {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for AccountSettings {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut out = self.expiry.to_bytes()?;
+        out.extend(self.delay.to_bytes()?);
+        out.extend(self.max_attempts.to_bytes()?);
+        out.extend(self.veto_window.to_bytes()?);
+        out.extend(self.approval_window.to_bytes()?);
+        out.extend(self.by_height.to_bytes()?);
+        out.extend(self.rotation_window.to_bytes()?);
+        out.extend(self.freeze_period.to_bytes()?);
+        out.extend(self.grace_period.to_bytes()?);
+        out.extend(self.grace_weight.to_bytes()?);
+        out.extend(self.cooldown.to_bytes()?);
+        Ok(out)
+    }
+
+    fn serialized_length(&self) -> usize {
+        8 + 8 + 1 + 8 + 8 + 1 + 8 + 8 + 8 + 1 + 8
+    }
+}
+
+impl FromBytes for AccountSettings {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (expiry, rest) = u64::from_bytes(bytes)?;
+        let (delay, rest) = u64::from_bytes(rest)?;
+        let (max_attempts, rest) = u8::from_bytes(rest)?;
+        let (veto_window, rest) = u64::from_bytes(rest)?;
+        let (approval_window, rest) = u64::from_bytes(rest)?;
+        let (by_height, rest) = bool::from_bytes(rest)?;
+        let (rotation_window, rest) = u64::from_bytes(rest)?;
+        let (freeze_period, rest) = u64::from_bytes(rest)?;
+        let (grace_period, rest) = u64::from_bytes(rest)?;
+        let (grace_weight, rest) = u8::from_bytes(rest)?;
+        let (cooldown, rest) = u64::from_bytes(rest)?;
+        let cfg = AccountSettings {
+            expiry, delay, max_attempts, veto_window, approval_window, by_height, rotation_window, freeze_period,
+            grace_period, grace_weight, cooldown,
+        };
+        Ok((cfg, rest))
+    }
+}
@@ -0,0 +1,91 @@
+//! Built-in guardian policy templates: a guardian count, threshold and account settings known to
+//! work together, so a new account can be set up without choosing each number. `init_from_template`
+//! applies one by name; the guardians it takes fill the template's slots in order.
+//!
+//! Templates are fixed in the registry build. An account set up from one is an ordinary account
+//! afterwards, and its owner can change anything the template chose.
+
+use crate::{settings::AccountSettings, time::Span};
+
+/// A named preset
+#[derive(Clone, Copy, Debug)]
+pub struct Template {
+    pub name: &'static str,
+    /// One line, for listings
+    pub description: &'static str,
+    /// What each guardian stands for, in the order `init_from_template` takes the guardians
+    pub slots: &'static [&'static str],
+    pub threshold: u8,
+    /// Block-time settings, so `by_height` is off
+    pub settings: AccountSettings,
+}
+
+const OFF: AccountSettings = AccountSettings {
+    expiry: 0, delay: 0, max_attempts: 0, veto_window: 0, approval_window: 0, by_height: false, rotation_window: 0,
+    freeze_period: 0, grace_period: 0, grace_weight: 0, cooldown: 0,
+};
+
+/// Three relatives or friends, any two of whom can recover the account after a day's delay
+pub const FAMILY_2_OF_3: Template = Template {
+    name: "family-2-of-3",
+    description: "Three family members or friends; any two recover the account after a one-day delay",
+    slots: &["family-1", "family-2", "family-3"],
+    threshold: 2,
+    settings: AccountSettings {
+        expiry: Span::days(14).0,
+        delay: Span::days(1).0,
+        max_attempts: 3,
+        veto_window: Span::days(1).0,
+        freeze_period: Span::days(7).0,
+        cooldown: Span::days(7).0,
+        ..OFF
+    },
+};
+
+/// Five officers, any three of whom can recover the account, behind a three-day timelock the owner
+/// can veto in and a grace period that keeps the replaced key usable for a week
+pub const CORPORATE_3_OF_5_WITH_TIMELOCK: Template = Template {
+    name: "corporate-3-of-5-with-timelock",
+    description: "Five officers; any three recover the account after a three-day timelock, with a week's grace for the old key",
+    slots: &["officer-1", "officer-2", "officer-3", "officer-4", "officer-5"],
+    threshold: 3,
+    settings: AccountSettings {
+        expiry: Span::days(21).0,
+        delay: Span::days(3).0,
+        max_attempts: 2,
+        veto_window: Span::days(2).0,
+        approval_window: Span::days(7).0,
+        rotation_window: Span::days(2).0,
+        freeze_period: Span::days(14).0,
+        grace_period: Span::days(7).0,
+        grace_weight: 1,
+        cooldown: Span::days(14).0,
+        ..OFF
+    },
+};
+
+/// The owner's own backup key and a custodian, either of whom can recover the account, but only
+/// after a week the owner has to veto a recovery they didn't start
+pub const SOLO_PLUS_CUSTODIAN: Template = Template {
+    name: "solo-plus-custodian",
+    description: "The owner's backup key and a custodian; either recovers the account after a one-week timelock the owner can veto in",
+    slots: &["backup-key", "custodian"],
+    threshold: 1,
+    settings: AccountSettings {
+        expiry: Span::days(30).0,
+        delay: Span::days(7).0,
+        max_attempts: 2,
+        veto_window: Span::days(3).0,
+        freeze_period: Span::days(14).0,
+        cooldown: Span::days(14).0,
+        ..OFF
+    },
+};
+
+/// Every built-in template
+pub const ALL: &[Template] = &[FAMILY_2_OF_3, CORPORATE_3_OF_5_WITH_TIMELOCK, SOLO_PLUS_CUSTODIAN];
+
+/// Built-in template called `name`
+pub fn find(name: &str) -> Option<&'static Template> {
+    ALL.iter().find(|t| t.name == name)
+}
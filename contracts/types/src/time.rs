@@ -0,0 +1,93 @@
+//! Block time arithmetic for expiries, delays, cool-downs and heartbeats.
+//!
+//! The registry measures an account's settings on one of two clocks: block time in ms
+//! (`runtime::get_blocktime`) or, for `by_height` accounts, block height. An `Instant` is a reading
+//! of either clock and a `Span` a length on the same clock, so a span can't be compared with an
+//! instant by mistake. Additions saturate: a huge setting means "never" rather than wrapping around
+//! to a moment in the past.
+
+/// A reading of a recovery's clock: ms since the Unix epoch, or a block height
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Instant(pub u64);
+
+/// A length of time on one clock: ms, or blocks
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Span(pub u64);
+
+pub const MS_PER_SECOND: u64 = 1000;
+pub const MS_PER_MINUTE: u64 = 60 * MS_PER_SECOND;
+pub const MS_PER_HOUR: u64 = 60 * MS_PER_MINUTE;
+pub const MS_PER_DAY: u64 = 24 * MS_PER_HOUR;
+
+impl Span {
+    pub const ZERO: Span = Span(0);
+
+    pub const fn seconds(n: u64) -> Span {
+        Span(n.saturating_mul(MS_PER_SECOND))
+    }
+
+    pub const fn minutes(n: u64) -> Span {
+        Span(n.saturating_mul(MS_PER_MINUTE))
+    }
+
+    pub const fn hours(n: u64) -> Span {
+        Span(n.saturating_mul(MS_PER_HOUR))
+    }
+
+    pub const fn days(n: u64) -> Span {
+        Span(n.saturating_mul(MS_PER_DAY))
+    }
+
+    /// Zero spans switch the setting they measure off
+    pub const fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+
+    pub const fn max(self, other: Span) -> Span {
+        if self.0 >= other.0 { self } else { other }
+    }
+
+    pub const fn plus(self, other: Span) -> Span {
+        Span(self.0.saturating_add(other.0))
+    }
+}
+
+impl Instant {
+    /// `span` after `self`, saturating at the end of time
+    pub const fn plus(self, span: Span) -> Instant {
+        Instant(self.0.saturating_add(span.0))
+    }
+
+    /// `span` after `self`, or `None` if that overflows
+    pub const fn checked_plus(self, span: Span) -> Option<Instant> {
+        match self.0.checked_add(span.0) {
+            Some(t) => Some(Instant(t)),
+            None => None,
+        }
+    }
+
+    /// Time from `earlier` to `self`; zero if `earlier` isn't earlier
+    pub const fn since(self, earlier: Instant) -> Span {
+        Span(self.0.saturating_sub(earlier.0))
+    }
+
+    /// Time left from `now` until `self`; zero once it has passed
+    pub const fn remaining(self, now: Instant) -> Span {
+        self.since(now)
+    }
+
+    /// Whether `now` is at or past `self`
+    pub const fn reached(self, now: Instant) -> bool {
+        now.0 >= self.0
+    }
+
+    /// Whether `span` has passed since `self` as of `now`
+    pub const fn has_elapsed(self, span: Span, now: Instant) -> bool {
+        self.plus(span).reached(now)
+    }
+
+    /// Whether `now` falls in the `span` starting at `self`
+    pub const fn within(self, span: Span, now: Instant) -> bool {
+        now.0 >= self.0 && !self.has_elapsed(span, now)
+    }
+}
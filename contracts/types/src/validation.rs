@@ -0,0 +1,21 @@
+//! Checks the registry applies to a guardian set, usable off-chain before submitting one.
+
+use casper_types::account::AccountHash;
+
+use crate::{GuardianError, MIN_GUARDIANS};
+
+/// Accepts `guards` as `owner`'s guardians with `threshold`: at least `MIN_GUARDIANS`, none of them
+/// the owner or repeated (two keys of one controller would count twice), and a threshold between 1
+/// and the guardian count
+pub fn check_guardian_set(owner: AccountHash, guards: &[AccountHash], threshold: u8) -> Result<(), GuardianError> {
+    if guards.len() < MIN_GUARDIANS {
+        return Err(GuardianError::InvalidGuardianSetup);
+    }
+    if guards.contains(&owner) || (1..guards.len()).any(|i| guards[..i].contains(&guards[i])) {
+        return Err(GuardianError::InvalidGuardianSetup);
+    }
+    if threshold == 0 || threshold as usize > guards.len() {
+        return Err(GuardianError::InvalidThreshold);
+    }
+    Ok(())
+}